@@ -0,0 +1,66 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::ty::{implements_trait, is_type_diagnostic_item};
+use rustc_hir::{Expr, ExprKind, QPath};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TypeVisitable;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::symbol::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Arc::new` calls on a value whose type is neither `Send` nor `Sync`.
+    ///
+    /// ### Why is this bad?
+    /// An `Arc<T>` can only be sent to or shared with another thread if `T` is `Send` and
+    /// `Sync`. When it is neither, the `Arc` is stuck on the thread that created it, and an `Rc`
+    /// would do the same job without the cost of atomic reference counting.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::cell::Cell;
+    /// # use std::sync::Arc;
+    /// let value = Arc::new(Cell::new(std::ptr::null::<u8>()));
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// let value = Rc::new(Cell::new(std::ptr::null::<u8>()));
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub ARC_WITH_NON_SEND_SYNC,
+    suspicious,
+    "using `Arc` with a type that is neither `Send` nor `Sync`"
+}
+
+declare_lint_pass!(ArcWithNonSendSync => [ARC_WITH_NON_SEND_SYNC]);
+
+impl<'tcx> LateLintPass<'tcx> for ArcWithNonSendSync {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if !expr.span.from_expansion()
+            && let ExprKind::Call(func, [arg]) = expr.kind
+            && let ExprKind::Path(QPath::TypeRelative(_, func_name)) = func.kind
+            && func_name.ident.name == sym::new
+            && is_type_diagnostic_item(cx, cx.typeck_results().expr_ty(expr), sym::Arc)
+            && let arg_ty = cx.typeck_results().expr_ty(arg)
+            // Whether a type like `T` or `Vec<T>` is `Send` or `Sync` can be up to the caller.
+            && !arg_ty.has_non_region_param()
+            && let Some(send) = cx.tcx.get_diagnostic_item(sym::Send)
+            && let Some(sync) = cx.tcx.lang_items().sync_trait()
+            && !implements_trait(cx, arg_ty, send, &[])
+            && !implements_trait(cx, arg_ty, sync, &[])
+        {
+            span_lint_and_then(
+                cx,
+                ARC_WITH_NON_SEND_SYNC,
+                expr.span,
+                "usage of an `Arc` that is neither `Send` nor `Sync`",
+                |diag| {
+                    diag.note(&format!("`{arg_ty}` is neither `Send` nor `Sync`"));
+                    diag.help("consider using an `Rc` instead, as this `Arc` can't be shared with other threads");
+                },
+            );
+        }
+    }
+}
@@ -24,6 +24,9 @@
     /// other solution is to ensure the mutex is unlocked before calling await,
     /// either by introducing a scope or an explicit call to Drop::drop.
     ///
+    /// Further guard types, for example those of third-party locks, can be added
+    /// with the `await-holding-lock-types` configuration.
+    ///
     /// ### Known problems
     /// Will report false positive for explicitly dropped guards
     /// ([#6446](https://github.com/rust-lang/rust-clippy/issues/6446)). A workaround for this is
@@ -174,13 +177,17 @@
 pub struct AwaitHolding {
     conf_invalid_types: Vec<DisallowedPath>,
     def_ids: FxHashMap<DefId, DisallowedPath>,
+    conf_lock_types: Vec<String>,
+    lock_def_ids: FxHashMap<DefId, String>,
 }
 
 impl AwaitHolding {
-    pub(crate) fn new(conf_invalid_types: Vec<DisallowedPath>) -> Self {
+    pub(crate) fn new(conf_invalid_types: Vec<DisallowedPath>, conf_lock_types: Vec<String>) -> Self {
         Self {
             conf_invalid_types,
             def_ids: FxHashMap::default(),
+            conf_lock_types,
+            lock_def_ids: FxHashMap::default(),
         }
     }
 }
@@ -193,6 +200,12 @@ fn check_crate(&mut self, cx: &LateContext<'_>) {
                 self.def_ids.insert(id, conf.clone());
             }
         }
+        for path in &self.conf_lock_types {
+            let segs: Vec<_> = path.split("::").collect();
+            if let Res::Def(_, id) = clippy_utils::def_path_res(cx, &segs, Some(Namespace::TypeNS)) {
+                self.lock_def_ids.insert(id, path.clone());
+            }
+        }
     }
 
     fn check_body(&mut self, cx: &LateContext<'_>, body: &'_ Body<'_>) {
@@ -232,6 +245,20 @@ fn check_interior_types(&self, cx: &LateContext<'_>, ty_causes: &[GeneratorInter
                             );
                         },
                     );
+                } else if let Some(path) = self.lock_def_ids.get(&adt.did()) {
+                    span_lint_and_then(
+                        cx,
+                        AWAIT_HOLDING_LOCK,
+                        ty_cause.span,
+                        &format!("this `{path}` is held across an `await` point"),
+                        |diag| {
+                            diag.help("ensure the guard is dropped before calling `await`");
+                            diag.span_note(
+                                ty_cause.scope_span.unwrap_or(span),
+                                "these are all the `await` points this lock is held through",
+                            );
+                        },
+                    );
                 } else if is_refcell_ref(cx, adt.did()) {
                     span_lint_and_then(
                         cx,
@@ -0,0 +1,158 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{def_path_def_ids, fn_def_id};
+use rustc_hir::def_id::DefIdMap;
+use rustc_hir::{AsyncGeneratorKind, Body, Expr, GeneratorKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+use crate::utils::conf::DisallowedPath;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to functions that block the current thread, like `std::thread::sleep`
+    /// or the file system functions of `std::fs`, inside of an `async fn` or `async` block.
+    ///
+    /// Further functions, like `std::sync::Mutex::lock`, can be added with the
+    /// `blocking-in-async-methods` configuration.
+    ///
+    /// ### Why is this bad?
+    /// An executor usually runs many futures on a small number of threads. A future that blocks
+    /// its thread keeps all other futures scheduled on that thread from making progress.
+    ///
+    /// ### Known problems
+    /// Blocking for a very short time, e.g. reading a small file, may be acceptable in some
+    /// applications.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::time::Duration;
+    /// async fn wait() {
+    ///     std::thread::sleep(Duration::from_secs(1));
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// # use std::time::Duration;
+    /// async fn wait() {
+    ///     tokio::time::sleep(Duration::from_secs(1)).await;
+    /// }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub BLOCKING_IN_ASYNC,
+    pedantic,
+    "calling a function that blocks the thread inside of an async context"
+}
+
+/// The functions that are always linted, along with the asynchronous alternative to suggest.
+const BLOCKING_FNS: &[(&str, &str)] = &[
+    (
+        "std::thread::sleep",
+        "use the sleep function of your runtime, like `tokio::time::sleep`",
+    ),
+    (
+        "std::thread::JoinHandle::join",
+        "use the blocking task support of your runtime, like `tokio::task::spawn_blocking`",
+    ),
+    ("std::fs::canonicalize", FS_ALTERNATIVE),
+    ("std::fs::copy", FS_ALTERNATIVE),
+    ("std::fs::create_dir", FS_ALTERNATIVE),
+    ("std::fs::create_dir_all", FS_ALTERNATIVE),
+    ("std::fs::metadata", FS_ALTERNATIVE),
+    ("std::fs::read", FS_ALTERNATIVE),
+    ("std::fs::read_dir", FS_ALTERNATIVE),
+    ("std::fs::read_to_string", FS_ALTERNATIVE),
+    ("std::fs::remove_dir", FS_ALTERNATIVE),
+    ("std::fs::remove_dir_all", FS_ALTERNATIVE),
+    ("std::fs::remove_file", FS_ALTERNATIVE),
+    ("std::fs::rename", FS_ALTERNATIVE),
+    ("std::fs::write", FS_ALTERNATIVE),
+    ("std::fs::File::create", FS_ALTERNATIVE),
+    ("std::fs::File::open", FS_ALTERNATIVE),
+    (
+        "std::io::Stdin::read_line",
+        "use the standard input of your runtime, like `tokio::io::stdin`",
+    ),
+    (
+        "std::net::TcpListener::accept",
+        "use the networking types of your runtime, like `tokio::net`",
+    ),
+    (
+        "std::net::TcpStream::connect",
+        "use the networking types of your runtime, like `tokio::net`",
+    ),
+    (
+        "std::process::Command::output",
+        "use the process support of your runtime, like `tokio::process`",
+    ),
+    (
+        "std::process::Command::status",
+        "use the process support of your runtime, like `tokio::process`",
+    ),
+];
+
+const FS_ALTERNATIVE: &str = "use the file system functions of your runtime, like `tokio::fs`";
+
+#[derive(Debug)]
+pub struct BlockingInAsync {
+    conf_blocking: Vec<DisallowedPath>,
+    /// The resolved blocking functions, with the alternative to suggest, if any.
+    blocking: DefIdMap<(String, Option<String>)>,
+    /// For every body that is currently being visited, whether it is an async body.
+    in_async: Vec<bool>,
+}
+
+impl BlockingInAsync {
+    pub fn new(conf_blocking: Vec<DisallowedPath>) -> Self {
+        Self {
+            conf_blocking,
+            blocking: DefIdMap::default(),
+            in_async: Vec::new(),
+        }
+    }
+}
+
+impl_lint_pass!(BlockingInAsync => [BLOCKING_IN_ASYNC]);
+
+impl<'tcx> LateLintPass<'tcx> for BlockingInAsync {
+    fn check_crate(&mut self, cx: &LateContext<'_>) {
+        let fns = BLOCKING_FNS.iter().map(|&(path, alternative)| (path, Some(alternative)));
+        let conf = self.conf_blocking.iter().map(|conf| (conf.path(), conf.reason()));
+        self.blocking = def_path_def_ids(cx, fns.chain(conf));
+    }
+
+    fn check_body(&mut self, _: &LateContext<'tcx>, body: &'tcx Body<'_>) {
+        use AsyncGeneratorKind::{Block, Closure, Fn};
+        self.in_async.push(matches!(
+            body.generator_kind,
+            Some(GeneratorKind::Async(Block | Closure | Fn))
+        ));
+    }
+
+    fn check_body_post(&mut self, _: &LateContext<'tcx>, _: &'tcx Body<'_>) {
+        self.in_async.pop();
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if self.in_async.last() != Some(&true) {
+            return;
+        }
+        let Some(def_id) = fn_def_id(cx, expr) else {
+            return
+        };
+        let Some((path, alternative)) = self.blocking.get(&def_id) else {
+            return
+        };
+        span_lint_and_then(
+            cx,
+            BLOCKING_IN_ASYNC,
+            expr.span,
+            &format!("call to the blocking function `{path}` in an async context"),
+            |diag| {
+                if let Some(alternative) = alternative {
+                    diag.help(alternative);
+                }
+            },
+        );
+    }
+}
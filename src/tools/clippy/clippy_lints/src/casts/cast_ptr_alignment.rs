@@ -1,10 +1,14 @@
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::ty::is_c_void;
-use clippy_utils::{get_parent_expr, is_hir_ty_cfg_dependant, match_any_def_paths, paths};
+use clippy_utils::{
+    expr_or_init, get_parent_expr, is_hir_ty_cfg_dependant, is_path_diagnostic_item, match_any_def_paths, path_def_id,
+    paths,
+};
 use rustc_hir::{Expr, ExprKind, GenericArg};
 use rustc_lint::LateContext;
 use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::{self, Ty};
+use rustc_span::sym;
 
 use super::CAST_PTR_ALIGNMENT;
 
@@ -17,6 +21,12 @@ pub(super) fn check(cx: &LateContext<'_>, expr: &Expr<'_>) {
             cx.typeck_results().expr_ty(cast_expr),
             cx.typeck_results().expr_ty(expr),
         );
+        // An integer cast to a pointer may have been a pointer itself.
+        let cast_from = if cast_to.is_unsafe_ptr() {
+            int_origin_ptr_ty(cx, cast_expr).unwrap_or(cast_from)
+        } else {
+            cast_from
+        };
         lint_cast_ptr_alignment(cx, expr, cast_from, cast_to);
     } else if let ExprKind::MethodCall(method_path, self_arg, ..) = &expr.kind {
         if method_path.ident.name == sym!(cast)
@@ -29,6 +39,41 @@ pub(super) fn check(cx: &LateContext<'_>, expr: &Expr<'_>) {
                 (cx.typeck_results().expr_ty(self_arg), cx.typeck_results().expr_ty(expr));
             lint_cast_ptr_alignment(cx, expr, cast_from, cast_to);
         }
+    } else if let ExprKind::Call(func, [arg]) = expr.kind
+        && is_path_diagnostic_item(cx, func, sym::transmute)
+        && let cast_to = cx.typeck_results().expr_ty(expr)
+        && cast_to.is_unsafe_ptr()
+        && let Some(cast_from) = int_origin_ptr_ty(cx, arg)
+    {
+        lint_cast_ptr_alignment(cx, expr, cast_from, cast_to);
+    }
+}
+
+/// If the integer expression is the address of a pointer, returns the type of that pointer.
+///
+/// This follows `as` casts, `transmute`s, `From::from` calls and local bindings, but not
+/// arithmetic, as that is how pointers are usually aligned by hand.
+fn int_origin_ptr_ty<'tcx>(cx: &LateContext<'tcx>, expr: &Expr<'_>) -> Option<Ty<'tcx>> {
+    let expr = expr_or_init(cx, expr);
+    if !cx.typeck_results().expr_ty(expr).is_integral() {
+        return None;
+    }
+    let inner = match expr.kind {
+        ExprKind::Cast(inner, _) => inner,
+        ExprKind::Call(func, [arg]) => {
+            let def_id = path_def_id(cx, func)?;
+            if !cx.tcx.is_diagnostic_item(sym::transmute, def_id) && cx.tcx.lang_items().from_fn() != Some(def_id) {
+                return None;
+            }
+            arg
+        },
+        _ => return None,
+    };
+    let inner_ty = cx.typeck_results().expr_ty(inner);
+    if inner_ty.is_unsafe_ptr() {
+        Some(inner_ty)
+    } else {
+        int_origin_ptr_ty(cx, inner)
     }
 }
 
@@ -17,6 +17,7 @@
 mod fn_to_numeric_cast;
 mod fn_to_numeric_cast_any;
 mod fn_to_numeric_cast_with_truncation;
+mod pointer_width_dependent_cast;
 mod ptr_as_ptr;
 mod unnecessary_cast;
 mod utils;
@@ -185,7 +186,9 @@
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for casts, using `as` or `pointer::cast`,
-    /// from a less-strictly-aligned pointer to a more-strictly-aligned pointer
+    /// from a less-strictly-aligned pointer to a more-strictly-aligned pointer.
+    /// This includes pointers that were turned into an integer and back, e.g.
+    /// `ptr as usize as *const u64` or a `transmute` of the address.
     ///
     /// ### Why is this bad?
     /// Dereferencing the resulting pointer may be undefined
@@ -203,6 +206,8 @@
     ///
     /// (&1u8 as *const u8).cast::<u16>();
     /// (&mut 1u8 as *mut u8).cast::<u16>();
+    ///
+    /// let _ = &1u8 as *const u8 as usize as *const u16;
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub CAST_PTR_ALIGNMENT,
@@ -647,14 +652,48 @@
     "casting a known floating-point NaN into an integer"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for casts between `usize` or `isize` and fixed-width integer types which truncate
+    /// the value on some of the pointer widths the crate supports. The supported pointer widths
+    /// can be configured, and default to 32 and 64 bits.
+    ///
+    /// ### Why is this bad?
+    /// Such a cast is lossless on the target the code is usually built for, and silently
+    /// truncates the value on the others, e.g. casting a `u64` to `usize` on 32-bit targets.
+    ///
+    /// ### Known problems
+    /// The lint doesn't know about `#[cfg(target_pointer_width = "..")]` attributes.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let file_size = 0u64;
+    /// let len = file_size as usize;
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # let file_size = 0u64;
+    /// let len = usize::try_from(file_size).expect("file too large");
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub POINTER_WIDTH_DEPENDENT_CAST,
+    restriction,
+    "casts between pointer-sized and fixed-width integers which truncate on some pointer widths"
+}
+
 pub struct Casts {
     msrv: Option<RustcVersion>,
+    supported_pointer_widths: Vec<u64>,
 }
 
 impl Casts {
     #[must_use]
-    pub fn new(msrv: Option<RustcVersion>) -> Self {
-        Self { msrv }
+    pub fn new(msrv: Option<RustcVersion>, supported_pointer_widths: Vec<u64>) -> Self {
+        Self {
+            msrv,
+            supported_pointer_widths,
+        }
     }
 }
 
@@ -681,6 +720,7 @@ pub fn new(msrv: Option<RustcVersion>) -> Self {
     CAST_SLICE_FROM_RAW_PARTS,
     AS_PTR_CAST_MUT,
     CAST_NAN_TO_INT,
+    POINTER_WIDTH_DEPENDENT_CAST,
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Casts {
@@ -719,6 +759,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                     cast_sign_loss::check(cx, expr, cast_expr, cast_from, cast_to);
                     cast_abs_to_unsigned::check(cx, expr, cast_expr, cast_from, cast_to, self.msrv);
                     cast_nan_to_int::check(cx, expr, cast_expr, cast_from, cast_to);
+                    pointer_width_dependent_cast::check(cx, expr, cast_from, cast_to, &self.supported_pointer_widths);
                 }
                 cast_lossless::check(cx, expr, cast_expr, cast_from, cast_to, self.msrv);
                 cast_enum_constructor::check(cx, expr, cast_expr, cast_from);
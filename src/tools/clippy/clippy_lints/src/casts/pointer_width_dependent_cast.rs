@@ -0,0 +1,58 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::is_isize_or_usize;
+use rustc_hir::Expr;
+use rustc_lint::LateContext;
+use rustc_middle::ty::Ty;
+
+use super::{utils, POINTER_WIDTH_DEPENDENT_CAST};
+
+pub(super) fn check(
+    cx: &LateContext<'_>,
+    expr: &Expr<'_>,
+    cast_from: Ty<'_>,
+    cast_to: Ty<'_>,
+    supported_pointer_widths: &[u64],
+) {
+    if !cast_from.is_integral() || !cast_to.is_integral() {
+        return;
+    }
+
+    let mut truncating_widths: Vec<u64> = match (is_isize_or_usize(cast_from), is_isize_or_usize(cast_to)) {
+        (true, false) => {
+            let to_nbits = utils::int_ty_to_nbits(cast_to, cx.tcx);
+            supported_pointer_widths
+                .iter()
+                .copied()
+                .filter(|&width| to_nbits < width)
+                .collect()
+        },
+        (false, true) => {
+            let from_nbits = utils::int_ty_to_nbits(cast_from, cx.tcx);
+            supported_pointer_widths
+                .iter()
+                .copied()
+                .filter(|&width| width < from_nbits)
+                .collect()
+        },
+        _ => return,
+    };
+    if truncating_widths.is_empty() {
+        return;
+    }
+    truncating_widths.sort_unstable();
+    truncating_widths.dedup();
+
+    let widths = truncating_widths
+        .iter()
+        .map(|width| format!("{width}-bit"))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    span_lint_and_help(
+        cx,
+        POINTER_WIDTH_DEPENDENT_CAST,
+        expr.span,
+        &format!("casting `{cast_from}` to `{cast_to}` truncates the value on targets with {widths} wide pointers"),
+        None,
+        &format!("consider using `{cast_to}::try_from` and handling the error"),
+    );
+}
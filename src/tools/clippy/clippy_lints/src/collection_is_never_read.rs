@@ -0,0 +1,136 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::visitors::for_each_expr_with_closures;
+use clippy_utils::{get_enclosing_block, get_parent_node, path_to_local_id};
+use core::ops::ControlFlow;
+use rustc_hir::{Block, Expr, ExprKind, HirId, Local, Node, PatKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::Ty;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+use rustc_span::symbol::Symbol;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for local collections, like `Vec`, `HashMap` or `String`, that are only ever
+    /// added to, cleared or assigned, but never read.
+    ///
+    /// ### Why is this bad?
+    /// Filling a collection that is never looked at does nothing but cost time and memory. It is
+    /// usually bookkeeping left over from a refactoring, or a sign that the code meant to use the
+    /// collection somewhere.
+    ///
+    /// ### Known problems
+    /// Any other use of the collection, e.g. passing `&mut` to a function, counts as reading it.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let items = [1, 2, 3];
+    /// let mut seen = Vec::new();
+    /// for item in items {
+    ///     seen.push(item);
+    ///     println!("{item}");
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # let items = [1, 2, 3];
+    /// for item in items {
+    ///     println!("{item}");
+    /// }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub COLLECTION_IS_NEVER_READ,
+    nursery,
+    "a collection is written to but never read"
+}
+declare_lint_pass!(CollectionIsNeverRead => [COLLECTION_IS_NEVER_READ]);
+
+/// Methods which add elements to the collection, or remove them without returning them.
+const WRITE_METHODS: [&str; 13] = [
+    "append",
+    "clear",
+    "extend",
+    "extend_from_slice",
+    "insert",
+    "push",
+    "push_back",
+    "push_front",
+    "push_str",
+    "reserve",
+    "reserve_exact",
+    "shrink_to_fit",
+    "truncate",
+];
+
+const COLLECTIONS: [Symbol; 9] = [
+    sym::BTreeMap,
+    sym::BTreeSet,
+    sym::BinaryHeap,
+    sym::HashMap,
+    sym::HashSet,
+    sym::LinkedList,
+    sym::String,
+    sym::Vec,
+    sym::VecDeque,
+];
+
+impl<'tcx> LateLintPass<'tcx> for CollectionIsNeverRead {
+    fn check_local(&mut self, cx: &LateContext<'tcx>, local: &'tcx Local<'tcx>) {
+        if let PatKind::Binding(_, local_id, name, None) = local.pat.kind
+            && !in_external_macro(cx.sess(), local.span)
+            && is_collection(cx, cx.typeck_results().pat_ty(local.pat))
+            && let Some(block) = get_enclosing_block(cx, local.hir_id)
+            && is_only_written(cx, block, local_id)
+        {
+            span_lint_and_help(
+                cx,
+                COLLECTION_IS_NEVER_READ,
+                local.span,
+                &format!("collection `{name}` is never read"),
+                None,
+                "consider removing it, along with the code that fills it",
+            );
+        }
+    }
+}
+
+fn is_collection<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    COLLECTIONS.iter().any(|&name| is_type_diagnostic_item(cx, ty, name))
+}
+
+/// Whether the local is used at least once in the block, and every use only writes to it.
+fn is_only_written<'tcx>(cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>, local_id: HirId) -> bool {
+    let mut has_writes = false;
+    let has_reads = for_each_expr_with_closures(cx, block, |e| {
+        if !path_to_local_id(e, local_id) {
+            ControlFlow::Continue(())
+        } else if is_write(cx, e) {
+            has_writes = true;
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    })
+    .is_some();
+    has_writes && !has_reads
+}
+
+/// Whether the use of the local is the target of an assignment, or the receiver of a method
+/// call from `WRITE_METHODS` whose result is discarded.
+fn is_write(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    let Some(Node::Expr(parent)) = get_parent_node(cx.tcx, e.hir_id) else {
+        return false;
+    };
+    match parent.kind {
+        ExprKind::Assign(lhs, ..) | ExprKind::AssignOp(_, lhs, _) => lhs.hir_id == e.hir_id,
+        ExprKind::MethodCall(path, recv, ..) => {
+            recv.hir_id == e.hir_id
+                && WRITE_METHODS.contains(&path.ident.as_str())
+                && matches!(get_parent_node(cx.tcx, parent.hir_id), Some(Node::Stmt(_)))
+        },
+        _ => false,
+    }
+}
@@ -1,10 +1,10 @@
-use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::macros::root_macro_call_first_node;
 use clippy_utils::source::snippet_with_applicability;
-use clippy_utils::{is_in_cfg_test, is_in_test_function};
+use clippy_utils::{get_parent_node, is_in_test};
 use rustc_errors::Applicability;
-use rustc_hir::{Expr, ExprKind};
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_hir::{Expr, ExprKind, Node};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::sym;
 
@@ -16,6 +16,10 @@
     /// `dbg!` macro is intended as a debugging tool. It
     /// should not be in version control.
     ///
+    /// The suggestion replaces the macro call with its arguments, or removes
+    /// the whole statement for a `dbg!();` without arguments, so it can be
+    /// applied with `cargo clippy --fix`.
+    ///
     /// ### Example
     /// ```rust,ignore
     /// dbg!(true)
@@ -49,15 +53,24 @@ fn check_expr(&mut self, cx: &LateContext<'_>, expr: &Expr<'_>) {
         let Some(macro_call) = root_macro_call_first_node(cx, expr) else { return };
         if cx.tcx.is_diagnostic_item(sym::dbg_macro, macro_call.def_id) {
             // allows `dbg!` in test code if allow-dbg-in-test is set to true in clippy.toml
-            if self.allow_dbg_in_tests
-                && (is_in_test_function(cx.tcx, expr.hir_id) || is_in_cfg_test(cx.tcx, expr.hir_id))
-            {
+            if self.allow_dbg_in_tests && is_in_test(cx.tcx, expr.hir_id) {
                 return;
             }
             let mut applicability = Applicability::MachineApplicable;
+            let mut sugg_span = macro_call.span;
             let suggestion = match expr.peel_drop_temps().kind {
                 // dbg!()
-                ExprKind::Block(_, _) => String::new(),
+                ExprKind::Block(_, _) => {
+                    // remove the whole statement rather than leaving a lone `;` behind
+                    if let Some(Node::Stmt(_)) = get_parent_node(cx.tcx, expr.hir_id)
+                        && let Some(semi_span) = cx.sess().source_map().mac_call_stmt_semi_span(macro_call.span)
+                    {
+                        sugg_span = macro_call.span.to(semi_span);
+                        String::new()
+                    } else {
+                        String::from("()")
+                    }
+                },
                 // dbg!(1)
                 ExprKind::Match(val, ..) => {
                     snippet_with_applicability(cx, val.span.source_callsite(), "..", &mut applicability).to_string()
@@ -87,14 +100,19 @@ fn check_expr(&mut self, cx: &LateContext<'_>, expr: &Expr<'_>) {
                 _ => return,
             };
 
-            span_lint_and_sugg(
+            span_lint_and_then(
                 cx,
                 DBG_MACRO,
                 macro_call.span,
                 "`dbg!` macro is intended as a debugging tool",
-                "ensure to avoid having uses of it in version control",
-                suggestion,
-                applicability,
+                |diag| {
+                    diag.span_suggestion(
+                        sugg_span,
+                        "ensure to avoid having uses of it in version control",
+                        suggestion,
+                        applicability,
+                    );
+                },
             );
         }
     }
@@ -14,8 +14,8 @@
 use rustc_middle::hir::nested_filter;
 use rustc_middle::traits::Reveal;
 use rustc_middle::ty::{
-    self, Binder, BoundConstness, GenericParamDefKind, ImplPolarity, ParamEnv, PredicateKind, TraitPredicate, TraitRef,
-    Ty, TyCtxt,
+    self, subst::GenericArgKind, Binder, BoundConstness, GenericParamDefKind, ImplPolarity, ParamEnv, PredicateKind,
+    TraitPredicate, TraitRef, Ty, TyCtxt,
 };
 use rustc_session::{declare_lint_pass, declare_tool_lint};
 use rustc_span::source_map::Span;
@@ -36,6 +36,9 @@
     /// k1 == k2 ⇒ hash(k1) == hash(k2)
     /// ```
     ///
+    /// This is easy to get wrong for types with float fields, as a derived
+    /// `PartialEq` considers `0.0` and `-0.0` equal, while their bits differ.
+    ///
     /// ### Example
     /// ```ignore
     /// #[derive(Hash)]
@@ -170,8 +173,9 @@
     /// ### Why is this bad?
     /// If a type `T` derives `PartialEq` and all of its members implement `Eq`,
     /// then `T` can always implement `Eq`. Implementing `Eq` allows `T` to be used
-    /// in APIs that require `Eq` types. It also allows structs containing `T` to derive
-    /// `Eq` themselves.
+    /// in APIs that require `Eq` types, like as the key of a `HashMap`, and constants
+    /// of type `T` can only be used in patterns if `Eq` is derived as well. It also
+    /// allows structs containing `T` to derive `Eq` themselves.
     ///
     /// ### Example
     /// ```rust
@@ -271,6 +275,16 @@ fn check_hash_peq<'tcx>(
                                     "`PartialEq` implemented here"
                                 );
                             }
+                            if peq_is_automatically_derived && has_float_field(cx, ty) {
+                                diag.note(
+                                    "the derived `PartialEq` compares floats with `==`, so `0.0 == -0.0`, but \
+                                    hashing the bits of the floats gives them different hashes"
+                                );
+                                diag.help(
+                                    "consider wrapping the floats in a type with total equality, like \
+                                    `ordered_float::OrderedFloat`, or implementing `PartialEq` to agree with `Hash`"
+                                );
+                            }
                         }
                     );
                 }
@@ -279,6 +293,20 @@ fn check_hash_peq<'tcx>(
     }
 }
 
+/// Whether a field of the type contains a float, e.g. `f64` or `[f32; 2]`.
+fn has_float_field<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    if let ty::Adt(adt, substs) = ty.kind() {
+        adt.all_fields().any(|field| {
+            field
+                .ty(cx.tcx, substs)
+                .walk()
+                .any(|arg| matches!(arg.unpack(), GenericArgKind::Type(ty) if ty.is_floating_point()))
+        })
+    } else {
+        false
+    }
+}
+
 /// Implementation of the `DERIVE_ORD_XOR_PARTIAL_ORD` lint.
 fn check_ord_partial_ord<'tcx>(
     cx: &LateContext<'tcx>,
@@ -11,8 +11,12 @@
     ///
     /// ### Why is this bad?
     /// Empty `Drop` implementations have no effect when dropping an instance of the type. They are
-    /// most likely useless. However, an empty `Drop` implementation prevents a type from being
-    /// destructured, which might be the intention behind adding the implementation as a marker.
+    /// most likely useless, e.g. leftover scaffolding. They still give the type drop glue, so it
+    /// can't be `Copy`, can't be dropped in a `const` context, and `std::mem::needs_drop` returns
+    /// `true` for it, which keeps collections from skipping the drop of their elements.
+    ///
+    /// However, an empty `Drop` implementation prevents a type from being destructured, which
+    /// might be the intention behind adding the implementation as a marker.
     ///
     /// ### Example
     /// ```rust
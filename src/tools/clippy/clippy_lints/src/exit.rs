@@ -1,22 +1,37 @@
-use clippy_utils::diagnostics::span_lint;
+use clippy_utils::diagnostics::span_lint_and_help;
 use clippy_utils::{is_entrypoint_fn, match_def_path, paths};
 use if_chain::if_chain;
-use rustc_hir::{Expr, ExprKind, Item, ItemKind, Node};
+use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::{declare_lint_pass, declare_tool_lint};
 
 declare_clippy_lint! {
     /// ### What it does
-    /// `exit()`  terminates the program and doesn't provide a
-    /// stack trace.
+    /// Checks for calls to `std::process::exit` outside of the `main` function, e.g. in
+    /// library code or in a method of a binary.
     ///
     /// ### Why is this bad?
-    /// Ideally a program is terminated by finishing
-    /// the main function.
+    /// `exit()` terminates the program without running the destructors of the values on the
+    /// stack, and makes the code calling it impossible to test. Ideally a program is terminated
+    /// by finishing the main function, which can return a `Result` or an `ExitCode`.
     ///
     /// ### Example
     /// ```ignore
-    /// std::process::exit(0)
+    /// fn run(path: &str) {
+    ///     if !Path::new(path).exists() {
+    ///         std::process::exit(1);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```ignore
+    /// fn run(path: &str) -> Result<(), String> {
+    ///     if !Path::new(path).exists() {
+    ///         return Err(format!("`{path}` doesn't exist"));
+    ///     }
+    ///     Ok(())
+    /// }
     /// ```
     #[clippy::version = "1.41.0"]
     pub EXIT,
@@ -34,12 +49,17 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
             if let Some(def_id) = cx.qpath_res(path, path_expr.hir_id).opt_def_id();
             if match_def_path(cx, def_id, &paths::EXIT);
             let parent = cx.tcx.hir().get_parent_item(e.hir_id).def_id;
-            if let Some(Node::Item(Item{kind: ItemKind::Fn(..), ..})) = cx.tcx.hir().find_by_def_id(parent);
-            // If the next item up is a function we check if it is an entry point
-            // and only then emit a linter warning
+            // Exiting from the entry point is fine, this includes closures defined in it
             if !is_entrypoint_fn(cx, parent.to_def_id());
             then {
-                span_lint(cx, EXIT, e.span, "usage of `process::exit`");
+                span_lint_and_help(
+                    cx,
+                    EXIT,
+                    e.span,
+                    "usage of `process::exit`",
+                    None,
+                    "consider returning an error, and exiting from `main` by returning a `Result` or an `ExitCode`",
+                );
             }
         }
     }
@@ -17,13 +17,15 @@
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::def_id::DefId;
 use rustc_span::edition::Edition::Edition2021;
+use rustc_span::symbol::kw;
 use rustc_span::{sym, ExpnData, ExpnKind, Span, Symbol};
 
 declare_clippy_lint! {
     /// ### What it does
     /// Detects `format!` within the arguments of another macro that does
     /// formatting such as `format!` itself, `write!` or `println!`. Suggests
-    /// inlining the `format!` call.
+    /// inlining the `format!` call, or formatting its argument directly if it
+    /// is just `format!("{}", arg)`.
     ///
     /// ### Why is this bad?
     /// The recommended code is both shorter and avoids a temporary allocation.
@@ -330,11 +332,11 @@ fn outermost_expn_data(expn_data: ExpnData) -> ExpnData {
     }
 }
 
-fn check_format_in_format_args(
-    cx: &LateContext<'_>,
+fn check_format_in_format_args<'tcx>(
+    cx: &LateContext<'tcx>,
     call_site: Span,
     name: Symbol,
-    arg: &Expr<'_>,
+    arg: &'tcx Expr<'tcx>,
 ) {
     let expn_data = arg.span.ctxt().outer_expn_data();
     if expn_data.call_site.from_expansion() {
@@ -344,20 +346,45 @@ fn check_format_in_format_args(
     if !cx.tcx.is_diagnostic_item(sym::format_macro, mac_id) {
         return;
     }
+    let only_arg = only_formatted_arg(cx, arg);
     span_lint_and_then(
         cx,
         FORMAT_IN_FORMAT_ARGS,
         call_site,
         &format!("`format!` in `{name}!` args"),
         |diag| {
-            diag.help(&format!(
-                "combine the `format!(..)` arguments with the outer `{name}!(..)` call"
-            ));
-            diag.help("or consider changing `format!` to `format_args!`");
+            if let Some(only_arg) = only_arg {
+                diag.span_suggestion_verbose(
+                    expn_data.call_site,
+                    "use the argument directly",
+                    only_arg,
+                    Applicability::MachineApplicable,
+                );
+            } else {
+                diag.help(&format!(
+                    "combine the `format!(..)` arguments with the outer `{name}!(..)` call"
+                ));
+                diag.help("or consider changing `format!` to `format_args!`");
+            }
         },
     );
 }
 
+/// If the `format!` call is `format!("{}", arg)`, returns the snippet of `arg`, which can be
+/// formatted in its place.
+fn only_formatted_arg<'tcx>(cx: &LateContext<'tcx>, format: &'tcx Expr<'tcx>) -> Option<String> {
+    let format_args = FormatArgsExpn::find_nested(cx, format, format.span.ctxt().outer_expn())?;
+    if let [arg] = &format_args.args[..]
+        && arg.format.is_default()
+        && format_args.format_string.parts.iter().all(|part| *part == kw::Empty)
+        && !arg.param.value.span.from_expansion()
+    {
+        snippet_opt(cx, arg.param.value.span)
+    } else {
+        None
+    }
+}
+
 fn check_to_string_in_format_args(cx: &LateContext<'_>, name: Symbol, value: &Expr<'_>) {
     if_chain! {
         if !value.span.from_expansion();
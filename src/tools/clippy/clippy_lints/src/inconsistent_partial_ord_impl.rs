@@ -0,0 +1,158 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{is_res_lang_ctor, path_res, path_to_local_id, peel_blocks};
+use rustc_errors::Applicability;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Body, Expr, ExprKind, ImplItemKind, Item, ItemKind, LangItem, PatKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{Ty, TypeckResults};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for manual `PartialOrd` implementations on types that also implement `Ord` manually,
+    /// where `partial_cmp` is not simply `Some(self.cmp(other))`.
+    ///
+    /// ### Why is this bad?
+    /// `Ord` requires `partial_cmp(a, b)` to be `Some(cmp(a, b))`. If the two implementations
+    /// diverge, code relying on the contract, like sorting and ordered collections, may give
+    /// inconsistent results depending on which of the methods it uses. Calling `cmp` from
+    /// `partial_cmp` keeps the ordering in one place.
+    ///
+    /// ### Known problems
+    /// The check is syntactic, so a `partial_cmp` that is consistent with `cmp` in another way is
+    /// linted too.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::cmp::Ordering;
+    /// #[derive(PartialEq, Eq)]
+    /// struct Version(u32, u32);
+    ///
+    /// impl PartialOrd for Version {
+    ///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    ///         self.0.partial_cmp(&other.0)
+    ///     }
+    /// }
+    ///
+    /// impl Ord for Version {
+    ///     fn cmp(&self, other: &Self) -> Ordering {
+    ///         (self.0, self.1).cmp(&(other.0, other.1))
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # use std::cmp::Ordering;
+    /// # #[derive(PartialEq, Eq)]
+    /// # struct Version(u32, u32);
+    /// impl PartialOrd for Version {
+    ///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    ///         Some(self.cmp(other))
+    ///     }
+    /// }
+    /// # impl Ord for Version {
+    /// #     fn cmp(&self, other: &Self) -> Ordering {
+    /// #         (self.0, self.1).cmp(&(other.0, other.1))
+    /// #     }
+    /// # }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub INCONSISTENT_PARTIAL_ORD_IMPL,
+    suspicious,
+    "manual `PartialOrd` implementations that don't call `Ord::cmp` on types implementing `Ord` manually"
+}
+
+declare_lint_pass!(InconsistentPartialOrdImpl => [INCONSISTENT_PARTIAL_ORD_IMPL]);
+
+impl<'tcx> LateLintPass<'tcx> for InconsistentPartialOrdImpl {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Impl(imp) = &item.kind
+            && let Some(hir_trait_ref) = &imp.of_trait
+            && hir_trait_ref.trait_def_id() == cx.tcx.lang_items().partial_ord_trait()
+            && !in_external_macro(cx.sess(), item.span)
+            && !cx.tcx.has_attr(item.owner_id.to_def_id(), sym::automatically_derived)
+            && let Some(ord_id) = cx.tcx.get_diagnostic_item(sym::Ord)
+            && let Some(trait_ref) = cx.tcx.impl_trait_ref(item.owner_id)
+            // Only care about `impl PartialOrd<Foo> for Foo`
+            && trait_ref.substs.type_at(1) == trait_ref.self_ty()
+            && has_manual_impl(cx, ord_id, trait_ref.self_ty())
+            && let Some(impl_item_ref) = imp.items.iter().find(|item| item.ident.name == sym::partial_cmp)
+            && let ImplItemKind::Fn(_, body_id) = cx.tcx.hir().impl_item(impl_item_ref.id).kind
+        {
+            let body = cx.tcx.hir().body(body_id);
+            let typeck = cx.tcx.typeck_body(body_id);
+            if calls_cmp(cx, typeck, body, ord_id) {
+                return;
+            }
+            span_lint_and_then(
+                cx,
+                INCONSISTENT_PARTIAL_ORD_IMPL,
+                cx.tcx.def_span(impl_item_ref.id.owner_id),
+                "`partial_cmp` of a type implementing `Ord` doesn't call `Ord::cmp`",
+                |diag| {
+                    let expr = peel_blocks(body.value);
+                    if let [_, other] = body.params
+                        && let PatKind::Binding(_, _, other, None) = other.pat.kind
+                        && !matches!(expr.kind, ExprKind::Block(..))
+                    {
+                        diag.span_suggestion(
+                            expr.span,
+                            "call `cmp` instead",
+                            format!("Some(self.cmp({other}))"),
+                            Applicability::MaybeIncorrect,
+                        );
+                    } else {
+                        diag.help("implement `partial_cmp` as `Some(self.cmp(other))`");
+                    }
+                    diag.note("`Ord` requires `partial_cmp` to be consistent with `cmp`");
+                },
+            );
+        }
+    }
+}
+
+/// Whether the type implements the trait, without deriving it.
+fn has_manual_impl(cx: &LateContext<'_>, trait_id: DefId, ty: Ty<'_>) -> bool {
+    let mut found = false;
+    cx.tcx.for_each_relevant_impl(trait_id, ty, |impl_id| {
+        found |= !cx.tcx.has_attr(impl_id, sym::automatically_derived);
+    });
+    found
+}
+
+/// Whether the body is `Some(self.cmp(other))` or `Some(Ord::cmp(self, other))`.
+fn calls_cmp<'tcx>(cx: &LateContext<'tcx>, typeck: &TypeckResults<'tcx>, body: &Body<'_>, ord_id: DefId) -> bool {
+    let [this, other] = body.params else {
+        return false;
+    };
+    let is_ord_cmp = |did: Option<DefId>| {
+        did.map_or(false, |did| {
+            cx.tcx.trait_of_item(did) == Some(ord_id) && cx.tcx.item_name(did) == sym::cmp
+        })
+    };
+    let is_args = |recv: &Expr<'_>, arg: &Expr<'_>| {
+        path_to_local_id(recv, this.pat.hir_id) && path_to_local_id(arg, other.pat.hir_id)
+    };
+
+    if let ExprKind::Call(some, [arg]) = peel_blocks(body.value).kind
+        && is_res_lang_ctor(cx, path_res(cx, some), LangItem::OptionSome)
+    {
+        match arg.kind {
+            ExprKind::MethodCall(_, recv, [cmp_arg], _) => {
+                is_ord_cmp(typeck.type_dependent_def_id(arg.hir_id)) && is_args(recv, cmp_arg)
+            },
+            ExprKind::Call(func, [recv, cmp_arg]) => match func.kind {
+                ExprKind::Path(ref qpath) => {
+                    is_ord_cmp(typeck.qpath_res(qpath, func.hir_id).opt_def_id()) && is_args(recv, cmp_arg)
+                },
+                _ => false,
+            },
+            _ => false,
+        }
+    } else {
+        false
+    }
+}
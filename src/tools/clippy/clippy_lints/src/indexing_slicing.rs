@@ -2,12 +2,12 @@
 
 use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_help};
-use clippy_utils::higher;
+use clippy_utils::{higher, is_in_test};
 use rustc_ast::ast::RangeLimits;
 use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty;
-use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -49,6 +49,9 @@
     /// Indexing and slicing can panic at runtime and there are
     /// safe alternatives.
     ///
+    /// Test functions and `#[cfg(test)]` modules can be allowed with the
+    /// `allow-panic-in-tests` configuration.
+    ///
     /// ### Example
     /// ```rust,no_run
     /// // Vector
@@ -82,7 +85,22 @@
     "indexing/slicing usage"
 }
 
-declare_lint_pass!(IndexingSlicing => [INDEXING_SLICING, OUT_OF_BOUNDS_INDEXING]);
+pub struct IndexingSlicing {
+    allow_panic_in_tests: bool,
+}
+
+impl IndexingSlicing {
+    pub fn new(allow_panic_in_tests: bool) -> Self {
+        Self { allow_panic_in_tests }
+    }
+
+    /// Whether `INDEXING_SLICING` is disabled for the expression because it's in test code.
+    fn is_allowed_in_tests(&self, cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+        self.allow_panic_in_tests && is_in_test(cx.tcx, expr.hir_id)
+    }
+}
+
+impl_lint_pass!(IndexingSlicing => [INDEXING_SLICING, OUT_OF_BOUNDS_INDEXING]);
 
 impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
@@ -140,6 +158,9 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                     (Some(_), Some(_)) => "consider using `.get(n..m)` or `.get_mut(n..m)` instead",
                     (None, None) => return, // [..] is ok.
                 };
+                if self.is_allowed_in_tests(cx, expr) {
+                    return;
+                }
 
                 span_lint_and_help(cx, INDEXING_SLICING, expr.span, "slicing may panic", None, help_msg);
             } else {
@@ -155,6 +176,9 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                         return;
                     }
                 }
+                if self.is_allowed_in_tests(cx, expr) {
+                    return;
+                }
 
                 span_lint_and_help(
                     cx,
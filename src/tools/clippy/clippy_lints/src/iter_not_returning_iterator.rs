@@ -6,10 +6,11 @@
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Detects methods named `iter` or `iter_mut` that do not have a return type that implements `Iterator`.
+    /// Detects methods named `iter`, `iter_mut` or `into_iter` that do not have a return type that
+    /// implements `Iterator`.
     ///
     /// ### Why is this bad?
-    /// Methods named `iter` or `iter_mut` conventionally return an `Iterator`.
+    /// Methods named `iter`, `iter_mut` or `into_iter` conventionally return an `Iterator`.
     ///
     /// ### Example
     /// ```rust
@@ -34,7 +35,7 @@
     #[clippy::version = "1.57.0"]
     pub ITER_NOT_RETURNING_ITERATOR,
     pedantic,
-    "methods named `iter` or `iter_mut` that do not return an `Iterator`"
+    "methods named `iter`, `iter_mut` or `into_iter` that do not return an `Iterator`"
 }
 
 declare_lint_pass!(IterNotReturningIterator => [ITER_NOT_RETURNING_ITERATOR]);
@@ -42,7 +43,7 @@
 impl<'tcx> LateLintPass<'tcx> for IterNotReturningIterator {
     fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx TraitItem<'_>) {
         let name = item.ident.name.as_str();
-        if matches!(name, "iter" | "iter_mut") {
+        if matches!(name, "iter" | "iter_mut" | "into_iter") {
             if let TraitItemKind::Fn(fn_sig, _) = &item.kind {
                 check_sig(cx, name, fn_sig, item.owner_id.def_id);
             }
@@ -51,7 +52,7 @@ fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx TraitItem<'_>
 
     fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'tcx>) {
         let name = item.ident.name.as_str();
-        if matches!(name, "iter" | "iter_mut")
+        if matches!(name, "iter" | "iter_mut" | "into_iter")
             && !matches!(
                 get_parent_node(cx.tcx, item.hir_id()),
                 Some(Node::Item(Item { kind: ItemKind::Impl(i), .. })) if i.of_trait.is_some()
@@ -0,0 +1,106 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use rustc_hir::{AsyncGeneratorKind, Body, GeneratorKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::TypeVisitable;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `async fn`s and `async` blocks whose future is larger than the
+    /// `future-size-threshold` configuration (16 KiB by default).
+    ///
+    /// ### Why is this bad?
+    /// Everything that is held across an `await` point is stored in the future itself, so a
+    /// large local easily makes for a future of several kilobytes. Such a future is moved
+    /// around when it is polled from the stack, and futures awaiting it grow by its size, too.
+    ///
+    /// ### Example
+    /// ```rust
+    /// async fn fetch() {}
+    ///
+    /// async fn large() {
+    ///     let buffer = [0u8; 32 * 1024];
+    ///     fetch().await;
+    ///     drop(buffer);
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// async fn fetch() {}
+    ///
+    /// async fn small() {
+    ///     let buffer = vec![0u8; 32 * 1024];
+    ///     fetch().await;
+    ///     drop(buffer);
+    /// }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub LARGE_FUTURES,
+    pedantic,
+    "`async fn`s and `async` blocks whose future is larger than the configured size"
+}
+
+pub struct LargeFutures {
+    future_size_threshold: u64,
+}
+
+impl LargeFutures {
+    #[must_use]
+    pub fn new(future_size_threshold: u64) -> Self {
+        Self { future_size_threshold }
+    }
+}
+
+impl_lint_pass!(LargeFutures => [LARGE_FUTURES]);
+
+/// How many of the largest values held across `await` points are pointed out.
+const MAX_NOTED_VALUES: usize = 3;
+
+impl<'tcx> LateLintPass<'tcx> for LargeFutures {
+    fn check_body(&mut self, cx: &LateContext<'tcx>, body: &'tcx Body<'_>) {
+        use AsyncGeneratorKind::{Block, Closure, Fn};
+        if !matches!(body.generator_kind, Some(GeneratorKind::Async(Block | Closure | Fn))) {
+            return;
+        }
+        let def_id = cx.tcx.hir().body_owner_def_id(body.id());
+        // The layout of a future that depends on generic parameters is not known.
+        let Ok(layout) = cx.layout_of(cx.tcx.type_of(def_id)) else {
+            return
+        };
+        let size = layout.size.bytes();
+        if size <= self.future_size_threshold {
+            return;
+        }
+
+        // Only values that account for a good part of the threshold are worth pointing out.
+        let mut large_values: Vec<_> = cx
+            .tcx
+            .typeck(def_id)
+            .generator_interior_types
+            .as_ref()
+            .skip_binder()
+            .iter()
+            .filter(|cause| !cause.ty.has_escaping_bound_vars())
+            .filter_map(|cause| {
+                let size = cx.layout_of(cause.ty).ok()?.size.bytes();
+                (size >= self.future_size_threshold / 4).then_some((cause.span, size))
+            })
+            .collect();
+        large_values.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        span_lint_and_then(
+            cx,
+            LARGE_FUTURES,
+            body.value.span,
+            &format!("this future is {size} bytes large"),
+            |diag| {
+                for &(span, size) in large_values.iter().take(MAX_NOTED_VALUES) {
+                    diag.span_note(span, &format!("this value of {size} bytes is held across an `await` point"));
+                }
+                diag.help("consider moving large values to the heap, or boxing the future with `Box::pin`");
+            },
+        );
+    }
+}
@@ -37,6 +37,7 @@
     utils::internal_lints::unnecessary_def_path::UNNECESSARY_DEF_PATH,
     almost_complete_letter_range::ALMOST_COMPLETE_LETTER_RANGE,
     approx_const::APPROX_CONSTANT,
+    arc_with_non_send_sync::ARC_WITH_NON_SEND_SYNC,
     as_conversions::AS_CONVERSIONS,
     asm_syntax::INLINE_ASM_X86_ATT_SYNTAX,
     asm_syntax::INLINE_ASM_X86_INTEL_SYNTAX,
@@ -54,6 +55,7 @@
     await_holding_invalid::AWAIT_HOLDING_INVALID_TYPE,
     await_holding_invalid::AWAIT_HOLDING_LOCK,
     await_holding_invalid::AWAIT_HOLDING_REFCELL_REF,
+    blocking_in_async::BLOCKING_IN_ASYNC,
     blocks_in_if_conditions::BLOCKS_IN_IF_CONDITIONS,
     bool_assert_comparison::BOOL_ASSERT_COMPARISON,
     bool_to_int_with_if::BOOL_TO_INT_WITH_IF,
@@ -86,12 +88,14 @@
     casts::FN_TO_NUMERIC_CAST,
     casts::FN_TO_NUMERIC_CAST_ANY,
     casts::FN_TO_NUMERIC_CAST_WITH_TRUNCATION,
+    casts::POINTER_WIDTH_DEPENDENT_CAST,
     casts::PTR_AS_PTR,
     casts::UNNECESSARY_CAST,
     checked_conversions::CHECKED_CONVERSIONS,
     cognitive_complexity::COGNITIVE_COMPLEXITY,
     collapsible_if::COLLAPSIBLE_ELSE_IF,
     collapsible_if::COLLAPSIBLE_IF,
+    collection_is_never_read::COLLECTION_IS_NEVER_READ,
     comparison_chain::COMPARISON_CHAIN,
     copies::BRANCHES_SHARING_CODE,
     copies::IFS_SAME_COND,
@@ -190,6 +194,7 @@
     implicit_return::IMPLICIT_RETURN,
     implicit_saturating_add::IMPLICIT_SATURATING_ADD,
     implicit_saturating_sub::IMPLICIT_SATURATING_SUB,
+    inconsistent_partial_ord_impl::INCONSISTENT_PARTIAL_ORD_IMPL,
     inconsistent_struct_constructor::INCONSISTENT_STRUCT_CONSTRUCTOR,
     index_refutable_slice::INDEX_REFUTABLE_SLICE,
     indexing_slicing::INDEXING_SLICING,
@@ -208,6 +213,7 @@
     iter_not_returning_iterator::ITER_NOT_RETURNING_ITERATOR,
     large_const_arrays::LARGE_CONST_ARRAYS,
     large_enum_variant::LARGE_ENUM_VARIANT,
+    large_futures::LARGE_FUTURES,
     large_include_file::LARGE_INCLUDE_FILE,
     large_stack_arrays::LARGE_STACK_ARRAYS,
     len_zero::COMPARISON_TO_EMPTY,
@@ -251,6 +257,7 @@
     manual_bits::MANUAL_BITS,
     manual_clamp::MANUAL_CLAMP,
     manual_instant_elapsed::MANUAL_INSTANT_ELAPSED,
+    manual_let_else::MANUAL_LET_ELSE,
     manual_non_exhaustive::MANUAL_NON_EXHAUSTIVE,
     manual_rem_euclid::MANUAL_REM_EUCLID,
     manual_retain::MANUAL_RETAIN,
@@ -427,6 +434,7 @@
     needless_for_each::NEEDLESS_FOR_EACH,
     needless_late_init::NEEDLESS_LATE_INIT,
     needless_parens_on_range_literals::NEEDLESS_PARENS_ON_RANGE_LITERALS,
+    needless_pass_by_ref_mut::NEEDLESS_PASS_BY_REF_MUT,
     needless_pass_by_value::NEEDLESS_PASS_BY_VALUE,
     needless_question_mark::NEEDLESS_QUESTION_MARK,
     needless_update::NEEDLESS_UPDATE,
@@ -474,7 +482,9 @@
     operators::VERBOSE_BIT_MASK,
     option_env_unwrap::OPTION_ENV_UNWRAP,
     option_if_let_else::OPTION_IF_LET_ELSE,
+    order_dependent_hash_iteration::ORDER_DEPENDENT_HASH_ITERATION,
     overflow_check_conditional::OVERFLOW_CHECK_CONDITIONAL,
+    panic_in_drop::PANIC_IN_DROP,
     panic_in_result_fn::PANIC_IN_RESULT_FN,
     panic_unimplemented::PANIC,
     panic_unimplemented::TODO,
@@ -498,6 +508,7 @@
     ranges::RANGE_MINUS_ONE,
     ranges::RANGE_PLUS_ONE,
     ranges::REVERSED_EMPTY_RANGES,
+    raw_ptr_wrapper_send_sync::RAW_PTR_WRAPPER_SEND_SYNC,
     rc_clone_in_vec_init::RC_CLONE_IN_VEC_INIT,
     read_zero_byte_vec::READ_ZERO_BYTE_VEC,
     redundant_clone::REDUNDANT_CLONE,
@@ -522,10 +533,13 @@
     shadow::SHADOW_REUSE,
     shadow::SHADOW_SAME,
     shadow::SHADOW_UNRELATED,
+    shadowed_trait_method::SHADOWED_TRAIT_METHOD,
+    shell_command_from_format::SHELL_COMMAND_FROM_FORMAT,
     single_char_lifetime_names::SINGLE_CHAR_LIFETIME_NAMES,
     single_component_path_imports::SINGLE_COMPONENT_PATH_IMPORTS,
     size_of_in_element_count::SIZE_OF_IN_ELEMENT_COUNT,
     slow_vector_initialization::SLOW_VECTOR_INITIALIZATION,
+    static_mut::STATIC_MUT,
     std_instead_of_core::ALLOC_INSTEAD_OF_CORE,
     std_instead_of_core::STD_INSTEAD_OF_ALLOC,
     std_instead_of_core::STD_INSTEAD_OF_CORE,
@@ -574,7 +588,10 @@
     types::REDUNDANT_ALLOCATION,
     types::TYPE_COMPLEXITY,
     types::VEC_BOX,
+    unbounded_channel::UNBOUNDED_CHANNEL,
+    unchecked_time_subtraction::UNCHECKED_TIME_SUBTRACTION,
     undocumented_unsafe_blocks::UNDOCUMENTED_UNSAFE_BLOCKS,
+    undocumented_unsafe_blocks::UNNECESSARY_SAFETY_COMMENT,
     unicode::INVISIBLE_CHARACTERS,
     unicode::NON_ASCII_LITERAL,
     unicode::UNICODE_NOT_NFC,
@@ -10,6 +10,7 @@
     LintId::of(attrs::ALLOW_ATTRIBUTES_WITHOUT_REASON),
     LintId::of(casts::AS_UNDERSCORE),
     LintId::of(casts::FN_TO_NUMERIC_CAST_ANY),
+    LintId::of(casts::POINTER_WIDTH_DEPENDENT_CAST),
     LintId::of(create_dir::CREATE_DIR),
     LintId::of(dbg_macro::DBG_MACRO),
     LintId::of(default_numeric_fallback::DEFAULT_NUMERIC_FALLBACK),
@@ -57,6 +58,7 @@
     LintId::of(operators::INTEGER_ARITHMETIC),
     LintId::of(operators::INTEGER_DIVISION),
     LintId::of(operators::MODULO_ARITHMETIC),
+    LintId::of(order_dependent_hash_iteration::ORDER_DEPENDENT_HASH_ITERATION),
     LintId::of(panic_in_result_fn::PANIC_IN_RESULT_FN),
     LintId::of(panic_unimplemented::PANIC),
     LintId::of(panic_unimplemented::TODO),
@@ -70,7 +72,9 @@
     LintId::of(shadow::SHADOW_REUSE),
     LintId::of(shadow::SHADOW_SAME),
     LintId::of(shadow::SHADOW_UNRELATED),
+    LintId::of(shadowed_trait_method::SHADOWED_TRAIT_METHOD),
     LintId::of(single_char_lifetime_names::SINGLE_CHAR_LIFETIME_NAMES),
+    LintId::of(static_mut::STATIC_MUT),
     LintId::of(std_instead_of_core::ALLOC_INSTEAD_OF_CORE),
     LintId::of(std_instead_of_core::STD_INSTEAD_OF_ALLOC),
     LintId::of(std_instead_of_core::STD_INSTEAD_OF_CORE),
@@ -80,7 +84,9 @@
     LintId::of(strings::STR_TO_STRING),
     LintId::of(types::RC_BUFFER),
     LintId::of(types::RC_MUTEX),
+    LintId::of(unbounded_channel::UNBOUNDED_CHANNEL),
     LintId::of(undocumented_unsafe_blocks::UNDOCUMENTED_UNSAFE_BLOCKS),
+    LintId::of(undocumented_unsafe_blocks::UNNECESSARY_SAFETY_COMMENT),
     LintId::of(unicode::NON_ASCII_LITERAL),
     LintId::of(unnecessary_self_imports::UNNECESSARY_SELF_IMPORTS),
     LintId::of(unwrap_in_result::UNWRAP_IN_RESULT),
@@ -168,6 +168,7 @@ macro_rules! declare_clippy_lint {
 // begin lints modules, do not remove this comment, it’s used in `update_lints`
 mod almost_complete_letter_range;
 mod approx_const;
+mod arc_with_non_send_sync;
 mod as_conversions;
 mod asm_syntax;
 mod assertions_on_constants;
@@ -175,6 +176,7 @@ macro_rules! declare_clippy_lint {
 mod async_yields_async;
 mod attrs;
 mod await_holding_invalid;
+mod blocking_in_async;
 mod blocks_in_if_conditions;
 mod bool_assert_comparison;
 mod bool_to_int_with_if;
@@ -186,6 +188,7 @@ macro_rules! declare_clippy_lint {
 mod checked_conversions;
 mod cognitive_complexity;
 mod collapsible_if;
+mod collection_is_never_read;
 mod comparison_chain;
 mod copies;
 mod copy_iterator;
@@ -241,6 +244,7 @@ macro_rules! declare_clippy_lint {
 mod implicit_return;
 mod implicit_saturating_add;
 mod implicit_saturating_sub;
+mod inconsistent_partial_ord_impl;
 mod inconsistent_struct_constructor;
 mod index_refutable_slice;
 mod indexing_slicing;
@@ -256,6 +260,7 @@ macro_rules! declare_clippy_lint {
 mod iter_not_returning_iterator;
 mod large_const_arrays;
 mod large_enum_variant;
+mod large_futures;
 mod large_include_file;
 mod large_stack_arrays;
 mod len_zero;
@@ -271,6 +276,7 @@ macro_rules! declare_clippy_lint {
 mod manual_bits;
 mod manual_clamp;
 mod manual_instant_elapsed;
+mod manual_let_else;
 mod manual_non_exhaustive;
 mod manual_rem_euclid;
 mod manual_retain;
@@ -306,6 +312,7 @@ macro_rules! declare_clippy_lint {
 mod needless_for_each;
 mod needless_late_init;
 mod needless_parens_on_range_literals;
+mod needless_pass_by_ref_mut;
 mod needless_pass_by_value;
 mod needless_question_mark;
 mod needless_update;
@@ -323,7 +330,9 @@ macro_rules! declare_clippy_lint {
 mod operators;
 mod option_env_unwrap;
 mod option_if_let_else;
+mod order_dependent_hash_iteration;
 mod overflow_check_conditional;
+mod panic_in_drop;
 mod panic_in_result_fn;
 mod panic_unimplemented;
 mod partial_pub_fields;
@@ -337,6 +346,7 @@ macro_rules! declare_clippy_lint {
 mod pub_use;
 mod question_mark;
 mod ranges;
+mod raw_ptr_wrapper_send_sync;
 mod rc_clone_in_vec_init;
 mod read_zero_byte_vec;
 mod redundant_clone;
@@ -356,10 +366,13 @@ macro_rules! declare_clippy_lint {
 mod semicolon_if_nothing_returned;
 mod serde_api;
 mod shadow;
+mod shadowed_trait_method;
+mod shell_command_from_format;
 mod single_char_lifetime_names;
 mod single_component_path_imports;
 mod size_of_in_element_count;
 mod slow_vector_initialization;
+mod static_mut;
 mod std_instead_of_core;
 mod strings;
 mod strlen_on_c_strings;
@@ -374,6 +387,8 @@ macro_rules! declare_clippy_lint {
 mod trait_bounds;
 mod transmute;
 mod types;
+mod unbounded_channel;
+mod unchecked_time_subtraction;
 mod undocumented_unsafe_blocks;
 mod unicode;
 mod uninit_vec;
@@ -559,9 +574,11 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::new(utils::dump_hir::DumpHir));
     store.register_late_pass(|_| Box::new(utils::author::Author));
     let await_holding_invalid_types = conf.await_holding_invalid_types.clone();
+    let await_holding_lock_types = conf.await_holding_lock_types.clone();
     store.register_late_pass(move |_| {
         Box::new(await_holding_invalid::AwaitHolding::new(
             await_holding_invalid_types.clone(),
+            await_holding_lock_types.clone(),
         ))
     });
     store.register_late_pass(|_| Box::new(serde_api::SerdeApi));
@@ -626,7 +643,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(move |_| Box::new(use_self::UseSelf::new(msrv)));
     store.register_late_pass(move |_| Box::new(missing_const_for_fn::MissingConstForFn::new(msrv)));
     store.register_late_pass(move |_| Box::new(needless_question_mark::NeedlessQuestionMark));
-    store.register_late_pass(move |_| Box::new(casts::Casts::new(msrv)));
+    let supported_pointer_widths = conf.supported_pointer_widths.clone();
+    store.register_late_pass(move |_| Box::new(casts::Casts::new(msrv, supported_pointer_widths.clone())));
     store.register_early_pass(move || Box::new(unnested_or_patterns::UnnestedOrPatterns::new(msrv)));
     store.register_late_pass(|_| Box::new(size_of_in_element_count::SizeOfInElementCount));
     store.register_late_pass(|_| Box::new(same_name_method::SameNameMethod));
@@ -661,7 +679,14 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     let too_large_for_stack = conf.too_large_for_stack;
     store.register_late_pass(move |_| Box::new(escape::BoxedLocal { too_large_for_stack }));
     store.register_late_pass(move |_| Box::new(vec::UselessVec { too_large_for_stack }));
-    store.register_late_pass(|_| Box::new(panic_unimplemented::PanicUnimplemented));
+    let allow_todo_in_tests = conf.allow_todo_in_tests;
+    let allow_panic_in_tests = conf.allow_panic_in_tests;
+    store.register_late_pass(move |_| {
+        Box::new(panic_unimplemented::PanicUnimplemented::new(
+            allow_todo_in_tests,
+            allow_panic_in_tests,
+        ))
+    });
     store.register_late_pass(|_| Box::new(strings::StringLitAsBytes));
     store.register_late_pass(|_| Box::new(derive::Derive));
     store.register_late_pass(|_| Box::new(derivable_impls::DerivableImpls));
@@ -723,7 +748,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::new(inherent_impl::MultipleInherentImpl));
     store.register_late_pass(|_| Box::new(neg_cmp_op_on_partial_ord::NoNegCompOpForPartialOrd));
     store.register_late_pass(|_| Box::new(unwrap::Unwrap));
-    store.register_late_pass(|_| Box::new(indexing_slicing::IndexingSlicing));
+    store.register_late_pass(move |_| Box::new(indexing_slicing::IndexingSlicing::new(allow_panic_in_tests)));
     store.register_late_pass(|_| Box::new(non_copy_const::NonCopyConst));
     store.register_late_pass(|_| Box::new(ptr_offset_with_cast::PtrOffsetWithCast));
     store.register_late_pass(|_| Box::new(redundant_clone::RedundantClone));
@@ -845,7 +870,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(move |_| Box::new(if_then_some_else_none::IfThenSomeElseNone::new(msrv)));
     store.register_late_pass(|_| Box::new(bool_assert_comparison::BoolAssertComparison));
     store.register_early_pass(move || Box::new(module_style::ModStyle));
-    store.register_late_pass(|_| Box::new(unused_async::UnusedAsync));
+    store.register_late_pass(|_| Box::<unused_async::UnusedAsync>::default());
     let disallowed_types = conf.disallowed_types.clone();
     store.register_late_pass(move |_| Box::new(disallowed_types::DisallowedTypes::new(disallowed_types.clone())));
     let import_renames = conf.enforced_import_renames.clone();
@@ -866,7 +891,12 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
             enable_raw_pointer_heuristic_for_send,
         ))
     });
-    store.register_late_pass(move |_| Box::new(undocumented_unsafe_blocks::UndocumentedUnsafeBlocks));
+    let allow_undocumented_unsafe_in_unsafe_fn = conf.allow_undocumented_unsafe_in_unsafe_fn;
+    store.register_late_pass(move |_| {
+        Box::new(undocumented_unsafe_blocks::UndocumentedUnsafeBlocks::new(
+            allow_undocumented_unsafe_in_unsafe_fn,
+        ))
+    });
     store.register_late_pass(move |_| Box::new(format_args::FormatArgs::new(msrv)));
     store.register_late_pass(|_| Box::new(trailing_empty_array::TrailingEmptyArray));
     store.register_early_pass(|| Box::new(octal_escapes::OctalEscapes));
@@ -885,7 +915,16 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
             ignore_publish: cargo_ignore_publish,
         })
     });
-    store.register_late_pass(|_| Box::<write::Write>::default());
+    let allow_print_in_main_module = conf.allow_print_in_main_module;
+    let allowed_print_modules = conf.allowed_print_modules.clone();
+    let allowed_print_macros = conf.allowed_print_macros.clone();
+    store.register_late_pass(move |_| {
+        Box::new(write::Write::new(
+            allow_print_in_main_module,
+            &allowed_print_modules,
+            &allowed_print_macros,
+        ))
+    });
     store.register_early_pass(|| Box::new(crate_in_macro_def::CrateInMacroDef));
     store.register_early_pass(|| Box::new(empty_structs_with_brackets::EmptyStructsWithBrackets));
     store.register_late_pass(|_| Box::new(unnecessary_owned_empty_strings::UnnecessaryOwnedEmptyStrings));
@@ -919,6 +958,50 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::new(implicit_saturating_add::ImplicitSaturatingAdd));
     store.register_early_pass(|| Box::new(partial_pub_fields::PartialPubFields));
     store.register_late_pass(|_| Box::new(missing_trait_methods::MissingTraitMethods));
+    let blocking_in_async_methods = conf.blocking_in_async_methods.clone();
+    store.register_late_pass(move |_| {
+        Box::new(blocking_in_async::BlockingInAsync::new(
+            blocking_in_async_methods.clone(),
+        ))
+    });
+    let future_size_threshold = conf.future_size_threshold;
+    store.register_late_pass(move |_| Box::new(large_futures::LargeFutures::new(future_size_threshold)));
+    store.register_late_pass(|_| Box::new(arc_with_non_send_sync::ArcWithNonSendSync));
+    let allowed_raw_ptr_wrappers = conf.allowed_raw_ptr_wrappers.clone();
+    store.register_late_pass(move |_| {
+        Box::new(raw_ptr_wrapper_send_sync::RawPtrWrapperSendSync::new(
+            &allowed_raw_ptr_wrappers,
+        ))
+    });
+    store.register_late_pass(move |_| Box::new(static_mut::StaticMut::new(msrv)));
+    let allowed_panicking_drop_types = conf.allowed_panicking_drop_types.clone();
+    store.register_late_pass(move |_| Box::new(panic_in_drop::PanicInDrop::new(&allowed_panicking_drop_types)));
+    store.register_late_pass(|_| Box::<shadowed_trait_method::ShadowedTraitMethod>::default());
+    store.register_late_pass(|_| Box::new(inconsistent_partial_ord_impl::InconsistentPartialOrdImpl));
+    store.register_late_pass(|_| Box::new(collection_is_never_read::CollectionIsNeverRead));
+    store.register_late_pass(move |_| {
+        Box::new(needless_pass_by_ref_mut::NeedlessPassByRefMut::new(
+            avoid_breaking_exported_api,
+        ))
+    });
+    let warn_on_all_hash_iteration = conf.warn_on_all_hash_iteration;
+    store.register_late_pass(move |_| {
+        Box::new(order_dependent_hash_iteration::OrderDependentHashIteration::new(
+            warn_on_all_hash_iteration,
+        ))
+    });
+    store.register_late_pass(move |_| Box::new(unchecked_time_subtraction::UncheckedTimeSubtraction::new(msrv)));
+    store.register_late_pass(move |_| Box::new(manual_let_else::ManualLetElse::new(msrv)));
+    let shell_binaries = conf.shell_binaries.clone();
+    store.register_late_pass(move |_| {
+        Box::new(shell_command_from_format::ShellCommandFromFormat::new(&shell_binaries))
+    });
+    let unbounded_channel_functions = conf.unbounded_channel_functions.clone();
+    store.register_late_pass(move |_| {
+        Box::new(unbounded_channel::UnboundedChannel::new(
+            unbounded_channel_functions.clone(),
+        ))
+    });
     // add lints here, do not remove this comment, it's used in `new_lint`
 }
 
@@ -13,7 +13,8 @@
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for uses of `std::mem::size_of::<T>() * 8` when
-    /// `T::BITS` is available.
+    /// `T::BITS` is available. For thin pointers, this suggests
+    /// `usize::BITS`.
     ///
     /// ### Why is this bad?
     /// Can be written as the shorter `T::BITS`.
@@ -56,12 +57,21 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
             if let ExprKind::Binary(bin_op, left_expr, right_expr) = expr.kind;
             if let BinOpKind::Mul = &bin_op.node;
             if let Some((real_ty, resolved_ty, other_expr)) = get_one_size_of_ty(cx, left_expr, right_expr);
-            if matches!(resolved_ty.kind(), ty::Int(_) | ty::Uint(_));
             if let ExprKind::Lit(lit) = &other_expr.kind;
             if let LitKind::Int(8, _) = lit.node;
             then {
                 let mut app = Applicability::MachineApplicable;
-                let ty_snip = snippet_with_applicability(cx, real_ty.span, "..", &mut app);
+                let ty_snip = match resolved_ty.kind() {
+                    ty::Int(_) | ty::Uint(_) => snippet_with_applicability(cx, real_ty.span, "..", &mut app),
+                    // Thin pointers have the width of `usize`.
+                    ty::RawPtr(ty::TypeAndMut { ty: pointee, .. }) | ty::Ref(_, pointee, _)
+                        if pointee.is_sized(cx.tcx, cx.param_env) =>
+                    {
+                        "usize".into()
+                    },
+                    ty::FnPtr(_) => "usize".into(),
+                    _ => return,
+                };
                 let sugg = create_sugg(cx, expr, format!("{ty_snip}::BITS"));
 
                 span_lint_and_sugg(
@@ -98,8 +108,8 @@ fn get_size_of_ty<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) -> Option<
         if let ExprKind::Path(ref count_func_qpath) = count_func.kind;
 
         if let QPath::Resolved(_, count_func_path) = count_func_qpath;
-        if let Some(segment_zero) = count_func_path.segments.get(0);
-        if let Some(args) = segment_zero.args;
+        if let Some(last_segment) = count_func_path.segments.last();
+        if let Some(args) = last_segment.args;
         if let Some(GenericArg::Type(real_ty)) = args.args.get(0);
 
         if let Some(def_id) = cx.qpath_res(count_func_qpath, count_func.hir_id).opt_def_id();
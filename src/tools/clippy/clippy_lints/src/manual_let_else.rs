@@ -0,0 +1,187 @@
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::higher::IfLet;
+use clippy_utils::source::{snippet_with_applicability, snippet_with_context};
+use clippy_utils::{meets_msrv, msrvs, path_to_local_id, peel_blocks};
+use rustc_errors::Applicability;
+use rustc_hir::{BindingAnnotation, Expr, ExprKind, MatchSource, Pat, PatKind, Stmt, StmtKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_semver::RustcVersion;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `let` statements whose initializer is a `match` or an `if let` that evaluates
+    /// to the binding of a pattern, and diverges, e.g. returns, otherwise.
+    ///
+    /// ### Why is this bad?
+    /// `let ... else` says the same without repeating the binding, and keeps the happy path
+    /// unindented.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # fn get() -> Option<u32> { None }
+    /// # fn f() {
+    /// let value = match get() {
+    ///     Some(value) => value,
+    ///     None => return,
+    /// };
+    /// let other = if let Some(other) = get() { other } else { return };
+    /// # }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # fn get() -> Option<u32> { None }
+    /// # fn f() {
+    /// let Some(value) = get() else { return };
+    /// let Some(other) = get() else { return };
+    /// # }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub MANUAL_LET_ELSE,
+    nursery,
+    "manual implementation of a `let ... else` statement"
+}
+
+pub struct ManualLetElse {
+    msrv: Option<RustcVersion>,
+}
+
+impl ManualLetElse {
+    #[must_use]
+    pub fn new(msrv: Option<RustcVersion>) -> Self {
+        Self { msrv }
+    }
+}
+
+impl_lint_pass!(ManualLetElse => [MANUAL_LET_ELSE]);
+
+impl<'tcx> LateLintPass<'tcx> for ManualLetElse {
+    fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx Stmt<'tcx>) {
+        if let StmtKind::Local(local) = stmt.kind
+            && let Some(init) = local.init
+            && local.els.is_none()
+            && local.ty.is_none()
+            && !stmt.span.from_expansion()
+            && let PatKind::Binding(..) = local.pat.kind
+            && meets_msrv(self.msrv, msrvs::LET_ELSE)
+            && let Some((scrutinee, pat, diverging)) = binding_or_diverge(cx, init)
+        {
+            let mut app = Applicability::MachineApplicable;
+            let Some(pat) = replace_binding(cx, pat, local.pat, &mut app) else {
+                return;
+            };
+            let (scrutinee_snip, _) = snippet_with_context(cx, scrutinee.span, stmt.span.ctxt(), "..", &mut app);
+            // `let ... else` doesn't allow the expression to end with a `}`, or to be a lazy boolean.
+            let scrutinee_snip = if scrutinee_snip.ends_with('}')
+                || matches!(scrutinee.kind, ExprKind::Binary(op, ..) if op.node.is_lazy())
+            {
+                format!("({scrutinee_snip})")
+            } else {
+                scrutinee_snip.into_owned()
+            };
+            let (diverging_snip, _) = snippet_with_context(cx, diverging.span, stmt.span.ctxt(), "..", &mut app);
+            let else_block = if let ExprKind::Block(..) = diverging.kind {
+                diverging_snip.into_owned()
+            } else {
+                format!("{{ {diverging_snip} }}")
+            };
+            span_lint_and_sugg(
+                cx,
+                MANUAL_LET_ELSE,
+                stmt.span,
+                "this could be rewritten as `let...else`",
+                "consider writing",
+                format!("let {pat} = {scrutinee_snip} else {else_block};"),
+                app,
+            );
+        }
+    }
+
+    extract_msrv_attr!(LateContext);
+}
+
+/// If the expression is a `match` or an `if let` which evaluates to the only binding of a
+/// pattern, and diverges otherwise, returns the matched expression, the pattern and the diverging
+/// expression.
+fn binding_or_diverge<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+) -> Option<(&'tcx Expr<'tcx>, &'tcx Pat<'tcx>, &'tcx Expr<'tcx>)> {
+    if let Some(IfLet {
+        let_pat,
+        let_expr,
+        if_then,
+        if_else: Some(if_else),
+    }) = IfLet::hir(cx, expr)
+    {
+        return (is_binding_value(let_pat, if_then) && diverges(cx, if_else)).then_some((let_expr, let_pat, if_else));
+    }
+
+    if let ExprKind::Match(scrutinee, [first, second], MatchSource::Normal) = expr.kind
+        && first.guard.is_none()
+        && second.guard.is_none()
+    {
+        [(first, second), (second, first)]
+            .into_iter()
+            .find(|(value_arm, diverging_arm)| {
+                is_binding_value(value_arm.pat, value_arm.body)
+                    // The `else` block can't use the bindings of the pattern that didn't match.
+                    && diverging_arm.pat.walk_short(|pat| !matches!(pat.kind, PatKind::Binding(..)))
+                    && diverges(cx, diverging_arm.body)
+            })
+            .map(|(value_arm, diverging_arm)| (scrutinee, value_arm.pat, diverging_arm.body))
+    } else {
+        None
+    }
+}
+
+/// Whether the pattern is refutable and has a single by-value binding, which the expression
+/// evaluates to.
+fn is_binding_value(pat: &Pat<'_>, expr: &Expr<'_>) -> bool {
+    if matches!(pat.kind, PatKind::Binding(..) | PatKind::Wild) {
+        return false;
+    }
+    let mut bindings = Vec::new();
+    pat.each_binding(|annotation, id, _, _| bindings.push((annotation, id)));
+    matches!(bindings[..], [(BindingAnnotation::NONE, id)] if path_to_local_id(peel_blocks(expr), id))
+}
+
+fn diverges(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    cx.typeck_results().expr_ty(expr).is_never()
+}
+
+/// Returns the snippet of the refutable pattern, with its binding replaced by the pattern of the
+/// `let` statement.
+fn replace_binding(
+    cx: &LateContext<'_>,
+    pat: &Pat<'_>,
+    local_pat: &Pat<'_>,
+    app: &mut Applicability,
+) -> Option<String> {
+    let mut binding = None;
+    pat.each_binding(|_, id, span, ident| binding = Some((id, span, ident)));
+    let (binding_id, binding_span, binding_ident) = binding?;
+    if pat.span.from_expansion() || !pat.span.contains(binding_span) {
+        return None;
+    }
+    let pat_snip = snippet_with_applicability(cx, pat.span, "..", app);
+    let local_pat_snip = snippet_with_applicability(cx, local_pat.span, "..", app);
+    // In `Struct { field }` the binding is also the field name, which has to be kept.
+    let mut is_shorthand_field = false;
+    pat.walk_always(|pat| {
+        if let PatKind::Struct(_, fields, _) = pat.kind {
+            is_shorthand_field |= fields
+                .iter()
+                .any(|field| field.is_shorthand && field.pat.hir_id == binding_id);
+        }
+    });
+    let replacement = if is_shorthand_field {
+        format!("{binding_ident}: {local_pat_snip}")
+    } else {
+        local_pat_snip.into_owned()
+    };
+    let lo = (binding_span.lo() - pat.span.lo()).0 as usize;
+    let hi = (binding_span.hi() - pat.span.lo()).0 as usize;
+    Some(format!("{}{replacement}{}", pat_snip.get(..lo)?, pat_snip.get(hi..)?))
+}
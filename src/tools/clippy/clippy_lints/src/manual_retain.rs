@@ -29,7 +29,8 @@
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for code to be replaced by `.retain()`.
+    /// Checks for code to be replaced by `.retain()`, including assignments through a mutable
+    /// reference like `*vec = vec.iter().filter(..).copied().collect()`.
     /// ### Why is this bad?
     /// `.retain()` is simpler and avoids needless allocation.
     /// ### Example
@@ -94,8 +95,8 @@ fn check_into_iter(
         && let Some(into_iter_def_id) = cx.typeck_results().type_dependent_def_id(into_iter_expr.hir_id)
         && Some(into_iter_def_id) == cx.tcx.lang_items().into_iter_fn()
         && match_acceptable_type(cx, left_expr, msrv)
-        && SpanlessEq::new(cx).eq_expr(left_expr, struct_expr) {
-        suggest(cx, parent_expr, left_expr, target_expr);
+        && is_assigned_to(cx, left_expr, struct_expr) {
+        suggest(cx, parent_expr, struct_expr, target_expr);
     }
 }
 
@@ -117,8 +118,8 @@ fn check_iter(
         && let Some(iter_expr_def_id) = cx.typeck_results().type_dependent_def_id(iter_expr.hir_id)
         && match_acceptable_def_path(cx, iter_expr_def_id)
         && match_acceptable_type(cx, left_expr, msrv)
-        && SpanlessEq::new(cx).eq_expr(left_expr, struct_expr) {
-        suggest(cx, parent_expr, left_expr, filter_expr);
+        && is_assigned_to(cx, left_expr, struct_expr) {
+        suggest(cx, parent_expr, struct_expr, filter_expr);
     }
 }
 
@@ -141,27 +142,35 @@ fn check_to_owned(
         && match_def_path(cx, chars_expr_def_id, &paths::STR_CHARS)
         && let ty = cx.typeck_results().expr_ty(str_expr).peel_refs()
         && is_type_diagnostic_item(cx, ty, sym::String)
-        && SpanlessEq::new(cx).eq_expr(left_expr, str_expr) {
-        suggest(cx, parent_expr, left_expr, filter_expr);
+        && is_assigned_to(cx, left_expr, str_expr) {
+        suggest(cx, parent_expr, str_expr, filter_expr);
     }
 }
 
-fn suggest(cx: &LateContext<'_>, parent_expr: &hir::Expr<'_>, left_expr: &hir::Expr<'_>, filter_expr: &hir::Expr<'_>) {
+/// Whether the collection is assigned the result of its own iterator, either directly or through
+/// a mutable reference, as in `*vec = vec.iter()...`.
+fn is_assigned_to(cx: &LateContext<'_>, left_expr: &hir::Expr<'_>, struct_expr: &hir::Expr<'_>) -> bool {
+    let mut eq = SpanlessEq::new(cx);
+    eq.eq_expr(left_expr, struct_expr)
+        || matches!(left_expr.kind, hir::ExprKind::Unary(hir::UnOp::Deref, inner) if eq.eq_expr(inner, struct_expr))
+}
+
+fn suggest(cx: &LateContext<'_>, parent_expr: &hir::Expr<'_>, recv_expr: &hir::Expr<'_>, filter_expr: &hir::Expr<'_>) {
     if let hir::ExprKind::MethodCall(_, _, [closure], _) = filter_expr.kind
         && let hir::ExprKind::Closure(&hir::Closure { body, ..}) = closure.kind
         && let filter_body = cx.tcx.hir().body(body)
         && let [filter_params] = filter_body.params
         && let Some(sugg) = match filter_params.pat.kind {
             hir::PatKind::Binding(_, _, filter_param_ident, None) => {
-                Some(format!("{}.retain(|{filter_param_ident}| {})", snippet(cx, left_expr.span, ".."), snippet(cx, filter_body.value.span, "..")))
+                Some(format!("{}.retain(|{filter_param_ident}| {})", snippet(cx, recv_expr.span, ".."), snippet(cx, filter_body.value.span, "..")))
             },
             hir::PatKind::Tuple([key_pat, value_pat], _) => {
-                make_sugg(cx, key_pat, value_pat, left_expr, filter_body)
+                make_sugg(cx, key_pat, value_pat, recv_expr, filter_body)
             },
             hir::PatKind::Ref(pat, _) => {
                 match pat.kind {
                     hir::PatKind::Binding(_, _, filter_param_ident, None) => {
-                        Some(format!("{}.retain(|{filter_param_ident}| {})", snippet(cx, left_expr.span, ".."), snippet(cx, filter_body.value.span, "..")))
+                        Some(format!("{}.retain(|{filter_param_ident}| {})", snippet(cx, recv_expr.span, ".."), snippet(cx, filter_body.value.span, "..")))
                     },
                     _ => None
                 }
@@ -184,25 +193,25 @@ fn make_sugg(
     cx: &LateContext<'_>,
     key_pat: &rustc_hir::Pat<'_>,
     value_pat: &rustc_hir::Pat<'_>,
-    left_expr: &hir::Expr<'_>,
+    recv_expr: &hir::Expr<'_>,
     filter_body: &hir::Body<'_>,
 ) -> Option<String> {
     match (&key_pat.kind, &value_pat.kind) {
         (hir::PatKind::Binding(_, _, key_param_ident, None), hir::PatKind::Binding(_, _, value_param_ident, None)) => {
             Some(format!(
                 "{}.retain(|{key_param_ident}, &mut {value_param_ident}| {})",
-                snippet(cx, left_expr.span, ".."),
+                snippet(cx, recv_expr.span, ".."),
                 snippet(cx, filter_body.value.span, "..")
             ))
         },
         (hir::PatKind::Binding(_, _, key_param_ident, None), hir::PatKind::Wild) => Some(format!(
             "{}.retain(|{key_param_ident}, _| {})",
-            snippet(cx, left_expr.span, ".."),
+            snippet(cx, recv_expr.span, ".."),
             snippet(cx, filter_body.value.span, "..")
         )),
         (hir::PatKind::Wild, hir::PatKind::Binding(_, _, value_param_ident, None)) => Some(format!(
             "{}.retain(|_, &mut {value_param_ident}| {})",
-            snippet(cx, left_expr.span, ".."),
+            snippet(cx, recv_expr.span, ".."),
             snippet(cx, filter_body.value.span, "..")
         )),
         _ => None,
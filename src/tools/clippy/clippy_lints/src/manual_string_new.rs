@@ -1,6 +1,7 @@
 use clippy_utils::diagnostics::span_lint_and_sugg;
 use rustc_ast::LitKind;
 use rustc_errors::Applicability::MachineApplicable;
+use rustc_hir::def::Res;
 use rustc_hir::{Expr, ExprKind, PathSegment, QPath, TyKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty;
@@ -13,6 +14,8 @@
     /// Checks for usage of `""` to create a `String`, such as `"".to_string()`, `"".to_owned()`,
     /// `String::from("")` and others.
     ///
+    /// `format!("")` is linted by `useless_format`.
+    ///
     /// ### Why is this bad?
     ///
     /// Different ways of creating an empty string makes your code less standardized, which can
@@ -114,18 +117,20 @@ fn parse_call(cx: &LateContext<'_>, span: Span, func: &Expr<'_>, args: &[Expr<'_
                 [sym::from, sym::try_from].contains(&path_seg.ident.name) &&
                 let TyKind::Path(qpath) = &ty.kind &&
                 let QPath::Resolved(_, path) = qpath &&
-                let [path_seg] = path.segments &&
-                path_seg.ident.name == sym::String &&
+                let Res::Def(_, def_id) = path.res &&
+                cx.tcx.is_diagnostic_item(sym::String, def_id) &&
                 is_expr_kind_empty_str(arg_kind)
             {
                 warn_then_suggest(cx, span);
             }
         } else if let QPath::Resolved(_, path) = qpath {
-            // From::from(...) or TryFrom::try_from(...)
+            // From::from(...), TryFrom::try_from(...), ToString::to_string(...) or ToOwned::to_owned(...)
             if  let [path_seg1, path_seg2] = path.segments &&
                 is_expr_kind_empty_str(arg_kind) && (
                     (path_seg1.ident.name == sym::From && path_seg2.ident.name == sym::from) ||
-                    (path_seg1.ident.name == sym::TryFrom && path_seg2.ident.name == sym::try_from)
+                    (path_seg1.ident.name == sym::TryFrom && path_seg2.ident.name == sym::try_from) ||
+                    (path_seg1.ident.name == sym::ToString && path_seg2.ident.name == sym::to_string) ||
+                    (path_seg1.ident.name == sym::ToOwned && path_seg2.ident.as_str() == "to_owned")
                 )
             {
                 warn_then_suggest(cx, span);
@@ -236,6 +236,8 @@
     /// messages on display. Therefore, it may be beneficial to look at the places
     /// where they may get displayed. Activate this lint to do just that.
     ///
+    /// Test code can be allowed with the `allow-unwrap-in-tests` configuration.
+    ///
     /// ### Examples
     /// ```rust
     /// # let option = Some(1);
@@ -281,6 +283,8 @@
     /// values. Normally, you want to implement more sophisticated error handling,
     /// and propagate errors upwards with `?` operator.
     ///
+    /// Test code can be allowed with the `allow-expect-in-tests` configuration.
+    ///
     /// ### Examples
     /// ```rust,ignore
     /// # let option = Some(1);
@@ -1631,6 +1635,11 @@
     ///     MaybeUninit::uninit().assume_init()
     /// };
     /// ```
+    ///
+    /// `MaybeUninit::zeroed().assume_init()` and `mem::zeroed()` are not linted here: the
+    /// compiler's `invalid_value` lint already warns about them for types that don't permit being
+    /// zeroed, like references, `Box`, `NonNull`, `NonZero*` integers and function pointers, even
+    /// when nested in structs.
     #[clippy::version = "1.39.0"]
     pub UNINIT_ASSUMED_INIT,
     correctness,
@@ -2218,7 +2227,8 @@
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for unnecessary calls to [`ToOwned::to_owned`](https://doc.rust-lang.org/std/borrow/trait.ToOwned.html#tymethod.to_owned)
-    /// and other `to_owned`-like functions.
+    /// and other `to_owned`-like functions, as well as calls to `clone` on arguments of functions
+    /// that only need a reference, e.g. `foo(&v.clone())`.
     ///
     /// ### Why is this bad?
     /// The unnecessary calls result in useless allocations.
@@ -2,9 +2,12 @@
 use super::unnecessary_iter_cloned::{self, is_into_iter};
 use clippy_utils::diagnostics::span_lint_and_sugg;
 use clippy_utils::source::snippet_opt;
-use clippy_utils::ty::{get_associated_type, get_iterator_item_ty, implements_trait, is_copy, peel_mid_ty_refs};
-use clippy_utils::visitors::find_all_ret_expressions;
-use clippy_utils::{fn_def_id, get_parent_expr, is_diag_item_method, is_diag_trait_item, return_ty};
+use clippy_utils::ty::{
+    get_associated_type, get_iterator_item_ty, implements_trait, is_copy, is_interior_mut_ty, is_type_diagnostic_item,
+    peel_mid_ty_refs,
+};
+use clippy_utils::visitors::{find_all_ret_expressions, is_local_used};
+use clippy_utils::{fn_def_id, get_parent_expr, is_diag_item_method, is_diag_trait_item, path_to_local, return_ty};
 use clippy_utils::{meets_msrv, msrvs};
 use rustc_errors::Applicability;
 use rustc_hir::{def_id::DefId, BorrowKind, Expr, ExprKind, ItemKind, Node};
@@ -49,6 +52,12 @@ pub fn check<'tcx>(
                     return;
                 }
                 check_other_call_arg(cx, expr, method_name, receiver);
+            } else if is_clone_of_non_copy(cx, expr, method_name, method_def_id) {
+                // Other uses of `clone` are left to `redundant_clone` and `implicit_clone`.
+                if check_clone_addr_of_arg(cx, expr, receiver) {
+                    return;
+                }
+                check_other_call_arg(cx, expr, method_name, receiver);
             }
         }
     }
@@ -285,6 +294,58 @@ fn check_other_call_arg<'tcx>(
     false
 }
 
+/// Checks whether `expr` is a call of `clone` whose result is borrowed and passed to a function,
+/// in which case the receiver can be borrowed instead.
+fn check_clone_addr_of_arg<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, receiver: &'tcx Expr<'tcx>) -> bool {
+    if let Some(parent) = get_parent_expr(cx, expr)
+        && let ExprKind::AddrOf(BorrowKind::Ref, Mutability::Not, _) = parent.kind
+        && let Some(call) = get_parent_expr(cx, parent)
+        && let Some((_, _, recv, call_args)) = get_callee_substs_and_args(cx, call)
+        && recv.into_iter().chain(call_args).any(|arg| arg.hir_id == parent.hir_id)
+        && !is_receiver_used_by_other_arg(cx, receiver, parent, recv, call_args)
+        && let (receiver_ty, n_receiver_refs @ (0 | 1)) = peel_mid_ty_refs(cx.typeck_results().expr_ty(receiver))
+        && receiver_ty == cx.typeck_results().expr_ty(expr)
+        // A clone of a `RefCell` can be borrowed mutably while the original is borrowed, and a
+        // clone of an `Rc` changes its reference count, so the clone may be intended.
+        && !is_interior_mut_ty(cx, receiver_ty)
+        && !is_type_diagnostic_item(cx, receiver_ty, sym::Rc)
+        && !is_type_diagnostic_item(cx, receiver_ty, sym::Arc)
+        && let Some(receiver_snippet) = snippet_opt(cx, receiver.span)
+    {
+        span_lint_and_sugg(
+            cx,
+            UNNECESSARY_TO_OWNED,
+            parent.span,
+            "unnecessary use of `clone`",
+            "use",
+            format!("{:&>width$}{receiver_snippet}", "", width = 1 - n_receiver_refs),
+            Applicability::MachineApplicable,
+        );
+        return true;
+    }
+    false
+}
+
+/// Returns true if the local the receiver is a place of, e.g. `x` in `x.field.clone()`, is also
+/// used by another argument of the call, which may move or mutate it while it is borrowed.
+fn is_receiver_used_by_other_arg<'tcx>(
+    cx: &LateContext<'tcx>,
+    mut receiver: &Expr<'_>,
+    arg: &Expr<'_>,
+    recv: Option<&'tcx Expr<'tcx>>,
+    call_args: &'tcx [Expr<'tcx>],
+) -> bool {
+    while let ExprKind::Field(base, _) = receiver.kind {
+        receiver = base;
+    }
+    let Some(local_id) = path_to_local(receiver) else {
+        return false;
+    };
+    recv.into_iter()
+        .chain(call_args)
+        .any(|other| other.hir_id != arg.hir_id && is_local_used(cx, other, local_id))
+}
+
 /// Walks an expression's ancestors until it finds a non-`AddrOf` expression. Returns the first such
 /// expression found (if any) along with the immediately prior expression.
 fn skip_addr_of_ancestors<'tcx>(
@@ -468,6 +529,19 @@ fn is_to_owned_like<'a>(cx: &LateContext<'a>, call_expr: &Expr<'a>, method_name:
         || is_to_string_on_string_like(cx, call_expr, method_name, method_def_id)
 }
 
+/// Returns true if the named method is `Clone::clone` and it's called on a type that isn't `Copy`.
+/// Clones of `Copy` types are linted by `clone_on_copy`.
+fn is_clone_of_non_copy<'a>(
+    cx: &LateContext<'a>,
+    call_expr: &Expr<'a>,
+    method_name: Symbol,
+    method_def_id: DefId,
+) -> bool {
+    method_name == sym::clone
+        && is_diag_trait_item(cx, method_def_id, sym::Clone)
+        && !is_copy(cx, cx.typeck_results().expr_ty(call_expr))
+}
+
 /// Returns true if the named method is `Cow::into_owned`.
 fn is_cow_into_owned(cx: &LateContext<'_>, method_name: Symbol, method_def_id: DefId) -> bool {
     method_name.as_str() == "into_owned" && is_diag_item_method(cx, method_def_id, sym::Cow)
@@ -18,6 +18,11 @@
     /// Checks for late initializations that can be replaced by a `let` statement
     /// with an initializer.
     ///
+    /// The local may be initialized by an assignment, or by assignments in every
+    /// branch of an `if` or `match` expression, as long as the statements before
+    /// it don't use the local. Mutable bindings are ignored, as declaring them
+    /// first is common when they are reassigned afterwards.
+    ///
     /// ### Why is this bad?
     /// Assigning in the `let` statement is less repetitive.
     ///
@@ -0,0 +1,187 @@
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::is_self;
+use clippy_utils::source::snippet;
+use clippy_utils::visitors::is_local_used;
+use rustc_errors::Applicability;
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, FnDecl, HirId, HirIdSet, Impl, ItemKind, MutTy, Mutability, Node, PatKind, TyKind};
+use rustc_hir_typeck::expr_use_visitor as euv;
+use rustc_infer::infer::TyCtxtInferExt;
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::mir::FakeReadCause;
+use rustc_middle::ty::{self, BorrowKind};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+use rustc_target::spec::abi::Abi;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for function arguments of type `&mut T` that are only used to read the value
+    /// behind the reference.
+    ///
+    /// The uses are found from the type checked body, so passing the reference on to a
+    /// function taking `&mut T`, which implicitly reborrows it, counts as a mutable use.
+    ///
+    /// ### Why is this bad?
+    /// A mutable reference can't be shared, so callers have to give up all other references to
+    /// the value for as long as the call lasts, even though the function doesn't need to mutate
+    /// it. It also misleads readers of the signature.
+    ///
+    /// ### Known problems
+    /// The function may have to take `&mut T` anyway, e.g. because it is passed where a function
+    /// taking a mutable reference is expected, or to ensure exclusive access in `unsafe` code.
+    ///
+    /// ### Example
+    /// ```rust
+    /// fn total(values: &mut Vec<u32>) -> u32 {
+    ///     values.iter().sum()
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// fn total(values: &Vec<u32>) -> u32 {
+    ///     values.iter().sum()
+    /// }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub NEEDLESS_PASS_BY_REF_MUT,
+    nursery,
+    "using a `&mut` argument only to read from it"
+}
+
+pub struct NeedlessPassByRefMut {
+    avoid_breaking_exported_api: bool,
+}
+
+impl NeedlessPassByRefMut {
+    pub fn new(avoid_breaking_exported_api: bool) -> Self {
+        Self {
+            avoid_breaking_exported_api,
+        }
+    }
+}
+
+impl_lint_pass!(NeedlessPassByRefMut => [NEEDLESS_PASS_BY_REF_MUT]);
+
+impl<'tcx> LateLintPass<'tcx> for NeedlessPassByRefMut {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        decl: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        span: Span,
+        hir_id: HirId,
+    ) {
+        if span.from_expansion() {
+            return;
+        }
+
+        let header = match kind {
+            FnKind::ItemFn(.., header) => header,
+            FnKind::Method(_, sig) => sig.header,
+            FnKind::Closure => return,
+        };
+        // The body of an `async fn` is a generator, whose uses of the arguments aren't visited.
+        if header.abi != Abi::Rust || header.is_async() {
+            return;
+        }
+
+        // The signature of trait methods is given by the trait.
+        if let Some(Node::Item(item)) = cx.tcx.hir().find(cx.tcx.hir().get_parent_node(hir_id))
+            && matches!(item.kind, ItemKind::Impl(Impl { of_trait: Some(_), .. }) | ItemKind::Trait(..))
+        {
+            return;
+        }
+
+        let fn_def_id = cx.tcx.hir().local_def_id(hir_id);
+        if self.avoid_breaking_exported_api && cx.effective_visibilities.is_exported(fn_def_id) {
+            return;
+        }
+
+        let fn_sig = cx.tcx.fn_sig(fn_def_id).skip_binder();
+        let candidates = body
+            .params
+            .iter()
+            .zip(decl.inputs)
+            .zip(fn_sig.inputs())
+            .filter_map(|((param, input), ty)| {
+                if let ty::Ref(_, _, Mutability::Mut) = ty.kind()
+                    && !is_self(param)
+                    && let PatKind::Binding(_, id, ident, None) = param.pat.kind
+                    && !ident.as_str().starts_with('_')
+                    && is_local_used(cx, body, id)
+                {
+                    Some((id, input))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return;
+        }
+
+        let mut ctx = MutablyUsedVariablesCtxt::default();
+        let infcx = cx.tcx.infer_ctxt().build();
+        euv::ExprUseVisitor::new(&mut ctx, &infcx, fn_def_id, cx.param_env, cx.typeck_results()).consume_body(body);
+
+        for (id, input) in candidates {
+            if !ctx.mutably_used_vars.contains(&id)
+                && let TyKind::Ref(lifetime, MutTy { ty: inner, .. }) = input.kind
+            {
+                let lifetime = snippet(cx, lifetime.span, "");
+                let inner = snippet(cx, inner.span, "_");
+                let sugg = if lifetime.is_empty() {
+                    format!("&{inner}")
+                } else {
+                    format!("&{lifetime} {inner}")
+                };
+                span_lint_and_sugg(
+                    cx,
+                    NEEDLESS_PASS_BY_REF_MUT,
+                    input.span,
+                    "this argument is a mutable reference, but not used mutably",
+                    "consider changing to",
+                    sugg,
+                    Applicability::MaybeIncorrect,
+                );
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct MutablyUsedVariablesCtxt {
+    mutably_used_vars: HirIdSet,
+}
+
+impl MutablyUsedVariablesCtxt {
+    fn add(&mut self, cmt: &euv::PlaceWithHirId<'_>) {
+        if let euv::PlaceBase::Local(vid) = cmt.place.base {
+            self.mutably_used_vars.insert(vid);
+        }
+    }
+}
+
+impl<'tcx> euv::Delegate<'tcx> for MutablyUsedVariablesCtxt {
+    fn consume(&mut self, cmt: &euv::PlaceWithHirId<'tcx>, _: HirId) {
+        // Moving the reference itself hands the mutable access on.
+        if cmt.place.projections.is_empty() {
+            self.add(cmt);
+        }
+    }
+
+    fn borrow(&mut self, cmt: &euv::PlaceWithHirId<'tcx>, _: HirId, kind: BorrowKind) {
+        if kind != BorrowKind::ImmBorrow {
+            self.add(cmt);
+        }
+    }
+
+    fn mutate(&mut self, cmt: &euv::PlaceWithHirId<'tcx>, _: HirId) {
+        self.add(cmt);
+    }
+
+    fn fake_read(&mut self, _: &euv::PlaceWithHirId<'tcx>, _: FakeReadCause, _: HirId) {}
+}
@@ -3,6 +3,7 @@
 use clippy_utils::ty::{is_type_diagnostic_item, match_type};
 use clippy_utils::{match_def_path, paths};
 use if_chain::if_chain;
+use rustc_ast::ast::LitKind;
 use rustc_errors::Applicability;
 use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
@@ -11,7 +12,8 @@
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for non-octal values used to set Unix file permissions.
+    /// Checks for non-octal values used to set Unix file permissions, i.e. decimal literals
+    /// which only have octal digits, like `644`.
     ///
     /// ### Why is this bad?
     /// They will be converted into octal, creating potentially
@@ -52,16 +54,10 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
                         && (match_type(cx, obj_ty, &paths::OPEN_OPTIONS)
                             || is_type_diagnostic_item(cx, obj_ty, sym::DirBuilder)))
                         || (path.ident.name == sym!(set_mode) && match_type(cx, obj_ty, &paths::PERMISSIONS));
-                    if let ExprKind::Lit(_) = param.kind;
+                    if is_octal_lookalike_decimal(cx, param);
 
                     then {
-                        let Some(snip) = snippet_opt(cx, param.span) else {
-                            return
-                        };
-
-                        if !snip.starts_with("0o") {
-                            show_error(cx, param);
-                        }
+                        show_error(cx, param);
                     }
                 }
             },
@@ -70,9 +66,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
                     if let ExprKind::Path(ref path) = func.kind;
                     if let Some(def_id) = cx.qpath_res(path, func.hir_id).opt_def_id();
                     if match_def_path(cx, def_id, &paths::PERMISSIONS_FROM_MODE);
-                    if let ExprKind::Lit(_) = param.kind;
-                    if let Some(snip) = snippet_opt(cx, param.span);
-                    if !snip.starts_with("0o");
+                    if is_octal_lookalike_decimal(cx, param);
                     then {
                         show_error(cx, param);
                     }
@@ -83,6 +77,23 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
     }
 }
 
+/// Whether the expression is a decimal literal which only has octal digits. Hexadecimal and binary
+/// literals are deliberate, and values below 8 are the same in octal.
+fn is_octal_lookalike_decimal(cx: &LateContext<'_>, param: &Expr<'_>) -> bool {
+    if let ExprKind::Lit(lit) = &param.kind
+        && let LitKind::Int(value, _) = lit.node
+        && value >= 8
+        && let Some(snip) = snippet_opt(cx, param.span)
+        && !["0x", "0o", "0b"].iter().any(|prefix| snip.starts_with(prefix))
+    {
+        // strip the suffix, e.g. `u32`
+        let digits = snip.split(|c: char| c.is_ascii_alphabetic()).next().unwrap_or_default();
+        digits.chars().all(|c| matches!(c, '0'..='7' | '_'))
+    } else {
+        false
+    }
+}
+
 fn show_error(cx: &LateContext<'_>, param: &Expr<'_>) {
     let mut applicability = Applicability::MachineApplicable;
     span_lint_and_sugg(
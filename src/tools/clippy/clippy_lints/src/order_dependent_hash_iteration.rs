@@ -0,0 +1,207 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::higher::ForLoop;
+use clippy_utils::macros::root_macro_call_first_node;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{get_enclosing_block, get_parent_node, is_trait_method, path_to_local_id, peel_blocks};
+use core::ops::ControlFlow;
+use rustc_hir::{Expr, ExprKind, Node, PatKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::Ty;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for iterations over a `HashMap` or `HashSet` whose order is observable: collecting
+    /// the elements into a `Vec`, `VecDeque` or `String`, or `for` loops and `for_each` calls
+    /// that print or write output, push to a sequence, or hash the elements.
+    ///
+    /// With the `warn-on-all-hash-iteration` configuration, every `for` loop and `for_each` call
+    /// over a hash-based collection is linted.
+    ///
+    /// ### Why is this bad?
+    /// The iteration order of hash-based collections is unspecified. With the default hasher, it
+    /// changes from one run of the program to the next, which makes output, tests and anything
+    /// derived from it non-deterministic.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// let scores: HashMap<&str, u32> = HashMap::new();
+    /// for (name, score) in &scores {
+    ///     println!("{name}: {score}");
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # use std::collections::BTreeMap;
+    /// let scores: BTreeMap<&str, u32> = BTreeMap::new();
+    /// for (name, score) in &scores {
+    ///     println!("{name}: {score}");
+    /// }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub ORDER_DEPENDENT_HASH_ITERATION,
+    restriction,
+    "iterating over a `HashMap` or `HashSet` where the order of the elements is observable"
+}
+
+pub struct OrderDependentHashIteration {
+    warn_on_all: bool,
+}
+
+impl OrderDependentHashIteration {
+    pub fn new(warn_on_all: bool) -> Self {
+        Self { warn_on_all }
+    }
+}
+
+impl_lint_pass!(OrderDependentHashIteration => [ORDER_DEPENDENT_HASH_ITERATION]);
+
+/// Methods of `HashMap` and `HashSet` which return an iterator.
+const ITER_METHODS: [&str; 9] = [
+    "drain",
+    "into_iter",
+    "into_keys",
+    "into_values",
+    "iter",
+    "iter_mut",
+    "keys",
+    "values",
+    "values_mut",
+];
+
+impl<'tcx> LateLintPass<'tcx> for OrderDependentHashIteration {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+
+        if let Some(for_loop) = ForLoop::hir(expr) {
+            if let Some(collection) = hash_collection(cx, for_loop.arg) {
+                self.check_body(cx, for_loop.arg.span, collection, for_loop.body);
+            }
+            return;
+        }
+
+        let ExprKind::MethodCall(path, recv, args, _) = expr.kind else {
+            return;
+        };
+        let Some(collection) = hash_collection(cx, recv) else {
+            return;
+        };
+        match (path.ident.as_str(), args) {
+            ("collect", []) if is_sequence(cx, cx.typeck_results().expr_ty(expr)) && !is_sorted_later(cx, expr) => {
+                emit(cx, expr.span, collection, None);
+            },
+            ("for_each", [closure]) => {
+                if let ExprKind::Closure(closure) = closure.kind {
+                    let body = cx.tcx.hir().body(closure.body);
+                    self.check_body(cx, recv.span, collection, body.value);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+impl OrderDependentHashIteration {
+    fn check_body<'tcx>(&self, cx: &LateContext<'tcx>, span: Span, collection: &'static str, body: &'tcx Expr<'tcx>) {
+        let observed = find_order_observation(cx, body);
+        if observed.is_some() || self.warn_on_all {
+            emit(cx, span, collection, observed);
+        }
+    }
+}
+
+fn emit(cx: &LateContext<'_>, span: Span, collection: &'static str, observed: Option<Span>) {
+    span_lint_and_then(
+        cx,
+        ORDER_DEPENDENT_HASH_ITERATION,
+        span,
+        &format!("iterating over a `{collection}`, whose order is unspecified"),
+        |diag| {
+            if let Some(observed) = observed {
+                diag.span_note(observed, "the order of the elements is observable here");
+            }
+            let ordered = if collection == "HashMap" { "BTreeMap" } else { "BTreeSet" };
+            diag.help(&format!("consider using a `{ordered}`, or sorting the elements first"));
+        },
+    );
+}
+
+/// If the expression is a `HashMap` or `HashSet`, a reference to one, or an iterator over one,
+/// returns the name of the collection.
+fn hash_collection(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<&'static str> {
+    let ty = cx.typeck_results().expr_ty(expr).peel_refs();
+    if is_type_diagnostic_item(cx, ty, sym::HashMap) {
+        return Some("HashMap");
+    }
+    if is_type_diagnostic_item(cx, ty, sym::HashSet) {
+        return Some("HashSet");
+    }
+    match expr.kind {
+        ExprKind::MethodCall(path, recv, ..) if ITER_METHODS.contains(&path.ident.as_str()) => {
+            hash_collection(cx, recv)
+        },
+        // Iterator adaptors keep the order of the underlying iterator.
+        ExprKind::MethodCall(_, recv, ..) if is_trait_method(cx, expr, sym::Iterator) => hash_collection(cx, recv),
+        _ => None,
+    }
+}
+
+/// Whether collecting into the type keeps the order of the elements.
+fn is_sequence<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    is_type_diagnostic_item(cx, ty, sym::Vec)
+        || is_type_diagnostic_item(cx, ty, sym::VecDeque)
+        || is_type_diagnostic_item(cx, ty, sym::LinkedList)
+        || is_type_diagnostic_item(cx, ty, sym::String)
+}
+
+/// Whether the collected sequence is bound to a local that is sorted afterwards.
+fn is_sorted_later(cx: &LateContext<'_>, collect: &Expr<'_>) -> bool {
+    if let Some(Node::Local(local)) = get_parent_node(cx.tcx, collect.hir_id)
+        && let PatKind::Binding(_, local_id, ..) = local.pat.kind
+        && let Some(block) = get_enclosing_block(cx, local.hir_id)
+    {
+        for_each_expr(block, |e| {
+            if let ExprKind::MethodCall(path, recv, ..) = e.kind
+                && path.ident.as_str().starts_with("sort")
+                && path_to_local_id(recv, local_id)
+            {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .is_some()
+    } else {
+        false
+    }
+}
+
+/// Finds an expression in the loop body whose effect depends on the order of the iterations:
+/// printing or writing, pushing to a sequence, or hashing.
+fn find_order_observation<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Expr<'tcx>) -> Option<Span> {
+    for_each_expr(peel_blocks(body), |e| {
+        if let Some(macro_call) = root_macro_call_first_node(cx, e)
+            && matches!(
+                cx.tcx.item_name(macro_call.def_id).as_str(),
+                "print" | "println" | "eprint" | "eprintln" | "write" | "writeln"
+            )
+        {
+            return ControlFlow::Break(macro_call.span);
+        }
+        if let ExprKind::MethodCall(path, recv, ..) = e.kind {
+            let pushes = matches!(path.ident.as_str(), "push" | "push_back" | "push_front" | "push_str")
+                && is_sequence(cx, cx.typeck_results().expr_ty(recv).peel_refs());
+            if pushes || is_trait_method(cx, e, sym::Hash) || is_trait_method(cx, e, sym::IoWrite) {
+                return ControlFlow::Break(e.span);
+            }
+        }
+        ControlFlow::Continue(())
+    })
+}
@@ -0,0 +1,139 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::is_in_allow_list;
+use clippy_utils::macros::{is_assert_macro, is_panic, root_macro_call_first_node};
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::visitors::{for_each_expr, Descend};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, ExprKind, FnDecl, HirId, ItemKind, Node};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Drop::drop` implementations that use `panic!`, assertions, `unwrap`, `expect`
+    /// or indexing directly in their body.
+    ///
+    /// Guard types that are meant to panic when they are dropped can be listed in the
+    /// `allowed-panicking-drop-types` configuration.
+    ///
+    /// ### Why is this bad?
+    /// Values are also dropped while the thread unwinds from another panic. Panicking a second
+    /// time during unwinding aborts the whole process, without running any further destructors.
+    ///
+    /// ### Known problems
+    /// Indexing is linted even if the index is known to be in bounds, and functions called from
+    /// `drop` may panic as well. This is not checked.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::io::Write;
+    /// struct Output(std::fs::File);
+    ///
+    /// impl Drop for Output {
+    ///     fn drop(&mut self) {
+    ///         self.0.flush().unwrap();
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # use std::io::Write;
+    /// struct Output(std::fs::File);
+    ///
+    /// impl Drop for Output {
+    ///     fn drop(&mut self) {
+    ///         if let Err(e) = self.0.flush() {
+    ///             eprintln!("failed to flush the output: {e}");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub PANIC_IN_DROP,
+    pedantic,
+    "`Drop` implementations that may panic"
+}
+
+pub struct PanicInDrop {
+    allowed_types: FxHashSet<String>,
+}
+
+impl PanicInDrop {
+    pub fn new(allowed_types: &[String]) -> Self {
+        Self {
+            allowed_types: allowed_types.iter().cloned().collect(),
+        }
+    }
+}
+
+impl_lint_pass!(PanicInDrop => [PANIC_IN_DROP]);
+
+impl<'tcx> LateLintPass<'tcx> for PanicInDrop {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        span: Span,
+        hir_id: HirId,
+    ) {
+        if let FnKind::Method(..) = kind
+            && let Some(Node::Item(item)) = cx.tcx.hir().find_by_def_id(cx.tcx.hir().get_parent_item(hir_id).def_id)
+            && let ItemKind::Impl(imp) = &item.kind
+            && let Some(trait_ref) = &imp.of_trait
+            && trait_ref.trait_def_id() == cx.tcx.lang_items().drop_trait()
+            && !cx
+                .tcx
+                .type_of(item.owner_id)
+                .ty_adt_def()
+                .map_or(false, |adt| is_in_allow_list(cx, adt.did(), &self.allowed_types, false))
+        {
+            let panics = find_panics(cx, body);
+            if !panics.is_empty() {
+                span_lint_and_then(cx, PANIC_IN_DROP, span, "this `drop` implementation may panic", |diag| {
+                    let note = if panics.len() == 1 { "this may panic" } else { "these may panic" };
+                    diag.span_note(panics, note);
+                    diag.help("a panic while the thread is already unwinding aborts the process");
+                });
+            }
+        }
+    }
+}
+
+fn find_panics<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'_>) -> Vec<Span> {
+    let mut panics = Vec::new();
+    let _: Option<!> = for_each_expr(body.value, |e| {
+        if let Some(macro_call) = root_macro_call_first_node(cx, e)
+            && (is_panic(cx, macro_call.def_id)
+                || is_assert_macro(cx, macro_call.def_id)
+                || matches!(
+                    cx.tcx.get_diagnostic_name(macro_call.def_id),
+                    Some(sym::todo_macro | sym::unimplemented_macro | sym::unreachable_macro)
+                ))
+        {
+            panics.push(macro_call.span);
+            return ControlFlow::Continue(Descend::No);
+        }
+        match e.kind {
+            // A closure isn't necessarily called from `drop`.
+            ExprKind::Closure(..) => return ControlFlow::Continue(Descend::No),
+            ExprKind::MethodCall(path, recv, ..) if matches!(path.ident.as_str(), "unwrap" | "expect") => {
+                let recv_ty = cx.typeck_results().expr_ty(recv).peel_refs();
+                if is_type_diagnostic_item(cx, recv_ty, sym::Option)
+                    || is_type_diagnostic_item(cx, recv_ty, sym::Result)
+                {
+                    panics.push(e.span);
+                }
+            },
+            ExprKind::Index(..) => panics.push(e.span),
+            _ => {},
+        }
+        ControlFlow::Continue(Descend::Yes)
+    });
+    panics
+}
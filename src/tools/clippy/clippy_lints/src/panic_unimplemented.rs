@@ -1,8 +1,9 @@
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::macros::{is_panic, root_macro_call_first_node};
+use clippy_utils::is_in_test;
 use rustc_hir::Expr;
 use rustc_lint::{LateContext, LateLintPass};
-use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -11,6 +12,9 @@
     /// ### Why is this bad?
     /// `panic!` will stop the execution of the executable
     ///
+    /// Test functions and `#[cfg(test)]` modules can be allowed with the
+    /// `allow-panic-in-tests` configuration.
+    ///
     /// ### Example
     /// ```no_run
     /// panic!("even with a good reason");
@@ -28,6 +32,9 @@
     /// ### Why is this bad?
     /// This macro should not be present in production code
     ///
+    /// Test functions and `#[cfg(test)]` modules are not linted, unless the
+    /// `allow-todo-in-tests` configuration is set to `false`.
+    ///
     /// ### Example
     /// ```no_run
     /// unimplemented!();
@@ -45,6 +52,9 @@
     /// ### Why is this bad?
     /// This macro should not be present in production code
     ///
+    /// Test functions and `#[cfg(test)]` modules are not linted, unless the
+    /// `allow-todo-in-tests` configuration is set to `false`.
+    ///
     /// ### Example
     /// ```no_run
     /// todo!();
@@ -72,13 +82,29 @@
     "usage of the `unreachable!` macro"
 }
 
-declare_lint_pass!(PanicUnimplemented => [UNIMPLEMENTED, UNREACHABLE, TODO, PANIC]);
+pub struct PanicUnimplemented {
+    allow_todo_in_tests: bool,
+    allow_panic_in_tests: bool,
+}
+
+impl PanicUnimplemented {
+    pub fn new(allow_todo_in_tests: bool, allow_panic_in_tests: bool) -> Self {
+        Self {
+            allow_todo_in_tests,
+            allow_panic_in_tests,
+        }
+    }
+}
+
+impl_lint_pass!(PanicUnimplemented => [UNIMPLEMENTED, UNREACHABLE, TODO, PANIC]);
 
 impl<'tcx> LateLintPass<'tcx> for PanicUnimplemented {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
         let Some(macro_call) = root_macro_call_first_node(cx, expr) else { return };
         if is_panic(cx, macro_call.def_id) {
-            if cx.tcx.hir().is_inside_const_context(expr.hir_id) {
+            if cx.tcx.hir().is_inside_const_context(expr.hir_id)
+                || (self.allow_panic_in_tests && is_in_test(cx.tcx, expr.hir_id))
+            {
                 return;
             }
 
@@ -90,7 +116,14 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
             );
             return;
         }
-        match cx.tcx.item_name(macro_call.def_id).as_str() {
+        let name = cx.tcx.item_name(macro_call.def_id);
+        if matches!(name.as_str(), "todo" | "unimplemented")
+            && self.allow_todo_in_tests
+            && is_in_test(cx.tcx, expr.hir_id)
+        {
+            return;
+        }
+        match name.as_str() {
             "todo" => {
                 span_lint(
                     cx,
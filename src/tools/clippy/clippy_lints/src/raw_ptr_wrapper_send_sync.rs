@@ -0,0 +1,171 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::is_in_allow_list;
+use clippy_utils::ty::contains_adt_constructor;
+use rustc_ast::ImplPolarity;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Item, ItemKind, Unsafety};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, AdtDef, AssocKind, FieldDef, SubstsRef};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `unsafe impl Send` and `unsafe impl Sync` on structs whose only field is a raw
+    /// pointer, if safe code can put an arbitrary pointer into the struct, either through a public
+    /// field or through a public safe function taking a raw pointer and returning the struct.
+    ///
+    /// Types that were checked to be fine can be listed in the `allowed-raw-ptr-wrappers`
+    /// configuration.
+    ///
+    /// ### Why is this bad?
+    /// Such an impl promises that the pointee can be used from any thread. As the pointer can come
+    /// from anywhere, e.g. from an `Rc` or a thread local, that promise can't be kept by the
+    /// wrapper itself. Either the constructor should be `unsafe` and document what the pointer has
+    /// to uphold, or the impl should explain why any pointer is fine.
+    ///
+    /// ### Known problems
+    /// The lint doesn't check whether the constructor actually stores the pointer it is given.
+    ///
+    /// ### Example
+    /// ```rust
+    /// pub struct Handle(*mut u8);
+    ///
+    /// impl Handle {
+    ///     pub fn new(ptr: *mut u8) -> Self {
+    ///         Self(ptr)
+    ///     }
+    /// }
+    ///
+    /// unsafe impl Send for Handle {}
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// pub struct Handle(*mut u8);
+    ///
+    /// impl Handle {
+    ///     /// # Safety
+    ///     ///
+    ///     /// `ptr` must be valid to use from any thread.
+    ///     pub unsafe fn new(ptr: *mut u8) -> Self {
+    ///         Self(ptr)
+    ///     }
+    /// }
+    ///
+    /// unsafe impl Send for Handle {}
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub RAW_PTR_WRAPPER_SEND_SYNC,
+    suspicious,
+    "`Send` or `Sync` impls for raw pointer wrappers that safe code can construct from any pointer"
+}
+
+pub struct RawPtrWrapperSendSync {
+    allowed_types: FxHashSet<String>,
+}
+
+impl RawPtrWrapperSendSync {
+    pub fn new(allowed_types: &[String]) -> Self {
+        Self {
+            allowed_types: allowed_types.iter().cloned().collect(),
+        }
+    }
+}
+
+impl_lint_pass!(RawPtrWrapperSendSync => [RAW_PTR_WRAPPER_SEND_SYNC]);
+
+impl<'tcx> LateLintPass<'tcx> for RawPtrWrapperSendSync {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Impl(hir_impl) = &item.kind
+            && hir_impl.unsafety == Unsafety::Unsafe
+            && hir_impl.polarity == ImplPolarity::Positive
+            && !in_external_macro(cx.sess(), item.span)
+            && let Some(trait_id) = hir_impl.of_trait.as_ref().and_then(|trait_ref| trait_ref.trait_def_id())
+            && let Some(trait_name) = send_or_sync(cx, trait_id)
+            && let Some(trait_ref) = cx.tcx.impl_trait_ref(item.owner_id)
+            && let ty::Adt(adt, substs) = trait_ref.self_ty().kind()
+            && adt.is_struct()
+            && let Some(ptr_field) = only_raw_ptr_field(cx, *adt, substs)
+            && !is_in_allow_list(cx, adt.did(), &self.allowed_types, false)
+            && let Some((span, note, help)) = safe_ptr_setter(cx, *adt, ptr_field)
+        {
+            let ty_name = cx.tcx.item_name(adt.did());
+            span_lint_and_then(
+                cx,
+                RAW_PTR_WRAPPER_SEND_SYNC,
+                item.span,
+                &format!("`unsafe impl {trait_name}` for `{ty_name}`, which wraps a raw pointer safe code can set"),
+                |diag| {
+                    diag.span_note(span, &note);
+                    diag.help(help);
+                },
+            );
+        }
+    }
+}
+
+fn send_or_sync(cx: &LateContext<'_>, trait_id: DefId) -> Option<&'static str> {
+    if cx.tcx.is_diagnostic_item(sym::Send, trait_id) {
+        Some("Send")
+    } else if cx.tcx.lang_items().sync_trait() == Some(trait_id) {
+        Some("Sync")
+    } else {
+        None
+    }
+}
+
+/// Returns the field of the struct if it is a raw pointer and, apart from `PhantomData`s, the
+/// only field.
+fn only_raw_ptr_field<'tcx>(
+    cx: &LateContext<'tcx>,
+    adt: AdtDef<'tcx>,
+    substs: SubstsRef<'tcx>,
+) -> Option<&'tcx FieldDef> {
+    let mut fields = adt
+        .non_enum_variant()
+        .fields
+        .iter()
+        .filter(|field| !field.ty(cx.tcx, substs).ty_adt_def().map_or(false, AdtDef::is_phantom_data));
+    match (fields.next(), fields.next()) {
+        (Some(field), None) if field.ty(cx.tcx, substs).is_unsafe_ptr() => Some(field),
+        _ => None,
+    }
+}
+
+/// Finds a way for safe code to store an arbitrary pointer in the struct, and returns its span
+/// along with a note and a help message to emit.
+fn safe_ptr_setter<'tcx>(
+    cx: &LateContext<'tcx>,
+    adt: AdtDef<'tcx>,
+    ptr_field: &FieldDef,
+) -> Option<(Span, String, &'static str)> {
+    if ptr_field.vis.is_public() {
+        return Some((
+            cx.tcx.def_span(ptr_field.did),
+            "the pointer field is public, so any pointer can be stored in it".to_owned(),
+            "consider making the field private and adding an `unsafe` constructor",
+        ));
+    }
+
+    cx.tcx
+        .inherent_impls(adt.did())
+        .iter()
+        .flat_map(|&impl_id| cx.tcx.associated_items(impl_id).in_definition_order())
+        .filter(|assoc| assoc.kind == AssocKind::Fn && cx.tcx.visibility(assoc.def_id).is_public())
+        .find(|assoc| {
+            let sig = cx.tcx.fn_sig(assoc.def_id).skip_binder();
+            sig.unsafety == Unsafety::Normal
+                && sig.inputs().iter().any(|input| input.is_unsafe_ptr())
+                && contains_adt_constructor(sig.output(), adt)
+        })
+        .map(|assoc| {
+            (
+                cx.tcx.def_span(assoc.def_id),
+                format!("any pointer can be passed to `{}`", assoc.name),
+                "consider making the function `unsafe` and documenting what the pointer has to uphold",
+            )
+        })
+}
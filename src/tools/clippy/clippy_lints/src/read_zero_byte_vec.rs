@@ -1,5 +1,5 @@
 use clippy_utils::{
-    diagnostics::{span_lint, span_lint_and_sugg},
+    diagnostics::{span_lint_and_help, span_lint_and_sugg},
     higher::{get_vec_init_kind, VecInitKind},
     source::snippet,
     visitors::for_each_expr,
@@ -68,24 +68,24 @@ fn check_block(&mut self, cx: &LateContext<'tcx>, block: &hir::Block<'tcx>) {
                         && let [inner_seg] = inner_path.segments
                         && ident.name == inner_seg.ident.name
                     {
-                        ControlFlow::Break(())
+                        ControlFlow::Break(read_or_read_exact.name)
                     } else {
                         ControlFlow::Continue(())
                     }
                 };
 
-                let (read_found, next_stmt_span) =
+                let (read_method, next_stmt_span) =
                 if let Some(next_stmt) = block.stmts.get(idx + 1) {
                     // case { .. stmt; stmt; .. }
-                    (for_each_expr(next_stmt, visitor).is_some(), next_stmt.span)
+                    (for_each_expr(next_stmt, visitor), next_stmt.span)
                 } else if let Some(e) = block.expr {
                     // case { .. stmt; expr }
-                    (for_each_expr(e, visitor).is_some(), e.span)
+                    (for_each_expr(e, visitor), e.span)
                 } else {
                     return
                 };
 
-                if read_found && !next_stmt_span.from_expansion() {
+                if let Some(read_method) = read_method && !next_stmt_span.from_expansion() {
                     let applicability = Applicability::MaybeIncorrect;
                     match vec_init_kind {
                         VecInitKind::WithConstCapacity(len) => {
@@ -119,13 +119,20 @@ fn check_block(&mut self, cx: &LateContext<'tcx>, block: &hir::Block<'tcx>) {
                             );
                         }
                         _ => {
-                            span_lint(
+                            let help = if read_method.as_str() == "read" {
+                                "resize the `Vec` to the number of bytes to read first, \
+                                or use `read_to_end` to read all the data"
+                            } else {
+                                "resize the `Vec` to the number of bytes to read first"
+                            };
+                            span_lint_and_help(
                                 cx,
                                 READ_ZERO_BYTE_VEC,
                                 next_stmt_span,
                                 "reading zero byte data to `Vec`",
+                                None,
+                                help,
                             );
-
                         }
                     }
                 }
@@ -33,7 +33,8 @@ macro_rules! unwrap_or_continue {
     /// allocations and deallocations generated by redundant `clone()`s.
     ///
     /// ### Known problems
-    /// False-negatives: analysis performed by this lint is conservative and limited.
+    /// False-negatives: analysis performed by this lint is conservative and limited. In
+    /// particular, clones of values that are held across an `.await` are not linted.
     ///
     /// ### Example
     /// ```rust
@@ -129,9 +130,12 @@ fn check_fn(
             // `Local` to be cloned, and a local of `clone` call's destination
             let (local, ret_local) = if from_borrow {
                 // `res = clone(arg)` can be turned into `res = move arg;`
-                // if `arg` is the only borrow of `cloned` at this point.
+                // if `arg` is the only borrow of `cloned` at this point, apart from closures
+                // which aren't used anymore.
 
-                if cannot_move_out || !possible_borrower.only_borrowers(&[arg], cloned, loc) {
+                if cannot_move_out
+                    || !only_borrowers_or_unused_closures(cx, mir, &mut possible_borrower, arg, cloned, loc)
+                {
                     continue;
                 }
 
@@ -257,6 +261,41 @@ fn check_fn(
     }
 }
 
+/// Returns true if `arg` is the only borrower of `cloned` living at `loc`, ignoring closures which
+/// capture `cloned` by reference but aren't used anymore.
+fn only_borrowers_or_unused_closures<'tcx>(
+    cx: &LateContext<'tcx>,
+    mir: &mir::Body<'tcx>,
+    possible_borrower: &mut PossibleBorrowerMap<'_, 'tcx>,
+    arg: mir::Local,
+    cloned: mir::Local,
+    loc: mir::Location,
+) -> bool {
+    if possible_borrower.only_borrowers(&[arg], cloned, loc) {
+        return true;
+    }
+
+    let Some(borrowers) = possible_borrower.map.get(&cloned) else {
+        return false;
+    };
+    let closures: Vec<_> = borrowers
+        .iter()
+        .filter(|&b| {
+            let local_ty = mir.local_decls[b].ty;
+            b != arg && matches!(local_ty.kind(), ty::Closure(..)) && !local_ty.needs_drop(cx.tcx, cx.param_env)
+        })
+        .collect();
+    if closures.is_empty() {
+        return false;
+    }
+
+    let unused = visit_local_usage(&closures, mir, loc)
+        .map_or(false, |usages| usages.iter().all(|usage| usage.local_use_locs.is_empty()));
+    let mut allowed = closures;
+    allowed.push(arg);
+    unused && possible_borrower.bounded_borrowers(&[arg], &allowed, cloned, loc)
+}
+
 /// If `kind` is `y = func(x: &T)` where `T: !Copy`, returns `(DefId of func, x, T, y)`.
 fn is_call_with_ref_arg<'tcx>(
     cx: &LateContext<'tcx>,
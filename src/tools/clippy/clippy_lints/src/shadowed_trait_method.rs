@@ -0,0 +1,171 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::peel_blocks;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Body, ExprKind, ImplItem, ImplItemKind, Mutability};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, AdtDef, AssocKind};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for inherent methods with the same name and the same kind of `self` receiver as a
+    /// method of a trait the type implements, unless the inherent method just calls the trait
+    /// method.
+    ///
+    /// ### Why is this bad?
+    /// Method call syntax picks the inherent method, so `x.method()` may not run the code that
+    /// readers expect, and the two implementations easily drift apart. Generic code that only
+    /// knows about the trait calls the trait method instead.
+    ///
+    /// This is a stricter, more targeted version of `same_name_method`: methods taking a
+    /// different kind of `self` and methods delegating to the trait method are not linted.
+    ///
+    /// ### Example
+    /// ```rust
+    /// trait Describe {
+    ///     fn describe(&self) -> String;
+    /// }
+    ///
+    /// struct Point(i32, i32);
+    ///
+    /// impl Describe for Point {
+    ///     fn describe(&self) -> String {
+    ///         format!("({}, {})", self.0, self.1)
+    ///     }
+    /// }
+    ///
+    /// impl Point {
+    ///     fn describe(&self) -> String {
+    ///         format!("{} {}", self.0, self.1)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # trait Describe {
+    /// #     fn describe(&self) -> String;
+    /// # }
+    /// # struct Point(i32, i32);
+    /// # impl Describe for Point {
+    /// #     fn describe(&self) -> String {
+    /// #         format!("({}, {})", self.0, self.1)
+    /// #     }
+    /// # }
+    /// impl Point {
+    ///     fn describe_plain(&self) -> String {
+    ///         format!("{} {}", self.0, self.1)
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub SHADOWED_TRAIT_METHOD,
+    restriction,
+    "inherent methods that shadow a method of an implemented trait"
+}
+
+#[derive(Default)]
+pub struct ShadowedTraitMethod {
+    /// The local trait impls of each type, as `(trait, impl)` pairs.
+    trait_impls: FxHashMap<DefId, Vec<(DefId, DefId)>>,
+}
+
+impl_lint_pass!(ShadowedTraitMethod => [SHADOWED_TRAIT_METHOD]);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Receiver {
+    Value,
+    Ref(Mutability),
+}
+
+impl<'tcx> LateLintPass<'tcx> for ShadowedTraitMethod {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for (&trait_id, trait_impls) in cx.tcx.all_local_trait_impls(()) {
+            for trait_impl_id in trait_impls.iter().map(|id| id.to_def_id()) {
+                if let Some(trait_ref) = cx.tcx.impl_trait_ref(trait_impl_id)
+                    && let Some(adt) = trait_ref.self_ty().ty_adt_def()
+                {
+                    self.trait_impls.entry(adt.did()).or_default().push((trait_id, trait_impl_id));
+                }
+            }
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, impl_item: &'tcx ImplItem<'_>) {
+        if let ImplItemKind::Fn(_, body_id) = impl_item.kind
+            && !in_external_macro(cx.sess(), impl_item.span)
+            && let method = cx.tcx.associated_item(impl_item.owner_id)
+            && method.fn_has_self_parameter
+            && let Some(impl_id) = cx.tcx.impl_of_method(method.def_id)
+            && cx.tcx.impl_trait_ref(impl_id).is_none()
+            && let Some(adt) = cx.tcx.type_of(impl_id).ty_adt_def()
+            && let Some(receiver) = receiver_kind(cx, method.def_id, adt)
+        {
+            let Some(trait_impls) = self.trait_impls.get(&adt.did()) else {
+                return;
+            };
+            let callee = delegated_callee(cx, cx.tcx.hir().body(body_id));
+            for &(trait_id, trait_impl_id) in trait_impls {
+                if let Some(trait_method) = cx
+                    .tcx
+                    .associated_items(trait_id)
+                    .filter_by_name_unhygienic(method.name)
+                    .find(|item| item.kind == AssocKind::Fn && item.fn_has_self_parameter)
+                    && receiver_kind(cx, trait_method.def_id, adt) == Some(receiver)
+                    && callee != Some(trait_method.def_id)
+                {
+                    // Point at the implementation of the method, if the impl overrides it.
+                    let impl_span = cx
+                        .tcx
+                        .associated_items(trait_impl_id)
+                        .in_definition_order()
+                        .find(|item| item.trait_item_def_id == Some(trait_method.def_id))
+                        .map_or_else(|| cx.tcx.def_span(trait_impl_id), |item| cx.tcx.def_span(item.def_id));
+                    let trait_method_path = format!("{}::{}", cx.tcx.def_path_str(trait_id), method.name);
+                    span_lint_and_then(
+                        cx,
+                        SHADOWED_TRAIT_METHOD,
+                        cx.tcx.def_span(method.def_id),
+                        &format!("this method shadows `{trait_method_path}`"),
+                        |diag| {
+                            diag.span_note(impl_span, &format!("`{trait_method_path}` is implemented here"));
+                            diag.help(
+                                "method calls pick the inherent method; \
+                                consider renaming it, or calling the trait method from it",
+                            );
+                        },
+                    );
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the kind of the `self` receiver of the method, if it is `self`, `&self` or `&mut self`.
+fn receiver_kind<'tcx>(cx: &LateContext<'tcx>, method_id: DefId, adt: AdtDef<'tcx>) -> Option<Receiver> {
+    let self_ty = *cx.tcx.fn_sig(method_id).skip_binder().inputs().first()?;
+    match *self_ty.kind() {
+        ty::Ref(_, _, mutability) => Some(Receiver::Ref(mutability)),
+        // `Self` in a trait, and the type itself in an inherent impl.
+        ty::Param(_) => Some(Receiver::Value),
+        ty::Adt(self_adt, _) if self_adt == adt => Some(Receiver::Value),
+        _ => None,
+    }
+}
+
+/// If the body does nothing but call a function, returns that function.
+fn delegated_callee(cx: &LateContext<'_>, body: &Body<'_>) -> Option<DefId> {
+    let typeck = cx.tcx.typeck_body(body.id());
+    let expr = peel_blocks(body.value);
+    match expr.kind {
+        ExprKind::Call(func, _) => match func.kind {
+            ExprKind::Path(ref qpath) => typeck.qpath_res(qpath, func.hir_id).opt_def_id(),
+            _ => None,
+        },
+        ExprKind::MethodCall(..) => typeck.type_dependent_def_id(expr.hir_id),
+        _ => None,
+    }
+}
@@ -0,0 +1,170 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::macros::{root_macro_call_first_node, FormatArgsExpn};
+use clippy_utils::ty::match_type;
+use clippy_utils::{match_def_path, path_def_id, paths, peel_hir_expr_refs};
+use rustc_ast::ast::LitKind;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for commands running a shell with `-c` (or `/C` for `cmd`), whose script is built
+    /// with `format!`, like `Command::new("sh").arg("-c").arg(format!("ls {dir}"))`.
+    ///
+    /// The shells can be configured with the `shell-binaries` option.
+    ///
+    /// ### Why is this bad?
+    /// The shell interprets the interpolated values. Spaces split them into several
+    /// arguments, and quotes, `;`, `$(..)` and the like run other commands, which is a command
+    /// injection if the values come from the user. Passing the values as arguments of the
+    /// program itself avoids the shell altogether.
+    ///
+    /// ### Known problems
+    /// Only method chains starting with `Command::new` are checked.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// # use std::process::Command;
+    /// # let dir = "";
+    /// Command::new("sh").arg("-c").arg(format!("ls {dir}")).status();
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,no_run
+    /// # use std::process::Command;
+    /// # let dir = "";
+    /// Command::new("ls").arg(dir).status();
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub SHELL_COMMAND_FROM_FORMAT,
+    pedantic,
+    "running a shell script built with `format!`"
+}
+
+pub struct ShellCommandFromFormat {
+    shell_binaries: FxHashSet<String>,
+}
+
+impl ShellCommandFromFormat {
+    pub fn new(shell_binaries: &[String]) -> Self {
+        Self {
+            shell_binaries: shell_binaries.iter().cloned().collect(),
+        }
+    }
+}
+
+impl_lint_pass!(ShellCommandFromFormat => [SHELL_COMMAND_FROM_FORMAT]);
+
+impl<'tcx> LateLintPass<'tcx> for ShellCommandFromFormat {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let ExprKind::MethodCall(path, recv, [arg], _) = expr.kind else {
+            return;
+        };
+        if expr.span.from_expansion()
+            || !match_type(cx, cx.typeck_results().expr_ty(recv).peel_refs(), &paths::COMMAND)
+        {
+            return;
+        }
+
+        let (flag_in_args, formatted) = match path.ident.as_str() {
+            "arg" if is_interpolating_format(cx, arg) => (false, arg),
+            "args" => {
+                let ExprKind::Array(elems) = peel_hir_expr_refs(arg).0.kind else {
+                    return;
+                };
+                let Some(formatted) = elems.iter().find(|elem| is_interpolating_format(cx, *elem)) else {
+                    return;
+                };
+                (elems.iter().any(is_command_flag), formatted)
+            },
+            _ => return,
+        };
+
+        if let Some((program, flag_in_chain)) = command_chain(cx, recv)
+            && (flag_in_args || flag_in_chain)
+            && self.is_shell(program.as_str())
+        {
+            span_lint_and_help(
+                cx,
+                SHELL_COMMAND_FROM_FORMAT,
+                formatted.span,
+                "shell script built with `format!`",
+                None,
+                "pass the values as arguments of the program instead of going through the shell",
+            );
+        }
+    }
+}
+
+impl ShellCommandFromFormat {
+    fn is_shell(&self, program: &str) -> bool {
+        let name = program.rsplit(['/', '\\']).next().unwrap_or(program);
+        self.shell_binaries.contains(name)
+    }
+}
+
+/// Whether the expression is a `format!` call with at least one argument, possibly borrowed.
+fn is_interpolating_format<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> bool {
+    let (expr, _) = peel_hir_expr_refs(expr);
+    if let Some(macro_call) = root_macro_call_first_node(cx, expr)
+        && cx.tcx.is_diagnostic_item(sym::format_macro, macro_call.def_id)
+        && let Some(format_args) = FormatArgsExpn::find_nested(cx, expr, macro_call.expn)
+    {
+        !format_args.args.is_empty()
+    } else {
+        false
+    }
+}
+
+/// Whether the expression is the flag making a shell run its argument as a script, e.g. `-c`,
+/// `-ec`, `/C` or PowerShell's `-Command`. Shells on Windows ignore the case of their flags.
+fn is_command_flag(expr: &Expr<'_>) -> bool {
+    if let ExprKind::Lit(lit) = &peel_hir_expr_refs(expr).0.kind
+        && let LitKind::Str(flag, _) = lit.node
+    {
+        let flag = flag.as_str();
+        flag.eq_ignore_ascii_case("/c")
+            || flag
+                .strip_prefix('-')
+                .map_or(false, |flags| !flags.starts_with('-') && flags.contains(['c', 'C']))
+    } else {
+        false
+    }
+}
+
+/// Walks the method chain down to `Command::new`. Returns the program the command runs, and
+/// whether one of the arguments passed in the chain is a command flag.
+fn command_chain(cx: &LateContext<'_>, mut expr: &Expr<'_>) -> Option<(Symbol, bool)> {
+    let mut has_flag = false;
+    loop {
+        match expr.kind {
+            ExprKind::MethodCall(path, recv, args, _) => {
+                match (path.ident.as_str(), args) {
+                    ("arg", [arg]) => has_flag |= is_command_flag(arg),
+                    ("args", [arg]) => {
+                        if let ExprKind::Array(elems) = peel_hir_expr_refs(arg).0.kind {
+                            has_flag |= elems.iter().any(is_command_flag);
+                        }
+                    },
+                    _ => {},
+                }
+                expr = recv;
+            },
+            ExprKind::Call(func, [program]) => {
+                return if let Some(def_id) = path_def_id(cx, func)
+                    && match_def_path(cx, def_id, &paths::COMMAND_NEW)
+                    && let ExprKind::Lit(lit) = &program.kind
+                    && let LitKind::Str(program, _) = lit.node
+                {
+                    Some((program, has_flag))
+                } else {
+                    None
+                };
+            },
+            _ => return None,
+        }
+    }
+}
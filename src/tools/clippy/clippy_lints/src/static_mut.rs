@@ -0,0 +1,107 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::implements_trait;
+use clippy_utils::{meets_msrv, msrvs};
+use rustc_hir::{Item, ItemKind, Mutability};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, IntTy, Ty, UintTy};
+use rustc_semver::RustcVersion;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `static mut` items.
+    ///
+    /// ### Why is this bad?
+    /// Every access to a `static mut` is `unsafe`, and it is up to the programmer to rule out
+    /// data races and aliasing mutable references. A `static` of a type with interior
+    /// mutability, like an atomic or a `Mutex`, can be used from safe code instead.
+    ///
+    /// ### Example
+    /// ```rust
+    /// static mut COUNTER: u32 = 0;
+    ///
+    /// fn increment() {
+    ///     unsafe { COUNTER += 1 };
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    ///
+    /// static COUNTER: AtomicU32 = AtomicU32::new(0);
+    ///
+    /// fn increment() {
+    ///     COUNTER.fetch_add(1, Ordering::Relaxed);
+    /// }
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub STATIC_MUT,
+    restriction,
+    "declaring a `static mut`"
+}
+
+pub struct StaticMut {
+    msrv: Option<RustcVersion>,
+}
+
+impl StaticMut {
+    #[must_use]
+    pub fn new(msrv: Option<RustcVersion>) -> Self {
+        Self { msrv }
+    }
+}
+
+impl_lint_pass!(StaticMut => [STATIC_MUT]);
+
+impl<'tcx> LateLintPass<'tcx> for StaticMut {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Static(_, Mutability::Mut, _) = item.kind
+            && !in_external_macro(cx.sess(), item.span)
+        {
+            let ty = cx.tcx.type_of(item.owner_id);
+            let help = if let Some(atomic) = atomic_for(ty, self.msrv) {
+                format!("consider using a `static` of type `{atomic}` instead")
+            } else if !is_send(cx, ty) {
+                "consider using a `thread_local!` with a `Cell` or `RefCell` instead".to_owned()
+            } else if meets_msrv(self.msrv, msrvs::CONST_MUTEX_NEW) {
+                format!("consider using a `static` of type `Mutex<{ty}>` instead")
+            } else {
+                "consider using a `static` with interior mutability instead, like a lazily initialized `Mutex`"
+                    .to_owned()
+            };
+            span_lint_and_help(cx, STATIC_MUT, item.span, "declaration of a `static mut`", None, &help);
+        }
+    }
+
+    extract_msrv_attr!(LateContext);
+}
+
+/// Returns the atomic type that can hold a value of the given type, if there is one.
+fn atomic_for(ty: Ty<'_>, msrv: Option<RustcVersion>) -> Option<String> {
+    let sized_ints = meets_msrv(msrv, msrvs::INTEGER_ATOMICS);
+    let atomic = match ty.kind() {
+        ty::Bool => "AtomicBool",
+        ty::Int(IntTy::Isize) => "AtomicIsize",
+        ty::Uint(UintTy::Usize) => "AtomicUsize",
+        ty::Int(IntTy::I8) if sized_ints => "AtomicI8",
+        ty::Int(IntTy::I16) if sized_ints => "AtomicI16",
+        ty::Int(IntTy::I32) if sized_ints => "AtomicI32",
+        ty::Int(IntTy::I64) if sized_ints => "AtomicI64",
+        ty::Uint(UintTy::U8) if sized_ints => "AtomicU8",
+        ty::Uint(UintTy::U16) if sized_ints => "AtomicU16",
+        ty::Uint(UintTy::U32) if sized_ints => "AtomicU32",
+        ty::Uint(UintTy::U64) if sized_ints => "AtomicU64",
+        ty::RawPtr(ty::TypeAndMut { ty: pointee, .. }) => return Some(format!("AtomicPtr<{pointee}>")),
+        _ => return None,
+    };
+    Some(atomic.to_owned())
+}
+
+fn is_send<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    cx.tcx
+        .get_diagnostic_item(sym::Send)
+        .map_or(false, |send| implements_trait(cx, ty, send, &[]))
+}
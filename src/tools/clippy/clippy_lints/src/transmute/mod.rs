@@ -361,7 +361,11 @@
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for transmutes between types which do not have a representation defined relative to
-    /// each other.
+    /// each other. This includes structs and enums without a `repr` attribute, transmuted to each
+    /// other or to and from integers and arrays.
+    ///
+    /// Transmutes between types of different sizes are already rejected by the compiler, this lint
+    /// catches the ones that compile but rely on a layout the compiler may change.
     ///
     /// ### Why is this bad?
     /// The results of such a transmute are not defined.
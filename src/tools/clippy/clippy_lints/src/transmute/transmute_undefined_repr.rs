@@ -1,10 +1,11 @@
 use super::TRANSMUTE_UNDEFINED_REPR;
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::ty::is_c_void;
+use clippy_utils::ty::{is_c_void, is_type_diagnostic_item};
 use rustc_hir::Expr;
 use rustc_lint::LateContext;
 use rustc_middle::ty::SubstsRef;
 use rustc_middle::ty::{self, IntTy, Ty, TypeAndMut, UintTy};
+use rustc_span::sym;
 
 #[expect(clippy::too_many_lines)]
 pub(super) fn check<'tcx>(
@@ -264,7 +265,8 @@ enum ReducedTy<'tcx> {
     /// sized fields with a defined order.
     /// The second value is the first non-zero sized type.
     OrderedFields(Ty<'tcx>, Option<Ty<'tcx>>),
-    /// The type is a struct containing multiple non-zero sized fields with no defined order.
+    /// The type is a struct containing multiple non-zero sized fields with no defined order, or an
+    /// enum without a defined layout.
     UnorderedFields(Ty<'tcx>),
     /// Any other type.
     Other(Ty<'tcx>),
@@ -318,6 +320,15 @@ fn reduce_ty<'tcx>(cx: &LateContext<'tcx>, mut ty: Ty<'tcx>) -> ReducedTy<'tcx>
             {
                 ReducedTy::TypeErasure { raw_ptr_only: false }
             }
+            // Without a `repr` attribute, the layout of an enum is unspecified, even without fields.
+            // `Option` is left out, as its layout is guaranteed when wrapping non-nullable types.
+            ty::Adt(def, _)
+                if def.is_enum()
+                    && !def.repr().inhibit_enum_layout_opt()
+                    && !is_type_diagnostic_item(cx, ty, sym::Option) =>
+            {
+                ReducedTy::UnorderedFields(ty)
+            }
             // TODO: Check if the conversion to or from at least one of a union's fields is valid.
             ty::Adt(def, _) if def.is_union() => ReducedTy::TypeErasure { raw_ptr_only: false },
             ty::Foreign(_) | ty::Param(_) => ReducedTy::TypeErasure { raw_ptr_only: false },
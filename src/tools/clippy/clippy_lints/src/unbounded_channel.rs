@@ -0,0 +1,91 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{def_path_def_ids, fn_def_id};
+use rustc_hir::def_id::DefIdMap;
+use rustc_hir::Expr;
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+use crate::utils::conf::DisallowedPath;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to functions creating unbounded channels, like
+    /// `std::sync::mpsc::channel`, `crossbeam_channel::unbounded` and
+    /// `tokio::sync::mpsc::unbounded_channel`.
+    ///
+    /// Functions of other crates can be added with the `unbounded-channel-functions`
+    /// configuration.
+    ///
+    /// ### Why is this bad?
+    /// Sending to an unbounded channel never blocks, so a producer faster than its consumer
+    /// fills the memory instead of being slowed down. Bounded channels provide backpressure.
+    ///
+    /// ### Example
+    /// ```rust
+    /// let (sender, receiver) = std::sync::mpsc::channel::<u32>();
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// let (sender, receiver) = std::sync::mpsc::sync_channel::<u32>(64);
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub UNBOUNDED_CHANNEL,
+    restriction,
+    "creating a channel without a bound on the number of buffered messages"
+}
+
+/// The functions that are always linted, along with the bounded alternative to suggest.
+const UNBOUNDED_CHANNEL_FNS: &[(&str, &str)] = &[
+    ("std::sync::mpsc::channel", "use `std::sync::mpsc::sync_channel`"),
+    ("crossbeam_channel::unbounded", "use `crossbeam_channel::bounded`"),
+    ("tokio::sync::mpsc::unbounded_channel", "use `tokio::sync::mpsc::channel`"),
+];
+
+pub struct UnboundedChannel {
+    conf_unbounded: Vec<DisallowedPath>,
+    unbounded: DefIdMap<(String, Option<String>)>,
+}
+
+impl UnboundedChannel {
+    pub fn new(conf_unbounded: Vec<DisallowedPath>) -> Self {
+        Self {
+            conf_unbounded,
+            unbounded: DefIdMap::default(),
+        }
+    }
+}
+
+impl_lint_pass!(UnboundedChannel => [UNBOUNDED_CHANNEL]);
+
+impl<'tcx> LateLintPass<'tcx> for UnboundedChannel {
+    fn check_crate(&mut self, cx: &LateContext<'_>) {
+        let fns = UNBOUNDED_CHANNEL_FNS.iter().map(|&(path, alternative)| (path, Some(alternative)));
+        let conf = self.conf_unbounded.iter().map(|conf| (conf.path(), conf.reason()));
+        self.unbounded = def_path_def_ids(cx, fns.chain(conf));
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        let Some(def_id) = fn_def_id(cx, expr) else {
+            return;
+        };
+        let Some((path, alternative)) = self.unbounded.get(&def_id) else {
+            return;
+        };
+        span_lint_and_then(
+            cx,
+            UNBOUNDED_CHANNEL,
+            expr.span,
+            &format!("creation of an unbounded channel with `{path}`"),
+            |diag| {
+                if let Some(alternative) = alternative {
+                    diag.help(alternative);
+                }
+            },
+        );
+    }
+}
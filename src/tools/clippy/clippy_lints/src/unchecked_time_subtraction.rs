@@ -0,0 +1,133 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::{match_def_path, meets_msrv, msrvs, path_def_id, paths};
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, Ty};
+use rustc_semver::RustcVersion;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for subtractions with `Instant`, `SystemTime` and `Duration` operands that can
+    /// panic or silently saturate:
+    /// * `Instant - Duration` and `SystemTime - Duration` panic if the result can't be
+    ///   represented.
+    /// * `Duration - Duration` panics if the result would be negative.
+    /// * `Instant - Instant` is zero if the second `Instant` is later than the first one.
+    ///
+    /// ### Why is this bad?
+    /// The operators hide the failure case. The `checked_sub` methods make it explicit, and
+    /// `checked_duration_since` or `saturating_duration_since` state whether a zero duration is
+    /// what the code expects.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::time::{Duration, Instant};
+    /// # let timeout = Duration::from_secs(1);
+    /// let deadline = Instant::now() - timeout;
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # use std::time::{Duration, Instant};
+    /// # let timeout = Duration::from_secs(1);
+    /// let deadline = Instant::now().checked_sub(timeout);
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub UNCHECKED_TIME_SUBTRACTION,
+    pedantic,
+    "subtractions of times and durations that may panic or saturate"
+}
+
+pub struct UncheckedTimeSubtraction {
+    msrv: Option<RustcVersion>,
+}
+
+impl UncheckedTimeSubtraction {
+    #[must_use]
+    pub fn new(msrv: Option<RustcVersion>) -> Self {
+        Self { msrv }
+    }
+}
+
+impl_lint_pass!(UncheckedTimeSubtraction => [UNCHECKED_TIME_SUBTRACTION]);
+
+#[derive(Clone, Copy)]
+enum TimeType {
+    Instant,
+    SystemTime,
+    Duration,
+}
+
+impl<'tcx> LateLintPass<'tcx> for UncheckedTimeSubtraction {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        let (ExprKind::Binary(op, lhs, rhs) | ExprKind::AssignOp(op, lhs, rhs)) = expr.kind else {
+            return;
+        };
+        if op.node != BinOpKind::Sub || expr.span.from_expansion() {
+            return;
+        }
+        let (Some(lhs_ty), Some(rhs_ty)) = (
+            time_type(cx, cx.typeck_results().expr_ty(lhs)),
+            time_type(cx, cx.typeck_results().expr_ty(rhs)),
+        ) else {
+            return;
+        };
+
+        let (msg, help) = match (lhs_ty, rhs_ty) {
+            (TimeType::Instant, TimeType::Duration) if meets_msrv(self.msrv, msrvs::TIME_CHECKED_SUB) => (
+                "unchecked subtraction of a `Duration` from an `Instant`",
+                "this panics if the result can't be represented; consider using `Instant::checked_sub`",
+            ),
+            (TimeType::SystemTime, TimeType::Duration) if meets_msrv(self.msrv, msrvs::TIME_CHECKED_SUB) => (
+                "unchecked subtraction of a `Duration` from a `SystemTime`",
+                "this panics if the result can't be represented; consider using `SystemTime::checked_sub`",
+            ),
+            (TimeType::Duration, TimeType::Duration) => (
+                "unchecked subtraction between `Duration`s",
+                "this panics if the result would be negative; consider using `Duration::checked_sub`",
+            ),
+            // `Instant::now() - earlier` is linted by `manual_instant_elapsed`.
+            (TimeType::Instant, TimeType::Instant)
+                if !is_instant_now_call(cx, lhs) && meets_msrv(self.msrv, msrvs::CHECKED_DURATION_SINCE) =>
+            {
+                (
+                    "unchecked subtraction between `Instant`s",
+                    "this is zero if the second `Instant` is later; consider using `checked_duration_since` \
+                    or `saturating_duration_since` to make this explicit",
+                )
+            },
+            _ => return,
+        };
+        span_lint_and_help(cx, UNCHECKED_TIME_SUBTRACTION, expr.span, msg, None, help);
+    }
+
+    extract_msrv_attr!(LateContext);
+}
+
+fn time_type<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> Option<TimeType> {
+    if is_type_diagnostic_item(cx, ty, sym::Duration) {
+        return Some(TimeType::Duration);
+    }
+    if let ty::Adt(adt, _) = ty.kind() {
+        if match_def_path(cx, adt.did(), &paths::INSTANT) {
+            return Some(TimeType::Instant);
+        }
+        if match_def_path(cx, adt.did(), &paths::SYSTEM_TIME) {
+            return Some(TimeType::SystemTime);
+        }
+    }
+    None
+}
+
+fn is_instant_now_call(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    if let ExprKind::Call(fn_expr, []) = expr.kind
+        && let Some(fn_id) = path_def_id(cx, fn_expr)
+    {
+        match_def_path(cx, fn_id, &paths::INSTANT_NOW)
+    } else {
+        false
+    }
+}
@@ -1,13 +1,14 @@
 use clippy_utils::diagnostics::span_lint_and_help;
-use clippy_utils::source::walk_span_to_context;
+use clippy_utils::source::{first_line_of_span, walk_span_to_context};
+use clippy_utils::visitors::contains_unsafe_block;
 use clippy_utils::{get_parent_node, is_lint_allowed};
 use rustc_data_structures::sync::Lrc;
 use rustc_hir as hir;
-use rustc_hir::{Block, BlockCheckMode, ItemKind, Node, UnsafeSource};
+use rustc_hir::{Block, BlockCheckMode, HirId, ItemKind, Node, UnsafeSource};
 use rustc_lexer::{tokenize, TokenKind};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_middle::lint::in_external_macro;
-use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::{BytePos, Pos, Span, SyntaxContext};
 
 declare_clippy_lint! {
@@ -34,6 +35,9 @@
     /// );
     /// ```
     ///
+    /// Unsafe blocks in the body of an `unsafe fn`, whose safety is the caller's responsibility,
+    /// can be allowed with the `allow-undocumented-unsafe-in-unsafe-fn` configuration.
+    ///
     /// ### Why is this bad?
     /// Undocumented unsafe blocks and impls can make it difficult to
     /// read and maintain code, as well as uncover unsoundness
@@ -60,27 +64,61 @@
     "creating an unsafe block without explaining why it is safe"
 }
 
-declare_lint_pass!(UndocumentedUnsafeBlocks => [UNDOCUMENTED_UNSAFE_BLOCKS]);
+#[derive(Copy, Clone)]
+pub struct UndocumentedUnsafeBlocks {
+    allow_in_unsafe_fn: bool,
+}
+
+impl UndocumentedUnsafeBlocks {
+    pub fn new(allow_in_unsafe_fn: bool) -> Self {
+        Self { allow_in_unsafe_fn }
+    }
+}
 
-impl LateLintPass<'_> for UndocumentedUnsafeBlocks {
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `// SAFETY: ` comments on safe code, e.g. on a statement without an `unsafe`
+    /// block or on an impl of a safe trait.
+    ///
+    /// ### Why is this bad?
+    /// Safety comments explain why some unsafe code is sound. On code without anything unsafe,
+    /// they are usually left over from an `unsafe` block that was removed since, and mislead the
+    /// reader into looking for an invariant that doesn't matter anymore.
+    ///
+    /// ### Example
+    /// ```rust
+    /// let a = &mut 42;
+    ///
+    /// // SAFETY: references are guaranteed to be non-null.
+    /// let ptr = std::ptr::NonNull::from(a);
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// let a = &mut 42;
+    ///
+    /// let ptr = std::ptr::NonNull::from(a);
+    /// ```
+    #[clippy::version = "1.66.0"]
+    pub UNNECESSARY_SAFETY_COMMENT,
+    restriction,
+    "a safety comment on code that is not unsafe"
+}
+
+impl_lint_pass!(UndocumentedUnsafeBlocks => [UNDOCUMENTED_UNSAFE_BLOCKS, UNNECESSARY_SAFETY_COMMENT]);
+
+impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
     fn check_block(&mut self, cx: &LateContext<'_>, block: &'_ Block<'_>) {
         if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
             && !in_external_macro(cx.tcx.sess, block.span)
             && !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_BLOCKS, block.hir_id)
             && !is_unsafe_from_proc_macro(cx, block.span)
+            && !(self.allow_in_unsafe_fn && is_in_unsafe_fn(cx, block.hir_id))
             && !block_has_safety_comment(cx, block)
         {
-            let source_map = cx.tcx.sess.source_map();
-            let span = if source_map.is_multiline(block.span) {
-                source_map.span_until_char(block.span, '\n')
-            } else {
-                block.span
-            };
-
             span_lint_and_help(
                 cx,
                 UNDOCUMENTED_UNSAFE_BLOCKS,
-                span,
+                first_line(cx, block.span),
                 "unsafe block missing a safety comment",
                 None,
                 "consider adding a safety comment on the preceding line",
@@ -94,27 +132,114 @@ fn check_item(&mut self, cx: &LateContext<'_>, item: &hir::Item<'_>) {
             && !in_external_macro(cx.tcx.sess, item.span)
             && !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_BLOCKS, item.hir_id())
             && !is_unsafe_from_proc_macro(cx, item.span)
-            && !item_has_safety_comment(cx, item)
+            && item_has_safety_comment(cx, item) == HasSafetyComment::No
         {
-            let source_map = cx.tcx.sess.source_map();
-            let span = if source_map.is_multiline(item.span) {
-                source_map.span_until_char(item.span, '\n')
-            } else {
-                item.span
-            };
-
             span_lint_and_help(
                 cx,
                 UNDOCUMENTED_UNSAFE_BLOCKS,
-                span,
+                first_line(cx, item.span),
                 "unsafe impl missing a safety comment",
                 None,
                 "consider adding a safety comment on the preceding line",
             );
         }
+
+        if item.span.ctxt() == SyntaxContext::root()
+            && !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, item.hir_id())
+            && !item_may_need_safety_comment(cx, item)
+            && item_has_safety_comment(cx, item) == HasSafetyComment::Yes
+        {
+            span_lint_and_help(
+                cx,
+                UNNECESSARY_SAFETY_COMMENT,
+                first_line(cx, item.span),
+                &format!("{} has unnecessary safety comment", item.kind.descr()),
+                None,
+                "consider removing the safety comment",
+            );
+        }
+    }
+
+    fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx hir::Stmt<'tcx>) {
+        let expr = match stmt.kind {
+            hir::StmtKind::Local(&hir::Local {
+                init: Some(expr),
+                els: None,
+                ..
+            })
+            | hir::StmtKind::Expr(expr)
+            | hir::StmtKind::Semi(expr) => expr,
+            _ => return,
+        };
+        // Only look at statements that start their line, the comment might be meant for another
+        // statement on the same line otherwise.
+        if stmt.span.ctxt() == SyntaxContext::root()
+            && first_line_of_span(cx, stmt.span).lo() == stmt.span.lo()
+            && !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, stmt.hir_id)
+            && !contains_unsafe_block(cx, expr)
+            // Unsafe operations in an `unsafe fn` don't need an unsafe block.
+            && !is_in_unsafe_fn(cx, stmt.hir_id)
+            // Neither do unsafe operations in an `unsafe` block, which may document each of them.
+            && !is_in_unsafe_block(cx, stmt.hir_id)
+            && span_in_body_has_safety_comment(cx, stmt.span) == HasSafetyComment::Yes
+        {
+            span_lint_and_help(
+                cx,
+                UNNECESSARY_SAFETY_COMMENT,
+                first_line(cx, stmt.span),
+                "statement has unnecessary safety comment",
+                None,
+                "consider removing the safety comment",
+            );
+        }
+    }
+}
+
+/// Returns the first line of the span, to not point at all of a multi-line block or item.
+fn first_line(cx: &LateContext<'_>, span: Span) -> Span {
+    let source_map = cx.tcx.sess.source_map();
+    if source_map.is_multiline(span) {
+        source_map.span_until_char(span, '\n')
+    } else {
+        span
     }
 }
 
+/// Checks if the item is or may contain something unsafe that a safety comment on it explains.
+fn item_may_need_safety_comment(cx: &LateContext<'_>, item: &hir::Item<'_>) -> bool {
+    match item.kind {
+        hir::ItemKind::Impl(&hir::Impl { unsafety, .. }) | hir::ItemKind::Trait(_, unsafety, ..) => {
+            unsafety == hir::Unsafety::Unsafe
+        },
+        hir::ItemKind::Fn(ref sig, ..) => sig.header.unsafety == hir::Unsafety::Unsafe,
+        hir::ItemKind::Const(_, body) | hir::ItemKind::Static(_, _, body) => {
+            contains_unsafe_block(cx, cx.tcx.hir().body(body).value)
+        },
+        hir::ItemKind::ForeignMod { .. } | hir::ItemKind::GlobalAsm(_) | hir::ItemKind::Macro(..) => true,
+        _ => false,
+    }
+}
+
+/// Checks if the item containing the given node, looking through closures, is an `unsafe fn`.
+fn is_in_unsafe_fn(cx: &LateContext<'_>, hir_id: HirId) -> bool {
+    let map = cx.tcx.hir();
+    let owner = map.get_parent_item(hir_id);
+    map.fn_sig_by_hir_id(map.local_def_id_to_hir_id(owner.def_id))
+        .map_or(false, |sig| sig.header.unsafety == hir::Unsafety::Unsafe)
+}
+
+fn is_in_unsafe_block(cx: &LateContext<'_>, hir_id: HirId) -> bool {
+    cx.tcx.hir().parent_iter(hir_id).any(|(_, node)| {
+        matches!(
+            node,
+            Node::Block(Block {
+                rules: BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided),
+                ..
+            })
+        )
+    })
+}
+
 fn is_unsafe_from_proc_macro(cx: &LateContext<'_>, span: Span) -> bool {
     let source_map = cx.sess().source_map();
     let file_pos = source_map.lookup_byte_offset(span.lo());
@@ -136,14 +261,31 @@ fn block_has_safety_comment(cx: &LateContext<'_>, block: &hir::Block<'_>) -> boo
     // won't work. This is to avoid dealing with where such a comment should be place relative to
     // attributes and doc comments.
 
-    span_from_macro_expansion_has_safety_comment(cx, block.span) || span_in_body_has_safety_comment(cx, block.span)
+    span_from_macro_expansion_has_safety_comment(cx, block.span)
+        || span_in_body_has_safety_comment(cx, block.span) != HasSafetyComment::No
+}
+
+/// The result of looking for a safety comment. `Maybe` means the search didn't get to look at the
+/// source text, which counts as having a comment for `UNDOCUMENTED_UNSAFE_BLOCKS` and as not having
+/// one for `UNNECESSARY_SAFETY_COMMENT`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum HasSafetyComment {
+    Yes,
+    No,
+    Maybe,
+}
+
+impl From<bool> for HasSafetyComment {
+    fn from(has_comment: bool) -> Self {
+        if has_comment { Self::Yes } else { Self::No }
+    }
 }
 
 /// Checks if the lines immediately preceding the item contain a safety comment.
 #[allow(clippy::collapsible_match)]
-fn item_has_safety_comment(cx: &LateContext<'_>, item: &hir::Item<'_>) -> bool {
+fn item_has_safety_comment(cx: &LateContext<'_>, item: &hir::Item<'_>) -> HasSafetyComment {
     if span_from_macro_expansion_has_safety_comment(cx, item.span) {
-        return true;
+        return HasSafetyComment::Yes;
     }
 
     if item.span.ctxt() == SyntaxContext::root() {
@@ -156,8 +298,8 @@ fn item_has_safety_comment(cx: &LateContext<'_>, item: &hir::Item<'_>) -> bool {
                     if let ItemKind::Mod(parent_mod) = &parent_item.kind {
                         comment_start_before_impl_in_mod(cx, parent_mod, parent_item.span, item)
                     } else {
-                        // Doesn't support impls in this position. Pretend a comment was found.
-                        return true;
+                        // Doesn't support impls in this position.
+                        return HasSafetyComment::Maybe;
                     }
                 },
                 Node::Stmt(stmt) => {
@@ -165,18 +307,18 @@ fn item_has_safety_comment(cx: &LateContext<'_>, item: &hir::Item<'_>) -> bool {
                         match stmt_parent {
                             Node::Block(block) => walk_span_to_context(block.span, SyntaxContext::root()).map(Span::lo),
                             _ => {
-                                // Doesn't support impls in this position. Pretend a comment was found.
-                                return true;
+                                // Doesn't support impls in this position.
+                                return HasSafetyComment::Maybe;
                             },
                         }
                     } else {
-                        // Problem getting the parent node. Pretend a comment was found.
-                        return true;
+                        // Problem getting the parent node.
+                        return HasSafetyComment::Maybe;
                     }
                 },
                 _ => {
-                    // Doesn't support impls in this position. Pretend a comment was found.
-                    return true;
+                    // Doesn't support impls in this position.
+                    return HasSafetyComment::Maybe;
                 },
             };
 
@@ -187,23 +329,24 @@ fn item_has_safety_comment(cx: &LateContext<'_>, item: &hir::Item<'_>) -> bool {
                 && Lrc::ptr_eq(&unsafe_line.sf, &comment_start_line.sf)
                 && let Some(src) = unsafe_line.sf.src.as_deref()
             {
-                unsafe_line.sf.lines(|lines| {
+                let has_comment = unsafe_line.sf.lines(|lines| {
                     comment_start_line.line < unsafe_line.line && text_has_safety_comment(
                         src,
                         &lines[comment_start_line.line + 1..=unsafe_line.line],
                         unsafe_line.sf.start_pos.to_usize(),
                     )
-                })
+                });
+                has_comment.into()
             } else {
-                // Problem getting source text. Pretend a comment was found.
-                true
+                // Problem getting source text.
+                HasSafetyComment::Maybe
             }
         } else {
-            // No parent node. Pretend a comment was found.
-            true
+            // No parent node.
+            HasSafetyComment::Maybe
         }
     } else {
-        false
+        HasSafetyComment::No
     }
 }
 
@@ -279,7 +422,7 @@ fn get_body_search_span(cx: &LateContext<'_>) -> Option<Span> {
     Some(span)
 }
 
-fn span_in_body_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
+fn span_in_body_has_safety_comment(cx: &LateContext<'_>, span: Span) -> HasSafetyComment {
     let source_map = cx.sess().source_map();
     let ctxt = span.ctxt();
     if ctxt == SyntaxContext::root()
@@ -294,19 +437,20 @@ fn span_in_body_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
             // Get the text from the start of function body to the unsafe block.
             //     fn foo() { some_stuff; unsafe { stuff }; other_stuff; }
             //              ^-------------^
-            unsafe_line.sf.lines(|lines| {
+            let has_comment = unsafe_line.sf.lines(|lines| {
                 body_line.line < unsafe_line.line && text_has_safety_comment(
                     src,
                     &lines[body_line.line + 1..=unsafe_line.line],
                     unsafe_line.sf.start_pos.to_usize(),
                 )
-            })
+            });
+            has_comment.into()
         } else {
-            // Problem getting source text. Pretend a comment was found.
-            true
+            // Problem getting source text.
+            HasSafetyComment::Maybe
         }
     } else {
-        false
+        HasSafetyComment::No
     }
 }
 
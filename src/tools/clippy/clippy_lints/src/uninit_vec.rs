@@ -1,7 +1,10 @@
 use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
 use clippy_utils::higher::{get_vec_init_kind, VecInitKind};
 use clippy_utils::ty::{is_type_diagnostic_item, is_uninit_value_valid_for_ty};
-use clippy_utils::{is_integer_literal, is_lint_allowed, path_to_local_id, peel_hir_expr_while, SpanlessEq};
+use clippy_utils::visitors::is_local_used;
+use clippy_utils::{
+    is_integer_literal, is_lint_allowed, path_to_local, path_to_local_id, peel_hir_expr_while, SpanlessEq,
+};
 use rustc_hir::{Block, Expr, ExprKind, HirId, PatKind, PathSegment, Stmt, StmtKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::lint::in_external_macro;
@@ -25,7 +28,8 @@
     /// creates out-of-bound values that lead to heap memory corruption when used.
     ///
     /// ### Known Problems
-    /// This lint only checks directly adjacent statements.
+    /// This lint only checks statements of the same block, up to the first statement which
+    /// uses the `Vec` other than calling `set_len()`.
     ///
     /// ### Example
     /// ```rust,ignore
@@ -60,33 +64,58 @@
 
 declare_lint_pass!(UninitVec => [UNINIT_VEC]);
 
-// FIXME: update to a visitor-based implementation.
-// Threads: https://github.com/rust-lang/rust-clippy/pull/7682#discussion_r710998368
 impl<'tcx> LateLintPass<'tcx> for UninitVec {
     fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'_>) {
         if !in_external_macro(cx.tcx.sess, block.span) {
-            for w in block.stmts.windows(2) {
-                if let StmtKind::Expr(expr) | StmtKind::Semi(expr) = w[1].kind {
-                    handle_uninit_vec_pair(cx, &w[0], expr);
+            for (i, stmt) in block.stmts.iter().enumerate() {
+                if let Some(vec) = extract_init_or_reserve_target(cx, stmt) {
+                    check_following_stmts(cx, block, i + 1, stmt, vec);
                 }
             }
+        }
+    }
+}
 
-            if let (Some(stmt), Some(expr)) = (block.stmts.last(), block.expr) {
-                handle_uninit_vec_pair(cx, stmt, expr);
-            }
+/// Looks for a `set_len()` call on the target `Vec` in the statements of `block` starting at
+/// index `start`. Statements which don't use the `Vec` are skipped, any other statement ends the
+/// search.
+fn check_following_stmts<'tcx>(
+    cx: &LateContext<'tcx>,
+    block: &'tcx Block<'_>,
+    start: usize,
+    init_or_reserve: &'tcx Stmt<'tcx>,
+    vec: TargetVec<'tcx>,
+) {
+    let base_local = vec.location.base_local();
+    for stmt in &block.stmts[start..] {
+        if let StmtKind::Expr(expr) | StmtKind::Semi(expr) = stmt.kind
+            && handle_uninit_vec_pair(cx, init_or_reserve, vec, expr)
+        {
+            return;
         }
+        if base_local.map_or(true, |local| is_local_used(cx, stmt, local)) {
+            return;
+        }
+    }
+    if let Some(expr) = block.expr {
+        handle_uninit_vec_pair(cx, init_or_reserve, vec, expr);
     }
 }
 
+/// Lints if `maybe_set_len` is a `set_len()` call on the target `Vec`. Returns whether it is one.
 fn handle_uninit_vec_pair<'tcx>(
     cx: &LateContext<'tcx>,
     maybe_init_or_reserve: &'tcx Stmt<'tcx>,
+    vec: TargetVec<'tcx>,
     maybe_set_len: &'tcx Expr<'tcx>,
-) {
+) -> bool {
+    let Some((set_len_self, call_span)) = extract_set_len_self(cx, maybe_set_len) else {
+        return false;
+    };
+    if !vec.location.eq_expr(cx, set_len_self) {
+        return false;
+    }
     if_chain! {
-        if let Some(vec) = extract_init_or_reserve_target(cx, maybe_init_or_reserve);
-        if let Some((set_len_self, call_span)) = extract_set_len_self(cx, maybe_set_len);
-        if vec.location.eq_expr(cx, set_len_self);
         if let ty::Ref(_, vec_ty, _) = cx.typeck_results().expr_ty_adjusted(set_len_self).kind();
         if let ty::Adt(_, substs) = vec_ty.kind();
         // `#[allow(...)]` attribute can be set on enclosing unsafe block of `set_len()`
@@ -103,7 +132,7 @@ fn handle_uninit_vec_pair<'tcx>(
                         cx,
                         UNINIT_VEC,
                         vec![call_span, maybe_init_or_reserve.span],
-                        "calling `set_len()` immediately after reserving a buffer creates uninitialized values",
+                        "calling `set_len()` after reserving a buffer creates uninitialized values",
                         |diag| {
                             diag.help("initialize the buffer or wrap the content in `MaybeUninit`");
                         },
@@ -120,6 +149,7 @@ fn handle_uninit_vec_pair<'tcx>(
             }
         }
     }
+    true
 }
 
 /// The target `Vec` that is initialized or reserved
@@ -149,6 +179,20 @@ pub fn eq_expr(self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> bool {
             VecLocation::Expr(self_expr) => SpanlessEq::new(cx).eq_expr(self_expr, expr),
         }
     }
+
+    /// The local holding the `Vec`, or the struct it is a field of.
+    pub fn base_local(self) -> Option<HirId> {
+        match self {
+            VecLocation::Local(hir_id) => Some(hir_id),
+            VecLocation::Expr(expr) => path_to_local(peel_hir_expr_while(expr, |e| {
+                if let ExprKind::Field(base, _) = e.kind {
+                    Some(base)
+                } else {
+                    None
+                }
+            })),
+        }
+    }
 }
 
 /// Finds the target location where the result of `Vec` initialization is stored
@@ -1,9 +1,13 @@
-use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::get_parent_expr;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::intravisit::{walk_expr, walk_fn, FnKind, Visitor};
 use rustc_hir::{Body, Expr, ExprKind, FnDecl, HirId, IsAsync, YieldSource};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::hir::nested_filter;
-use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::def_id::LocalDefId;
 use rustc_span::Span;
 
 declare_clippy_lint! {
@@ -15,6 +19,9 @@
     /// Callers of async methods either need to be calling from an async function themselves or run it on an executor, both of which
     /// causes runtime overhead and hassle for the caller.
     ///
+    /// Functions that are used as a value, e.g. passed to a function expecting a closure that
+    /// returns a future, are not linted, as they need to be `async` to fit in there.
+    ///
     /// ### Example
     /// ```rust
     /// async fn get_random_number() -> i64 {
@@ -36,7 +43,15 @@
     "finds async functions with no await statements"
 }
 
-declare_lint_pass!(UnusedAsync => [UNUSED_ASYNC]);
+#[derive(Default)]
+pub struct UnusedAsync {
+    /// The `async` functions without `.await`s, with the span to report.
+    unused_async_fns: Vec<(LocalDefId, Span)>,
+    /// Functions that are used as a value rather than called, e.g. `map(async_fn)`.
+    fns_used_as_value: FxHashSet<LocalDefId>,
+}
+
+impl_lint_pass!(UnusedAsync => [UNUSED_ASYNC]);
 
 struct AsyncFnVisitor<'a, 'tcx> {
     cx: &'a LateContext<'tcx>,
@@ -72,15 +87,39 @@ fn check_fn(
             let mut visitor = AsyncFnVisitor { cx, found_await: false };
             walk_fn(&mut visitor, fn_kind, fn_decl, body.id(), hir_id);
             if !visitor.found_await {
-                span_lint_and_help(
-                    cx,
-                    UNUSED_ASYNC,
-                    span,
-                    "unused `async` for function with no await statements",
-                    None,
-                    "consider removing the `async` from this function",
-                );
+                self.unused_async_fns.push((cx.tcx.hir().local_def_id(hir_id), span));
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Path(ref qpath) = expr.kind
+            && let Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) = cx.qpath_res(qpath, expr.hir_id)
+            && let Some(local_def_id) = def_id.as_local()
+            && !matches!(
+                get_parent_expr(cx, expr),
+                Some(Expr { kind: ExprKind::Call(callee, _), .. }) if callee.hir_id == expr.hir_id
+            )
+        {
+            self.fns_used_as_value.insert(local_def_id);
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        for &(def_id, span) in &self.unused_async_fns {
+            if self.fns_used_as_value.contains(&def_id) {
+                continue;
             }
+            span_lint_hir_and_then(
+                cx,
+                UNUSED_ASYNC,
+                cx.tcx.hir().local_def_id_to_hir_id(def_id),
+                span,
+                "unused `async` for function with no await statements",
+                |diag| {
+                    diag.help("consider removing the `async` from this function");
+                },
+            );
         }
     }
 }
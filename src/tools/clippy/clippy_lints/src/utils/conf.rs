@@ -31,6 +31,19 @@
     "CamelCase",
 ];
 const DEFAULT_DISALLOWED_NAMES: &[&str] = &["foo", "baz", "quux"];
+const DEFAULT_SHELL_BINARIES: &[&str] = &[
+    "sh",
+    "bash",
+    "dash",
+    "zsh",
+    "ksh",
+    "fish",
+    "cmd",
+    "cmd.exe",
+    "powershell",
+    "powershell.exe",
+    "pwsh",
+];
 
 /// Holds information used by `MISSING_ENFORCED_IMPORT_RENAMES` lint.
 #[derive(Clone, Debug, Deserialize)]
@@ -209,11 +222,11 @@ pub(crate) fn get_configuration_metadata() -> Vec<ClippyConfiguration> {
     ///
     /// Suppress checking of the passed type names.
     (arithmetic_side_effects_allowed: rustc_data_structures::fx::FxHashSet<String> = <_>::default()),
-    /// Lint: ENUM_VARIANT_NAMES, LARGE_TYPES_PASSED_BY_VALUE, TRIVIALLY_COPY_PASS_BY_REF, UNNECESSARY_WRAPS, UNUSED_SELF, UPPER_CASE_ACRONYMS, WRONG_SELF_CONVENTION, BOX_COLLECTION, REDUNDANT_ALLOCATION, RC_BUFFER, VEC_BOX, OPTION_OPTION, LINKEDLIST, RC_MUTEX.
+    /// Lint: ENUM_VARIANT_NAMES, LARGE_TYPES_PASSED_BY_VALUE, TRIVIALLY_COPY_PASS_BY_REF, UNNECESSARY_WRAPS, UNUSED_SELF, UPPER_CASE_ACRONYMS, WRONG_SELF_CONVENTION, BOX_COLLECTION, REDUNDANT_ALLOCATION, RC_BUFFER, VEC_BOX, OPTION_OPTION, LINKEDLIST, RC_MUTEX, NEEDLESS_PASS_BY_REF_MUT.
     ///
     /// Suppress lints whenever the suggested change would cause breakage for other crates.
     (avoid_breaking_exported_api: bool = true),
-    /// Lint: MANUAL_SPLIT_ONCE, MANUAL_STR_REPEAT, CLONED_INSTEAD_OF_COPIED, REDUNDANT_FIELD_NAMES, REDUNDANT_STATIC_LIFETIMES, FILTER_MAP_NEXT, CHECKED_CONVERSIONS, MANUAL_RANGE_CONTAINS, USE_SELF, MEM_REPLACE_WITH_DEFAULT, MANUAL_NON_EXHAUSTIVE, OPTION_AS_REF_DEREF, MAP_UNWRAP_OR, MATCH_LIKE_MATCHES_MACRO, MANUAL_STRIP, MISSING_CONST_FOR_FN, UNNESTED_OR_PATTERNS, FROM_OVER_INTO, PTR_AS_PTR, IF_THEN_SOME_ELSE_NONE, APPROX_CONSTANT, DEPRECATED_CFG_ATTR, INDEX_REFUTABLE_SLICE, MAP_CLONE, BORROW_AS_PTR, MANUAL_BITS, ERR_EXPECT, CAST_ABS_TO_UNSIGNED, UNINLINED_FORMAT_ARGS, MANUAL_CLAMP.
+    /// Lint: MANUAL_SPLIT_ONCE, MANUAL_STR_REPEAT, CLONED_INSTEAD_OF_COPIED, REDUNDANT_FIELD_NAMES, REDUNDANT_STATIC_LIFETIMES, FILTER_MAP_NEXT, CHECKED_CONVERSIONS, MANUAL_RANGE_CONTAINS, USE_SELF, MEM_REPLACE_WITH_DEFAULT, MANUAL_NON_EXHAUSTIVE, OPTION_AS_REF_DEREF, MAP_UNWRAP_OR, MATCH_LIKE_MATCHES_MACRO, MANUAL_STRIP, MISSING_CONST_FOR_FN, UNNESTED_OR_PATTERNS, FROM_OVER_INTO, PTR_AS_PTR, IF_THEN_SOME_ELSE_NONE, APPROX_CONSTANT, DEPRECATED_CFG_ATTR, INDEX_REFUTABLE_SLICE, MAP_CLONE, BORROW_AS_PTR, MANUAL_BITS, ERR_EXPECT, CAST_ABS_TO_UNSIGNED, UNINLINED_FORMAT_ARGS, MANUAL_CLAMP, STATIC_MUT, UNCHECKED_TIME_SUBTRACTION, MANUAL_LET_ELSE.
     ///
     /// The minimum rust version that the project supports
     (msrv: Option<String> = None),
@@ -367,26 +380,94 @@ pub(crate) fn get_configuration_metadata() -> Vec<ClippyConfiguration> {
     (max_suggested_slice_pattern_length: u64 = 3),
     /// Lint: AWAIT_HOLDING_INVALID_TYPE
     (await_holding_invalid_types: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    /// Lint: BLOCKING_IN_ASYNC.
+    ///
+    /// Additional functions that block the thread and should not be called in an async context,
+    /// written as fully qualified paths. A `reason` can name an alternative to use instead.
+    (blocking_in_async_methods: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    /// Lint: AWAIT_HOLDING_LOCK.
+    ///
+    /// Additional guard types that must not be held across an `await` point, like the guards of
+    /// locks from crates Clippy doesn't know about. Paths must start with the crate name, e.g.
+    /// `["tracing::span::Entered"]`
+    (await_holding_lock_types: Vec<String> = Vec::new()),
     /// Lint: LARGE_INCLUDE_FILE.
     ///
     /// The maximum size of a file included via `include_bytes!()` or `include_str!()`, in bytes
     (max_include_file_size: u64 = 1_000_000),
     /// Lint: EXPECT_USED.
     ///
-    /// Whether `expect` should be allowed in test functions
+    /// Whether `expect` should be allowed in test functions and `#[cfg(test)]` modules
     (allow_expect_in_tests: bool = false),
     /// Lint: UNWRAP_USED.
     ///
-    /// Whether `unwrap` should be allowed in test functions
+    /// Whether `unwrap` should be allowed in test functions and `#[cfg(test)]` modules
     (allow_unwrap_in_tests: bool = false),
+    /// Lint: PANIC, INDEXING_SLICING.
+    ///
+    /// Whether `panic!` and indexing should be allowed in test functions and `#[cfg(test)]` modules
+    (allow_panic_in_tests: bool = false),
     /// Lint: DBG_MACRO.
     ///
     /// Whether `dbg!` should be allowed in test functions
     (allow_dbg_in_tests: bool = false),
+    /// Lint: TODO, UNIMPLEMENTED.
+    ///
+    /// Whether `todo!` and `unimplemented!` should be allowed in test functions and `#[cfg(test)]` modules
+    (allow_todo_in_tests: bool = true),
     /// Lint: RESULT_LARGE_ERR
     ///
     /// The maximum size of the `Err`-variant in a `Result` returned from a function
     (large_error_threshold: u64 = 128),
+    /// Lint: LARGE_FUTURES.
+    ///
+    /// The maximum size of the future of an `async fn` or `async` block, in bytes
+    (future_size_threshold: u64 = 16 * 1024),
+    /// Lint: UNDOCUMENTED_UNSAFE_BLOCKS.
+    ///
+    /// Whether `unsafe` blocks in the body of an `unsafe fn` may go without a safety comment
+    (allow_undocumented_unsafe_in_unsafe_fn: bool = false),
+    /// Lint: RAW_PTR_WRAPPER_SEND_SYNC.
+    ///
+    /// Raw pointer wrappers whose `Send` and `Sync` impls have been vetted, given by their name or
+    /// by their path within the crate, e.g. `["ffi::Handle"]`
+    (allowed_raw_ptr_wrappers: Vec<String> = Vec::new()),
+    /// Lint: PANIC_IN_DROP.
+    ///
+    /// Types whose `Drop` implementation is meant to panic, like guards that check an invariant,
+    /// given by their name or by their path within the crate, e.g. `["test_utils::Guard"]`
+    (allowed_panicking_drop_types: Vec<String> = Vec::new()),
+    /// Lint: ORDER_DEPENDENT_HASH_ITERATION.
+    ///
+    /// Whether to lint every `for` loop and `for_each` call over a `HashMap` or `HashSet`, not only
+    /// those whose order is observable
+    (warn_on_all_hash_iteration: bool = false),
+    /// Lint: POINTER_WIDTH_DEPENDENT_CAST.
+    ///
+    /// The pointer widths of the targets the crate supports, in bits
+    (supported_pointer_widths: Vec<u64> = vec![32, 64]),
+    /// Lint: SHELL_COMMAND_FROM_FORMAT.
+    ///
+    /// The names of the shells whose scripts shouldn't be built with `format!`
+    (shell_binaries: Vec<String> = super::DEFAULT_SHELL_BINARIES.iter().map(ToString::to_string).collect()),
+    /// Lint: UNBOUNDED_CHANNEL.
+    ///
+    /// Additional functions creating unbounded channels, written as fully qualified paths. A
+    /// `reason` can name the bounded alternative to use instead.
+    (unbounded_channel_functions: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    /// Lint: PRINT_STDOUT, PRINT_STDERR.
+    ///
+    /// Whether printing should be allowed in the root module of a binary crate
+    (allow_print_in_main_module: bool = true),
+    /// Lint: PRINT_STDOUT, PRINT_STDERR.
+    ///
+    /// Modules where printing is allowed, along with their submodules, given by their name or by
+    /// their path within the crate, e.g. `["cli::output"]`
+    (allowed_print_modules: Vec<String> = Vec::new()),
+    /// Lint: PRINT_STDOUT, PRINT_STDERR.
+    ///
+    /// Printing macros that are allowed anywhere, e.g. `["eprintln"]`
+    (allowed_print_macros: Vec<String> = Vec::new()),
 }
 
 /// Search for the configuration file.
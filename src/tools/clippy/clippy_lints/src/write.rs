@@ -1,10 +1,14 @@
 use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
+use clippy_utils::is_in_allow_list;
 use clippy_utils::macros::{root_macro_call_first_node, FormatArgsExpn, MacroCall};
 use clippy_utils::source::{expand_past_previous_comma, snippet_opt};
 use rustc_ast::LitKind;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::Applicability;
-use rustc_hir::{Expr, ExprKind, HirIdMap, Impl, Item, ItemKind};
+use rustc_hir::def_id::CRATE_DEF_ID;
+use rustc_hir::{Expr, ExprKind, HirId, HirIdMap, Impl, Item, ItemKind};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_session::config::CrateType;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::{sym, BytePos};
 
@@ -65,6 +69,11 @@
     /// People often print on *stdout* while debugging an
     /// application and might forget to remove those prints afterward.
     ///
+    /// The root module of a binary crate is not linted, as it's where a program
+    /// usually talks to the user; this can be changed with the
+    /// `allow-print-in-main-module` configuration. Other modules and macros can
+    /// be allowed with `allowed-print-modules` and `allowed-print-macros`.
+    ///
     /// ### Known problems
     /// Only catches `print!` and `println!` calls.
     ///
@@ -87,6 +96,10 @@
     /// People often print on *stderr* while debugging an
     /// application and might forget to remove those prints afterward.
     ///
+    /// This lint is configured like `print_stdout`, e.g.
+    /// `allowed-print-macros = ["eprintln"]` allows reporting errors with
+    /// `eprintln!`.
+    ///
     /// ### Known problems
     /// Only catches `eprint!` and `eprintln!` calls.
     ///
@@ -229,9 +242,47 @@
     "writing a literal with a format string"
 }
 
-#[derive(Default)]
 pub struct Write {
     in_debug_impl: bool,
+    allow_print_in_main_module: bool,
+    allowed_print_modules: FxHashSet<String>,
+    allowed_print_macros: FxHashSet<String>,
+}
+
+impl Write {
+    pub fn new(
+        allow_print_in_main_module: bool,
+        allowed_print_modules: &[String],
+        allowed_print_macros: &[String],
+    ) -> Self {
+        Self {
+            in_debug_impl: false,
+            allow_print_in_main_module,
+            allowed_print_modules: allowed_print_modules
+                .iter()
+                .map(|module| module.strip_prefix("crate::").unwrap_or(module).to_owned())
+                .collect(),
+            allowed_print_macros: allowed_print_macros
+                .iter()
+                .map(|name| name.trim_end_matches('!').to_owned())
+                .collect(),
+        }
+    }
+
+    /// Whether `print_stdout` and `print_stderr` are disabled for the `name!` call at `hir_id`,
+    /// by the macro name or by the module the call is in.
+    fn is_print_allowed(&self, cx: &LateContext<'_>, name: &str, hir_id: HirId) -> bool {
+        if self.allowed_print_macros.contains(name) {
+            return true;
+        }
+
+        let module = cx.tcx.parent_module(hir_id);
+        if module == CRATE_DEF_ID {
+            return self.allow_print_in_main_module && cx.tcx.sess.crate_types().contains(&CrateType::Executable);
+        }
+
+        is_in_allow_list(cx, module.to_def_id(), &self.allowed_print_modules, true)
+    }
 }
 
 impl_lint_pass!(Write => [
@@ -273,12 +324,14 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
 
         match diag_name {
             sym::print_macro | sym::println_macro => {
-                if !is_build_script {
+                if !is_build_script && !self.is_print_allowed(cx, name, expr.hir_id) {
                     span_lint(cx, PRINT_STDOUT, macro_call.span, &format!("use of `{name}!`"));
                 }
             },
             sym::eprint_macro | sym::eprintln_macro => {
-                span_lint(cx, PRINT_STDERR, macro_call.span, &format!("use of `{name}!`"));
+                if !self.is_print_allowed(cx, name, expr.hir_id) {
+                    span_lint(cx, PRINT_STDERR, macro_call.span, &format!("use of `{name}!`"));
+                }
             },
             sym::write_macro | sym::writeln_macro => {},
             _ => return,
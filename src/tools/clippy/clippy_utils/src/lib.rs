@@ -78,11 +78,11 @@
 use if_chain::if_chain;
 use rustc_ast::ast::{self, LitKind};
 use rustc_ast::Attribute;
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::unhash::UnhashMap;
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Namespace, Res};
-use rustc_hir::def_id::{CrateNum, DefId, LocalDefId};
+use rustc_hir::def_id::{CrateNum, DefId, DefIdMap, LocalDefId};
 use rustc_hir::hir_id::{HirIdMap, HirIdSet};
 use rustc_hir::intravisit::{walk_expr, FnKind, Visitor};
 use rustc_hir::LangItem::{OptionNone, ResultErr, ResultOk};
@@ -646,6 +646,49 @@ fn find_crate(tcx: TyCtxt<'_>, name: &str) -> Option<DefId> {
     Res::Err
 }
 
+/// Resolves the function paths like `std::thread::sleep`, each paired with an optional note to
+/// show when the function is used, e.g. the reason given in the configuration. Paths that don't
+/// resolve are left out, and a path listed more than once keeps the note of its last occurrence.
+///
+/// This function is expensive and should be used sparingly.
+pub fn def_path_def_ids<'a>(
+    cx: &LateContext<'_>,
+    paths: impl IntoIterator<Item = (&'a str, Option<&'a str>)>,
+) -> DefIdMap<(String, Option<String>)> {
+    let mut def_ids = DefIdMap::default();
+    for (path, note) in paths {
+        let segs: Vec<_> = path.split("::").collect();
+        if let Res::Def(_, id) = def_path_res(cx, &segs, Some(Namespace::ValueNS)) {
+            def_ids.insert(id, (path.to_owned(), note.map(ToOwned::to_owned)));
+        }
+    }
+    def_ids
+}
+
+/// Whether the item is in the allow list `allowed`, by its name or by its path within its crate,
+/// e.g. `ffi::Handle`. With `with_children`, the items within an allowed module or item are
+/// allowed as well, whether that is listed by its name or by its path.
+pub fn is_in_allow_list(cx: &LateContext<'_>, def_id: DefId, allowed: &FxHashSet<String>, with_children: bool) -> bool {
+    if allowed.contains(cx.tcx.item_name(def_id).as_str()) {
+        return true;
+    }
+    let def_path = cx.get_def_path(def_id);
+    // The first segment is the name of the crate.
+    let segments = &def_path[1..];
+    let mut path = String::new();
+    for (i, segment) in segments.iter().enumerate() {
+        if i > 0 {
+            path.push_str("::");
+        }
+        path.push_str(segment.as_str());
+        if (with_children || i == segments.len() - 1) && (allowed.contains(segment.as_str()) || allowed.contains(&path))
+        {
+            return true;
+        }
+    }
+    false
+}
+
 /// Convenience function to get the `DefId` of a trait by path.
 /// It could be a trait or trait alias.
 ///
@@ -2348,6 +2391,14 @@ fn is_cfg_test(attr: &Attribute) -> bool {
         .any(is_cfg_test)
 }
 
+/// Checks if the given `HirId` is within test code, i.e. a `#[test]` function or an item with the
+/// `#[cfg(test)]` attribute
+///
+/// Note: Add `// compile-flags: --test` to UI tests with test code
+pub fn is_in_test(tcx: TyCtxt<'_>, id: hir::HirId) -> bool {
+    is_in_test_function(tcx, id) || is_in_cfg_test(tcx, id)
+}
+
 /// Checks whether item either has `test` attribute applied, or
 /// is a module with `test` in its name.
 ///
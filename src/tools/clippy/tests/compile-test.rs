@@ -25,6 +25,7 @@
 static TEST_DEPENDENCIES: &[&str] = &[
     "clippy_lints",
     "clippy_utils",
+    "crossbeam_channel",
     "derive_new",
     "futures",
     "if_chain",
@@ -46,6 +47,8 @@
 #[allow(unused_extern_crates)]
 extern crate clippy_utils;
 #[allow(unused_extern_crates)]
+extern crate crossbeam_channel;
+#[allow(unused_extern_crates)]
 extern crate derive_new;
 #[allow(unused_extern_crates)]
 extern crate futures;
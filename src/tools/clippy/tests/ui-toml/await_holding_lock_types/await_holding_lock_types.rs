@@ -0,0 +1,27 @@
+#![warn(clippy::await_holding_lock)]
+use std::io::{self, Write};
+
+async fn bad() -> u32 {
+    let mut lock = io::stdout().lock();
+    let _ = writeln!(lock, "hello");
+    baz().await
+}
+
+async fn good() -> u32 {
+    {
+        let mut lock = io::stdout().lock();
+        let _ = writeln!(lock, "hello");
+    }
+    baz().await;
+    let _lock = io::stdout().lock();
+    47
+}
+
+async fn baz() -> u32 {
+    42
+}
+
+fn main() {
+    good();
+    bad();
+}
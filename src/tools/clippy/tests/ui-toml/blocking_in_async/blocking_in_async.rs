@@ -0,0 +1,15 @@
+#![warn(clippy::blocking_in_async)]
+
+use std::sync::{Mutex, RwLock};
+
+async fn lock(x: &Mutex<u32>, y: &RwLock<u32>) {
+    *x.lock().unwrap() += 1;
+    *y.write().unwrap() += 1;
+    let _ = y.read();
+}
+
+fn not_async(x: &Mutex<u32>) {
+    *x.lock().unwrap() += 1;
+}
+
+fn main() {}
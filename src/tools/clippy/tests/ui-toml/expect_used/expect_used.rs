@@ -27,3 +27,10 @@ fn test_expect_result() {
     let res: Result<u8, ()> = Ok(0);
     let _ = res.expect("");
 }
+
+#[cfg(test)]
+mod helpers {
+    fn first(values: &[u8]) -> u8 {
+        values.first().copied().expect("empty slice")
+    }
+}
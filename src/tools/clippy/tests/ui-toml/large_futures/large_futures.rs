@@ -0,0 +1,17 @@
+#![warn(clippy::large_futures)]
+
+async fn fetch() {}
+
+async fn above_threshold() {
+    let buffer = [0u8; 2_000];
+    fetch().await;
+    drop(buffer);
+}
+
+async fn below_threshold() {
+    let buffer = [0u8; 500];
+    fetch().await;
+    drop(buffer);
+}
+
+fn main() {}
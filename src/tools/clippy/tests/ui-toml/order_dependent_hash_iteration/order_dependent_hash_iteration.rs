@@ -0,0 +1,21 @@
+#![warn(clippy::order_dependent_hash_iteration)]
+
+use std::collections::{BTreeSet, HashSet};
+
+fn main() {
+    let set: HashSet<u32> = HashSet::new();
+    let mut total = 0;
+
+    // should lint
+    for x in &set {
+        total += x;
+    }
+    set.iter().for_each(|x| total += x);
+
+    // should not lint
+    let ordered: BTreeSet<u32> = BTreeSet::new();
+    for x in &ordered {
+        total += x;
+    }
+    let _ = total;
+}
@@ -0,0 +1,29 @@
+#![warn(clippy::panic_in_drop)]
+
+struct Guard(bool);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        assert!(self.0, "guard was not released");
+    }
+}
+
+mod checks {
+    pub struct Invariant(pub Option<u32>);
+
+    impl Drop for Invariant {
+        fn drop(&mut self) {
+            self.0.unwrap();
+        }
+    }
+
+    pub struct Other(pub Option<u32>);
+
+    impl Drop for Other {
+        fn drop(&mut self) {
+            self.0.unwrap();
+        }
+    }
+}
+
+fn main() {}
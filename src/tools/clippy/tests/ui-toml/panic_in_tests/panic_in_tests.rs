@@ -0,0 +1,30 @@
+// compile-flags: --test
+#![warn(clippy::panic, clippy::indexing_slicing)]
+
+fn check(values: &[u8]) -> u8 {
+    if values.is_empty() {
+        panic!("no values");
+    }
+    values[0]
+}
+
+fn main() {
+    let _ = check(&[1]);
+}
+
+#[test]
+fn test_check() {
+    let values = vec![1, 2];
+    assert_eq!(check(&values[1..]), values[1]);
+    panic!("unfinished");
+}
+
+#[cfg(test)]
+mod tests {
+    fn last(values: &[u8]) -> u8 {
+        if values.is_empty() {
+            panic!("no values");
+        }
+        values[values.len() - 1]
+    }
+}
@@ -0,0 +1,8 @@
+#![warn(clippy::pointer_width_dependent_cast)]
+
+fn main() {
+    let (a, b) = (1u32, 1usize);
+    let _ = a as usize;
+    let _ = b as u16;
+    let _ = b as u64;
+}
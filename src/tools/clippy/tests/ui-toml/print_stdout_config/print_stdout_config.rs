@@ -0,0 +1,35 @@
+#![warn(clippy::print_stdout, clippy::print_stderr)]
+
+mod cli {
+    pub mod output {
+        pub fn report(value: u32) {
+            println!("{value}");
+        }
+    }
+}
+
+mod client {
+    pub fn report(value: u32) {
+        println!("{value}");
+        eprintln!("{value}");
+        eprint!("{value}");
+    }
+}
+
+fn main() {
+    println!("Hello");
+    eprintln!("World");
+    cli::output::report(1);
+    client::report(2);
+    app::logging::sink::report(3);
+}
+
+mod app {
+    pub mod logging {
+        pub mod sink {
+            pub fn report(value: u32) {
+                println!("{value}");
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+#![warn(clippy::raw_ptr_wrapper_send_sync)]
+
+pub struct Handle(*mut u8);
+
+impl Handle {
+    pub fn new(ptr: *mut u8) -> Self {
+        Self(ptr)
+    }
+}
+
+unsafe impl Send for Handle {}
+
+mod ffi {
+    pub struct Raw(*mut u8);
+
+    impl Raw {
+        pub fn new(ptr: *mut u8) -> Self {
+            Self(ptr)
+        }
+    }
+
+    unsafe impl Send for Raw {}
+
+    pub struct Other(*mut u8);
+
+    impl Other {
+        pub fn new(ptr: *mut u8) -> Self {
+            Self(ptr)
+        }
+    }
+
+    unsafe impl Send for Other {}
+}
+
+fn main() {}
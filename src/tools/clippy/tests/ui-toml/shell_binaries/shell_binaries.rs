@@ -0,0 +1,13 @@
+#![warn(clippy::shell_command_from_format)]
+
+use std::process::Command;
+
+fn main() {
+    let dir = "/tmp";
+
+    Command::new("nu").arg("-c").arg(format!("ls {dir}"));
+    Command::new("/usr/bin/nu").args(["-c", &format!("ls {dir}")]);
+
+    // the configured shells replace the default ones
+    Command::new("sh").arg("-c").arg(format!("ls {dir}"));
+}
@@ -0,0 +1,33 @@
+// compile-flags: --test
+#![warn(clippy::todo, clippy::unimplemented)]
+
+fn placeholder() -> u32 {
+    todo!()
+}
+
+fn unfinished() -> u32 {
+    unimplemented!()
+}
+
+fn main() {
+    let _ = placeholder();
+    let _ = unfinished();
+}
+
+#[test]
+fn test_placeholder() {
+    todo!();
+}
+
+#[cfg(test)]
+mod tests {
+    fn helper() -> u32 {
+        unimplemented!()
+    }
+
+    #[test]
+    fn test_unfinished() {
+        let _ = helper();
+        todo!();
+    }
+}
@@ -0,0 +1,8 @@
+#![warn(clippy::unbounded_channel)]
+
+extern crate futures;
+
+fn main() {
+    let (_sender, _receiver) = futures::channel::mpsc::unbounded::<u32>();
+    let (_sender, _receiver) = futures::channel::mpsc::channel::<u32>(16);
+}
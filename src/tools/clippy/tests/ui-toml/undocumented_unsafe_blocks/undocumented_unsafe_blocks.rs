@@ -0,0 +1,23 @@
+#![warn(clippy::undocumented_unsafe_blocks)]
+
+unsafe fn read(ptr: *const u8) -> u8 {
+    unsafe { *ptr }
+}
+
+unsafe fn read_in_closure(ptr: *const u8) -> u8 {
+    let read = || unsafe { *ptr };
+    read()
+}
+
+fn safe_read(ptr: &u8) -> u8 {
+    unsafe { *(ptr as *const u8) }
+}
+
+fn main() {
+    // SAFETY: the reference is valid for reads.
+    unsafe {
+        read(&0);
+        read_in_closure(&0);
+    }
+    safe_read(&0);
+}
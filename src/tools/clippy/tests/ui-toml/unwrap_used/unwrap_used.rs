@@ -71,3 +71,10 @@ fn test() {
     let boxed_slice: Box<[u8]> = Box::new([0, 1, 2, 3]);
     let _ = boxed_slice.get(1).unwrap();
 }
+
+#[cfg(test)]
+mod helpers {
+    fn first(values: &[u8]) -> u8 {
+        values.first().copied().unwrap()
+    }
+}
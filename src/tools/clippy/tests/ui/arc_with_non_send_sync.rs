@@ -0,0 +1,33 @@
+#![warn(clippy::arc_with_non_send_sync)]
+#![allow(unused_variables)]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+fn foo<T>(x: T) {
+    // Should not lint: whether `T` is `Send` or `Sync` depends on the caller.
+    let a = Arc::new(x);
+}
+
+fn bar<T>(x: Vec<T>) {
+    // Should not lint: whether `Vec<T>` is `Send` or `Sync` depends on the caller.
+    let a = Arc::new(x);
+}
+
+fn guard(mutex: &Mutex<i32>) {
+    // Should not lint: `MutexGuard` is `Sync`, even though it is not `Send`.
+    let guard: MutexGuard<'_, i32> = mutex.lock().unwrap();
+    let _ = Arc::new(guard);
+}
+
+fn main() {
+    let _ = Arc::new(42);
+    let _ = Arc::new(Mutex::new(42));
+
+    // Should not lint: `RefCell` is `Send`, even though it is not `Sync`.
+    let _ = Arc::new(RefCell::new(42));
+
+    let _ = Arc::new(Rc::new(42));
+    let _ = Arc::new(Cell::new(std::ptr::null::<u8>()));
+}
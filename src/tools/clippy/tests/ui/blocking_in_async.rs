@@ -0,0 +1,41 @@
+#![warn(clippy::blocking_in_async)]
+
+use std::fs::{self, File};
+use std::time::Duration;
+
+async fn sleep() {
+    std::thread::sleep(Duration::from_millis(10));
+}
+
+async fn read_file() -> std::io::Result<String> {
+    let _file = File::open("foo.txt")?;
+    fs::read_to_string("foo.txt")
+}
+
+fn in_block() -> impl std::future::Future<Output = ()> {
+    async {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn in_closure() -> impl std::future::Future<Output = ()> {
+    async {
+        // Not linted: the closure may be run on a thread where blocking is fine.
+        let read = || fs::read("foo.txt");
+        let _ = std::thread::spawn(read);
+    }
+}
+
+fn not_async() {
+    std::thread::sleep(Duration::from_millis(10));
+    let _ = fs::read("foo.txt");
+}
+
+async fn nested() {
+    fn inner() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    inner();
+}
+
+fn main() {}
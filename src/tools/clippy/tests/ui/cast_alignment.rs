@@ -9,7 +9,10 @@
     clippy::no_effect,
     clippy::unnecessary_operation,
     clippy::cast_lossless,
-    clippy::borrow_as_ptr
+    clippy::borrow_as_ptr,
+    clippy::useless_conversion,
+    clippy::useless_transmute,
+    clippy::transmutes_expressible_as_ptr_casts
 )]
 
 fn main() {
@@ -23,6 +26,17 @@ fn main() {
     (&1u8 as *const u8).cast::<u16>();
     (&mut 1u8 as *mut u8).cast::<u16>();
 
+    // cast to more-strictly-aligned type, but through an integer.
+    let ptr = &1u8 as *const u8;
+    ptr as usize as *const u16;
+    let addr = ptr as usize;
+    addr as *const u16;
+    usize::from(addr) as *const u16;
+    unsafe {
+        std::mem::transmute::<usize, *const u16>(addr);
+        std::mem::transmute::<usize, *const u16>(std::mem::transmute::<*const u8, usize>(ptr));
+    }
+
     /* These should be ok */
 
     // not a pointer type
@@ -35,6 +49,9 @@ fn main() {
     (&1u32 as *const u32 as *const libc::c_void) as *const u32;
     // For ZST, we should trust the user. See #4256
     (&1u32 as *const u32 as *const ()) as *const u32;
+    // Arithmetic on the address, as done to align pointers by hand, is not followed.
+    let addr = &1u8 as *const u8 as usize;
+    ((addr + 1) & !1) as *const u16;
 
     // Issue #2881
     let mut data = [0u8, 0u8];
@@ -0,0 +1,80 @@
+#![warn(clippy::collection_is_never_read)]
+
+use std::collections::{HashMap, HashSet};
+
+fn main() {}
+
+fn not_read() {
+    let mut v = Vec::new(); // WARNING
+    v.push(1);
+    v.extend([2, 3]);
+}
+
+fn not_read_map() {
+    let mut map = HashMap::new(); // WARNING
+    map.insert(1, 2);
+    map.clear();
+}
+
+fn not_read_string() {
+    let mut s = String::new(); // WARNING
+    s.push_str("hello");
+    s += " world";
+    s = String::from("bye");
+}
+
+fn not_read_in_loop(items: &[u32]) {
+    let mut seen = HashSet::new(); // WARNING
+    for &item in items {
+        seen.insert(item);
+    }
+}
+
+fn read() -> usize {
+    let mut v = Vec::new(); // Ok
+    v.push(1);
+    v.len()
+}
+
+fn returned() -> Vec<i32> {
+    let mut v = Vec::new(); // Ok
+    v.push(1);
+    v
+}
+
+fn iterated() {
+    let mut v = Vec::new(); // Ok
+    v.push(1);
+    for x in &v {
+        let _ = x;
+    }
+}
+
+fn result_used() -> bool {
+    let mut set = HashSet::new(); // Ok
+    set.insert(1) && set.insert(2)
+}
+
+fn passed_on(sink: &mut Vec<i32>) {
+    let mut v = Vec::new(); // Ok
+    v.push(1);
+    sink.append(&mut v);
+}
+
+fn read_in_macro() {
+    let mut v = Vec::new(); // Ok
+    v.push(1);
+    println!("{v:?}");
+}
+
+fn read_in_closure() {
+    let mut v = Vec::new(); // Ok
+    v.push(1);
+    let f = || v.is_empty();
+    let _ = f();
+}
+
+fn not_a_collection() {
+    let mut x = 0; // Ok
+    x = 1;
+}
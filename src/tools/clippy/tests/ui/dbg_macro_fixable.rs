@@ -0,0 +1,7 @@
+// run-rustfix
+#![warn(clippy::dbg_macro)]
+
+fn main() {
+    dbg!();
+    let _ = dbg!();
+}
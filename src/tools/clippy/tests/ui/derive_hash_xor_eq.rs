@@ -53,4 +53,21 @@ fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
     }
 }
 
+mod float {
+    use std::hash::{Hash, Hasher};
+
+    #[derive(PartialEq)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    impl Hash for Point {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.x.to_bits().hash(state);
+            self.y.to_bits().hash(state);
+        }
+    }
+}
+
 fn main() {}
@@ -13,3 +13,12 @@ fn main() {
     not_main();
     std::process::exit(1);
 }
+
+struct App;
+
+#[warn(clippy::exit)]
+impl App {
+    fn run(&self) {
+        std::process::exit(5);
+    }
+}
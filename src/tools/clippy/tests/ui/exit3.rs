@@ -4,5 +4,7 @@ fn main() {
     if true {
         std::process::exit(2);
     };
+    let exit = || std::process::exit(3);
+    exit();
     std::process::exit(1);
 }
@@ -165,3 +165,10 @@ fn main() {
         print_substring("Hello, world!");
     }
 }
+
+#[warn(clippy::format_in_format_args)]
+fn format_in_format_args(error: &str, x: u32) {
+    // the inner `format!` only formats its argument
+    println!("error: {}", format!("{}", Location::caller()));
+    let _ = format!("{}: {}", error, format!("{}", x));
+}
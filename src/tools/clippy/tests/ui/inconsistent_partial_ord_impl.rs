@@ -0,0 +1,115 @@
+#![warn(clippy::inconsistent_partial_ord_impl)]
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, Eq)]
+struct Version(u32, u32);
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0, self.1).cmp(&(other.0, other.1))
+    }
+}
+
+#[derive(PartialEq, Eq)]
+struct Reversed(u32);
+
+impl PartialOrd for Reversed {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(rhs.cmp(self))
+    }
+}
+
+impl Ord for Reversed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+// ok, calls `cmp`
+#[derive(PartialEq, Eq)]
+struct Method(u32);
+
+impl PartialOrd for Method {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Method {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+// ok, calls `cmp` with path syntax
+#[derive(PartialEq, Eq)]
+struct Path(u32);
+
+impl PartialOrd for Path {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, other))
+    }
+}
+
+impl Ord for Path {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+// ok, `Ord` is not implemented
+#[derive(PartialEq)]
+struct NoOrd(f64);
+
+impl PartialOrd for NoOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+// ok, `Ord` is derived, covered by `derive_ord_xor_partial_ord`
+#[derive(PartialEq, Eq, Ord)]
+struct DerivedOrd(u32);
+
+impl PartialOrd for DerivedOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+// ok, compares with another type
+#[derive(PartialEq, Eq)]
+struct Other(u32);
+
+impl PartialEq<u32> for Other {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialOrd<u32> for Other {
+    fn partial_cmp(&self, other: &u32) -> Option<Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+impl PartialOrd for Other {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Other {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+fn main() {}
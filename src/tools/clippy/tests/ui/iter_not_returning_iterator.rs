@@ -71,4 +71,11 @@ fn iter(&self) -> core::slice::Iter<u8> {
     }
 }
 
+struct Data3;
+impl Data3 {
+    fn into_iter(self) -> Counter2 {
+        todo!()
+    }
+}
+
 fn main() {}
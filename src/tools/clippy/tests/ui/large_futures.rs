@@ -0,0 +1,36 @@
+#![warn(clippy::large_futures)]
+
+async fn fetch() {}
+
+async fn large() {
+    let buffer = [0u8; 20_000];
+    fetch().await;
+    drop(buffer);
+}
+
+async fn small() {
+    let buffer = vec![0u8; 20_000];
+    fetch().await;
+    drop(buffer);
+}
+
+async fn not_held() {
+    {
+        let buffer = [0u8; 20_000];
+        drop(buffer);
+    }
+    fetch().await;
+}
+
+async fn generic<T>(value: T) {
+    fetch().await;
+    drop(value);
+}
+
+fn main() {
+    let _ = async {
+        let buffer = [0u8; 20_000];
+        fetch().await;
+        drop(buffer);
+    };
+}
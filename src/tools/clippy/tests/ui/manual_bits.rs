@@ -57,4 +57,10 @@ fn main() {
     let _: u32 = (size_of::<u128>() * 8).try_into().unwrap();
     let _ = (size_of::<u128>() * 8).pow(5);
     let _ = &(size_of::<u128>() * 8);
+
+    std::mem::size_of::<u16>() * 8;
+    size_of::<*const u8>() * 8;
+    size_of::<&u8>() * 8;
+    size_of::<fn()>() * 8;
+    size_of::<&[u8]>() * 8;
 }
@@ -0,0 +1,103 @@
+// run-rustfix
+#![feature(custom_inner_attributes)]
+#![warn(clippy::manual_let_else)]
+#![allow(clippy::single_match_else)]
+
+fn get() -> Option<u32> {
+    Some(1)
+}
+
+fn parse(s: &str) -> Result<u32, std::num::ParseIntError> {
+    s.parse()
+}
+
+fn lint() {
+    let v = match get() {
+        Some(v) => v,
+        None => return,
+    };
+    let w = if let Some(w) = get() { w } else { return };
+    let x = match parse("1") {
+        Err(_) => panic!("not a number"),
+        Ok(x) => x,
+    };
+    let mut y = match get() {
+        Some(y) => y,
+        None => {
+            println!("nothing");
+            return;
+        },
+    };
+    y += v + w + x;
+    println!("{y}");
+}
+
+fn lint_continue() {
+    for s in ["1", "a"] {
+        let n = if let Ok(n) = parse(s) {
+            n
+        } else {
+            continue;
+        };
+        println!("{n}");
+    }
+}
+
+fn no_lint() {
+    // A guard
+    let a = match get() {
+        Some(a) if a > 1 => a,
+        _ => return,
+    };
+    // A type annotation
+    let b: u32 = match get() {
+        Some(b) => b,
+        None => return,
+    };
+    // The else branch doesn't diverge
+    let c = if let Some(c) = get() { c } else { 0 };
+    // The diverging arm binds a value
+    let d = match parse("1") {
+        Ok(d) => d,
+        Err(e) => panic!("{e}"),
+    };
+    // The value isn't the binding
+    let e = match get() {
+        Some(e) => e + 1,
+        None => return,
+    };
+    println!("{a}{b}{c}{d}{e}");
+}
+
+fn msrv_1_64() {
+    #![clippy::msrv = "1.64"]
+    let v = match get() {
+        Some(v) => v,
+        None => return,
+    };
+    println!("{v}");
+}
+
+fn msrv_1_65() {
+    #![clippy::msrv = "1.65"]
+    let v = match get() {
+        Some(v) => v,
+        None => return,
+    };
+    println!("{v}");
+}
+
+enum Shape {
+    Circle { radius: u32 },
+    Square { side: u32 },
+}
+
+fn lint_shorthand_field(shape: Shape) {
+    let r = match shape {
+        Shape::Circle { radius } => radius,
+        Shape::Square { .. } => return,
+    };
+    println!("{r}");
+}
+
+fn main() {}
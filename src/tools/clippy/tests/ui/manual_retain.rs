@@ -222,6 +222,17 @@ fn vec_deque_retain() {
     bar = foobar.into_iter().filter(|x| x % 2 == 0).collect();
 }
 
+fn deref_retain(vec: &mut Vec<i8>, s: &mut String) {
+    // Do lint.
+    *vec = vec.iter().filter(|&x| x % 2 == 0).copied().collect();
+    *vec = vec.iter().filter(|&x| x % 2 == 0).cloned().collect();
+    *s = s.chars().filter(|&c| c != 'o').to_owned().collect();
+
+    // Do not lint, because it is an assignment to a different variable.
+    let bar = vec![0, 1, 2];
+    *vec = bar.iter().filter(|&x| x % 2 == 0).copied().collect();
+}
+
 fn _msrv_153() {
     #![clippy::msrv = "1.52"]
     let mut btree_map: BTreeMap<i8, i8> = (0..8).map(|x| (x, x * 10)).collect();
@@ -49,6 +49,11 @@ fn main() {
     let _: SomeOtherStruct = TryFrom::try_from("no_warning").unwrap();
     let _: SomeOtherStruct = TryFrom::try_from("").unwrap(); // Again: no warning.
 
+    let _ = std::string::String::from("");
+    let _ = ToString::to_string("");
+    let _ = ToOwned::to_owned("");
+    let _ = ToString::to_string("no warning");
+
     // Macros (never warn)
     create_strings_from_macro!("");
     create_strings_from_macro!("Hey");
@@ -0,0 +1,100 @@
+#![warn(clippy::needless_pass_by_ref_mut)]
+#![allow(clippy::ptr_arg)]
+
+fn main() {}
+
+// Should lint
+fn total(values: &mut Vec<u32>) -> u32 {
+    values.iter().sum()
+}
+
+// Should lint
+fn first<'a>(values: &'a mut [u32]) -> Option<&'a u32> {
+    values.first()
+}
+
+// Should lint
+fn copy_out(x: &mut i32, y: &mut i32) -> i32 {
+    *y = 1;
+    *x + *y
+}
+
+// Should lint
+fn pass_on_shared(values: &mut Vec<u32>) -> u32 {
+    total_shared(values)
+}
+
+fn total_shared(values: &Vec<u32>) -> u32 {
+    values.iter().sum()
+}
+
+// Should not lint
+fn push(values: &mut Vec<u32>) {
+    values.push(1);
+}
+
+// Should not lint
+fn assign(x: &mut i32) {
+    *x += 1;
+}
+
+// Should not lint, the reborrow is passed on
+fn reborrow(values: &mut Vec<u32>) {
+    push(values);
+}
+
+// Should not lint, the reference is moved
+fn moved(values: &mut Vec<u32>) -> &mut Vec<u32> {
+    values
+}
+
+// Should not lint
+fn closure(values: &mut Vec<u32>) {
+    let mut f = || values.push(1);
+    f();
+}
+
+// Should not lint
+fn iter_mut(values: &mut [u32]) {
+    for v in values.iter_mut() {
+        *v = 0;
+    }
+}
+
+// Should not lint
+fn unused(_values: &mut Vec<u32>) {}
+
+// Should not lint
+pub fn exported(values: &mut Vec<u32>) -> usize {
+    values.len()
+}
+
+struct Counter(u32);
+
+impl Counter {
+    // Should not lint `self`
+    fn get(&mut self) -> u32 {
+        self.0
+    }
+
+    // Should lint
+    fn add_to(&self, other: &mut Counter) -> u32 {
+        self.0 + other.0
+    }
+}
+
+trait Visit {
+    fn visit(&self, values: &mut Vec<u32>) -> usize;
+}
+
+impl Visit for Counter {
+    // Should not lint, the signature is given by the trait
+    fn visit(&self, values: &mut Vec<u32>) -> usize {
+        values.len()
+    }
+}
+
+// Should not lint
+async fn async_fn(values: &mut Vec<u32>) -> usize {
+    values.len()
+}
@@ -30,4 +30,12 @@ fn main() {
     let mut builder = DirBuilder::new();
     builder.mode(755);
     builder.mode(0o406);
+
+    // not written in decimal, or the same in octal (shouldn't trigger)
+    builder.mode(0x1ed);
+    builder.mode(0b111_101_101);
+    builder.mode(7);
+
+    // can't be an octal value (shouldn't trigger)
+    builder.mode(789);
 }
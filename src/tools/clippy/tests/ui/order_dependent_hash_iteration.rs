@@ -0,0 +1,42 @@
+#![warn(clippy::order_dependent_hash_iteration)]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+fn main() {
+    let map: HashMap<&str, u32> = HashMap::new();
+    let set: HashSet<u32> = HashSet::new();
+
+    // should lint
+    for (name, score) in &map {
+        println!("{name}: {score}");
+    }
+    let _: Vec<_> = map.keys().copied().collect();
+    let _: String = set.iter().map(|x| x.to_string()).collect();
+    let mut out = Vec::new();
+    for x in set.iter() {
+        out.push(*x);
+    }
+    set.iter().for_each(|x| eprintln!("{x}"));
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for x in &set {
+        x.hash(&mut hasher);
+    }
+    let _ = hasher.finish();
+
+    // should not lint
+    let mut total = 0;
+    for score in map.values() {
+        total += score;
+    }
+    let _: HashSet<_> = map.keys().collect();
+    let _: BTreeMap<_, _> = map.iter().collect();
+    let mut sorted: Vec<_> = map.keys().collect();
+    sorted.sort();
+    let ordered: BTreeMap<&str, u32> = BTreeMap::new();
+    for (name, score) in &ordered {
+        println!("{name}: {score}");
+    }
+    set.iter().for_each(|x| total += x);
+    let _ = total;
+}
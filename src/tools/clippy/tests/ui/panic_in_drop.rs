@@ -0,0 +1,41 @@
+#![warn(clippy::panic_in_drop)]
+
+struct Unwraps(Option<u32>, Result<u32, ()>);
+
+impl Drop for Unwraps {
+    fn drop(&mut self) {
+        self.0.unwrap();
+        self.1.expect("no error");
+    }
+}
+
+struct Panics(Vec<u32>);
+
+impl Drop for Panics {
+    fn drop(&mut self) {
+        assert!(!self.0.is_empty());
+        let _ = self.0[0];
+        panic!("dropped");
+    }
+}
+
+// Closures aren't necessarily called from `drop`
+struct Closure(Option<u32>);
+
+impl Drop for Closure {
+    fn drop(&mut self) {
+        let _ = || self.0.unwrap();
+    }
+}
+
+struct Fine(Option<u32>);
+
+impl Drop for Fine {
+    fn drop(&mut self) {
+        if let Some(x) = self.0.take() {
+            println!("{x}");
+        }
+    }
+}
+
+fn main() {}
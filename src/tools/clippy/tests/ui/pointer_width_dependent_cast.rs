@@ -0,0 +1,22 @@
+#![warn(clippy::pointer_width_dependent_cast)]
+
+fn to_pointer_sized(a: u16, b: u32, c: u64, d: i64, e: u128) {
+    let _ = a as usize;
+    let _ = b as usize;
+    let _ = c as usize;
+    let _ = d as isize;
+    let _ = e as usize;
+}
+
+fn from_pointer_sized(a: usize, b: isize) {
+    let _ = a as u16;
+    let _ = a as u32;
+    let _ = a as u64;
+    let _ = b as i32;
+    let _ = b as i128;
+
+    // both are pointer-sized
+    let _ = a as isize;
+}
+
+fn main() {}
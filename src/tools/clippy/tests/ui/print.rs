@@ -1,3 +1,4 @@
+#![crate_type = "lib"]
 #![allow(clippy::print_literal, clippy::write_literal)]
 #![warn(clippy::print_stdout, clippy::use_debug)]
 
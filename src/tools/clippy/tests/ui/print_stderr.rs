@@ -1,3 +1,4 @@
+#![crate_type = "lib"]
 #![warn(clippy::print_stderr)]
 
 fn main() {
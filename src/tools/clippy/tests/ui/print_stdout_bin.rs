@@ -0,0 +1,15 @@
+#![warn(clippy::print_stdout, clippy::print_stderr)]
+
+mod output {
+    pub fn report(value: u32) {
+        println!("{value}");
+        eprintln!("done");
+    }
+}
+
+fn main() {
+    // ok, printing is allowed in the root module of a binary
+    println!("Hello");
+    eprintln!("World");
+    output::report(1);
+}
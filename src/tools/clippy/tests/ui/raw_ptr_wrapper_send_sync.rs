@@ -0,0 +1,86 @@
+#![warn(clippy::raw_ptr_wrapper_send_sync)]
+#![allow(clippy::new_without_default)]
+
+use std::marker::PhantomData;
+
+pub struct Handle(*mut u8);
+
+impl Handle {
+    pub fn new(ptr: *mut u8) -> Self {
+        Self(ptr)
+    }
+}
+
+unsafe impl Send for Handle {}
+unsafe impl Sync for Handle {}
+
+pub struct PublicField {
+    pub ptr: *const u8,
+}
+
+unsafe impl Send for PublicField {}
+
+pub struct Typed<T> {
+    ptr: *mut T,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Typed<T> {
+    pub fn try_new(ptr: *mut T) -> Option<Self> {
+        (!ptr.is_null()).then_some(Self {
+            ptr,
+            _marker: PhantomData,
+        })
+    }
+}
+
+unsafe impl<T: Send> Send for Typed<T> {}
+
+// The constructor is `unsafe`
+pub struct Checked(*mut u8);
+
+impl Checked {
+    /// # Safety
+    ///
+    /// `ptr` must be valid to use from any thread.
+    pub unsafe fn new(ptr: *mut u8) -> Self {
+        Self(ptr)
+    }
+}
+
+unsafe impl Send for Checked {}
+
+// The pointer can't be chosen by the caller
+pub struct Owned(*mut u8);
+
+impl Owned {
+    pub fn new() -> Self {
+        Self(Box::into_raw(Box::new(0)))
+    }
+}
+
+unsafe impl Send for Owned {}
+
+// The constructor is private
+pub struct Internal(*mut u8);
+
+impl Internal {
+    fn new(ptr: *mut u8) -> Self {
+        Self(ptr)
+    }
+}
+
+unsafe impl Send for Internal {}
+
+// Not only a pointer
+pub struct WithLen(*mut u8, usize);
+
+impl WithLen {
+    pub fn new(ptr: *mut u8, len: usize) -> Self {
+        Self(ptr, len)
+    }
+}
+
+unsafe impl Send for WithLen {}
+
+fn main() {}
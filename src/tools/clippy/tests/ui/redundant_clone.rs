@@ -239,3 +239,44 @@ fn foo(_x: &Alpha, _y: &mut Alpha) {}
     let _z = x.clone(); // pr 7346 can't lint on `x`
     drop(y);
 }
+
+fn closure_borrow() {
+    let s = String::new();
+    let len = || s.len();
+    let _ = len();
+    let _t = s.clone();
+
+    let s = String::new();
+    let len = || s.len();
+    let _t = s.clone(); // ok, the closure is used afterwards
+    let _ = len();
+}
+
+fn closure_captured_by_move() {
+    let s = String::new();
+    let t = s.clone();
+    let len = move || t.len();
+    let _ = len();
+
+    let s = String::new();
+    let _t = s.clone(); // ok, `s` is moved into the closure afterwards
+    let len = move || s.len();
+    let _ = len();
+}
+
+fn closure_used_in_other_branch(b: bool) {
+    let s = String::new();
+    let len = || s.len();
+    if b {
+        let _t = s.clone();
+    } else {
+        let _ = len();
+    }
+}
+
+async fn clone_across_await() {
+    let s = String::new();
+    let t = s.clone(); // ok, `s` is used after the `.await`
+    async {}.await;
+    drop((s, t));
+}
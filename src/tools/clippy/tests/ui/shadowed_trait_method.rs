@@ -0,0 +1,64 @@
+#![warn(clippy::shadowed_trait_method)]
+
+trait Describe {
+    fn describe(&self) -> String;
+
+    fn shout(&self) -> String {
+        self.describe().to_uppercase()
+    }
+
+    fn consume(self) -> String;
+}
+
+struct Point(i32, i32);
+
+impl Describe for Point {
+    fn describe(&self) -> String {
+        format!("({}, {})", self.0, self.1)
+    }
+
+    fn consume(self) -> String {
+        self.describe()
+    }
+}
+
+impl Point {
+    fn describe(&self) -> String {
+        format!("{} {}", self.0, self.1)
+    }
+
+    fn shout(&self) -> String {
+        String::from("POINT")
+    }
+
+    // Different receiver, calls of `point.consume()` pick the trait method
+    fn consume(&self) -> String {
+        String::new()
+    }
+}
+
+struct Delegating;
+
+impl Describe for Delegating {
+    fn describe(&self) -> String {
+        String::from("delegating")
+    }
+
+    fn consume(self) -> String {
+        String::new()
+    }
+}
+
+impl Delegating {
+    // Calls the trait method, so that callers don't need to import the trait
+    fn describe(&self) -> String {
+        Describe::describe(self)
+    }
+
+    // Not a method
+    fn shout() -> String {
+        String::new()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+#![warn(clippy::shell_command_from_format)]
+#![allow(clippy::useless_format)]
+
+use std::process::Command;
+
+fn main() {
+    let dir = "/tmp";
+
+    Command::new("sh").arg("-c").arg(format!("ls {dir}"));
+    Command::new("/bin/bash").arg("-ec").arg(&format!("ls {}", dir));
+    Command::new("sh").args(["-c", &format!("ls {dir}")]);
+    Command::new("cmd").arg("/C").arg(format!("dir {dir}"));
+    Command::new("sh").arg("-c").current_dir(dir).arg(format!("ls {dir}"));
+    Command::new("powershell").arg("-Command").arg(format!("Get-ChildItem {dir}"));
+
+    // no interpolated values
+    Command::new("sh").arg("-c").arg(format!("ls"));
+    // not a shell
+    Command::new("ls").arg("-c").arg(format!("{dir}"));
+    // not running a script
+    Command::new("sh").arg(format!("{dir}/script.sh"));
+    Command::new("sh").arg("--version").arg(format!("{dir}"));
+}
@@ -0,0 +1,34 @@
+#![feature(custom_inner_attributes)]
+#![warn(clippy::static_mut)]
+
+use std::rc::Rc;
+use std::sync::atomic::AtomicU32;
+
+static mut FLAG: bool = false;
+static mut COUNT: usize = 0;
+static mut SMALL: u8 = 0;
+static mut PTR: *const u8 = std::ptr::null();
+static mut NAMES: Vec<String> = Vec::new();
+static mut SHARED: Option<Rc<u32>> = None;
+
+// Not `mut`
+static FINE: AtomicU32 = AtomicU32::new(0);
+
+extern "C" {
+    // Can't be changed, as it is defined elsewhere
+    static mut EXTERN: i32;
+}
+
+fn msrv_1_33() {
+    #![clippy::msrv = "1.33"]
+    static mut SMALL: u8 = 0;
+    static mut NAMES: Vec<String> = Vec::new();
+}
+
+fn msrv_1_62() {
+    #![clippy::msrv = "1.62"]
+    static mut SMALL: u8 = 0;
+    static mut NAMES: Vec<String> = Vec::new();
+}
+
+fn main() {}
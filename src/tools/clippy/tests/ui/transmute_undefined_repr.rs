@@ -154,3 +154,23 @@ fn _with_generics<T: 'static, U: 'static>() {
         let _: Box<T> = transmute(value::<*const u32>()); // Ok
     }
 }
+
+enum Fieldless {
+    A,
+    B,
+}
+
+#[repr(u8)]
+enum FieldlessU8 {
+    A,
+    B,
+}
+
+fn _enums() {
+    unsafe {
+        let _: Fieldless = transmute(0u8); // Err
+        let _: u8 = transmute(Fieldless::A); // Err
+        let _: FieldlessU8 = transmute(0u8); // Ok
+        let _: Option<&u32> = transmute(value::<*const u32>()); // Ok
+    }
+}
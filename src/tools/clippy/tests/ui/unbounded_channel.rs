@@ -0,0 +1,18 @@
+#![warn(clippy::unbounded_channel)]
+
+extern crate crossbeam_channel;
+extern crate tokio;
+
+use std::sync::mpsc;
+
+fn main() {
+    let (_sender, _receiver) = mpsc::channel::<u32>();
+    let (_sender, _receiver) = std::sync::mpsc::channel::<u32>();
+    let (_sender, _receiver) = crossbeam_channel::unbounded::<u32>();
+    let (_sender, _receiver) = tokio::sync::mpsc::unbounded_channel::<u32>();
+
+    // bounded
+    let (_sender, _receiver) = mpsc::sync_channel::<u32>(16);
+    let (_sender, _receiver) = crossbeam_channel::bounded::<u32>(16);
+    let (_sender, _receiver) = tokio::sync::mpsc::channel::<u32>(16);
+}
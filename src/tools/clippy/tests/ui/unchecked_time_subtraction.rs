@@ -0,0 +1,36 @@
+#![feature(custom_inner_attributes)]
+#![warn(clippy::unchecked_time_subtraction)]
+#![allow(clippy::manual_instant_elapsed)]
+
+use std::time::{Duration, Instant, SystemTime};
+
+fn main() {
+    let start = Instant::now();
+    let timeout = Duration::from_secs(1);
+
+    let _ = start - timeout;
+    let _ = SystemTime::now() - timeout;
+    let _ = timeout - Duration::from_millis(10);
+    let mut remaining = timeout;
+    remaining -= Duration::from_millis(10);
+    let end = Instant::now();
+    let _ = start - end;
+
+    // don't lint
+    let _ = Instant::now() - start;
+    let _ = start.checked_sub(timeout);
+    let _ = end.saturating_duration_since(start);
+    let _ = start + timeout;
+}
+
+fn msrv_1_33() {
+    #![clippy::msrv = "1.33"]
+
+    let _ = Instant::now() - Duration::from_secs(1);
+}
+
+fn msrv_1_34() {
+    #![clippy::msrv = "1.34"]
+
+    let _ = Instant::now() - Duration::from_secs(1);
+}
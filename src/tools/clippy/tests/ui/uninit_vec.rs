@@ -84,7 +84,8 @@ fn main() {
         vec.set_len(200);
     }
 
-    // known false negative
+    // statements not using the `Vec` are skipped, only the first statement of the unsafe block is
+    // checked for `vec2`
     let mut vec1: Vec<u8> = Vec::with_capacity(1000);
     let mut vec2: Vec<u8> = Vec::with_capacity(1000);
     unsafe {
@@ -92,6 +93,20 @@ fn main() {
         vec2.set_len(200);
     }
 
+    // test `set_len()` after statements which don't use the `Vec`
+    let mut vec: Vec<u8> = Vec::with_capacity(1000);
+    let len = 200;
+    unsafe {
+        vec.set_len(len);
+    }
+
+    // the `Vec` is initialized between `with_capacity()` and `set_len()` (shouldn't trigger)
+    let mut vec: Vec<u8> = Vec::with_capacity(1000);
+    vec.extend_from_slice(&[0; 200]);
+    unsafe {
+        vec.set_len(200);
+    }
+
     // set_len(0) should not be detected
     let mut vec: Vec<u8> = Vec::with_capacity(1000);
     unsafe {
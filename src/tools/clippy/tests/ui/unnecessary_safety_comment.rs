@@ -0,0 +1,53 @@
+#![warn(clippy::unnecessary_safety_comment)]
+
+mod unsafe_items_invalid_comment {
+    // SAFETY:
+    const CONST: u32 = 0;
+    // SAFETY:
+    static STATIC: u32 = 0;
+    // SAFETY:
+    struct Struct;
+    // SAFETY:
+    enum Enum {}
+    // SAFETY:
+    mod module {}
+}
+
+mod unsafe_items_valid_comment {
+    // SAFETY: the pointer is valid.
+    const CONST: u32 = unsafe { *(&0 as *const u32) };
+
+    struct Struct;
+    // SAFETY: `Struct` has no fields.
+    unsafe impl Send for Struct {}
+
+    // SAFETY: this is a valid safety comment, even though `# Safety` docs would be better.
+    unsafe fn read(ptr: *const u8) -> u8 {
+        *ptr
+    }
+}
+
+fn statements(ptr: *const u8) {
+    // SAFETY:
+    let _ = 42;
+
+    // SAFETY:
+    let _ = ptr;
+
+    // SAFETY: the comment is for the unsafe block in this statement.
+    let _ = unsafe { *ptr };
+}
+
+fn statements_in_unsafe_block(ptr: *const u8) {
+    unsafe {
+        // SAFETY: the caller guarantees that `ptr` is valid.
+        let _ = *ptr;
+    }
+}
+
+unsafe fn statements_in_unsafe_fn(ptr: *const u8) {
+    // SAFETY: unsafe operations don't need an unsafe block here.
+    let _ = *ptr;
+}
+
+fn main() {}
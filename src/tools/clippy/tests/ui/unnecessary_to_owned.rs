@@ -426,3 +426,33 @@ async fn bar() {
         foo(std::path::PathBuf::new().to_string_lossy().to_string()).await;
     }
 }
+
+mod clone_arg {
+    #![allow(dead_code)]
+
+    fn require_vec(_: &Vec<u8>) {}
+    fn require_as_ref_str<T: AsRef<str>>(_: T) {}
+    fn require_vec_and_closure<F: FnOnce()>(_: &Vec<u8>, _: F) {}
+
+    fn f(v: Vec<u8>, v_ref: &Vec<u8>, s: String, n: u32) {
+        require_vec(&v.clone());
+        require_vec(&v_ref.clone());
+        require_as_ref_str(s.clone());
+        println!("{v:?}{v_ref:?}{s}{n}");
+
+        // Don't lint, `v` is moved by the other argument.
+        require_vec_and_closure(&v.clone(), move || drop(v));
+    }
+
+    fn require_cell(cell: &std::cell::RefCell<Vec<u8>>) {
+        cell.borrow_mut().push(0);
+    }
+
+    fn interior_mutability(cell: std::cell::RefCell<Vec<u8>>, rc: std::rc::Rc<u8>) {
+        // Don't lint, `require_cell` mutates the clone, not `cell`.
+        let _borrowed = cell.borrow();
+        require_cell(&cell.clone());
+        // Don't lint, cloning the `Rc` changes the count.
+        assert_eq!(std::rc::Rc::strong_count(&rc.clone()), 2);
+    }
+}
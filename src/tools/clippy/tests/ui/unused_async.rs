@@ -42,7 +42,17 @@ async fn unused() -> i32 {
 }
 async_trait_impl!();
 
+// Not linted: the function is passed where a function returning a future is expected.
+async fn used_as_value() -> i32 {
+    6
+}
+
+fn call_async_fn<F: Future<Output = i32>>(f: fn() -> F) -> F {
+    f()
+}
+
 fn main() {
     foo();
     bar();
+    call_async_fn(used_as_value);
 }
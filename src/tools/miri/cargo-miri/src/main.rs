@@ -49,6 +49,15 @@ fn main() {
     //   - We also set `--runtool` to ourselves, which ends up in `phase_runner` with
     //     `RunnerPhase::Rustdoc`. There we parse the JSON file written in `phase_rustc` and invoke
     //     the Miri driver for interpretation.
+    // - If the user asked for a package's build script to be interpreted, `phase_rustc` writes a
+    //   JSON file for it and puts a copy of ourselves where cargo expects the build script binary.
+    //   When cargo runs that, we end up in `phase_runner` with `RunnerPhase::BuildScript`.
+
+    // Dispatch running in place of a build script that we want to interpret.
+    if let Some(info) = interpreted_build_script_info() {
+        phase_runner(iter::once(info).chain(args), RunnerPhase::BuildScript);
+        return;
+    }
 
     // Dispatch running as part of sysroot compilation.
     if env::var_os("MIRI_CALLED_FROM_SETUP").is_some() {
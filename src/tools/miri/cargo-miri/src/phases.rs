@@ -31,6 +31,9 @@
         This will print the path to the generated sysroot (and nothing else) on stdout.
         stderr will still contain progress information about how the build is doing.
 
+    cargo miri setup --clean
+        This will remove all sysroots that Miri built and cached, for all toolchains.
+
 "#;
 
 fn show_help() {
@@ -59,6 +62,46 @@ fn forward_patched_extern_arg(args: &mut impl Iterator<Item = String>, cmd: &mut
     }
 }
 
+/// Runs `cargo miri` once for each of the given targets, then prints the per-target results and
+/// exits with an error if any of them failed.
+fn phase_cargo_miri_for_each_target(targets: &[String], verbose: usize) -> ! {
+    if env::var_os("MIRI_SYSROOT").is_some() {
+        show_error!("`MIRI_SYSROOT` cannot be used together with multiple `--target` flags");
+    }
+    let cargo_miri_path = std::env::current_exe().expect("current executable path invalid");
+
+    let mut results = Vec::new();
+    for target in targets {
+        // Forward all our arguments except for the `--target` flags, and then add this target.
+        let mut cmd = Command::new(&cargo_miri_path);
+        let mut args = env::args().skip(1); // skip binary name
+        for arg in ArgSplitFlagValue::from_string_iter(&mut args, "--target") {
+            if let Err(arg) = arg {
+                cmd.arg(arg);
+            }
+        }
+        cmd.arg("--target").arg(target);
+        cmd.arg("--").args(args);
+
+        debug_cmd("[cargo-miri miri]", verbose, &cmd);
+        let status = cmd.status().expect("failed to run `cargo miri`");
+        results.push((target, status));
+    }
+
+    eprintln!("Miri results per target:");
+    for (target, status) in &results {
+        if status.success() {
+            eprintln!("    {target}: ok");
+        } else if let Some(code) = status.code() {
+            eprintln!("    {target}: FAILED (exit code {code})");
+        } else {
+            eprintln!("    {target}: FAILED");
+        }
+    }
+    let success = results.iter().all(|(_, status)| status.success());
+    std::process::exit(if success { 0 } else { 1 })
+}
+
 pub fn phase_cargo_miri(mut args: impl Iterator<Item = String>) {
     // Check for version and help flags even when invoked as `cargo-miri`.
     if has_arg_flag("--help") || has_arg_flag("-h") {
@@ -86,6 +129,14 @@ pub fn phase_cargo_miri(mut args: impl Iterator<Item = String>) {
     };
     let verbose = num_arg_flag("-v");
 
+    // Every target needs its own sysroot, so with several `--target` flags we do the whole job
+    // once per target. The runs share the target directory, so host crates (build scripts and proc
+    // macros) are only built once.
+    let targets: Vec<String> = get_arg_flag_values("--target").collect();
+    if targets.len() > 1 {
+        phase_cargo_miri_for_each_target(&targets, verbose);
+    }
+
     // Determine the involved architectures.
     let rustc_version = VersionMeta::for_command(miri_for_host())
         .expect("failed to determine underlying rustc version of Miri");
@@ -93,6 +144,10 @@ pub fn phase_cargo_miri(mut args: impl Iterator<Item = String>) {
     let target = get_arg_flag_value("--target");
     let target = target.as_ref().unwrap_or(host);
 
+    if env::var_os("MIRI_INTERPRET_BUILD_SCRIPTS").is_some() && target != host {
+        show_error!("`MIRI_INTERPRET_BUILD_SCRIPTS` is not supported when cross-interpreting");
+    }
+
     // We always setup.
     setup(&subcommand, target, &rustc_version);
 
@@ -236,6 +291,15 @@ fn is_runnable_crate() -> bool {
         is_bin || is_test
     }
 
+    /// Returns whether this is the build script of a package that the user listed in
+    /// `MIRI_INTERPRET_BUILD_SCRIPTS`, meaning we should interpret it instead of building it.
+    fn is_interpreted_build_script() -> bool {
+        let Ok(packages) = env::var("MIRI_INTERPRET_BUILD_SCRIPTS") else { return false };
+        let Ok(package) = env::var("CARGO_PKG_NAME") else { return false };
+        get_arg_flag_value("--crate-name").as_deref() == Some("build_script_build")
+            && packages.split(',').any(|p| p.trim() == package)
+    }
+
     fn out_filename(prefix: &str, suffix: &str) -> PathBuf {
         if let Some(out_dir) = get_arg_flag_value("--out-dir") {
             let mut path = PathBuf::from(out_dir);
@@ -345,6 +409,34 @@ fn out_filename(prefix: &str, suffix: &str) -> PathBuf {
         return;
     }
 
+    if runnable_crate && !target_crate && is_interpreted_build_script() {
+        // This is a build script we want to interpret under Miri. Cargo runs build scripts
+        // directly rather than through the runner, so we put a copy of ourselves where the build
+        // script binary is expected, and store the JSON file next to it. When cargo executes the
+        // copy, `interpreted_build_script_info` finds the JSON file and we enter `phase_runner`.
+        if get_arg_flag_values("--extern").next().is_some() {
+            // The build-dependencies were built natively, so Miri cannot use them.
+            show_error!(
+                "interpreting build scripts with `[build-dependencies]` is not supported by Miri"
+            );
+        }
+        let out_dir = PathBuf::from(get_arg_flag_value("--out-dir").unwrap());
+        let info_name = out_dir.join(BUILD_SCRIPT_INFO_FILE);
+        if verbose > 0 {
+            eprintln!("[cargo-miri rustc] writing build script info to `{}`", info_name.display());
+        }
+        CrateRunInfo::RunWith(CrateRunEnv::collect(args, false)).store(&info_name);
+        // See `store_json` for why we need the stub dep-info file.
+        File::create(out_filename("", ".d")).expect("failed to create fake .d file");
+        let cargo_miri_path = env::current_exe().expect("current executable path invalid");
+        // Again, also provide the `.exe` variant for Windows.
+        for suffix in ["", ".exe"] {
+            fs::copy(&cargo_miri_path, out_filename("", suffix))
+                .expect("failed to copy `cargo-miri` as the build script");
+        }
+        return;
+    }
+
     if runnable_crate && get_arg_flag_values("--extern").any(|krate| krate == "proc_macro") {
         // This is a "runnable" `proc-macro` crate (unit tests). We do not support
         // interpreting that under Miri now, so we write a JSON file to (display a
@@ -442,6 +534,24 @@ pub enum RunnerPhase {
     Cargo,
     /// `rustdoc` is running a binary
     Rustdoc,
+    /// `cargo` is running a build script we want to interpret
+    BuildScript,
+}
+
+/// The name of the file that stores how to run a build script under Miri. It is put into the
+/// directory cargo builds the build script in.
+const BUILD_SCRIPT_INFO_FILE: &str = "build-script-build.miri.json";
+
+/// If we are a copy of `cargo-miri` that `phase_rustc` put in place of a build script, returns the
+/// file with the information needed to interpret that build script.
+pub fn interpreted_build_script_info() -> Option<String> {
+    let exe = env::current_exe().ok()?;
+    let stem = exe.file_stem()?.to_str()?;
+    if !stem.starts_with("build-script-") && !stem.starts_with("build_script_") {
+        return None;
+    }
+    let info = exe.with_file_name(BUILD_SCRIPT_INFO_FILE);
+    info.exists().then(|| info.into_os_string().into_string().unwrap())
 }
 
 pub fn phase_runner(mut binary_args: impl Iterator<Item = String>, phase: RunnerPhase) {
@@ -516,6 +626,18 @@ pub fn phase_runner(mut binary_args: impl Iterator<Item = String>, phase: Runner
             cmd.arg(arg);
         }
     }
+    // Build scripts need to read their environment and write to `OUT_DIR`.
+    if phase == RunnerPhase::BuildScript {
+        cmd.arg("-Zmiri-disable-isolation");
+    }
+    // If the test harness is asked to print JSON, make sure Miri's errors also show up in that
+    // format. (This is just a heuristic, the binary might not even be using libtest.)
+    let binary_args: Vec<String> = binary_args.collect();
+    if binary_args.iter().any(|arg| arg == "--format=json")
+        || binary_args.windows(2).any(|args| args[0] == "--format" && args[1] == "json")
+    {
+        cmd.arg("-Zmiri-report-json");
+    }
     // Respect `MIRIFLAGS`.
     if let Ok(a) = env::var("MIRIFLAGS") {
         // This code is taken from `RUSTFLAGS` handling in cargo.
@@ -536,7 +658,7 @@ pub fn phase_runner(mut binary_args: impl Iterator<Item = String>, phase: Runner
     debug_cmd("[cargo-miri runner]", verbose, &cmd);
     match phase {
         RunnerPhase::Rustdoc => exec_with_pipe(cmd, &info.stdin, format!("{binary}.stdin")),
-        RunnerPhase::Cargo => exec(cmd),
+        RunnerPhase::Cargo | RunnerPhase::BuildScript => exec(cmd),
     }
 }
 
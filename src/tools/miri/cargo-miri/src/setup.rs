@@ -1,8 +1,11 @@
 //! Implements `cargo miri setup`.
 
+use std::collections::hash_map::DefaultHasher;
 use std::env;
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 use std::process::{self, Command};
 
 use rustc_build_sysroot::{BuildMode, SysrootBuilder, SysrootConfig};
@@ -21,6 +24,21 @@ pub fn setup(subcommand: &MiriCommand, target: &str, rustc_version: &VersionMeta
         // Skip setup step if MIRI_SYSROOT is explicitly set, *unless* we are `cargo miri setup`.
         return;
     }
+    if only_setup && has_arg_flag("--clean") {
+        if std::env::var_os("MIRI_SYSROOT").is_some() {
+            show_error!(
+                "`--clean` only removes the sysroots in the cache, but `MIRI_SYSROOT` is set"
+            );
+        }
+        let cache_dir = sysroot_cache_dir();
+        if cache_dir.exists() {
+            fs::remove_dir_all(&cache_dir).unwrap_or_else(|err| {
+                show_error!("failed to remove `{}`: {err}", cache_dir.display())
+            });
+        }
+        eprintln!("Removed all sysroots cached by Miri from `{}`.", cache_dir.display());
+        return;
+    }
 
     // Determine where the rust sources are located.  The env var trumps auto-detection.
     let rust_src_env_var = std::env::var_os("MIRI_LIB_SRC");
@@ -58,16 +76,29 @@ pub fn setup(subcommand: &MiriCommand, target: &str, rustc_version: &VersionMeta
         );
     }
 
+    let no_std = std::env::var_os("MIRI_NO_STD").is_some();
+
+    // A custom target spec is a path, which has to be absolute since the sysroot is not built in
+    // the current directory.
+    let target_spec = target_spec_file(target);
+    let target = match &target_spec {
+        Some(path) => path.to_str().expect("target spec path is not valid UTF-8"),
+        None => target,
+    };
+
     // Determine where to put the sysroot.
     let sysroot_dir = match std::env::var_os("MIRI_SYSROOT") {
         Some(dir) => PathBuf::from(dir),
-        None => {
-            let user_dirs = directories::ProjectDirs::from("org", "rust-lang", "miri").unwrap();
-            user_dirs.cache_dir().to_owned()
-        }
+        None =>
+            sysroot_cache_dir().join(sysroot_cache_key(
+                rustc_version,
+                &rust_src,
+                no_std,
+                target_spec.as_deref(),
+            )),
     };
     // Sysroot configuration and build details.
-    let sysroot_config = if std::env::var_os("MIRI_NO_STD").is_some() {
+    let sysroot_config = if no_std {
         SysrootConfig::NoStd
     } else {
         SysrootConfig::WithStd {
@@ -153,3 +184,45 @@ pub fn setup(subcommand: &MiriCommand, target: &str, rustc_version: &VersionMeta
         println!("{}", sysroot_dir.display());
     }
 }
+
+/// The directory where we keep the sysroots we build, shared by all projects.
+fn sysroot_cache_dir() -> PathBuf {
+    let user_dirs = directories::ProjectDirs::from("org", "rust-lang", "miri").unwrap();
+    user_dirs.cache_dir().to_owned()
+}
+
+/// If `target` is the path of a custom target spec, returns its absolute path.
+fn target_spec_file(target: &str) -> Option<PathBuf> {
+    // This is the same check rustc uses to tell target specs and target names apart.
+    let path = Path::new(target);
+    if path.extension().and_then(OsStr::to_str) != Some("json") {
+        return None;
+    }
+    Some(path.canonicalize().unwrap_or_else(|err| {
+        show_error!("cannot read target spec `{}`: {err}", path.display())
+    }))
+}
+
+/// The name of the cached sysroot for the given toolchain, standard library sources and
+/// configuration, so that switching between them does not require a rebuild each time. (The
+/// sysroot has a separate directory for each target, so the target does not need to be part of the
+/// name. The exception are custom target specs: their directory is named after the file, which
+/// says nothing about what is in it, so we include its contents.)
+fn sysroot_cache_key(
+    rustc_version: &VersionMeta,
+    rust_src: &Path,
+    no_std: bool,
+    target_spec: Option<&Path>,
+) -> String {
+    let mut hasher = DefaultHasher::new();
+    rustc_version.short_version_string.hash(&mut hasher);
+    rust_src.hash(&mut hasher);
+    if let Some(target_spec) = target_spec {
+        let spec = fs::read(target_spec).unwrap_or_else(|err| {
+            show_error!("cannot read target spec `{}`: {err}", target_spec.display())
+        });
+        spec.hash(&mut hasher);
+    }
+    let std = if no_std { "no-std" } else { "std" };
+    format!("{}-{std}-{:016x}", rustc_version.semver, hasher.finish())
+}
@@ -85,7 +85,12 @@ case $HOST_TARGET in
     MIRI_TEST_TARGET=i686-pc-windows-msvc run_tests
     MIRI_TEST_TARGET=x86_64-unknown-freebsd run_tests_minimal hello integer vec panic/panic concurrency/simple atomic data_race env/var
     MIRI_TEST_TARGET=aarch64-linux-android run_tests_minimal hello integer vec panic/panic
+    MIRI_TEST_TARGET=s390x-unknown-linux-gnu run_tests_minimal hello integer vec panic/panic concurrency/simple atomic env/var shims/time # big-endian architecture
     MIRI_TEST_TARGET=thumbv7em-none-eabihf MIRI_NO_STD=1 run_tests_minimal no_std # no_std embedded architecture
+    MIRI_TEST_TARGET=avr-unknown-gnu-atmega328 MIRI_NO_STD=1 run_tests_minimal no_std # 16bit pointers
+    MIRI_TEST_TARGET=tests/targets/thumbv7em-custom.json MIRI_NO_STD=1 run_tests_minimal no_std # custom target spec
+    MIRI_TEST_TARGET=wasm32-wasi run_tests_minimal hello integer vec string env/var shims/wasi
+    MIRI_TEST_TARGET=wasm32-unknown-unknown run_tests_minimal integer vec string
     ;;
   x86_64-apple-darwin)
     MIRI_TEST_TARGET=mips64-unknown-linux-gnuabi64 run_tests # big-endian architecture
@@ -5,53 +5,72 @@
     clippy::field_reassign_with_default
 )]
 
+extern crate rustc_ast;
+extern crate rustc_builtin_macros;
 extern crate rustc_data_structures;
 extern crate rustc_driver;
+extern crate rustc_errors;
 extern crate rustc_hir;
 extern crate rustc_interface;
 extern crate rustc_metadata;
 extern crate rustc_middle;
 extern crate rustc_session;
+extern crate rustc_span;
 
 use std::env;
+use std::io::{self, Write};
 use std::num::NonZeroU64;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
 
 use log::debug;
 
+use rustc_ast as ast;
 use rustc_data_structures::sync::Lrc;
 use rustc_driver::Compilation;
-use rustc_hir::{self as hir, def_id::LOCAL_CRATE, Node};
-use rustc_interface::interface::Config;
+use rustc_errors::{emitter::EmitterWriter, Handler};
+use rustc_hir::{
+    self as hir,
+    def_id::{DefId, LOCAL_CRATE},
+    Node,
+};
+use rustc_interface::{interface::Config, Queries};
 use rustc_middle::{
     middle::exported_symbols::{
         ExportedSymbol, SymbolExportInfo, SymbolExportKind, SymbolExportLevel,
     },
     ty::{query::ExternProviders, TyCtxt},
 };
-use rustc_session::{config::CrateType, search_paths::PathKind, CtfeBacktrace};
+use rustc_session::{
+    config::{CrateType, EntryFnType},
+    search_paths::PathKind,
+    CtfeBacktrace,
+};
+use rustc_span::symbol::sym;
 
-use miri::{BacktraceStyle, ProvenanceMode, RetagFields};
+use miri::{BacktraceStyle, ExternStaticValue, OutputTarget, ProvenanceMode, RetagFields};
 
 struct MiriCompilerCalls {
     miri_config: miri::MiriConfig,
+    /// The arguments rustc was invoked with, so that the sessions that run the tests of a harness
+    /// with `-Zmiri-test-jobs` can compile the crate again.
+    rustc_args: Vec<String>,
 }
 
 impl rustc_driver::Callbacks for MiriCompilerCalls {
     fn config(&mut self, config: &mut Config) {
-        config.override_queries = Some(|_, _, external_providers| {
-            external_providers.used_crate_source = |tcx, cnum| {
-                let mut providers = ExternProviders::default();
-                rustc_metadata::provide_extern(&mut providers);
-                let mut crate_source = (providers.used_crate_source)(tcx, cnum);
-                // HACK: rustc will emit "crate ... required to be available in rlib format, but
-                // was not found in this form" errors once we use `tcx.dependency_formats()` if
-                // there's no rlib provided, so setting a dummy path here to workaround those errors.
-                Lrc::make_mut(&mut crate_source).rlib = Some((PathBuf::new(), PathKind::All));
-                crate_source
-            };
-        });
+        override_used_crate_source(config);
+    }
+
+    fn after_parsing<'tcx>(
+        &mut self,
+        compiler: &rustc_interface::interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        register_miri_tool(compiler, queries);
+        Compilation::Continue
     }
 
     fn after_analysis<'tcx>(
@@ -67,22 +86,23 @@ fn after_analysis<'tcx>(
                 tcx.sess.fatal("miri only makes sense on bin crates");
             }
 
-            let (entry_def_id, entry_type) = if let Some(entry_def) = tcx.entry_fn(()) {
-                entry_def
-            } else {
-                tcx.sess.fatal("miri can only run programs that have a main function");
-            };
+            let (entry_def_id, entry_type) = entry_fn(tcx);
             let mut config = self.miri_config.clone();
 
             // Add filename to `miri` arguments.
             config.args.insert(0, compiler.input().filestem().to_string());
 
-            // Adjust working directory for interpretation.
-            if let Some(cwd) = env::var_os("MIRI_CWD") {
-                env::set_current_dir(cwd).unwrap();
-            }
-
-            if let Some(return_code) = miri::eval_entry(tcx, entry_def_id, entry_type, config) {
+            let return_code = if miri::can_run_tests_in_parallel(tcx, &config) {
+                // The sessions that run the tests adjust the working directory themselves.
+                run_tests_in_parallel(tcx, entry_def_id, entry_type, &config, &self.rustc_args)
+            } else {
+                // Adjust working directory for interpretation.
+                if let Some(cwd) = env::var_os("MIRI_CWD") {
+                    env::set_current_dir(cwd).unwrap();
+                }
+                miri::eval_entry(tcx, entry_def_id, entry_type, config)
+            };
+            if let Some(return_code) = return_code {
                 std::process::exit(
                     i32::try_from(return_code).expect("Return value was too large!"),
                 );
@@ -95,6 +115,204 @@ fn after_analysis<'tcx>(
     }
 }
 
+/// One of the sessions that run the tests of a harness with `-Zmiri-test-jobs`. The `TyCtxt` of
+/// a session cannot be shared between host threads, so every host thread compiles the crate
+/// again; then it takes tests from the queue until none are left.
+struct TestSessionCalls<'a> {
+    miri_config: miri::MiriConfig,
+    queue: &'a miri::TestQueue,
+    compiling: &'a CompilingSessions,
+    /// Whether the session told `compiling` that it is done compiling.
+    compiled: bool,
+    /// The buffer the session emits its diagnostics to, so that they can be shown along with the
+    /// test they belong to.
+    diagnostics: Arc<Mutex<Vec<u8>>>,
+}
+
+impl TestSessionCalls<'_> {
+    fn compiled(&mut self) {
+        if !self.compiled {
+            self.compiled = true;
+            self.compiling.done();
+        }
+    }
+}
+
+impl Drop for TestSessionCalls<'_> {
+    fn drop(&mut self) {
+        // Don't keep the other sessions waiting if this one stopped while compiling.
+        self.compiled();
+    }
+}
+
+impl rustc_driver::Callbacks for TestSessionCalls<'_> {
+    fn config(&mut self, config: &mut Config) {
+        override_used_crate_source(config);
+        let diagnostics = Arc::clone(&self.diagnostics);
+        config.parse_sess_created = Some(Box::new(move |parse_sess| {
+            let fallback_bundle =
+                rustc_errors::fallback_fluent_bundle(rustc_errors::DEFAULT_LOCALE_RESOURCES, false);
+            let emitter = EmitterWriter::new(
+                Box::new(SharedBuffer(diagnostics)),
+                Some(parse_sess.clone_source_map()),
+                None,
+                fallback_bundle,
+                false,
+                false,
+                false,
+                None,
+                false,
+                false,
+            );
+            parse_sess.span_diagnostic = Handler::with_emitter(true, None, Box::new(emitter));
+        }));
+    }
+
+    fn after_parsing<'tcx>(
+        &mut self,
+        compiler: &rustc_interface::interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        register_miri_tool(compiler, queries);
+        Compilation::Continue
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        compiler: &rustc_interface::interface::Compiler,
+        queries: &'tcx rustc_interface::Queries<'tcx>,
+    ) -> Compilation {
+        compiler.session().abort_if_errors();
+        // The main session already showed the warnings of the crate.
+        self.diagnostics.lock().unwrap().clear();
+
+        // Sessions that are still compiling need the working directory we got started in.
+        self.compiled();
+        self.compiling.wait();
+        if let Some(cwd) = env::var_os("MIRI_CWD") {
+            env::set_current_dir(cwd).unwrap();
+        }
+
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            set_ctfe_backtrace(tcx);
+            let (entry_def_id, entry_type) = entry_fn(tcx);
+            self.queue.run_tests(
+                tcx,
+                entry_def_id,
+                entry_type,
+                &self.miri_config,
+                &self.diagnostics,
+            );
+        });
+
+        // Miri errors are already reported with the tests they belong to.
+        Compilation::Stop
+    }
+}
+
+/// The number of sessions that run tests and are still compiling the crate.
+struct CompilingSessions {
+    count: Mutex<usize>,
+    all_done: Condvar,
+}
+
+impl CompilingSessions {
+    fn done(&self) {
+        let mut count = self.count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            self.all_done.notify_all();
+        }
+    }
+
+    fn wait(&self) {
+        let mut count = self.count.lock().unwrap();
+        while *count > 0 {
+            count = self.all_done.wait(count).unwrap();
+        }
+    }
+}
+
+/// Appends everything that is written to it to the given buffer.
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs the tests of the libtest harness with the given entry point, each test on a Miri machine
+/// of its own, in `config.test_jobs` sessions at once. The results are printed like libtest
+/// prints them: a status line as soon as a test is done, then the output of the failed tests and a
+/// summary of all tests. Returns the exit code of the harness, or `None` if Miri reported an error
+/// while listing the tests.
+fn run_tests_in_parallel<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    entry_def_id: DefId,
+    entry_type: EntryFnType,
+    config: &miri::MiriConfig,
+    rustc_args: &[String],
+) -> Option<i64> {
+    let queue = match miri::TestQueue::new(tcx, entry_def_id, entry_type, config) {
+        Ok(queue) => queue,
+        Err(return_code) => return return_code,
+    };
+    let compiling =
+        CompilingSessions { count: Mutex::new(config.test_jobs), all_done: Condvar::new() };
+    thread::scope(|s| {
+        for _ in 0..config.test_jobs {
+            let mut callbacks = TestSessionCalls {
+                miri_config: config.clone(),
+                queue: &queue,
+                compiling: &compiling,
+                compiled: false,
+                diagnostics: Arc::default(),
+            };
+            s.spawn(move || {
+                rustc_driver::catch_with_exit_code(|| {
+                    rustc_driver::RunCompiler::new(rustc_args, &mut callbacks).run()
+                });
+                if !callbacks.compiled {
+                    // The session did not get to run any tests, so show why.
+                    let diagnostics = callbacks.diagnostics.lock().unwrap();
+                    eprint!("{}", String::from_utf8_lossy(&diagnostics));
+                }
+            });
+        }
+    });
+    Some(queue.finish())
+}
+
+fn override_used_crate_source(config: &mut Config) {
+    config.override_queries = Some(|_, _, external_providers| {
+        external_providers.used_crate_source = |tcx, cnum| {
+            let mut providers = ExternProviders::default();
+            rustc_metadata::provide_extern(&mut providers);
+            let mut crate_source = (providers.used_crate_source)(tcx, cnum);
+            // HACK: rustc will emit "crate ... required to be available in rlib format, but
+            // was not found in this form" errors once we use `tcx.dependency_formats()` if
+            // there's no rlib provided, so setting a dummy path here to workaround those errors.
+            Lrc::make_mut(&mut crate_source).rlib = Some((PathBuf::new(), PathKind::All));
+            crate_source
+        };
+    });
+}
+
+fn entry_fn(tcx: TyCtxt<'_>) -> (DefId, EntryFnType) {
+    if let Some(entry_def) = tcx.entry_fn(()) {
+        entry_def
+    } else if let Some(main_def_id) = miri::no_main_entry_fn(tcx) {
+        (main_def_id, EntryFnType::Start)
+    } else {
+        tcx.sess.fatal("miri can only run programs that have a main function");
+    }
+}
+
 struct MiriBeRustCompilerCalls {
     target_crate: bool,
 }
@@ -149,6 +367,61 @@ fn config(&mut self, config: &mut Config) {
             });
         }
     }
+
+    fn after_parsing<'tcx>(
+        &mut self,
+        compiler: &rustc_interface::interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        if self.target_crate {
+            register_miri_tool(compiler, queries);
+        }
+        Compilation::Continue
+    }
+}
+
+/// Makes `#[miri::...]` attributes available to the interpreted program by registering the
+/// `miri` tool on the crate root. Crates that already do `#![register_tool(miri)]` themselves
+/// are left alone, since registering a tool twice is an error.
+fn register_miri_tool<'tcx>(
+    compiler: &rustc_interface::interface::Compiler,
+    queries: &'tcx Queries<'tcx>,
+) {
+    let Ok(krate) = queries.parse() else {
+        // Parse errors are reported by the compiler; there is nothing to inject into.
+        return;
+    };
+    let mut krate = krate.peek_mut();
+    let has_crate_attr = |name, arg| {
+        krate.attrs.iter().any(|attr| {
+            attr.has_name(name)
+                && attr
+                    .meta_item_list()
+                    .map_or(false, |list| list.iter().any(|item| item.has_name(arg)))
+        })
+    };
+    if has_crate_attr(sym::register_tool, sym::miri) {
+        return;
+    }
+    let mut attrs = vec![];
+    if !has_crate_attr(sym::feature, sym::register_tool) {
+        attrs.push("feature(register_tool)".to_string());
+    }
+    attrs.push("register_tool(miri)".to_string());
+
+    let placeholder = ast::Crate {
+        attrs: Default::default(),
+        items: Vec::new(),
+        spans: krate.spans,
+        id: ast::DUMMY_NODE_ID,
+        is_placeholder: false,
+    };
+    let parsed = std::mem::replace(&mut *krate, placeholder);
+    *krate = rustc_builtin_macros::cmdline_attrs::inject(
+        parsed,
+        &compiler.session().parse_sess,
+        &attrs,
+    );
 }
 
 fn show_error(msg: &impl std::fmt::Display) -> ! {
@@ -204,6 +477,10 @@ fn init_late_loggers(tcx: TyCtxt<'_>) {
         }
     }
 
+    set_ctfe_backtrace(tcx);
+}
+
+fn set_ctfe_backtrace(tcx: TyCtxt<'_>) {
     // If `MIRI_BACKTRACE` is set and `RUSTC_CTFE_BACKTRACE` is not, set `RUSTC_CTFE_BACKTRACE`.
     // Do this late, so we ideally only apply this to Miri's errors.
     if let Some(val) = env::var_os("MIRI_BACKTRACE") {
@@ -216,12 +493,9 @@ fn init_late_loggers(tcx: TyCtxt<'_>) {
     }
 }
 
-/// Execute a compiler with the given CLI arguments and callbacks.
-fn run_compiler(
-    mut args: Vec<String>,
-    target_crate: bool,
-    callbacks: &mut (dyn rustc_driver::Callbacks + Send),
-) -> ! {
+/// Adjusts the given CLI arguments for a compiler that builds a target crate (`target_crate`) or
+/// a host crate.
+fn adjust_rustc_args(mut args: Vec<String>, target_crate: bool) -> Vec<String> {
     if target_crate {
         // Miri needs a custom sysroot for target crates.
         // If no `--sysroot` is given, the `MIRI_SYSROOT` env var is consulted to find where
@@ -250,7 +524,11 @@ fn run_compiler(
         // them the first arguments after the binary name (but later arguments can overwrite them).
         args.splice(1..1, miri::MIRI_DEFAULT_ARGS.iter().map(ToString::to_string));
     }
+    args
+}
 
+/// Execute a compiler with the given CLI arguments and callbacks.
+fn run_compiler(args: Vec<String>, callbacks: &mut (dyn rustc_driver::Callbacks + Send)) -> ! {
     // Invoke compiler, and handle return code.
     let exit_code = rustc_driver::catch_with_exit_code(move || {
         rustc_driver::RunCompiler::new(&args, callbacks).run()
@@ -265,6 +543,17 @@ fn parse_comma_list<T: FromStr>(input: &str) -> Result<Vec<T>, T::Err> {
     input.split(',').map(str::parse::<T>).collect()
 }
 
+/// Parses a string of hex digits, two per byte.
+fn parse_hex_bytes(input: &str) -> Option<Vec<u8>> {
+    if input.len() % 2 != 0 {
+        return None;
+    }
+    (0..input.len())
+        .step_by(2)
+        .map(|i| input.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
 fn main() {
     // Snapshot a copy of the environment before `rustc` starts messing with it.
     // (`install_ice_hook` might change `RUST_BACKTRACE`.)
@@ -287,8 +576,7 @@ fn main() {
 
         // We cannot use `rustc_driver::main` as we need to adjust the CLI arguments.
         run_compiler(
-            env::args().collect(),
-            target_crate,
+            adjust_rustc_args(env::args().collect(), target_crate),
             &mut MiriBeRustCompilerCalls { target_crate },
         )
     }
@@ -318,6 +606,12 @@ fn main() {
             miri_config.validate = false;
         } else if arg == "-Zmiri-disable-stacked-borrows" {
             miri_config.stacked_borrows = false;
+        } else if arg == "-Zmiri-relax-dependencies" {
+            miri_config.relax_dependencies = true;
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-relaxed-crates=") {
+            miri_config.relaxed_crates.extend(param.split(',').map(str::to_owned));
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-fully-checked-crates=") {
+            miri_config.fully_checked_crates.extend(param.split(',').map(str::to_owned));
         } else if arg == "-Zmiri-disable-data-race-detector" {
             miri_config.data_race_detector = false;
             miri_config.weak_memory_emulation = false;
@@ -345,6 +639,8 @@ fn main() {
             miri_config.weak_memory_emulation = false;
         } else if arg == "-Zmiri-track-weak-memory-loads" {
             miri_config.track_outdated_loads = true;
+        } else if arg == "-Zmiri-shuffle-tls-dtors" {
+            miri_config.shuffle_tls_dtors = true;
         } else if let Some(param) = arg.strip_prefix("-Zmiri-isolation-error=") {
             if matches!(isolation_enabled, Some(false)) {
                 show_error!(
@@ -365,8 +661,105 @@ fn main() {
                         "-Zmiri-isolation-error must be `abort`, `hide`, `warn`, or `warn-nobacktrace`"
                     ),
             };
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-isolation=") {
+            for entry in param.split(',') {
+                let Some((classes, policy)) = entry.split_once(':') else {
+                    show_error!(
+                        "-Zmiri-isolation must be a comma separated list of `<class>:<policy>` entries"
+                    )
+                };
+                let classes: &[miri::IsolationClass] = match classes {
+                    "env" => &[miri::IsolationClass::Env],
+                    "clock" => &[miri::IsolationClass::Clock],
+                    "random" => &[miri::IsolationClass::Random],
+                    "fs" => &[miri::IsolationClass::FsRead, miri::IsolationClass::FsWrite],
+                    "fs-read" => &[miri::IsolationClass::FsRead],
+                    "fs-write" => &[miri::IsolationClass::FsWrite],
+                    "process" => &[miri::IsolationClass::Process],
+                    "net" => &[miri::IsolationClass::Net],
+                    _ =>
+                        show_error!(
+                            "-Zmiri-isolation classes must be `env`, `clock`, `random`, `fs`, `fs-read`, `fs-write`, `process`, or `net`"
+                        ),
+                };
+                let policy = match policy {
+                    "reject" => miri::IsolationPolicy::Reject,
+                    "warn" => miri::IsolationPolicy::Warn,
+                    "passthrough" => miri::IsolationPolicy::Passthrough,
+                    _ =>
+                        show_error!(
+                            "-Zmiri-isolation policies must be `reject`, `warn`, or `passthrough`"
+                        ),
+                };
+                for &class in classes {
+                    miri_config.isolation_policies.insert(class, policy);
+                }
+            }
         } else if arg == "-Zmiri-ignore-leaks" {
             miri_config.ignore_leaks = true;
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-ignore=") {
+            for class in param.split(',') {
+                match class {
+                    "abi" => miri_config.check_abi = false,
+                    "alignment" => miri_config.check_alignment = miri::AlignmentCheck::None,
+                    "data-race" => {
+                        miri_config.data_race_detector = false;
+                        miri_config.weak_memory_emulation = false;
+                    }
+                    "int2ptr" => miri_config.provenance_mode = ProvenanceMode::Permissive,
+                    "isolation" => {
+                        if matches!(isolation_enabled, Some(false)) {
+                            show_error!(
+                                "-Zmiri-ignore=isolation cannot be used along with -Zmiri-disable-isolation"
+                            );
+                        }
+                        isolation_enabled = Some(true);
+                        miri_config.isolated_op =
+                            miri::IsolatedOp::Reject(miri::RejectOpWith::NoWarning);
+                    }
+                    "leaks" => miri_config.ignore_leaks = true,
+                    "stacked-borrows" => miri_config.stacked_borrows = false,
+                    "threads" => miri_config.ignore_running_threads = true,
+                    "validation" => miri_config.validate = false,
+                    _ =>
+                        show_error!(
+                            "-Zmiri-ignore must be a comma separated list of `abi`, `alignment`, `data-race`, `int2ptr`, `isolation`, `leaks`, `stacked-borrows`, `threads`, or `validation`"
+                        ),
+                }
+            }
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-warn=") {
+            for class in param.split(',') {
+                match class {
+                    "int2ptr" => miri_config.provenance_mode = ProvenanceMode::Default,
+                    "isolation" => {
+                        if matches!(isolation_enabled, Some(false)) {
+                            show_error!(
+                                "-Zmiri-warn=isolation cannot be used along with -Zmiri-disable-isolation"
+                            );
+                        }
+                        isolation_enabled = Some(true);
+                        miri_config.isolated_op =
+                            miri::IsolatedOp::Reject(miri::RejectOpWith::Warning);
+                    }
+                    "leaks" => miri_config.warn_leaks = true,
+                    "stacked-borrows" => miri_config.warn_stacked_borrows = true,
+                    "threads" => miri_config.warn_running_threads = true,
+                    "abi" | "alignment" | "data-race" | "validation" =>
+                        show_error!(
+                            "`{class}` errors cannot be downgraded to warnings, but they can be ignored with `-Zmiri-ignore={class}`"
+                        ),
+                    _ =>
+                        show_error!(
+                            "-Zmiri-warn must be a comma separated list of `int2ptr`, `isolation`, `leaks`, `stacked-borrows`, or `threads`"
+                        ),
+                }
+            }
+        } else if arg == "-Zmiri-abort-exit-code" {
+            miri_config.abort_exit_code = true;
+        } else if arg == "-Zmiri-report-json" {
+            miri_config.report_json = true;
+        } else if arg == "-Zmiri-continue-after-error" {
+            miri_config.continue_after_error = true;
         } else if arg == "-Zmiri-panic-on-unsupported" {
             miri_config.panic_on_unsupported = true;
         } else if arg == "-Zmiri-tag-raw-pointers" {
@@ -375,6 +768,12 @@ fn main() {
             miri_config.provenance_mode = ProvenanceMode::Strict;
         } else if arg == "-Zmiri-permissive-provenance" {
             miri_config.provenance_mode = ProvenanceMode::Permissive;
+        } else if arg == "-Zmiri-provenance-audit" {
+            miri_config.provenance_audit = true;
+        } else if arg == "-Zmiri-shim-report" {
+            miri_config.shim_report = true;
+        } else if arg == "-Zmiri-process-passthrough" {
+            miri_config.process_passthrough = true;
         } else if arg == "-Zmiri-mute-stdout-stderr" {
             miri_config.mute_stdout_stderr = true;
         } else if arg == "-Zmiri-retag-fields" {
@@ -399,12 +798,42 @@ fn main() {
                             "-Zmiri-seed should only contain valid hex digits [0-9a-fA-F] and must fit into a u64 (max 16 characters)"
                         ));
             miri_config.seed = Some(seed);
-        } else if let Some(_param) = arg.strip_prefix("-Zmiri-env-exclude=") {
-            show_error!(
-                "`-Zmiri-env-exclude` has been removed; unset env vars before starting Miri instead"
-            );
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-env-exclude=") {
+            miri_config.excluded_env_vars.push(param.to_owned());
         } else if let Some(param) = arg.strip_prefix("-Zmiri-env-forward=") {
             miri_config.forwarded_env_vars.push(param.to_owned());
+        } else if arg == "-Zmiri-env-forward-all" {
+            miri_config.forward_all_env_vars = true;
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-env-set=") {
+            let Some((name, value)) = param.split_once('=') else {
+                show_error!("-Zmiri-env-set requires an argument of the form <name>=<value>");
+            };
+            if name.is_empty() {
+                show_error!("-Zmiri-env-set requires a non-empty variable name");
+            }
+            miri_config.synthetic_env_vars.push((name.into(), value.into()));
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-extern-static=") {
+            let Some((name, value)) = param.split_once('=') else {
+                show_error!("-Zmiri-extern-static requires an argument of the form <name>=<value>");
+            };
+            let value = if value == "zeroed" {
+                ExternStaticValue::Zeroed
+            } else if let Some(hex) = value.strip_prefix("bytes:") {
+                match parse_hex_bytes(hex) {
+                    Some(bytes) => ExternStaticValue::Bytes(bytes),
+                    None =>
+                        show_error!(
+                            "-Zmiri-extern-static bytes must be pairs of hex digits [0-9a-fA-F]"
+                        ),
+                }
+            } else if let Some(builtin) = value.strip_prefix("builtin:") {
+                ExternStaticValue::Builtin(builtin.to_owned())
+            } else {
+                show_error!(
+                    "-Zmiri-extern-static values must be `zeroed`, `bytes:<hex>` or `builtin:<name>`"
+                );
+            };
+            miri_config.extern_statics.push((name.to_owned(), value));
         } else if let Some(param) = arg.strip_prefix("-Zmiri-track-pointer-tag=") {
             let ids: Vec<u64> = match parse_comma_list(param) {
                 Ok(ids) => ids,
@@ -472,6 +901,30 @@ fn main() {
                     ),
             };
             miri_config.preemption_rate = rate;
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-spawn-failure-rate=") {
+            let rate = match param.parse::<f64>() {
+                Ok(rate) if rate >= 0.0 && rate <= 1.0 => rate,
+                Ok(_) => show_error!("-Zmiri-spawn-failure-rate must be between `0.0` and `1.0`"),
+                Err(err) =>
+                    show_error!(
+                        "-Zmiri-spawn-failure-rate requires a `f64` between `0.0` and `1.0`: {}",
+                        err
+                    ),
+            };
+            miri_config.spawn_failure_rate = rate;
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-spawn-failure-at=") {
+            let attempts: Vec<u64> = match parse_comma_list(param) {
+                Ok(attempts) => attempts,
+                Err(err) =>
+                    show_error!(
+                        "-Zmiri-spawn-failure-at requires a comma separated list of valid `u64` arguments: {}",
+                        err
+                    ),
+            };
+            if attempts.contains(&0) {
+                show_error!("-Zmiri-spawn-failure-at requires nonzero arguments");
+            }
+            miri_config.spawn_failures.extend(attempts);
         } else if arg == "-Zmiri-report-progress" {
             // This makes it take a few seconds between progress reports on my laptop.
             miri_config.report_progress = Some(1_000_000);
@@ -516,13 +969,75 @@ fn main() {
             };
 
             miri_config.num_cpus = num_cpus;
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-test-jobs=") {
+            let jobs = match param.parse::<usize>() {
+                Ok(i) if i > 0 => i,
+                _ => show_error!("-Zmiri-test-jobs requires a positive number: {}", param),
+            };
+            miri_config.test_jobs = jobs;
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-force-page-size=") {
+            let page_size = match param.parse::<u64>() {
+                Ok(i) if !i.is_power_of_two() =>
+                    show_error!("-Zmiri-force-page-size requires a power of 2: {}", i),
+                // `GetSystemInfo` reports the page size as a `DWORD`, so it has to fit in a `u32`.
+                Ok(i) =>
+                    match i.checked_mul(1024).filter(|&size| u32::try_from(size).is_ok()) {
+                        Some(size) => size,
+                        None =>
+                            show_error!(
+                                "-Zmiri-force-page-size must be at most 2097152 (2 GiB): {}",
+                                i
+                            ),
+                    },
+                Err(err) => show_error!("-Zmiri-force-page-size requires a `u64`: {}", err),
+            };
+
+            miri_config.page_size = Some(page_size);
+        } else if let Some(path) = arg.strip_prefix("-Zmiri-redirect-stdout=") {
+            miri_config.stdout_target = OutputTarget::File(path.into());
+        } else if let Some(path) = arg.strip_prefix("-Zmiri-redirect-stderr=") {
+            miri_config.stderr_target = OutputTarget::File(path.into());
+        } else if let Some(path) = arg.strip_prefix("-Zmiri-tee-stdout=") {
+            miri_config.stdout_target = OutputTarget::Tee(path.into());
+        } else if let Some(path) = arg.strip_prefix("-Zmiri-tee-stderr=") {
+            miri_config.stderr_target = OutputTarget::Tee(path.into());
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-force-tty=") {
+            miri_config.force_tty = match param {
+                "yes" => Some(true),
+                "no" => Some(false),
+                _ => show_error!("`-Zmiri-force-tty` can only be `yes` or `no`"),
+            };
         } else {
             // Forward to rustc.
             rustc_args.push(arg);
         }
     }
+    if miri_config.provenance_audit && miri_config.provenance_mode == ProvenanceMode::Strict {
+        show_error!("-Zmiri-provenance-audit cannot be used along with -Zmiri-strict-provenance");
+    }
+    if let (
+        OutputTarget::File(stdout) | OutputTarget::Tee(stdout),
+        OutputTarget::File(stderr) | OutputTarget::Tee(stderr),
+    ) = (&miri_config.stdout_target, &miri_config.stderr_target)
+    {
+        if stdout == stderr {
+            show_error!("stdout and stderr cannot be redirected to the same file");
+        }
+    }
+    if miri_config.test_jobs > 1
+        && !matches!(
+            (&miri_config.stdout_target, &miri_config.stderr_target),
+            (OutputTarget::Host, OutputTarget::Host)
+        )
+    {
+        // The output of every test is captured to keep it apart from that of other tests.
+        show_error!(
+            "-Zmiri-test-jobs cannot be used along with -Zmiri-redirect-* or -Zmiri-tee-* flags"
+        );
+    }
 
     debug!("rustc arguments: {:?}", rustc_args);
     debug!("crate arguments: {:?}", miri_config.args);
-    run_compiler(rustc_args, /* target_crate: */ true, &mut MiriCompilerCalls { miri_config })
+    let rustc_args = adjust_rustc_args(rustc_args, /* target_crate: */ true);
+    run_compiler(rustc_args.clone(), &mut MiriCompilerCalls { miri_config, rustc_args })
 }
@@ -2,9 +2,14 @@
 use std::time::{Duration, Instant as StdInstant};
 
 /// When using a virtual clock, this defines how many nanoseconds we pretend are passing for each
-/// basic block.
+/// basic block. This is also the CPU time we pretend each basic block takes to execute.
 const NANOSECONDS_PER_BASIC_BLOCK: u64 = 10;
 
+/// Returns the CPU time we pretend it took to execute the given number of basic blocks.
+pub fn cpu_time_for_basic_blocks(basic_blocks: u64) -> Duration {
+    Duration::from_nanos(basic_blocks.saturating_mul(NANOSECONDS_PER_BASIC_BLOCK))
+}
+
 #[derive(Debug)]
 pub struct Instant {
     kind: InstantKind,
@@ -53,6 +58,9 @@ enum ClockKind {
     Host {
         /// The "time anchor" for this machine's monotone clock.
         time_anchor: StdInstant,
+        /// The total duration (in nanoseconds) the program has slept so far. Instead of actually
+        /// sleeping on the host, we move this clock ahead of the host clock.
+        slept_nanoseconds: AtomicU64,
     },
     Virtual {
         /// The "current virtual time".
@@ -64,7 +72,7 @@ impl Clock {
     /// Create a new clock based on the availability of communication with the host.
     pub fn new(communicate: bool) -> Self {
         let kind = if communicate {
-            ClockKind::Host { time_anchor: StdInstant::now() }
+            ClockKind::Host { time_anchor: StdInstant::now(), slept_nanoseconds: 0.into() }
         } else {
             ClockKind::Virtual { nanoseconds: 0.into() }
         };
@@ -85,27 +93,32 @@ pub fn tick(&self) {
     }
 
     /// Sleep for the desired duration.
+    ///
+    /// This never blocks the host: nobody but the interpreted program can observe this clock, so we
+    /// just pretend that we have slept for some time by moving the clock forward.
     pub fn sleep(&self, duration: Duration) {
-        match &self.kind {
-            ClockKind::Host { .. } => std::thread::sleep(duration),
-            ClockKind::Virtual { nanoseconds } => {
-                // Just pretend that we have slept for some time.
-                nanoseconds.fetch_add(duration.as_nanos().try_into().unwrap(), Ordering::SeqCst);
-            }
-        }
+        let nanoseconds = match &self.kind {
+            ClockKind::Host { slept_nanoseconds, .. } => slept_nanoseconds,
+            ClockKind::Virtual { nanoseconds } => nanoseconds,
+        };
+        nanoseconds.fetch_add(duration.as_nanos().try_into().unwrap(), Ordering::SeqCst);
     }
 
     /// Return the `anchor` instant, to convert between monotone instants and durations relative to the anchor.
     pub fn anchor(&self) -> Instant {
         match &self.kind {
-            ClockKind::Host { time_anchor } => Instant { kind: InstantKind::Host(*time_anchor) },
+            ClockKind::Host { time_anchor, .. } =>
+                Instant { kind: InstantKind::Host(*time_anchor) },
             ClockKind::Virtual { .. } => Instant { kind: InstantKind::Virtual { nanoseconds: 0 } },
         }
     }
 
     pub fn now(&self) -> Instant {
         match &self.kind {
-            ClockKind::Host { .. } => Instant { kind: InstantKind::Host(StdInstant::now()) },
+            ClockKind::Host { slept_nanoseconds, .. } => {
+                let slept = Duration::from_nanos(slept_nanoseconds.load(Ordering::SeqCst));
+                Instant { kind: InstantKind::Host(StdInstant::now() + slept) }
+            }
             ClockKind::Virtual { nanoseconds } =>
                 Instant {
                     kind: InstantKind::Virtual { nanoseconds: nanoseconds.load(Ordering::SeqCst) },
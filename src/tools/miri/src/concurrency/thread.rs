@@ -6,6 +6,8 @@
 use std::time::{Duration, SystemTime};
 
 use log::trace;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def_id::DefId;
@@ -42,7 +44,7 @@ pub trait MachineCallback<'mir, 'tcx>: VisitTags {
 pub struct ThreadId(u32);
 
 /// The main thread. When it terminates, the whole application terminates.
-const MAIN_THREAD: ThreadId = ThreadId(0);
+pub(crate) const MAIN_THREAD: ThreadId = ThreadId(0);
 
 impl ThreadId {
     pub fn to_u32(self) -> u32 {
@@ -128,6 +130,15 @@ pub struct Thread<'mir, 'tcx> {
 
     /// Last OS error location in memory. It is a 32-bit integer.
     pub(crate) last_error: Option<MPlaceTy<'tcx, Provenance>>,
+
+    /// The number of basic blocks this thread has executed so far. This is used to provide the
+    /// thread's CPU time.
+    pub(crate) basic_block_count: u64,
+
+    /// The random number generator for the randomness the program asks for on this thread, created
+    /// on first use. Each thread has its own stream, so the values a thread gets do not depend on
+    /// how it is interleaved with other threads.
+    rng: Option<StdRng>,
 }
 
 impl<'mir, 'tcx> Thread<'mir, 'tcx> {
@@ -170,6 +181,8 @@ fn default() -> Self {
             join_status: ThreadJoinStatus::Joinable,
             panic_payload: None,
             last_error: None,
+            basic_block_count: 0,
+            rng: None,
         }
     }
 }
@@ -184,8 +197,16 @@ fn new(name: &str) -> Self {
 
 impl VisitTags for Thread<'_, '_> {
     fn visit_tags(&self, visit: &mut dyn FnMut(SbTag)) {
-        let Thread { panic_payload, last_error, stack, state: _, thread_name: _, join_status: _ } =
-            self;
+        let Thread {
+            panic_payload,
+            last_error,
+            stack,
+            state: _,
+            thread_name: _,
+            join_status: _,
+            basic_block_count: _,
+            rng: _,
+        } = self;
 
         panic_payload.visit_tags(visit);
         last_error.visit_tags(visit);
@@ -361,6 +382,16 @@ pub fn all_stacks(
         self.threads.iter().map(|t| &t.stack[..])
     }
 
+    /// Iterate over the threads that have not terminated yet, with their stacks.
+    pub fn live_threads(
+        &self,
+    ) -> impl Iterator<Item = (ThreadId, &[Frame<'mir, 'tcx, Provenance, FrameData<'tcx>>])> {
+        self.threads
+            .iter_enumerated()
+            .filter(|(_, t)| t.state != ThreadState::Terminated)
+            .map(|(id, t)| (id, &t.stack[..]))
+    }
+
     /// Create a new thread and returns its id.
     fn create_thread(&mut self) -> ThreadId {
         let new_thread_id = ThreadId::new(self.threads.len());
@@ -418,6 +449,18 @@ fn active_thread_ref(&self) -> &Thread<'mir, 'tcx> {
         &self.threads[self.active_thread]
     }
 
+    /// Get the random number generator of the currently active thread, creating it from `seed`
+    /// and the thread ID on first use.
+    pub(crate) fn active_thread_rng(&mut self, seed: u64) -> &mut StdRng {
+        let id = self.active_thread;
+        self.threads[id].rng.get_or_insert_with(|| {
+            let mut thread_seed = <StdRng as SeedableRng>::Seed::default();
+            thread_seed[..8].copy_from_slice(&seed.to_le_bytes());
+            thread_seed[8..12].copy_from_slice(&id.to_u32().to_le_bytes());
+            StdRng::from_seed(thread_seed)
+        })
+    }
+
     /// Mark the thread as detached, which means that no other thread will try
     /// to join it and the thread is responsible for cleaning up.
     ///
@@ -681,8 +724,18 @@ fn schedule(&mut self, clock: &Clock) -> InterpResult<'tcx, SchedulingAction> {
             // All threads are currently blocked, but we have unexecuted
             // timeout_callbacks, which may unblock some of the threads. Hence,
             // sleep until the first callback.
-
-            clock.sleep(sleep_time);
+            //
+            // Our monotonic clock can simply be moved forward, so we only have to wait on the host
+            // if the first callback is waiting for the real (system) time.
+            let first_callback_is_realtime = self.timeout_callbacks.values().any(|info| {
+                matches!(info.call_time, Time::RealTime(_))
+                    && info.call_time.get_wait_time(clock) <= sleep_time
+            });
+            if first_callback_is_realtime {
+                std::thread::sleep(sleep_time);
+            } else {
+                clock.sleep(sleep_time);
+            }
             Ok(SchedulingAction::ExecuteTimeoutCallback)
         } else {
             throw_machine_stop!(TerminationInfo::Deadlock);
@@ -710,6 +763,10 @@ fn get_or_create_thread_local_alloc(
             // thread-local static.
             // First, we compute the initial value for this static.
             if tcx.is_foreign_item(def_id) {
+                // wasi-libc declares `errno` as a thread-local `extern` static.
+                if tcx.sess.target.os == "wasi" && this.item_link_name(def_id).as_str() == "errno" {
+                    return Ok(this.last_error_place()?.ptr.into_pointer_or_addr().unwrap());
+                }
                 throw_unsup_format!("foreign thread-local statics are not supported");
             }
             // We don't give a span -- statics don't need that, they cannot be generic or associated.
@@ -833,6 +890,15 @@ fn active_thread_ref(&self) -> &Thread<'mir, 'tcx> {
         this.machine.threads.active_thread_ref()
     }
 
+    /// Returns the random number generator for the randomness that the program running on the
+    /// active thread asks for, such as the bytes `getrandom` returns. It is seeded from the
+    /// `-Zmiri-seed` and the thread ID.
+    fn active_thread_rng(&mut self) -> &mut StdRng {
+        let this = self.eval_context_mut();
+        let seed = this.machine.seed;
+        this.machine.threads.active_thread_rng(seed)
+    }
+
     #[inline]
     fn get_total_thread_count(&self) -> usize {
         let this = self.eval_context_ref();
@@ -926,6 +992,21 @@ fn maybe_preempt_active_thread(&mut self) {
         }
     }
 
+    /// Decides whether the current attempt to spawn a thread fails, according to
+    /// `-Zmiri-spawn-failure-at` and `-Zmiri-spawn-failure-rate`.
+    fn spawn_should_fail(&mut self) -> bool {
+        use rand::Rng as _;
+
+        let this = self.eval_context_mut();
+        this.machine.spawn_attempts = this.machine.spawn_attempts.checked_add(1).unwrap();
+        if this.machine.spawn_failures.contains(&this.machine.spawn_attempts) {
+            return true;
+        }
+        // Only query the RNG if the rate is set, so that the other uses of it are not affected.
+        let rate = this.machine.spawn_failure_rate;
+        rate > 0.0 && this.machine.rng.get_mut().gen_bool(rate)
+    }
+
     #[inline]
     fn register_timeout_callback(
         &mut self,
@@ -934,7 +1015,7 @@ fn register_timeout_callback(
         callback: TimeoutCallback<'mir, 'tcx>,
     ) {
         let this = self.eval_context_mut();
-        if !this.machine.communicate() && matches!(call_time, Time::RealTime(..)) {
+        if matches!(call_time, Time::RealTime(..)) && !this.communicate(IsolationClass::Clock) {
             panic!("cannot have `RealTime` callback with isolation enabled!")
         }
         this.machine.threads.register_timeout_callback(thread, call_time, callback);
@@ -3,8 +3,10 @@
 
 use log::trace;
 
-use rustc_span::{source_map::DUMMY_SP, SpanData, Symbol};
+use rustc_span::{source_map::DUMMY_SP, Span, SpanData, Symbol};
 use rustc_target::abi::{Align, Size};
+use rustc_target::spec::abi::Abi;
+use rustc_target::spec::Target;
 
 use crate::stacked_borrows::{diagnostics::TagHistory, AccessKind};
 use crate::*;
@@ -13,6 +15,20 @@
 pub enum TerminationInfo {
     Exit(i64),
     Abort(String),
+    /// The program panicked while being compiled with `-C panic=abort`.
+    PanicAbort {
+        /// Where the panic occurred in the user's code, if we know it.
+        location: Option<SpanData>,
+    },
+    /// A panic tried to unwind out of a function that cannot unwind.
+    UnwindAbort {
+        /// The function the panic tried to unwind out of.
+        function: String,
+        /// The ABI of that function, if it is a non-unwinding ABI with an unwinding counterpart.
+        nounwind_abi: Option<Abi>,
+    },
+    /// The program was terminated by the default action of the given signal.
+    TerminatedBySignal(i32),
     UnsupportedInIsolation(String),
     StackedBorrowsUb {
         msg: String,
@@ -32,6 +48,27 @@ pub enum TerminationInfo {
         link_name: Symbol,
         span: SpanData,
     },
+    NonLocalJump {
+        link_name: Symbol,
+        /// The `setjmp` call the jump would return to, if we know it.
+        target: Option<SpanData>,
+        /// The functions whose frames the jump would skip, innermost first.
+        skipped: Vec<(String, SpanData)>,
+    },
+    /// Heap memory was passed to a deallocation function that must not be used for it.
+    HeapMisuse {
+        msg: String,
+        alloc_id: AllocId,
+        /// Where the memory was allocated, and by which allocator.
+        allocated: (SpanData, MiriMemoryKind),
+        /// Where the memory was deallocated, if it already was.
+        deallocated: Option<SpanData>,
+    },
+    /// There are no more free addresses in the address space of the target, which has the given
+    /// number of bits.
+    AddressSpaceFull {
+        bits: u64,
+    },
 }
 
 impl fmt::Display for TerminationInfo {
@@ -40,6 +77,14 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Exit(code) => write!(f, "the evaluated program completed with exit code {code}"),
             Abort(msg) => write!(f, "{msg}"),
+            PanicAbort { .. } =>
+                write!(f, "the program panicked with `-C panic=abort`, which aborts execution"),
+            UnwindAbort { function, .. } =>
+                write!(
+                    f,
+                    "the program aborted execution because a panic tried to unwind out of `{function}`, which cannot unwind"
+                ),
+            TerminatedBySignal(sig) => write!(f, "the program was terminated by signal {sig}"),
             UnsupportedInIsolation(msg) => write!(f, "{msg}"),
             Int2PtrWithStrictProvenance =>
                 write!(
@@ -52,6 +97,14 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "multiple definitions of symbol `{link_name}`"),
             SymbolShimClashing { link_name, .. } =>
                 write!(f, "found `{link_name}` symbol definition that clashes with a built-in shim",),
+            NonLocalJump { link_name, .. } =>
+                write!(f, "non-local jumps via `{link_name}` are not supported"),
+            HeapMisuse { msg, .. } => write!(f, "{msg}"),
+            AddressSpaceFull { bits } =>
+                write!(
+                    f,
+                    "there are no more free addresses in the {bits}-bit address space of the target"
+                ),
         }
     }
 }
@@ -71,6 +124,12 @@ pub enum NonHaltingDiagnostic {
     CreatedAlloc(AllocId, Size, Align, MemoryKind<MiriMemoryKind>),
     FreedAlloc(AllocId),
     RejectedIsolatedOp(String),
+    /// An op was rejected by isolation, but returned a made-up value instead of an error.
+    FakedIsolatedOp(String),
+    /// The program tried to create or manage a process in a way we cannot emulate. `spawn` is set
+    /// if it tried to spawn a process the way `std::process::Command` does, which
+    /// `-Zmiri-process-passthrough` would let it do.
+    UnsupportedProcessOp { op: String, spawn: bool },
     ProgressReport {
         block_count: u64, // how many basic blocks have been run so far
     },
@@ -146,6 +205,94 @@ fn prune_stacktrace<'tcx>(
     }
 }
 
+/// Computes the title and the help messages for reporting the given termination. Additional lines
+/// of the main message are pushed to `msg`.
+fn termination_title_and_helps(
+    info: &TerminationInfo,
+    msg: &mut Vec<String>,
+) -> (Option<&'static str>, Vec<(Option<SpanData>, String)>) {
+    use TerminationInfo::*;
+    let title = match info {
+        Abort(_) | PanicAbort { .. } | UnwindAbort { .. } | TerminatedBySignal(_) =>
+            Some("abnormal termination"),
+        UnsupportedInIsolation(_) | Int2PtrWithStrictProvenance | NonLocalJump { .. } =>
+            Some("unsupported operation"),
+        StackedBorrowsUb { .. } | HeapMisuse { .. } => Some("Undefined Behavior"),
+        Deadlock => Some("deadlock"),
+        AddressSpaceFull { .. } => Some("resource exhaustion"),
+        Exit(_) | MultipleSymbolDefinitions { .. } | SymbolShimClashing { .. } => None,
+    };
+    #[rustfmt::skip]
+    let helps = match info {
+        UnsupportedInIsolation(_) =>
+            vec![
+                (None, format!("pass the flag `-Zmiri-disable-isolation` to disable isolation;")),
+                (None, format!("or pass `-Zmiri-isolation-error=warn` to configure Miri to return an error code from isolated operations (if supported for that operation) and continue with a warning")),
+            ],
+        StackedBorrowsUb { help, history, .. } => {
+            let url = "https://github.com/rust-lang/unsafe-code-guidelines/blob/master/wip/stacked-borrows.md";
+            msg.extend(help.clone());
+            let mut helps = vec![
+                (None, format!("this indicates a potential bug in the program: it performed an invalid operation, but the Stacked Borrows rules it violated are still experimental")),
+                (None, format!("see {url} for further information")),
+            ];
+            if let Some(TagHistory {created, invalidated, protected}) = history.clone() {
+                helps.push((Some(created.1), created.0));
+                if let Some((msg, span)) = invalidated {
+                    helps.push((Some(span), msg));
+                }
+                if let Some((protector_msg, protector_span)) = protected {
+                    helps.push((Some(protector_span), protector_msg));
+                }
+            }
+            helps
+        }
+        MultipleSymbolDefinitions { first, first_crate, second, second_crate, .. } =>
+            vec![
+                (Some(*first), format!("it's first defined here, in crate `{first_crate}`")),
+                (Some(*second), format!("then it's defined here again, in crate `{second_crate}`")),
+            ],
+        SymbolShimClashing { link_name, span } =>
+            vec![(Some(*span), format!("the `{link_name}` symbol is defined here"))],
+        NonLocalJump { target, skipped, .. } => {
+            let mut helps = vec![
+                (None, format!("a non-local jump skips stack frames without unwinding them, which Miri cannot emulate")),
+            ];
+            if let Some(target) = target {
+                helps.push((Some(*target), format!("the jump would return to this `setjmp` call")));
+                for (name, span) in skipped {
+                    helps.push((Some(*span), format!("and skip the frame of `{name}`")));
+                }
+            } else {
+                helps.push((None, format!("the jump buffer was not set up by a `setjmp` call that is still active on this thread")));
+            }
+            helps
+        }
+        HeapMisuse { alloc_id, allocated: (allocated, kind), deallocated, .. } => {
+            let mut helps = vec![
+                (None, format!("this indicates a bug in the program: it performed an invalid operation, and caused Undefined Behavior")),
+                (None, format!("see https://doc.rust-lang.org/nightly/reference/behavior-considered-undefined.html for further information")),
+                (Some(*allocated), format!("{alloc_id:?} was allocated here, on the {kind}")),
+            ];
+            if let Some(deallocated) = deallocated {
+                helps.push((Some(*deallocated), format!("{alloc_id:?} was deallocated here")));
+            }
+            helps
+        }
+        PanicAbort { location: Some(location) } =>
+            vec![(Some(*location), format!("the panic occurred here"))],
+        UnwindAbort { nounwind_abi: Some(abi), .. } =>
+            vec![
+                (None, format!("functions with the `extern \"{}\"` ABI abort the program when a panic tries to unwind out of them", abi.name())),
+                (None, format!("if the panic is meant to propagate to the caller, declare the function `extern \"{}-unwind\"` instead", abi.name())),
+            ],
+        Int2PtrWithStrictProvenance =>
+            vec![(None, format!("use Strict Provenance APIs (https://doc.rust-lang.org/nightly/std/ptr/index.html#strict-provenance, https://crates.io/crates/sptr) instead"))],
+        _ => vec![],
+    };
+    (title, helps)
+}
+
 /// Emit a custom diagnostic without going through the miri-engine machinery.
 ///
 /// Returns `Some` if this was regular program termination with a given exit code, `None` otherwise.
@@ -160,51 +307,20 @@ pub fn report_error<'tcx, 'mir>(
     let (title, helps) = if let MachineStop(info) = e.kind() {
         let info = info.downcast_ref::<TerminationInfo>().expect("invalid MachineStop payload");
         use TerminationInfo::*;
-        let title = match info {
-            Exit(code) => return Some(*code),
-            Abort(_) => Some("abnormal termination"),
-            UnsupportedInIsolation(_) | Int2PtrWithStrictProvenance =>
-                Some("unsupported operation"),
-            StackedBorrowsUb { .. } => Some("Undefined Behavior"),
-            Deadlock => Some("deadlock"),
-            MultipleSymbolDefinitions { .. } | SymbolShimClashing { .. } => None,
-        };
-        #[rustfmt::skip]
-        let helps = match info {
-            UnsupportedInIsolation(_) =>
-                vec![
-                    (None, format!("pass the flag `-Zmiri-disable-isolation` to disable isolation;")),
-                    (None, format!("or pass `-Zmiri-isolation-error=warn` to configure Miri to return an error code from isolated operations (if supported for that operation) and continue with a warning")),
-                ],
-            StackedBorrowsUb { help, history, .. } => {
-                let url = "https://github.com/rust-lang/unsafe-code-guidelines/blob/master/wip/stacked-borrows.md";
-                msg.extend(help.clone());
-                let mut helps = vec![
-                    (None, format!("this indicates a potential bug in the program: it performed an invalid operation, but the Stacked Borrows rules it violated are still experimental")),
-                    (None, format!("see {url} for further information")),
-                ];
-                if let Some(TagHistory {created, invalidated, protected}) = history.clone() {
-                    helps.push((Some(created.1), created.0));
-                    if let Some((msg, span)) = invalidated {
-                        helps.push((Some(span), msg));
-                    }
-                    if let Some((protector_msg, protector_span)) = protected {
-                        helps.push((Some(protector_span), protector_msg));
-                    }
-                }
-                helps
-            }
-            MultipleSymbolDefinitions { first, first_crate, second, second_crate, .. } =>
-                vec![
-                    (Some(*first), format!("it's first defined here, in crate `{first_crate}`")),
-                    (Some(*second), format!("then it's defined here again, in crate `{second_crate}`")),
-                ],
-            SymbolShimClashing { link_name, span } =>
-                vec![(Some(*span), format!("the `{link_name}` symbol is defined here"))],
-            Int2PtrWithStrictProvenance =>
-                vec![(None, format!("use Strict Provenance APIs (https://doc.rust-lang.org/nightly/std/ptr/index.html#strict-provenance, https://crates.io/crates/sptr) instead"))],
-            _ => vec![],
-        };
+        if let Exit(code) = info {
+            return Some(*code);
+        }
+        let (title, mut helps) = termination_title_and_helps(info, &mut msg);
+        if matches!(info, Abort(_) | PanicAbort { .. } | UnwindAbort { .. } | TerminatedBySignal(_))
+            && ecx.machine.threads.get_live_thread_count() > 1
+        {
+            // With several live threads, it is not obvious which one brought the program down.
+            let thread = ecx.get_thread_name(ecx.get_active_thread());
+            helps.push((
+                None,
+                format!("the program terminated on thread `{}`", String::from_utf8_lossy(thread)),
+            ));
+        }
         (title, helps)
     } else {
         #[rustfmt::skip]
@@ -259,6 +375,9 @@ pub fn report_error<'tcx, 'mir>(
     let (stacktrace, was_pruned) = prune_stacktrace(stacktrace, &ecx.machine);
     e.print_backtrace();
     msg.insert(0, e.to_string());
+    if ecx.machine.report_json {
+        report_json(ecx, title, &msg, &helps, &stacktrace);
+    }
     report_msg(
         DiagLevel::Error,
         &if let Some(title) = title { format!("{title}: {}", msg[0]) } else { msg[0].clone() },
@@ -301,6 +420,73 @@ pub fn report_error<'tcx, 'mir>(
     None
 }
 
+/// Prints a JSON event describing an error that stopped the program to the program's stdout, so
+/// that tools consuming libtest's JSON output learn about it, too. See `-Zmiri-report-json`.
+fn report_json<'tcx>(
+    ecx: &InterpCx<'_, 'tcx, MiriMachine<'_, 'tcx>>,
+    title: Option<&str>,
+    msg: &[String],
+    helps: &[(Option<SpanData>, String)],
+    stacktrace: &[FrameInfo<'tcx>],
+) {
+    let details = format!(
+        r#"{{ "kind": {}, "message": {}, "help": [{}], "backtrace": [{}] }}"#,
+        json_string(title.unwrap_or("error")),
+        json_string(&msg.join("\n")),
+        json_list(helps.iter().map(|(_, help)| help)),
+        json_list(stacktrace.iter().map(|frame_info| frame_info.to_string())),
+    );
+    let thread = String::from_utf8_lossy(ecx.get_thread_name(ecx.get_active_thread()));
+    let event = if thread == "main" {
+        format!(r#"{{ "type": "miri", "event": "error", "miri": {details} }}"#)
+    } else {
+        // libtest runs each test on a thread named after the test.
+        let name = json_string(&thread);
+        format!(r#"{{ "type": "test", "name": {name}, "event": "failed", "miri": {details} }}"#)
+    };
+    // If this fails there is nothing we can do; the error is still reported on stderr.
+    let _ = ecx.machine.stdout.write(format!("{event}\n").as_bytes());
+}
+
+/// Formats `s` as a JSON string literal.
+fn json_string(s: &str) -> String {
+    use std::fmt::Write as _;
+
+    let mut json = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => write!(json, "\\u{:04x}", u32::from(c)).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Formats the elements of a JSON list of strings, without the surrounding brackets.
+fn json_list(items: impl Iterator<Item = impl AsRef<str>>) -> String {
+    items.map(|item| json_string(item.as_ref())).collect::<Vec<_>>().join(", ")
+}
+
+/// Returns the exit code a native process would have if it had terminated the way the interpreted
+/// program did with this error, for errors that are the program aborting or getting killed.
+pub fn native_abort_exit_code(e: &InterpErrorInfo<'_>, target: &Target) -> Option<i64> {
+    let InterpError::MachineStop(info) = e.kind() else { return None };
+    let info = info.downcast_ref::<TerminationInfo>().expect("invalid MachineStop payload");
+    use TerminationInfo::*;
+    match info {
+        // `abort` raises `SIGABRT` on Unix, and shells report a process killed by a signal as
+        // exiting with 128 plus the signal number. On Windows, `abort` exits with code 3.
+        Abort(_) | PanicAbort { .. } | UnwindAbort { .. } =>
+            Some(if target.os == "windows" { 3 } else { 128 + 6 }),
+        TerminatedBySignal(sig) => Some(128 + i64::from(*sig)),
+        _ => None,
+    }
+}
+
 /// Report an error or note (depending on the `error` argument) with the given stacktrace.
 /// Also emits a full stacktrace of the interpreter stack.
 /// We want to present a multi-line span message for some errors. Diagnostics do not support this
@@ -369,7 +555,189 @@ fn report_msg<'tcx>(
     err.emit();
 }
 
+/// Reports each thread that is still running, with its name and current backtrace. This is used
+/// when the main thread terminated without waiting for them.
+pub fn report_running_threads(machine: &MiriMachine<'_, '_>) {
+    for (id, stack) in machine.threads.live_threads() {
+        let name = String::from_utf8_lossy(machine.threads.get_thread_name(id));
+        let stacktrace = MiriInterpCx::generate_stacktrace_from_stack(stack);
+        let (stacktrace, _was_pruned) = prune_stacktrace(stacktrace, machine);
+        report_msg(
+            DiagLevel::Note,
+            &format!("thread `{name}` is still running"),
+            vec![format!("thread `{name}` is currently executing here")],
+            vec![],
+            vec![],
+            &stacktrace,
+            machine,
+        );
+    }
+}
+
+/// Reports the errors the program continued after with `-Zmiri-continue-after-error`, if there
+/// were any. Returns whether there were.
+pub fn report_recovered_errors(machine: &MiriMachine<'_, '_>) -> bool {
+    let recovered = machine.recovered_errors.borrow();
+    if recovered.is_empty() {
+        return false;
+    }
+    let occurrences: u64 = recovered.values().map(|(_, count)| count).sum();
+    let mut err = machine.tcx.sess.struct_err(format!(
+        "Miri continued after {occurrences} error{s}, {distinct} of them distinct",
+        s = if occurrences == 1 { "" } else { "s" },
+        distinct = recovered.len(),
+    ));
+    for ((title, span), (msg, count)) in recovered.iter() {
+        let location = if span.is_dummy() {
+            String::new()
+        } else {
+            format!(" at {}", machine.tcx.sess.source_map().span_to_diagnostic_string(*span))
+        };
+        let times = if *count == 1 { String::new() } else { format!(" ({count} times)") };
+        err.note(&format!("{title}{location}: {msg}{times}"));
+    }
+    err.emit();
+    true
+}
+
+/// Prints the integer-to-pointer casts recorded by `-Zmiri-provenance-audit`, if it is enabled.
+pub fn report_int2ptr_casts(machine: &MiriMachine<'_, '_>) {
+    let intptrcast = machine.intptrcast.borrow();
+    let Some(casts) = intptrcast.int2ptr_casts() else {
+        return;
+    };
+    let occurrences: u64 = casts.values().map(|site| site.count).sum();
+    let mut note = machine.tcx.sess.struct_note_without_error(format!(
+        "the program executed {occurrences} integer-to-pointer cast{s} at {locations} location{ls}",
+        s = if occurrences == 1 { "" } else { "s" },
+        locations = casts.len(),
+        ls = if casts.len() == 1 { "" } else { "s" },
+    ));
+    for (span, site) in casts.iter() {
+        let location = if span.is_dummy() {
+            "unknown location".to_string()
+        } else {
+            machine.tcx.sess.source_map().span_to_diagnostic_string(*span)
+        };
+        let times =
+            if site.count == 1 { "once".to_string() } else { format!("{} times", site.count) };
+        let use_ = if site.dereferenced {
+            "a resulting pointer was used to access memory"
+        } else {
+            "no resulting pointer was used to access memory"
+        };
+        note.note(&format!("{location}: {times}, {use_}"));
+    }
+    if !casts.is_empty() {
+        note.help(
+            "to avoid these casts, derive the pointers from an existing pointer with `with_addr` or `map_addr` instead",
+        );
+    }
+    note.emit();
+}
+
+/// Prints how the program used the shims, if `-Zmiri-shim-report` is enabled.
+pub fn report_shim_usage(machine: &MiriMachine<'_, '_>) {
+    let Some(usage) = &machine.shim_usage else {
+        return;
+    };
+    let usage = usage.borrow();
+    let calls: u64 = usage.values().map(|shim| shim.calls).sum();
+    let mut note = machine.tcx.sess.struct_note_without_error(format!(
+        "the program made {calls} call{s} to {shims} shim{ss}",
+        s = if calls == 1 { "" } else { "s" },
+        shims = usage.len(),
+        ss = if usage.len() == 1 { "" } else { "s" },
+    ));
+    let mut shims: Vec<_> = usage.iter().collect();
+    shims.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+    for (name, shim) in shims {
+        let mut line =
+            format!("`{name}`: {} call{}", shim.calls, if shim.calls == 1 { "" } else { "s" });
+        if !shim.fallbacks.is_empty() {
+            let fallbacks: Vec<_> =
+                shim.fallbacks.iter().map(|(what, count)| format!("{count} {what}")).collect();
+            line.push_str(&format!(", of which {}", fallbacks.join(", ")));
+        }
+        note.note(&line);
+    }
+    note.emit();
+}
+
 impl<'mir, 'tcx> MiriMachine<'mir, 'tcx> {
+    /// Handles an error that the program can keep running after, such as a Stacked Borrows
+    /// violation. Usually, this just returns the error so that it stops the program. With
+    /// `-Zmiri-continue-after-error`, or if errors of this kind were downgraded to warnings with
+    /// `-Zmiri-warn`, the error is reported instead (unless an error of the same kind was already
+    /// reported at the same location) and the caller carries on.
+    pub fn recover_from_error(&self, e: InterpError<'tcx>) -> InterpResult<'tcx> {
+        let InterpError::MachineStop(info) = &e else {
+            bug!("only errors raised by Miri itself can be recovered from");
+        };
+        let info = info.downcast_ref::<TerminationInfo>().expect("invalid MachineStop payload");
+        let as_warning = self.warn_stacked_borrows
+            && matches!(info, TerminationInfo::StackedBorrowsUb { .. });
+        if !as_warning && !self.continue_after_error {
+            return Err(e.into());
+        }
+        let mut msg = vec![];
+        let (title, helps) = termination_title_and_helps(info, &mut msg);
+        let title = title.unwrap_or("error");
+        msg.insert(0, e.to_string());
+
+        let stacktrace =
+            MiriInterpCx::generate_stacktrace_from_stack(self.threads.active_thread_stack());
+        let (stacktrace, _was_pruned) = prune_stacktrace(stacktrace, self);
+        let span = stacktrace.first().map_or(DUMMY_SP, |fi| fi.span);
+        if as_warning {
+            // Warnings do not make the run fail, so they are not part of the final report.
+            if self.reported_warnings.borrow_mut().insert((title, span)) {
+                report_msg(
+                    DiagLevel::Warning,
+                    &format!("{title}: {}", msg[0]),
+                    msg,
+                    vec![],
+                    helps,
+                    &stacktrace,
+                    self,
+                );
+            }
+            return Ok(());
+        }
+        self.record_recovered_error(title, span, &msg[0], || {
+            report_msg(
+                DiagLevel::Error,
+                &format!("{title}: {}", msg[0]),
+                msg.clone(),
+                vec![],
+                helps,
+                &stacktrace,
+                self,
+            )
+        });
+        Ok(())
+    }
+
+    /// Remembers an error the program continued after for the report at the end, and calls
+    /// `report` if this is the first error of its kind at this location.
+    pub fn record_recovered_error(
+        &self,
+        title: &'static str,
+        span: Span,
+        msg: &str,
+        report: impl FnOnce(),
+    ) {
+        let mut recovered = self.recovered_errors.borrow_mut();
+        if let Some((_, count)) = recovered.get_mut(&(title, span)) {
+            *count += 1;
+        } else {
+            recovered.insert((title, span), (msg.to_owned(), 1));
+            // Reporting might need the machine, so do not keep the borrow.
+            drop(recovered);
+            report();
+        }
+    }
+
     pub fn emit_diagnostic(&self, e: NonHaltingDiagnostic) {
         use NonHaltingDiagnostic::*;
 
@@ -378,7 +746,9 @@ pub fn emit_diagnostic(&self, e: NonHaltingDiagnostic) {
         let (stacktrace, _was_pruned) = prune_stacktrace(stacktrace, self);
 
         let (title, diag_level) = match &e {
-            RejectedIsolatedOp(_) => ("operation rejected by isolation", DiagLevel::Warning),
+            RejectedIsolatedOp(_) | FakedIsolatedOp(_) =>
+                ("operation rejected by isolation", DiagLevel::Warning),
+            UnsupportedProcessOp { .. } => ("unsupported process operation", DiagLevel::Warning),
             Int2Ptr { .. } => ("integer-to-pointer cast", DiagLevel::Warning),
             CreatedPointerTag(..)
             | PoppedPointerTag(..)
@@ -415,6 +785,10 @@ pub fn emit_diagnostic(&self, e: NonHaltingDiagnostic) {
             FreedAlloc(AllocId(id)) => format!("freed allocation with id {id}"),
             RejectedIsolatedOp(ref op) =>
                 format!("{op} was made to return an error due to isolation"),
+            FakedIsolatedOp(ref op) =>
+                format!("{op} was made to return a made-up value due to isolation"),
+            UnsupportedProcessOp { ref op, .. } =>
+                format!("{op} is not supported by Miri and was made to fail with `ENOSYS`"),
             ProgressReport { .. } =>
                 format!("progress report: current operation being executed is here"),
             Int2Ptr { .. } => format!("integer-to-pointer cast"),
@@ -469,6 +843,13 @@ pub fn emit_diagnostic(&self, e: NonHaltingDiagnostic) {
                         ),
                     ),
                 ],
+            UnsupportedProcessOp { spawn: true, .. } =>
+                vec![(
+                    None,
+                    format!(
+                        "pass `-Zmiri-process-passthrough` to let `std::process::Command` spawn processes on the host"
+                    ),
+                )],
             _ => vec![],
         };
 
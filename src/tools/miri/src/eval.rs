@@ -8,13 +8,18 @@
 
 use log::info;
 
-use rustc_data_structures::fx::FxHashSet;
-use rustc_hir::def_id::DefId;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::{
+    def::DefKind,
+    def_id::{DefId, LOCAL_CRATE},
+};
+use rustc_middle::middle::{codegen_fn_attrs::CodegenFnAttrFlags, exported_symbols::ExportedSymbol};
 use rustc_middle::ty::{
     self,
     layout::{LayoutCx, LayoutOf},
     TyCtxt,
 };
+use rustc_span::{sym, DUMMY_SP};
 use rustc_target::spec::abi::Abi;
 
 use rustc_session::config::EntryFnType;
@@ -60,6 +65,57 @@ pub enum IsolatedOp {
     Allow,
 }
 
+/// The classes of operations requiring communication with the host, for which the isolation
+/// policy can be configured separately.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IsolationClass {
+    /// Reading the host environment: environment variables, the working directory, the process
+    /// id, the host name, and whether the standard streams are terminals.
+    Env,
+    /// Reading the host's real-time and monotonic clocks.
+    Clock,
+    /// Using the host's random number generator.
+    Random,
+    /// Reading files and directories, including stdin.
+    FsRead,
+    /// Creating, writing, renaming and removing files and directories.
+    FsWrite,
+    /// Spawning and managing host processes.
+    Process,
+    /// Network access. Miri does not support any networking functions yet, so this class
+    /// currently has no effect.
+    Net,
+}
+
+/// How operations of one `IsolationClass` are handled, overriding the global `IsolatedOp`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IsolationPolicy {
+    /// Reject the op the way the global isolation setting does, aborting by default.
+    Reject,
+    /// Make the op fail, or return a made-up value, and continue with a warning. The warning has
+    /// a backtrace unless the global setting is `RejectOpWith::WarningWithoutBacktrace`.
+    Warn,
+    /// Let the op communicate with the host.
+    Passthrough,
+}
+
+impl IsolationPolicy {
+    /// Returns how to handle an op with this policy, given the global isolation setting.
+    pub fn isolated_op(self, global: IsolatedOp) -> IsolatedOp {
+        match (self, global) {
+            (IsolationPolicy::Reject, IsolatedOp::Reject(_)) => global,
+            (IsolationPolicy::Reject, IsolatedOp::Allow) =>
+                IsolatedOp::Reject(RejectOpWith::Abort),
+            (
+                IsolationPolicy::Warn,
+                IsolatedOp::Reject(RejectOpWith::Warning | RejectOpWith::WarningWithoutBacktrace),
+            ) => global,
+            (IsolationPolicy::Warn, _) => IsolatedOp::Reject(RejectOpWith::Warning),
+            (IsolationPolicy::Passthrough, _) => IsolatedOp::Allow,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum BacktraceStyle {
     /// Prints a terser backtrace which ideally only contains relevant information.
@@ -70,26 +126,75 @@ pub enum BacktraceStyle {
     Off,
 }
 
+/// The value of an `extern` static given by `-Zmiri-extern-static`.
+#[derive(Clone, Debug)]
+pub enum ExternStaticValue {
+    /// All bytes are zero.
+    Zeroed,
+    /// The given bytes, which must be exactly as many as the static is large.
+    Bytes(Vec<u8>),
+    /// The same memory as the `extern` static with the given name that Miri provides by itself,
+    /// such as `environ`.
+    Builtin(String),
+}
+
 /// Configuration needed to spawn a Miri instance.
 #[derive(Clone)]
 pub struct MiriConfig {
     /// The host environment snapshot to use as basis for what is provided to the interpreted program.
-    /// (This is still subject to isolation as well as `forwarded_env_vars`.)
+    /// (This is still subject to isolation, `forwarded_env_vars`, and `excluded_env_vars`.)
     pub env: Vec<(OsString, OsString)>,
     /// Determine if validity checking is enabled.
     pub validate: bool,
     /// Determines if Stacked Borrows is enabled.
     pub stacked_borrows: bool,
+    /// Whether to skip validity checking and Stacked Borrows retags in code from crates that are
+    /// not local.
+    pub relax_dependencies: bool,
+    /// Crates for which validity checking and Stacked Borrows retags are skipped.
+    pub relaxed_crates: Vec<String>,
+    /// Crates that are always fully checked, even if `relax_dependencies` is set.
+    pub fully_checked_crates: Vec<String>,
     /// Controls alignment checking.
     pub check_alignment: AlignmentCheck,
     /// Controls function [ABI](Abi) checking.
     pub check_abi: bool,
     /// Action for an op requiring communication with the host.
     pub isolated_op: IsolatedOp,
-    /// Determines if memory leaks should be ignored.
+    /// Overrides `isolated_op` for the given classes of operations.
+    pub isolation_policies: FxHashMap<IsolationClass, IsolationPolicy>,
+    /// Determines if memory leaks (including threads that are still running when the main thread
+    /// terminates) should be ignored.
     pub ignore_leaks: bool,
-    /// Environment variables that should always be forwarded from the host.
+    /// Whether to exit with the exit code of a native abort when the program aborts, instead of
+    /// the exit code used for all other errors.
+    pub abort_exit_code: bool,
+    /// Whether to also print errors that stop the program as JSON events on stdout.
+    pub report_json: bool,
+    /// Whether to report Stacked Borrows violations, isolation errors and leaks and continue
+    /// instead of stopping at the first one.
+    pub continue_after_error: bool,
+    /// Whether to report Stacked Borrows violations as warnings and continue.
+    pub warn_stacked_borrows: bool,
+    /// Whether to report leaks (including threads that are still running when the main thread
+    /// terminates) as warnings instead of errors.
+    pub warn_leaks: bool,
+    /// Determines if threads that are still running when the main thread terminates should be
+    /// ignored.
+    pub ignore_running_threads: bool,
+    /// Whether to report threads that are still running when the main thread terminates as
+    /// warnings instead of errors.
+    pub warn_running_threads: bool,
+    /// Environment variables that should always be forwarded from the host. These are patterns in
+    /// which `*` matches any sequence of characters and `?` matches any single character.
     pub forwarded_env_vars: Vec<String>,
+    /// Whether to forward all environment variables from the host, even with isolation enabled.
+    pub forward_all_env_vars: bool,
+    /// Patterns for environment variables that should never be forwarded from the host, even if
+    /// they match `forwarded_env_vars` or isolation is disabled.
+    pub excluded_env_vars: Vec<String>,
+    /// Environment variables that the interpreted program sees instead of the host's.
+    pub synthetic_env_vars: Vec<(OsString, OsString)>,
     /// Command-line arguments passed to the interpreted program.
     pub args: Vec<String>,
     /// The seed to use when non-determinism or randomness are required (e.g. ptr-to-int cast, `getrandom()`).
@@ -106,6 +211,8 @@ pub struct MiriConfig {
     pub weak_memory_emulation: bool,
     /// Track when an outdated (weak memory) load happens.
     pub track_outdated_loads: bool,
+    /// Run the pthread TLS destructors of a thread in a random order instead of by ascending key.
+    pub shuffle_tls_dtors: bool,
     /// Rate of spurious failures for compare_exchange_weak atomic operations,
     /// between 0.0 and 1.0, defaulting to 0.8 (80% chance of failure).
     pub cmpxchg_weak_failure_rate: f64,
@@ -118,11 +225,19 @@ pub struct MiriConfig {
     pub backtrace_style: BacktraceStyle,
     /// Which provenance to use for int2ptr casts
     pub provenance_mode: ProvenanceMode,
+    /// Record the int2ptr casts instead of warning about them, and print a summary at the end.
+    pub provenance_audit: bool,
+    /// Print which shims the program called at the end.
+    pub shim_report: bool,
     /// Whether to ignore any output by the program. This is helpful when debugging miri
     /// as its messages don't get intermingled with the program messages.
     pub mute_stdout_stderr: bool,
     /// The probability of the active thread being preempted at the end of each basic block.
     pub preemption_rate: f64,
+    /// The probability of an attempt to spawn a thread failing, between 0.0 and 1.0.
+    pub spawn_failure_rate: f64,
+    /// The attempts to spawn a thread that fail, counting from 1.
+    pub spawn_failures: FxHashSet<u64>,
     /// Report the current instruction being executed every N basic blocks.
     pub report_progress: Option<u32>,
     /// Whether Stacked Borrows retagging should recurse into fields of datatypes.
@@ -134,6 +249,22 @@ pub struct MiriConfig {
     pub gc_interval: u32,
     /// The number of CPUs to be reported by miri.
     pub num_cpus: u32,
+    /// The page size (in bytes) to be reported by miri. `None` means to use the default.
+    pub page_size: Option<u64>,
+    /// Whether the standard streams should be reported to be terminals. `None` means to ask the
+    /// host if isolation is disabled, and to report no terminal otherwise.
+    pub force_tty: Option<bool>,
+    /// Whether the program may spawn processes on the host (requires isolation to be disabled).
+    pub process_passthrough: bool,
+    /// Where the program's standard output should go. Ignored if `mute_stdout_stderr` is set.
+    pub stdout_target: OutputTarget,
+    /// Where the program's standard error should go. Ignored if `mute_stdout_stderr` is set.
+    pub stderr_target: OutputTarget,
+    /// Values for `extern` statics that Miri does not provide by itself, by link name.
+    pub extern_statics: Vec<(String, ExternStaticValue)>,
+    /// How many tests of a libtest harness to run at once, each on a machine of its own. `1` means
+    /// to run the harness like any other program.
+    pub test_jobs: usize,
 }
 
 impl Default for MiriConfig {
@@ -142,11 +273,25 @@ fn default() -> MiriConfig {
             env: vec![],
             validate: true,
             stacked_borrows: true,
+            relax_dependencies: false,
+            relaxed_crates: vec![],
+            fully_checked_crates: vec![],
             check_alignment: AlignmentCheck::Int,
             check_abi: true,
             isolated_op: IsolatedOp::Reject(RejectOpWith::Abort),
+            isolation_policies: FxHashMap::default(),
             ignore_leaks: false,
+            abort_exit_code: false,
+            report_json: false,
+            continue_after_error: false,
+            warn_stacked_borrows: false,
+            warn_leaks: false,
+            ignore_running_threads: false,
+            warn_running_threads: false,
             forwarded_env_vars: vec![],
+            forward_all_env_vars: false,
+            excluded_env_vars: vec![],
+            synthetic_env_vars: vec![],
             args: vec![],
             seed: None,
             tracked_pointer_tags: FxHashSet::default(),
@@ -155,11 +300,16 @@ fn default() -> MiriConfig {
             data_race_detector: true,
             weak_memory_emulation: true,
             track_outdated_loads: false,
+            shuffle_tls_dtors: false,
             cmpxchg_weak_failure_rate: 0.8, // 80%
+            spawn_failure_rate: 0.0,
+            spawn_failures: FxHashSet::default(),
             measureme_out: None,
             panic_on_unsupported: false,
             backtrace_style: BacktraceStyle::Short,
             provenance_mode: ProvenanceMode::Default,
+            provenance_audit: false,
+            shim_report: false,
             mute_stdout_stderr: false,
             preemption_rate: 0.01, // 1%
             report_progress: None,
@@ -167,10 +317,27 @@ fn default() -> MiriConfig {
             external_so_file: None,
             gc_interval: 10_000,
             num_cpus: 1,
+            page_size: None,
+            force_tty: None,
+            process_passthrough: false,
+            stdout_target: OutputTarget::Host,
+            stderr_target: OutputTarget::Host,
+            extern_statics: vec![],
+            test_jobs: 1,
         }
     }
 }
 
+impl MiriConfig {
+    /// Returns what Miri should do for an op of the given class that requires communicating with
+    /// the host.
+    pub fn isolated_op_for(&self, class: IsolationClass) -> IsolatedOp {
+        self.isolation_policies
+            .get(&class)
+            .map_or(self.isolated_op, |policy| policy.isolated_op(self.isolated_op))
+    }
+}
+
 /// Returns a freshly created `InterpCx`, along with an `MPlaceTy` representing
 /// the location where the return value of the `start` function will be
 /// written to.
@@ -271,8 +438,14 @@ pub fn create_ecx<'mir, 'tcx: 'mir>(
         argv
     };
 
+    // The `main` symbol of a `#[no_main]` program takes and returns C `int`s, while Rust entry
+    // points use `isize`.
+    let c_main = matches!(entry_type, EntryFnType::Start)
+        && matches!(tcx.fn_sig(entry_id).abi(), Abi::C { .. });
+
     // Return place (in static memory so that it does not count as leak).
-    let ret_place = ecx.allocate(ecx.machine.layouts.isize, MiriMemoryKind::Machine.into())?;
+    let ret_layout = if c_main { ecx.machine.layouts.i32 } else { ecx.machine.layouts.isize };
+    let ret_place = ecx.allocate(ret_layout, MiriMemoryKind::Machine.into())?;
     // Call start function.
 
     match entry_type {
@@ -309,6 +482,16 @@ pub fn create_ecx<'mir, 'tcx: 'mir>(
                 StackPopCleanup::Root { cleanup: true },
             )?;
         }
+        EntryFnType::Start if c_main => {
+            let argc = Scalar::from_i32(i32::try_from(config.args.len()).unwrap());
+            ecx.call_function(
+                entry_instance,
+                tcx.fn_sig(entry_id).abi(),
+                &[argc.into(), argv],
+                Some(&ret_place.into()),
+                StackPopCleanup::Root { cleanup: true },
+            )?;
+        }
         EntryFnType::Start => {
             ecx.call_function(
                 entry_instance,
@@ -323,6 +506,23 @@ pub fn create_ecx<'mir, 'tcx: 'mir>(
     Ok((ecx, ret_place))
 }
 
+/// Finds the `main` function that a `#[no_main]` program exports for the C runtime to call.
+/// We can start such a program like one with a `#[start]` function.
+pub fn no_main_entry_fn(tcx: TyCtxt<'_>) -> Option<DefId> {
+    tcx.exported_symbols(LOCAL_CRATE).iter().find_map(|&(symbol, _export_info)| {
+        let ExportedSymbol::NonGeneric(def_id) = symbol else { return None };
+        let attrs = tcx.codegen_fn_attrs(def_id);
+        let symbol_name = if let Some(export_name) = attrs.export_name {
+            export_name
+        } else if attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE) {
+            tcx.item_name(def_id)
+        } else {
+            return None;
+        };
+        (symbol_name == sym::main && tcx.def_kind(def_id) == DefKind::Fn).then_some(def_id)
+    })
+}
+
 /// Evaluates the entry function specified by `entry_id`.
 /// Returns `Some(return_code)` if program executed completed.
 /// Returns `None` if an evaluation error occurred.
@@ -335,6 +535,11 @@ pub fn eval_entry<'tcx>(
 ) -> Option<i64> {
     // Copy setting before we move `config`.
     let ignore_leaks = config.ignore_leaks;
+    let warn_leaks = config.warn_leaks;
+    // Leaking threads is a kind of leak, so the leak settings apply to it as well.
+    let ignore_running_threads = config.ignore_running_threads || config.ignore_leaks;
+    let warn_running_threads = config.warn_running_threads || config.warn_leaks;
+    let abort_exit_code = config.abort_exit_code;
 
     let (mut ecx, ret_place) = match create_ecx(tcx, entry_id, entry_type, &config) {
         Ok(v) => v,
@@ -366,7 +571,8 @@ pub fn eval_entry<'tcx>(
                 }
             }
         }
-        let return_code = ecx.read_scalar(&ret_place.into())?.to_machine_isize(&ecx)?;
+        let return_code = ecx.read_scalar(&ret_place.into())?.to_int(ret_place.layout.size)?;
+        let return_code = i64::try_from(return_code).unwrap();
         Ok(return_code)
     }));
     let res = res.unwrap_or_else(|panic_payload| {
@@ -385,32 +591,85 @@ pub fn eval_entry<'tcx>(
     }
 
     // Process the result.
-    match res {
-        Ok(return_code) => {
-            if !ignore_leaks {
-                // Check for thread leaks.
-                if !ecx.have_all_terminated() {
-                    tcx.sess.err(
-                        "the main thread terminated without waiting for all remaining threads",
-                    );
-                    tcx.sess.note_without_error("pass `-Zmiri-ignore-leaks` to disable this check");
-                    return None;
-                }
-                // Check for memory leaks.
-                info!("Additonal static roots: {:?}", ecx.machine.static_roots);
-                let leaks = ecx.leak_report(&ecx.machine.static_roots);
-                if leaks != 0 {
-                    tcx.sess.err("the evaluated program leaked memory");
-                    tcx.sess.note_without_error("pass `-Zmiri-ignore-leaks` to disable this check");
-                    // Ignore the provided return code - let the reported error
-                    // determine the return code.
-                    return None;
-                }
-            }
-            Some(return_code)
+    let return_code = match res {
+        Ok(return_code) =>
+            check_running_threads(&ecx, ignore_running_threads, warn_running_threads)
+                .and_then(|()| check_leaks(&ecx, ignore_leaks, warn_leaks))
+                .map(|()| return_code),
+        Err(e) => {
+            let native_exit_code =
+                if abort_exit_code { native_abort_exit_code(&e, &tcx.sess.target) } else { None };
+            report_error(&ecx, e).or(native_exit_code)
+        }
+    };
+    report_int2ptr_casts(&ecx.machine);
+    report_shim_usage(&ecx.machine);
+    if report_recovered_errors(&ecx.machine) {
+        // Like for leaks, the reported errors determine the return code.
+        return None;
+    }
+    return_code
+}
+
+/// Checks for threads that are still running after the main thread terminated, and reports them.
+/// Returns `None` if there were any that make the run fail.
+fn check_running_threads<'mir, 'tcx>(
+    ecx: &MiriInterpCx<'mir, 'tcx>,
+    ignore_running_threads: bool,
+    warn_running_threads: bool,
+) -> Option<()> {
+    if ignore_running_threads || ecx.have_all_terminated() {
+        return Some(());
+    }
+    let tcx = ecx.tcx;
+    let msg = "the main thread terminated without waiting for all remaining threads";
+    if warn_running_threads {
+        tcx.sess.warn(msg);
+        report_running_threads(&ecx.machine);
+        return Some(());
+    }
+    tcx.sess.err(msg);
+    tcx.sess.note_without_error("pass `-Zmiri-ignore=threads` to disable this check");
+    report_running_threads(&ecx.machine);
+    if ecx.machine.continue_after_error {
+        ecx.machine.record_recovered_error("thread leak", DUMMY_SP, msg, || ());
+    }
+    None
+}
+
+/// Checks for memory the program leaked, and reports it. Returns `None` if there were any leaks
+/// that make the run fail.
+fn check_leaks<'mir, 'tcx>(
+    ecx: &MiriInterpCx<'mir, 'tcx>,
+    ignore_leaks: bool,
+    warn_leaks: bool,
+) -> Option<()> {
+    // The memory of threads that are still running would show up as leaked, so we do not check
+    // for leaks in that case.
+    if ignore_leaks || !ecx.have_all_terminated() {
+        return Some(());
+    }
+    let tcx = ecx.tcx;
+    let continue_after_error = ecx.machine.continue_after_error;
+    // Check for memory leaks.
+    info!("Additonal static roots: {:?}", ecx.machine.static_roots);
+    let leaks = ecx.leak_report(&ecx.machine.static_roots);
+    if leaks != 0 {
+        let msg = "the evaluated program leaked memory";
+        if warn_leaks {
+            tcx.sess.warn(msg);
+            return Some(());
+        }
+        tcx.sess.err(msg);
+        tcx.sess.note_without_error("pass `-Zmiri-ignore-leaks` to disable this check");
+        if continue_after_error {
+            ecx.machine.record_recovered_error("memory leak", DUMMY_SP, msg, || ());
         }
-        Err(e) => report_error(&ecx, e),
+        // Ignore the provided return code - let the reported error
+        // determine the return code.
+        return None;
     }
+    Some(())
 }
 
 /// Turns an array of arguments into a Windows command line string.
@@ -514,4 +773,77 @@ fn windows_argv0_no_escape() {
         ));
         assert_eq!(cmd.trim_end_matches('\0'), r#""C:\Program Files\" arg1 "arg 2" "arg \" 3""#);
     }
+
+    /// Splits a command line the way the MSVC runtime (and thus `std::env::args`) does.
+    fn parse_windows_command_line(cmd: &str) -> Vec<String> {
+        let mut chars = cmd.chars().peekable();
+        let mut args = Vec::new();
+        // argv[0] ends at the closing quote, or at the first whitespace if it is not quoted.
+        let mut arg0 = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            arg0.extend(chars.by_ref().take_while(|&c| c != '"'));
+        } else {
+            while let Some(c) = chars.next_if(|&c| c != ' ' && c != '\t') {
+                arg0.push(c);
+            }
+        }
+        args.push(arg0);
+        loop {
+            while chars.next_if(|&c| c == ' ' || c == '\t').is_some() {}
+            if chars.peek().is_none() {
+                return args;
+            }
+            let mut arg = String::new();
+            let mut in_quotes = false;
+            loop {
+                let mut nslashes = 0;
+                while chars.next_if_eq(&'\\').is_some() {
+                    nslashes += 1;
+                }
+                match chars.next() {
+                    Some('"') => {
+                        arg.extend(iter::repeat('\\').take(nslashes / 2));
+                        if nslashes % 2 == 0 {
+                            in_quotes = !in_quotes;
+                        } else {
+                            arg.push('"');
+                        }
+                    }
+                    Some(c) if !in_quotes && (c == ' ' || c == '\t') => {
+                        arg.extend(iter::repeat('\\').take(nslashes));
+                        break;
+                    }
+                    Some(c) => {
+                        arg.extend(iter::repeat('\\').take(nslashes));
+                        arg.push(c);
+                    }
+                    None => {
+                        arg.extend(iter::repeat('\\').take(nslashes));
+                        break;
+                    }
+                }
+            }
+            args.push(arg);
+        }
+    }
+
+    #[test]
+    fn windows_command_line_round_trip() {
+        let args = [
+            r"C:\Program Files\miri.exe",
+            "",
+            "plain",
+            "with space",
+            "with\ttab",
+            r#"with"quote"#,
+            r"trailing\",
+            r"trailing space\ ",
+            r#"slashes\\"quote"#,
+            r"\\server\share",
+            "unicode 🦀",
+        ];
+        let cmd = String::from_utf16(&args_to_utf16_command_string(args.iter())).unwrap();
+        let parsed = parse_windows_command_line(cmd.trim_end_matches('\0'));
+        assert_eq!(parsed, args);
+    }
 }
@@ -8,13 +8,17 @@
 
 use log::trace;
 
-use rustc_hir::def_id::{DefId, CRATE_DEF_INDEX};
+use rustc_ast::ast;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::{DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
+use rustc_middle::middle::{dependency_format::Linkage, exported_symbols::ExportedSymbol};
 use rustc_middle::mir;
 use rustc_middle::ty::{
     self,
     layout::{LayoutOf, TyAndLayout},
     List, TyCtxt,
 };
+use rustc_session::config::CrateType;
 use rustc_span::{def_id::CrateNum, sym, Span, Symbol};
 use rustc_target::abi::{Align, FieldsShape, Size, Variants};
 use rustc_target::spec::abi::Abi;
@@ -73,6 +77,59 @@ impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir,
     ]
 };
 
+// This mapping should match `decode_error_kind` in
+// <https://github.com/rust-lang/rust/blob/master/library/std/src/sys/windows/mod.rs>.
+// We only list one of the many codes that std maps to `TimedOut`.
+const WINDOWS_IO_ERROR_TABLE: &[(&str, std::io::ErrorKind)] = {
+    use std::io::ErrorKind::*;
+    &[
+        ("ERROR_BROKEN_PIPE", BrokenPipe),
+        ("ERROR_BUSY", ResourceBusy),
+        ("ERROR_CALL_NOT_IMPLEMENTED", Unsupported),
+        ("ERROR_DIRECTORY", NotADirectory),
+        ("ERROR_DIRECTORY_NOT_SUPPORTED", IsADirectory),
+        ("ERROR_DIR_NOT_EMPTY", DirectoryNotEmpty),
+        ("ERROR_DISK_QUOTA_EXCEEDED", FilesystemQuotaExceeded),
+        ("ERROR_FILE_TOO_LARGE", FileTooLarge),
+        ("ERROR_HOST_UNREACHABLE", HostUnreachable),
+        ("ERROR_INVALID_PARAMETER", InvalidInput),
+        ("ERROR_NETWORK_UNREACHABLE", NetworkUnreachable),
+        ("ERROR_NOT_SAME_DEVICE", CrossesDevices),
+        ("ERROR_POSSIBLE_DEADLOCK", Deadlock),
+        ("ERROR_SEEK_ON_DEVICE", NotSeekable),
+        ("ERROR_TIMEOUT", TimedOut),
+        ("ERROR_TOO_MANY_LINKS", TooManyLinks),
+        ("ERROR_WRITE_PROTECT", ReadOnlyFilesystem),
+        ("WSAEADDRINUSE", AddrInUse),
+        ("WSAEADDRNOTAVAIL", AddrNotAvailable),
+        ("WSAECONNABORTED", ConnectionAborted),
+        ("WSAECONNREFUSED", ConnectionRefused),
+        ("WSAECONNRESET", ConnectionReset),
+        ("WSAENETDOWN", NetworkDown),
+        ("WSAENOTCONN", NotConnected),
+        ("WSAEWOULDBLOCK", WouldBlock),
+        // The following have several valid options. We have all of them for the forwards mapping;
+        // only the first one will be used for the backwards mapping.
+        ("ERROR_FILE_NOT_FOUND", NotFound),
+        ("ERROR_PATH_NOT_FOUND", NotFound),
+        ("ERROR_ACCESS_DENIED", PermissionDenied),
+        ("WSAEACCES", PermissionDenied),
+        ("ERROR_ALREADY_EXISTS", AlreadyExists),
+        ("ERROR_FILE_EXISTS", AlreadyExists),
+        ("ERROR_NO_DATA", BrokenPipe),
+        ("ERROR_INVALID_NAME", InvalidFilename),
+        ("ERROR_FILENAME_EXCED_RANGE", InvalidFilename),
+        ("WSAEINVAL", InvalidInput),
+        ("ERROR_NOT_ENOUGH_MEMORY", OutOfMemory),
+        ("ERROR_OUTOFMEMORY", OutOfMemory),
+        ("ERROR_DISK_FULL", StorageFull),
+        ("ERROR_HANDLE_DISK_FULL", StorageFull),
+        ("WSAETIMEDOUT", TimedOut),
+        ("WSAEHOSTUNREACH", HostUnreachable),
+        ("WSAENETUNREACH", NetworkUnreachable),
+    ]
+};
+
 /// Gets an instance for a path.
 fn try_resolve_did<'tcx>(tcx: TyCtxt<'tcx>, path: &[&str]) -> Option<DefId> {
     tcx.crates(()).iter().find(|&&krate| tcx.crate_name(krate).as_str() == path[0]).and_then(
@@ -252,13 +309,12 @@ fn gen_random(&mut self, ptr: Pointer<Option<Provenance>>, len: u64) -> InterpRe
 
         let mut data = vec![0; usize::try_from(len).unwrap()];
 
-        if this.machine.communicate() {
+        if this.communicate(IsolationClass::Random) {
             // Fill the buffer using the host's rng.
             getrandom::getrandom(&mut data)
                 .map_err(|err| err_unsup_format!("host getrandom failed: {}", err))?;
         } else {
-            let rng = this.machine.rng.get_mut();
-            rng.fill_bytes(&mut data);
+            this.active_thread_rng().fill_bytes(&mut data);
         }
 
         this.write_bytes_ptr(ptr, data.iter().copied())
@@ -491,9 +547,10 @@ fn visit_union(
     /// Helper function used inside the shims of foreign functions to check that isolation is
     /// disabled. It returns an error using the `name` of the foreign function if this is not the
     /// case.
-    fn check_no_isolation(&self, name: &str) -> InterpResult<'tcx> {
-        if !self.eval_context_ref().machine.communicate() {
-            self.reject_in_isolation(name, RejectOpWith::Abort)?;
+    fn check_no_isolation(&self, class: IsolationClass, name: &str) -> InterpResult<'tcx> {
+        if !self.communicate(class) {
+            // The callers cannot make the operation fail, so this always stops the program.
+            return isolation_abort_error(name);
         }
         Ok(())
     }
@@ -502,8 +559,13 @@ fn check_no_isolation(&self, name: &str) -> InterpResult<'tcx> {
     /// when isolation is enabled. It is used to print a warning/backtrace about the rejection.
     fn reject_in_isolation(&self, op_name: &str, reject_with: RejectOpWith) -> InterpResult<'tcx> {
         let this = self.eval_context_ref();
+        this.machine.record_shim_fallback("made to fail due to isolation");
         match reject_with {
-            RejectOpWith::Abort => isolation_abort_error(op_name),
+            // The caller makes the operation fail, so the program can go on if we continue after
+            // errors.
+            RejectOpWith::Abort =>
+                isolation_abort_error(op_name)
+                    .or_else(|err| this.machine.recover_from_error(err.into_kind())),
             RejectOpWith::WarningWithoutBacktrace => {
                 this.tcx
                     .sess
@@ -518,6 +580,29 @@ fn reject_in_isolation(&self, op_name: &str, reject_with: RejectOpWith) -> Inter
         }
     }
 
+    /// Like `reject_in_isolation`, but for ops that continue with a made-up result instead of
+    /// failing.
+    fn fake_in_isolation(&self, op_name: &str, reject_with: RejectOpWith) -> InterpResult<'tcx> {
+        let this = self.eval_context_ref();
+        this.machine.record_shim_fallback("returned a made-up value due to isolation");
+        match reject_with {
+            RejectOpWith::Abort =>
+                isolation_abort_error(op_name)
+                    .or_else(|err| this.machine.recover_from_error(err.into_kind())),
+            RejectOpWith::WarningWithoutBacktrace => {
+                this.tcx.sess.warn(format!(
+                    "{op_name} was made to return a made-up value due to isolation"
+                ));
+                Ok(())
+            }
+            RejectOpWith::Warning => {
+                this.emit_diagnostic(NonHaltingDiagnostic::FakedIsolatedOp(op_name.to_string()));
+                Ok(())
+            }
+            RejectOpWith::NoWarning => Ok(()),
+        }
+    }
+
     /// Helper function used inside the shims of foreign functions to assert that the target OS
     /// is `target_os`. It panics showing a message with the `name` of the foreign function
     /// if this is not the case.
@@ -542,6 +627,16 @@ fn assert_target_os_is_unix(&self, name: &str) {
         );
     }
 
+    /// Like `assert_target_os_is_unix`, but also accepts the other targets with POSIX environment
+    /// functions (see `target_os_has_posix_env`).
+    fn assert_target_os_has_posix_env(&self, name: &str) {
+        assert!(
+            target_os_has_posix_env(self.eval_context_ref().tcx.sess.target.os.as_ref()),
+            "`{}` is only available for targets with a POSIX environment",
+            name,
+        );
+    }
+
     /// Get last error variable as a place, lazily allocating thread-local storage for it if
     /// necessary.
     fn last_error_place(&mut self) -> InterpResult<'tcx, MPlaceTy<'tcx, Provenance>> {
@@ -580,7 +675,8 @@ fn io_error_to_errnum(
     ) -> InterpResult<'tcx, Scalar<Provenance>> {
         let this = self.eval_context_ref();
         let target = &this.tcx.sess.target;
-        if target.families.iter().any(|f| f == "unix") {
+        // wasi-libc uses the same error names as the UNIX C libraries.
+        if target.families.iter().any(|f| f == "unix") || target.os == "wasi" {
             for &(name, kind) in UNIX_IO_ERROR_TABLE {
                 if err_kind == kind {
                     return this.eval_libc(name);
@@ -588,20 +684,12 @@ fn io_error_to_errnum(
             }
             throw_unsup_format!("io error {:?} cannot be translated into a raw os error", err_kind)
         } else if target.families.iter().any(|f| f == "windows") {
-            // FIXME: we have to finish implementing the Windows equivalent of this.
-            use std::io::ErrorKind::*;
-            this.eval_windows(
-                "c",
-                match err_kind {
-                    NotFound => "ERROR_FILE_NOT_FOUND",
-                    PermissionDenied => "ERROR_ACCESS_DENIED",
-                    _ =>
-                        throw_unsup_format!(
-                            "io error {:?} cannot be translated into a raw os error",
-                            err_kind
-                        ),
-                },
-            )
+            for &(name, kind) in WINDOWS_IO_ERROR_TABLE {
+                if err_kind == kind {
+                    return this.eval_windows("c", name);
+                }
+            }
+            throw_unsup_format!("io error {:?} cannot be translated into a raw os error", err_kind)
         } else {
             throw_unsup_format!(
                 "converting io::Error into errnum is unsupported for OS {}",
@@ -618,7 +706,8 @@ fn try_errnum_to_io_error(
     ) -> InterpResult<'tcx, Option<std::io::ErrorKind>> {
         let this = self.eval_context_ref();
         let target = &this.tcx.sess.target;
-        if target.families.iter().any(|f| f == "unix") {
+        // wasi-libc uses the same error names as the UNIX C libraries.
+        if target.families.iter().any(|f| f == "unix") || target.os == "wasi" {
             let errnum = errnum.to_i32()?;
             for &(name, kind) in UNIX_IO_ERROR_TABLE {
                 if errnum == this.eval_libc_i32(name)? {
@@ -628,6 +717,15 @@ fn try_errnum_to_io_error(
             // Our table is as complete as the mapping in std, so we are okay with saying "that's a
             // strange one" here.
             return Ok(None);
+        } else if target.families.iter().any(|f| f == "windows") {
+            let errnum = errnum.to_u32()?;
+            for &(name, kind) in WINDOWS_IO_ERROR_TABLE {
+                if errnum == this.eval_windows("c", name)?.to_u32()? {
+                    return Ok(Some(kind));
+                }
+            }
+            // Like above, our table covers everything std knows about.
+            return Ok(None);
         } else {
             throw_unsup_format!(
                 "converting errnum into io::Error is unsupported for OS {}",
@@ -700,6 +798,17 @@ fn write_scalar_at_offset(
         this.write_scalar(value, &value_place.into())
     }
 
+    /// Reads an address that the program passed as an integer, because the target ABI passes
+    /// pointers that way, and turns it back into a pointer.
+    fn read_pointer_from_int(
+        &self,
+        op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, Pointer<Option<Provenance>>> {
+        let this = self.eval_context_ref();
+        let addr = this.read_scalar(op)?.to_machine_usize(this)?;
+        intptrcast::GlobalStateInner::ptr_from_abi_addr(this, addr)
+    }
+
     /// Parse a `timespec` struct and return it as a `std::time::Duration`. It returns `None`
     /// if the value in the `timespec` struct is invalid. Some libc functions will return
     /// `EINVAL` in this case.
@@ -846,6 +955,79 @@ fn check_abi<'a>(&self, abi: Abi, exp_abi: Abi) -> InterpResult<'a, ()> {
         Ok(())
     }
 
+    /// Whether the code that is currently executing belongs to a crate for which we skip the
+    /// expensive checks (see `-Zmiri-relax-dependencies`).
+    fn in_relaxed_crate(&self) -> bool {
+        let this = self.eval_context_ref();
+        if this.machine.relaxed_crates.is_empty() {
+            return false;
+        }
+        let Some(frame) = this.active_thread_stack().last() else { return false };
+        this.machine.relaxed_crates.contains(&frame.instance.def_id().krate)
+    }
+
+    /// Returns how operations of the given class that need to communicate with the host are
+    /// handled right now. Isolation is lifted while any function on the active thread's stack is
+    /// marked `#[miri::allow(isolation)]`, or is nested in a module or item marked that way.
+    fn isolated_op(&self, class: IsolationClass) -> IsolatedOp {
+        let this = self.eval_context_ref();
+        let isolated_op = this.machine.config.isolated_op_for(class);
+        if isolated_op != IsolatedOp::Allow
+            && this.active_thread_stack().last().map_or(false, |frame| frame.extra.isolation_exempt)
+        {
+            return IsolatedOp::Allow;
+        }
+        isolated_op
+    }
+
+    /// Whether the program may currently perform ops of the given class on the host (see
+    /// `isolated_op`).
+    fn communicate(&self, class: IsolationClass) -> bool {
+        self.isolated_op(class) == IsolatedOp::Allow
+    }
+
+    /// Whether `def_id` or any of its parents carries a `#[miri::allow(isolation)]` attribute.
+    fn is_isolation_exempt(&self, def_id: DefId) -> InterpResult<'tcx, bool> {
+        let this = self.eval_context_ref();
+        if let Some(&exempt) = this.machine.isolation_exempt_items.borrow().get(&def_id) {
+            return Ok(exempt);
+        }
+
+        let mut exempt = false;
+        for attr in this.tcx.get_attrs_unchecked(def_id) {
+            let ast::AttrKind::Normal(normal) = &attr.kind else { continue };
+            let segments = &normal.item.path.segments;
+            if segments.len() != 2
+                || segments[0].ident.name != sym::miri
+                || segments[1].ident.name != sym::allow
+            {
+                continue;
+            }
+            let Some(args) = attr.meta_item_list() else {
+                throw_unsup_format!(
+                    "`miri::allow` expects a list of checks, e.g. `miri::allow(isolation)`"
+                );
+            };
+            for arg in args {
+                if arg.name_or_empty().as_str() != "isolation" {
+                    throw_unsup_format!(
+                        "unknown check `{}` in `miri::allow`",
+                        arg.name_or_empty()
+                    );
+                }
+                exempt = true;
+            }
+        }
+        if !exempt {
+            if let Some(parent) = this.tcx.opt_parent(def_id) {
+                exempt = this.is_isolation_exempt(parent)?;
+            }
+        }
+
+        this.machine.isolation_exempt_items.borrow_mut().insert(def_id, exempt);
+        Ok(exempt)
+    }
+
     fn frame_in_std(&self) -> bool {
         let this = self.eval_context_ref();
         let Some(start_fn) = this.tcx.lang_items().start_fn() else {
@@ -1050,8 +1232,71 @@ pub fn get_local_crates(tcx: TyCtxt<'_>) -> Vec<CrateNum> {
     local_crates
 }
 
+/// Determines the crates we only check in a relaxed way, according to `-Zmiri-relax-dependencies`,
+/// `-Zmiri-relaxed-crates` and `-Zmiri-fully-checked-crates`.
+pub fn get_relaxed_crates(
+    tcx: TyCtxt<'_>,
+    local_crates: &[CrateNum],
+    config: &MiriConfig,
+) -> FxHashSet<CrateNum> {
+    let mut relaxed_crates = FxHashSet::default();
+    for &crate_num in iter::once(&LOCAL_CRATE).chain(tcx.crates(())) {
+        let name = tcx.crate_name(crate_num);
+        let name = name.as_str();
+        if config.fully_checked_crates.iter().any(|checked| checked == name) {
+            continue;
+        }
+        let is_local = crate_num == LOCAL_CRATE || local_crates.contains(&crate_num);
+        if (config.relax_dependencies && !is_local)
+            || config.relaxed_crates.iter().any(|relaxed| relaxed == name)
+        {
+            relaxed_crates.insert(crate_num);
+        }
+    }
+    relaxed_crates
+}
+
+/// Calls `f` for every non-generic symbol exported by the crates that are linked into the
+/// executable, starting with the local crate.
+pub fn iter_exported_symbols<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    mut f: impl FnMut(CrateNum, DefId) -> InterpResult<'tcx>,
+) -> InterpResult<'tcx> {
+    // `dependency_formats` includes all the transitive informations needed to link a crate,
+    // which is what we need here since we need to dig out `exported_symbols` from all transitive
+    // dependencies.
+    let dependency_formats = tcx.dependency_formats(());
+    let dependency_format = dependency_formats
+        .iter()
+        .find(|(crate_type, _)| *crate_type == CrateType::Executable)
+        .expect("interpreting a non-executable crate");
+    for cnum in iter::once(LOCAL_CRATE).chain(dependency_format.1.iter().enumerate().filter_map(
+        |(num, &linkage)| {
+            // We add 1 to the number because that's what rustc also does everywhere it
+            // calls `CrateNum::new`...
+            #[allow(clippy::integer_arithmetic)]
+            (linkage != Linkage::NotLinked).then_some(CrateNum::new(num + 1))
+        },
+    )) {
+        // We can ignore `_export_info` here: we are a Rust crate, and everything is exported
+        // from a Rust crate.
+        for &(symbol, _export_info) in tcx.exported_symbols(cnum) {
+            if let ExportedSymbol::NonGeneric(def_id) = symbol {
+                f(cnum, def_id)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Helper function used inside the shims of foreign functions to check that
 /// `target_os` is a supported UNIX OS.
 pub fn target_os_is_unix(target_os: &str) -> bool {
     matches!(target_os, "linux" | "macos" | "freebsd" | "android")
 }
+
+/// Helper function used inside the shims of foreign functions to check that `target_os` provides
+/// the POSIX environment functions. Besides the UNIX OSes, wasi-libc implements them on WASI.
+pub fn target_os_has_posix_env(target_os: &str) -> bool {
+    target_os_is_unix(target_os) || target_os == "wasi"
+}
@@ -5,9 +5,9 @@
 use log::trace;
 use rand::Rng;
 
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_span::Span;
-use rustc_target::abi::{HasDataLayout, Size};
+use rustc_target::abi::{Align, HasDataLayout, Size};
 
 use crate::*;
 
@@ -22,6 +22,15 @@ pub enum ProvenanceMode {
     Strict,
 }
 
+/// What `-Zmiri-provenance-audit` records about the integer-to-pointer casts at one location.
+#[derive(Clone, Debug, Default)]
+pub struct Int2PtrCastSite {
+    /// How often a cast was executed here.
+    pub count: u64,
+    /// Whether memory was accessed through a pointer that one of these casts may have produced.
+    pub dereferenced: bool,
+}
+
 pub type GlobalState = RefCell<GlobalStateInner>;
 
 #[derive(Clone, Debug)]
@@ -42,6 +51,12 @@ pub struct GlobalStateInner {
     next_base_addr: u64,
     /// The provenance to use for int2ptr casts
     provenance_mode: ProvenanceMode,
+    /// With `-Zmiri-provenance-audit`, the integer-to-pointer casts executed at each location, in
+    /// the order they were first executed.
+    int2ptr_casts: Option<FxIndexMap<Span, Int2PtrCastSite>>,
+    /// The locations of the casts whose result pointed into each allocation and that were not
+    /// followed by an access to that allocation through a wildcard pointer yet.
+    int2ptr_cast_targets: FxHashMap<AllocId, FxHashSet<Span>>,
 }
 
 impl VisitTags for GlobalStateInner {
@@ -51,15 +66,25 @@ fn visit_tags(&self, _visit: &mut dyn FnMut(SbTag)) {
 }
 
 impl GlobalStateInner {
-    pub fn new(config: &MiriConfig) -> Self {
+    pub fn new(config: &MiriConfig, pointer_size: Size) -> Self {
+        // Targets with a small address space (e.g. 16-bit pointers) cannot afford to leave that
+        // much of it unused.
+        let max_addr = u64::try_from(pointer_size.unsigned_int_max()).unwrap();
         GlobalStateInner {
             int_to_ptr_map: Vec::default(),
             base_addr: FxHashMap::default(),
             exposed: FxHashSet::default(),
-            next_base_addr: STACK_ADDR,
+            next_base_addr: STACK_ADDR.min(max_addr / 16),
             provenance_mode: config.provenance_mode,
+            int2ptr_casts: config.provenance_audit.then(FxIndexMap::default),
+            int2ptr_cast_targets: FxHashMap::default(),
         }
     }
+
+    /// The integer-to-pointer casts recorded by `-Zmiri-provenance-audit`, if it is enabled.
+    pub fn int2ptr_casts(&self) -> Option<&FxIndexMap<Span, Int2PtrCastSite>> {
+        self.int2ptr_casts.as_ref()
+    }
 }
 
 impl<'mir, 'tcx> GlobalStateInner {
@@ -136,9 +161,11 @@ pub fn ptr_from_addr_cast(
         trace!("Casting {:#x} to a pointer", addr);
 
         let global_state = ecx.machine.intptrcast.borrow();
+        let audit = global_state.int2ptr_casts.is_some();
 
         match global_state.provenance_mode {
-            ProvenanceMode::Default => {
+            // The audit report replaces the warning.
+            ProvenanceMode::Default if !audit => {
                 // The first time this happens at a particular location, print a warning.
                 thread_local! {
                     // `Span` is non-`Send`, so we use a thread-local instead.
@@ -155,13 +182,60 @@ pub fn ptr_from_addr_cast(
             ProvenanceMode::Strict => {
                 throw_machine_stop!(TerminationInfo::Int2PtrWithStrictProvenance);
             }
-            ProvenanceMode::Permissive => {}
+            ProvenanceMode::Default | ProvenanceMode::Permissive => {}
+        }
+        drop(global_state);
+        if audit {
+            Self::record_int2ptr_cast(ecx, addr);
         }
 
         // This is how wildcard pointers are born.
         Ok(Pointer::new(Some(Provenance::Wildcard), Size::from_bytes(addr)))
     }
 
+    /// Records an integer-to-pointer cast for `-Zmiri-provenance-audit`.
+    fn record_int2ptr_cast(ecx: &MiriInterpCx<'mir, 'tcx>, addr: u64) {
+        // The report is a worklist for the user, so we blame the cast on their code.
+        let span = ecx.machine.current_span().get();
+        let target = Self::alloc_id_from_addr(ecx, addr);
+
+        let mut global_state = ecx.machine.intptrcast.borrow_mut();
+        let global_state = &mut *global_state;
+        global_state.int2ptr_casts.as_mut().unwrap().entry(span).or_default().count += 1;
+        if let Some(alloc_id) = target {
+            global_state.int2ptr_cast_targets.entry(alloc_id).or_default().insert(span);
+        }
+    }
+
+    /// Marks the casts whose result pointed into `alloc_id` as dereferenced, for
+    /// `-Zmiri-provenance-audit`. Wildcard pointers do not remember where they came from, so we
+    /// cannot tell which of these casts produced the pointer that is being used.
+    fn record_wildcard_access(ecx: &MiriInterpCx<'mir, 'tcx>, alloc_id: AllocId) {
+        let mut global_state = ecx.machine.intptrcast.borrow_mut();
+        let global_state = &mut *global_state;
+        let Some(casts) = &mut global_state.int2ptr_casts else {
+            return;
+        };
+        if let Some(spans) = global_state.int2ptr_cast_targets.remove(&alloc_id) {
+            for span in spans {
+                casts.get_mut(&span).unwrap().dereferenced = true;
+            }
+        }
+    }
+
+    /// Turns an address that the program passed to a shim as an integer back into a pointer.
+    /// This is an integer-to-pointer cast like the one above, but the program cannot avoid it
+    /// (the target ABI passes pointers as integers), so we do not warn about it.
+    pub fn ptr_from_abi_addr(
+        ecx: &MiriInterpCx<'mir, 'tcx>,
+        addr: u64,
+    ) -> InterpResult<'tcx, Pointer<Option<Provenance>>> {
+        if ecx.machine.intptrcast.borrow().provenance_mode == ProvenanceMode::Strict {
+            throw_machine_stop!(TerminationInfo::Int2PtrWithStrictProvenance);
+        }
+        Ok(Pointer::new(Some(Provenance::Wildcard), Size::from_bytes(addr)))
+    }
+
     fn alloc_base_addr(ecx: &MiriInterpCx<'mir, 'tcx>, alloc_id: AllocId) -> u64 {
         let mut global_state = ecx.machine.intptrcast.borrow_mut();
         let global_state = &mut *global_state;
@@ -183,6 +257,16 @@ fn alloc_base_addr(ecx: &MiriInterpCx<'mir, 'tcx>, alloc_id: AllocId) -> u64 {
                 // From next_base_addr + slack, round up to adjust for alignment.
                 let base_addr = global_state.next_base_addr.checked_add(slack).unwrap();
                 let base_addr = Self::align_addr(base_addr, align.bytes());
+                // The allocation has to fit into the address space of the target. We cannot report
+                // an error from here; `check_alloc_fits` does that when the allocation is created.
+                // So this only happens for pointers to global allocations that are created before
+                // the allocation itself.
+                if base_addr.saturating_add(size.bytes()) > ecx.machine_usize_max() {
+                    ecx.tcx.sess.fatal(format!(
+                        "Miri ran out of addresses: the {}-bit address space of the target is full",
+                        ecx.pointer_size().bits()
+                    ));
+                }
                 entry.insert(base_addr);
                 trace!(
                     "Assigning base address {:#x} to allocation {:?} (size: {}, align: {}, slack: {})",
@@ -207,6 +291,29 @@ fn alloc_base_addr(ecx: &MiriInterpCx<'mir, 'tcx>, alloc_id: AllocId) -> u64 {
         }
     }
 
+    /// Makes sure that the new allocation `alloc_id` fits into the address space of the target once
+    /// `alloc_base_addr` picks its base address, so that running out of addresses stops the
+    /// program with a proper error.
+    pub fn check_alloc_fits(
+        ecx: &MiriInterpCx<'mir, 'tcx>,
+        alloc_id: AllocId,
+        size: Size,
+        align: Align,
+    ) -> InterpResult<'tcx> {
+        let global_state = ecx.machine.intptrcast.borrow();
+        if global_state.base_addr.contains_key(&alloc_id) {
+            return Ok(());
+        }
+        // Assume the largest slack `alloc_base_addr` might leave.
+        let base_addr = global_state.next_base_addr.saturating_add(15);
+        let base_addr = Self::align_addr(base_addr, align.bytes());
+        if base_addr.saturating_add(size.bytes()) > ecx.machine_usize_max() {
+            let bits = ecx.pointer_size().bits();
+            throw_machine_stop!(TerminationInfo::AddressSpaceFull { bits });
+        }
+        Ok(())
+    }
+
     /// Convert a relative (tcx) pointer to an absolute address.
     pub fn rel_ptr_to_addr(ecx: &MiriInterpCx<'mir, 'tcx>, ptr: Pointer<AllocId>) -> u64 {
         let (alloc_id, offset) = ptr.into_parts(); // offset is relative (AllocId provenance)
@@ -229,7 +336,9 @@ pub fn abs_ptr_to_rel(
             alloc_id
         } else {
             // A wildcard pointer.
-            GlobalStateInner::alloc_id_from_addr(ecx, addr.bytes())?
+            let alloc_id = GlobalStateInner::alloc_id_from_addr(ecx, addr.bytes())?;
+            GlobalStateInner::record_wildcard_access(ecx, alloc_id);
+            alloc_id
         };
 
         let base_addr = GlobalStateInner::alloc_base_addr(ecx, alloc_id);
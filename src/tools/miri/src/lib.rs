@@ -66,6 +66,7 @@
 mod shims;
 mod stacked_borrows;
 mod tag_gc;
+mod test_runner;
 
 // Establish a "crate-wide prelude": we often import `crate::*`.
 
@@ -74,12 +75,15 @@
 // Resolve ambiguity.
 pub use rustc_const_eval::interpret::{self, AllocMap, PlaceTy, Provenance as _};
 
+pub use crate::shims::asm::EvalContextExt as _;
 pub use crate::shims::dlsym::{Dlsym, EvalContextExt as _};
 pub use crate::shims::env::{EnvVars, EvalContextExt as _};
 pub use crate::shims::foreign_items::EvalContextExt as _;
 pub use crate::shims::intrinsics::EvalContextExt as _;
 pub use crate::shims::os_str::EvalContextExt as _;
+pub use crate::shims::output::{OutputStream, OutputTarget, StdStream};
 pub use crate::shims::panic::{CatchUnwindData, EvalContextExt as _};
+pub use crate::shims::setjmp::{EvalContextExt as _, SetjmpCall};
 pub use crate::shims::time::EvalContextExt as _;
 pub use crate::shims::tls::{EvalContextExt as _, TlsData};
 pub use crate::shims::EvalContextExt as _;
@@ -92,10 +96,13 @@
     thread::{EvalContextExt as _, SchedulingAction, ThreadId, ThreadManager, ThreadState, Time},
 };
 pub use crate::diagnostics::{
-    report_error, EvalContextExt as _, NonHaltingDiagnostic, TerminationInfo,
+    native_abort_exit_code, report_error, report_int2ptr_casts, report_recovered_errors,
+    report_running_threads, report_shim_usage, EvalContextExt as _, NonHaltingDiagnostic,
+    TerminationInfo,
 };
 pub use crate::eval::{
-    create_ecx, eval_entry, AlignmentCheck, BacktraceStyle, IsolatedOp, MiriConfig, RejectOpWith,
+    create_ecx, eval_entry, no_main_entry_fn, AlignmentCheck, BacktraceStyle, ExternStaticValue,
+    IsolatedOp, IsolationClass, IsolationPolicy, MiriConfig, RejectOpWith,
 };
 pub use crate::helpers::{CurrentSpan, EvalContextExt as _};
 pub use crate::intptrcast::ProvenanceMode;
@@ -110,6 +117,7 @@
     CallId, EvalContextExt as _, Item, Permission, RetagFields, SbTag, Stack, Stacks,
 };
 pub use crate::tag_gc::{EvalContextExt as _, VisitTags};
+pub use crate::test_runner::{can_run_tests_in_parallel, TestQueue};
 
 /// Insert rustc arguments at the beginning of the argument list that Miri wants to be
 /// set per default, for maximal validation power.
@@ -3,13 +3,16 @@
 
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::fmt;
+use std::iter;
 
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 
 use rustc_ast::ast::Mutability;
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_ast::{InlineAsmOptions, InlineAsmTemplatePiece};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 #[allow(unused)]
 use rustc_data_structures::static_assert_size;
 use rustc_middle::{
@@ -20,9 +23,10 @@
         Instance, Ty, TyCtxt, TypeAndMut,
     },
 };
-use rustc_span::def_id::{CrateNum, DefId};
-use rustc_span::Symbol;
-use rustc_target::abi::Size;
+use rustc_hir::def::DefKind;
+use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_span::{Span, Symbol};
+use rustc_target::abi::{Align, Size};
 use rustc_target::spec::abi::Abi;
 
 use crate::{
@@ -50,22 +54,27 @@ pub struct FrameData<'tcx> {
     /// for the start of this frame. When we finish executing this frame,
     /// we use this to register a completed event with `measureme`.
     pub timing: Option<measureme::DetachedTiming>,
+
+    /// Whether this frame, or one of the frames below it, runs code marked
+    /// `#[miri::allow(isolation)]`.
+    pub isolation_exempt: bool,
 }
 
 impl<'tcx> std::fmt::Debug for FrameData<'tcx> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Omitting `timing`, it does not support `Debug`.
-        let FrameData { stacked_borrows, catch_unwind, timing: _ } = self;
+        let FrameData { stacked_borrows, catch_unwind, timing: _, isolation_exempt } = self;
         f.debug_struct("FrameData")
             .field("stacked_borrows", stacked_borrows)
             .field("catch_unwind", catch_unwind)
+            .field("isolation_exempt", isolation_exempt)
             .finish()
     }
 }
 
 impl VisitTags for FrameData<'_> {
     fn visit_tags(&self, visit: &mut dyn FnMut(SbTag)) {
-        let FrameData { catch_unwind, stacked_borrows, timing: _ } = self;
+        let FrameData { catch_unwind, stacked_borrows, timing: _, isolation_exempt: _ } = self;
 
         catch_unwind.visit_tags(visit);
         stacked_borrows.visit_tags(visit);
@@ -245,6 +254,31 @@ pub fn and_then<T>(self, f: impl FnOnce(SbTag) -> Option<T>) -> Option<T> {
     }
 }
 
+/// How many deallocated heap allocations we keep around to explain double frees and the like.
+const FREED_HEAP_ALLOCS_LIMIT: usize = 1024;
+
+/// What we remember about a heap allocation, to explain misuse of the allocation functions.
+#[derive(Debug)]
+pub struct HeapAllocInfo {
+    /// The allocator the memory came from.
+    pub kind: MiriMemoryKind,
+    /// The layout the memory was allocated with.
+    pub size: Size,
+    pub align: Align,
+    /// Where the memory was allocated.
+    pub allocated: Span,
+    /// Where the memory was deallocated, if it was.
+    pub deallocated: Option<Span>,
+}
+
+/// How the program used one shim, for `-Zmiri-shim-report`.
+#[derive(Debug, Default)]
+pub struct ShimUsage {
+    pub calls: u64,
+    /// How often the shim did not do what the real function does, by how it fell short.
+    pub fallbacks: FxIndexMap<&'static str, u64>,
+}
+
 /// Extra per-allocation data
 #[derive(Debug, Clone)]
 pub struct AllocExtra {
@@ -367,10 +401,13 @@ pub struct MiriMachine<'mir, 'tcx> {
     /// TLS state.
     pub(crate) tls: TlsData<'tcx>,
 
-    /// What should Miri do when an op requires communicating with the host,
-    /// such as accessing host env vars, random number generation, and
-    /// file system access.
-    pub(crate) isolated_op: IsolatedOp,
+    /// The configuration Miri was started with. We look up what Miri should do when an op
+    /// requires communicating with the host (see `MiriConfig::isolated_op_for`) in here.
+    pub(crate) config: MiriConfig,
+
+    /// Caches, for each item we looked at, whether it or one of its parent modules is marked
+    /// `#[miri::allow(isolation)]`.
+    pub(crate) isolation_exempt_items: RefCell<FxHashMap<DefId, bool>>,
 
     /// Whether to enforce the validity invariant.
     pub(crate) validate: bool,
@@ -417,6 +454,9 @@ pub struct MiriMachine<'mir, 'tcx> {
     /// Crates which are considered local for the purposes of error reporting.
     pub(crate) local_crates: Vec<CrateNum>,
 
+    /// Crates in whose code we skip validity checking and Stacked Borrows retags.
+    pub(crate) relaxed_crates: FxHashSet<CrateNum>,
+
     /// Mapping extern static names to their base pointer.
     extern_statics: FxHashMap<Symbol, Pointer<Provenance>>,
 
@@ -424,6 +464,10 @@ pub struct MiriMachine<'mir, 'tcx> {
     /// Needs to be queried by ptr_to_int, hence needs interior mutability.
     pub(crate) rng: RefCell<StdRng>,
 
+    /// The seed of `rng`, also used to derive the per-thread RNGs that provide the randomness the
+    /// program asks for.
+    pub(crate) seed: u64,
+
     /// The allocation IDs to report when they are being allocated
     /// (helps for debugging memory leaks and use after free bugs).
     tracked_alloc_ids: FxHashSet<AllocId>,
@@ -434,17 +478,49 @@ pub struct MiriMachine<'mir, 'tcx> {
     /// Failure rate of compare_exchange_weak, between 0.0 and 1.0
     pub(crate) cmpxchg_weak_failure_rate: f64,
 
-    /// Corresponds to -Zmiri-mute-stdout-stderr and doesn't write the output but acts as if it succeeded.
-    pub(crate) mute_stdout_stderr: bool,
+    /// Failure rate of attempts to spawn a thread, between 0.0 and 1.0
+    pub(crate) spawn_failure_rate: f64,
+    /// The attempts to spawn a thread that fail, counting from 1.
+    pub(crate) spawn_failures: FxHashSet<u64>,
+    /// The number of attempts to spawn a thread so far.
+    pub(crate) spawn_attempts: u64,
+
+    /// The program's standard output and standard error, as configured by
+    /// -Zmiri-mute-stdout-stderr and the -Zmiri-redirect/tee flags.
+    pub(crate) stdout: OutputStream,
+    pub(crate) stderr: OutputStream,
 
     /// Whether weak memory emulation is enabled
     pub(crate) weak_memory: bool,
 
+    /// Whether to run pthread TLS destructors in a random order.
+    pub(crate) shuffle_tls_dtors: bool,
+
     /// The probability of the active thread being preempted at the end of each basic block.
     pub(crate) preemption_rate: f64,
 
     /// If `Some`, we will report the current stack every N basic blocks.
     pub(crate) report_progress: Option<u32>,
+    /// Whether to also print errors that stop the program as JSON events on stdout.
+    pub(crate) report_json: bool,
+    /// Whether to report errors the program can continue after and keep going.
+    pub(crate) continue_after_error: bool,
+    /// The errors we continued after, deduplicated by their kind and where they happened, with the
+    /// message of the first one and how often they occurred.
+    pub(crate) recovered_errors: RefCell<FxIndexMap<(&'static str, Span), (String, u64)>>,
+    /// Information about every live heap allocation, so that we can tell where memory that is
+    /// misused came from.
+    pub(crate) heap_allocs: RefCell<FxHashMap<AllocId, HeapAllocInfo>>,
+    /// The same information for the most recently deallocated heap allocations, oldest first.
+    pub(crate) freed_heap_allocs: RefCell<VecDeque<(AllocId, HeapAllocInfo)>>,
+    /// With `-Zmiri-shim-report`, how the program used each shim so far.
+    pub(crate) shim_usage: Option<RefCell<FxHashMap<Symbol, ShimUsage>>>,
+    /// The shim that is being executed, if any.
+    pub(crate) current_shim: Option<Symbol>,
+    /// Whether to report Stacked Borrows violations as warnings and continue.
+    pub(crate) warn_stacked_borrows: bool,
+    /// The errors we reported as warnings, deduplicated like `recovered_errors`.
+    pub(crate) reported_warnings: RefCell<FxHashSet<(&'static str, Span)>>,
     // The total number of blocks that have been executed.
     pub(crate) basic_block_count: u64,
 
@@ -460,17 +536,41 @@ pub struct MiriMachine<'mir, 'tcx> {
     pub(crate) since_gc: u32,
     /// The number of CPUs to be reported by miri.
     pub(crate) num_cpus: u32,
+    /// The page size (in bytes) to be reported by miri.
+    pub(crate) page_size: u64,
+    /// Whether the standard streams are reported to be terminals, overriding the host.
+    pub(crate) force_tty: Option<bool>,
+    /// Whether the program may spawn processes on the host.
+    pub(crate) process_passthrough: bool,
+    /// The host processes that the program spawned and has not waited for yet, indexed by their
+    /// process ID.
+    pub(crate) child_processes: FxHashMap<i32, std::process::Child>,
+    /// The handlers the program registered for signals.
+    pub(crate) signal_handlers: shims::unix::signal::SignalHandlers,
+    /// The `setjmp` calls the program made, indexed by the address of their `jmp_buf`.
+    pub(crate) setjmp_calls: FxHashMap<u64, SetjmpCall>,
 }
 
 impl<'mir, 'tcx> MiriMachine<'mir, 'tcx> {
     pub(crate) fn new(config: &MiriConfig, layout_cx: LayoutCx<'tcx, TyCtxt<'tcx>>) -> Self {
         let local_crates = helpers::get_local_crates(layout_cx.tcx);
+        let relaxed_crates = helpers::get_relaxed_crates(layout_cx.tcx, &local_crates, config);
         let layouts =
             PrimitiveLayouts::new(layout_cx).expect("Couldn't get layouts of primitive types");
         let profiler = config.measureme_out.as_ref().map(|out| {
             measureme::Profiler::new(out).expect("Couldn't create `measureme` profiler")
         });
-        let rng = StdRng::seed_from_u64(config.seed.unwrap_or(0));
+        let seed = config.seed.unwrap_or(0);
+        let rng = StdRng::seed_from_u64(seed);
+        let open_output = |stream, target: &OutputTarget| {
+            let target = if config.mute_stdout_stderr { &OutputTarget::Discard } else { target };
+            OutputStream::new(stream, target).unwrap_or_else(|err| {
+                let msg = format!("failed to open the output file for {stream:?}: {err}");
+                layout_cx.tcx.sess.fatal(msg)
+            })
+        };
+        let stdout = open_output(StdStream::Stdout, &config.stdout_target);
+        let stderr = open_output(StdStream::Stderr, &config.stderr_target);
         let stacked_borrows = config.stacked_borrows.then(|| {
             RefCell::new(stacked_borrows::GlobalStateInner::new(
                 config.tracked_pointer_tags.clone(),
@@ -483,17 +583,21 @@ pub(crate) fn new(config: &MiriConfig, layout_cx: LayoutCx<'tcx, TyCtxt<'tcx>>)
             tcx: layout_cx.tcx,
             stacked_borrows,
             data_race,
-            intptrcast: RefCell::new(intptrcast::GlobalStateInner::new(config)),
+            intptrcast: RefCell::new(intptrcast::GlobalStateInner::new(
+                config,
+                layout_cx.tcx.data_layout.pointer_size,
+            )),
             // `env_vars` depends on a full interpreter so we cannot properly initialize it yet.
             env_vars: EnvVars::default(),
             argc: None,
             argv: None,
             cmd_line: None,
             tls: TlsData::default(),
-            isolated_op: config.isolated_op,
+            config: config.clone(),
+            isolation_exempt_items: RefCell::new(FxHashMap::default()),
             validate: config.validate,
             enforce_abi: config.check_abi,
-            file_handler: FileHandler::new(config.mute_stdout_stderr),
+            file_handler: FileHandler::new(&stdout, &stderr),
             dir_handler: Default::default(),
             layouts,
             threads: ThreadManager::default(),
@@ -504,17 +608,33 @@ pub(crate) fn new(config: &MiriConfig, layout_cx: LayoutCx<'tcx, TyCtxt<'tcx>>)
             panic_on_unsupported: config.panic_on_unsupported,
             backtrace_style: config.backtrace_style,
             local_crates,
+            relaxed_crates,
             extern_statics: FxHashMap::default(),
             rng: RefCell::new(rng),
+            seed,
             tracked_alloc_ids: config.tracked_alloc_ids.clone(),
             check_alignment: config.check_alignment,
             cmpxchg_weak_failure_rate: config.cmpxchg_weak_failure_rate,
-            mute_stdout_stderr: config.mute_stdout_stderr,
+            spawn_failure_rate: config.spawn_failure_rate,
+            spawn_failures: config.spawn_failures.clone(),
+            spawn_attempts: 0,
+            stdout,
+            stderr,
             weak_memory: config.weak_memory_emulation,
+            shuffle_tls_dtors: config.shuffle_tls_dtors,
             preemption_rate: config.preemption_rate,
             report_progress: config.report_progress,
+            report_json: config.report_json,
+            continue_after_error: config.continue_after_error,
+            recovered_errors: RefCell::new(FxIndexMap::default()),
+            heap_allocs: RefCell::new(FxHashMap::default()),
+            freed_heap_allocs: RefCell::new(VecDeque::new()),
+            shim_usage: config.shim_report.then(|| RefCell::new(FxHashMap::default())),
+            current_shim: None,
+            warn_stacked_borrows: config.warn_stacked_borrows,
+            reported_warnings: RefCell::new(FxHashSet::default()),
             basic_block_count: 0,
-            clock: Clock::new(config.isolated_op == IsolatedOp::Allow),
+            clock: Clock::new(config.isolated_op_for(IsolationClass::Clock) == IsolatedOp::Allow),
             #[cfg(target_os = "linux")]
             external_so_lib: config.external_so_file.as_ref().map(|lib_file_path| {
                 let target_triple = layout_cx.tcx.sess.opts.target_triple.triple();
@@ -544,6 +664,12 @@ pub(crate) fn new(config: &MiriConfig, layout_cx: LayoutCx<'tcx, TyCtxt<'tcx>>)
             gc_interval: config.gc_interval,
             since_gc: 0,
             num_cpus: config.num_cpus,
+            page_size: config.page_size.unwrap_or(PAGE_SIZE),
+            force_tty: config.force_tty,
+            process_passthrough: config.process_passthrough,
+            child_processes: FxHashMap::default(),
+            signal_handlers: Default::default(),
+            setjmp_calls: FxHashMap::default(),
         }
     }
 
@@ -553,6 +679,7 @@ pub(crate) fn late_init(
     ) -> InterpResult<'tcx> {
         EnvVars::init(this, config)?;
         MiriMachine::init_extern_statics(this)?;
+        MiriMachine::init_configured_extern_statics(this, config)?;
         ThreadManager::init(this);
         Ok(())
     }
@@ -580,6 +707,13 @@ fn alloc_extern_static(
 
     /// Sets up the "extern statics" for this machine.
     fn init_extern_statics(this: &mut MiriInterpCx<'mir, 'tcx>) -> InterpResult<'tcx> {
+        // "__rust_alloc_error_handler_should_panic"
+        // rustc emits this next to `__rust_alloc_error_handler`, on all targets. The default
+        // allocation error hook in std reads it to decide whether to panic or to abort.
+        let should_panic = this.tcx.sess.opts.unstable_opts.oom.should_panic();
+        let val = ImmTy::from_uint(should_panic, this.machine.layouts.u8);
+        Self::alloc_extern_static(this, "__rust_alloc_error_handler_should_panic", val)?;
+
         match this.tcx.sess.target.os.as_ref() {
             "linux" => {
                 // "environ"
@@ -598,7 +732,7 @@ fn init_extern_statics(this: &mut MiriInterpCx<'mir, 'tcx>) -> InterpResult<'tcx
                     Self::alloc_extern_static(this, name, val)?;
                 }
             }
-            "freebsd" => {
+            "freebsd" | "wasi" => {
                 // "environ"
                 Self::add_extern_static(
                     this,
@@ -633,8 +767,93 @@ fn init_extern_statics(this: &mut MiriInterpCx<'mir, 'tcx>) -> InterpResult<'tcx
         Ok(())
     }
 
-    pub(crate) fn communicate(&self) -> bool {
-        self.isolated_op == IsolatedOp::Allow
+    /// Sets up the `extern` statics given by `-Zmiri-extern-static`. These take precedence over the
+    /// ones Miri provides by itself.
+    fn init_configured_extern_statics(
+        this: &mut MiriInterpCx<'mir, 'tcx>,
+        config: &MiriConfig,
+    ) -> InterpResult<'tcx> {
+        for (name, value) in &config.extern_statics {
+            let ptr = match value {
+                ExternStaticValue::Builtin(builtin) => {
+                    let builtin_ptr = this.machine.extern_statics.get(&Symbol::intern(builtin));
+                    let Some(&ptr) = builtin_ptr else {
+                        throw_unsup_format!(
+                            "`-Zmiri-extern-static` refers to the built-in extern static \
+                            `{builtin}`, which Miri does not provide on this target"
+                        );
+                    };
+                    ptr
+                }
+                ExternStaticValue::Zeroed | ExternStaticValue::Bytes(_) => {
+                    // We need to know how large the static is, so we look for its declaration. If
+                    // the program does not declare it, it cannot use it either.
+                    let Some(def_id) = Self::find_extern_static_decl(this, name) else {
+                        continue;
+                    };
+                    let layout = this.layout_of(this.tcx.type_of(def_id))?;
+                    let bytes = match value {
+                        ExternStaticValue::Bytes(bytes) => {
+                            if u64::try_from(bytes.len()).unwrap() != layout.size.bytes() {
+                                throw_unsup_format!(
+                                    "`-Zmiri-extern-static` gives {len} bytes for `extern` static \
+                                    `{name}`, but it has been declared with a size of {size} bytes",
+                                    len = bytes.len(),
+                                    size = layout.size.bytes(),
+                                );
+                            }
+                            bytes.clone()
+                        }
+                        _ => vec![0; usize::try_from(layout.size.bytes()).unwrap()],
+                    };
+                    let place = this.allocate(layout, MiriMemoryKind::ExternStatic.into())?;
+                    this.write_bytes_ptr(place.ptr, bytes)?;
+                    // This got just allocated, so there definitely is a pointer here.
+                    place.ptr.into_pointer_or_addr().unwrap()
+                }
+            };
+            this.machine.extern_statics.insert(Symbol::intern(name), ptr);
+        }
+        Ok(())
+    }
+
+    /// Finds a declaration of the `extern` static with the given link name in any crate.
+    fn find_extern_static_decl(this: &MiriInterpCx<'mir, 'tcx>, name: &str) -> Option<DefId> {
+        let tcx = this.tcx.tcx;
+        let name = Symbol::intern(name);
+        iter::once(LOCAL_CRATE)
+            .chain(tcx.crates(()).iter().copied())
+            .flat_map(|cnum| tcx.foreign_modules(cnum).values())
+            .flat_map(|module| module.foreign_items.iter().copied())
+            .find(|&def_id| {
+                matches!(tcx.def_kind(def_id), DefKind::Static(_))
+                    && this.item_link_name(def_id) == name
+            })
+    }
+
+    pub(crate) fn communicate(&self, class: IsolationClass) -> bool {
+        self.config.isolated_op_for(class) == IsolatedOp::Allow
+    }
+
+    /// Records a call to the shim for `link_name`, for `-Zmiri-shim-report`. `fallback` says how
+    /// the shim fell short if that was already clear before it ran.
+    pub(crate) fn record_shim_call(&self, link_name: Symbol, fallback: Option<&'static str>) {
+        if let Some(usage) = &self.shim_usage {
+            let mut usage = usage.borrow_mut();
+            let shim = usage.entry(link_name).or_default();
+            shim.calls += 1;
+            if let Some(what) = fallback {
+                *shim.fallbacks.entry(what).or_default() += 1;
+            }
+        }
+    }
+
+    /// Records that the shim that is being executed did not do what the real function does, but
+    /// fell back to `what`, for `-Zmiri-shim-report`.
+    pub(crate) fn record_shim_fallback(&self, what: &'static str) {
+        if let (Some(usage), Some(shim)) = (&self.shim_usage, self.current_shim) {
+            *usage.borrow_mut().entry(shim).or_default().fallbacks.entry(what).or_default() += 1;
+        }
     }
 
     /// Check whether the stack frame that this `FrameInfo` refers to is part of a local crate.
@@ -661,7 +880,8 @@ fn visit_tags(&self, visit: &mut dyn FnMut(SbTag)) {
             intptrcast,
             file_handler,
             tcx: _,
-            isolated_op: _,
+            config: _,
+            isolation_exempt_items: _,
             validate: _,
             enforce_abi: _,
             clock: _,
@@ -673,19 +893,41 @@ fn visit_tags(&self, visit: &mut dyn FnMut(SbTag)) {
             panic_on_unsupported: _,
             backtrace_style: _,
             local_crates: _,
+            relaxed_crates: _,
             rng: _,
+            seed: _,
             tracked_alloc_ids: _,
             check_alignment: _,
             cmpxchg_weak_failure_rate: _,
-            mute_stdout_stderr: _,
+            spawn_failure_rate: _,
+            spawn_failures: _,
+            spawn_attempts: _,
+            stdout: _,
+            stderr: _,
             weak_memory: _,
+            shuffle_tls_dtors: _,
             preemption_rate: _,
             report_progress: _,
+            report_json: _,
+            continue_after_error: _,
+            recovered_errors: _,
+            heap_allocs: _,
+            freed_heap_allocs: _,
+            shim_usage: _,
+            current_shim: _,
+            warn_stacked_borrows: _,
+            reported_warnings: _,
             basic_block_count: _,
             external_so_lib: _,
             gc_interval: _,
             since_gc: _,
             num_cpus: _,
+            page_size: _,
+            force_tty: _,
+            process_passthrough: _,
+            child_processes: _,
+            signal_handlers,
+            setjmp_calls: _,
         } = self;
 
         threads.visit_tags(visit);
@@ -693,6 +935,7 @@ fn visit_tags(&self, visit: &mut dyn FnMut(SbTag)) {
         env_vars.visit_tags(visit);
         dir_handler.visit_tags(visit);
         file_handler.visit_tags(visit);
+        signal_handlers.visit_tags(visit);
         data_race.visit_tags(visit);
         stacked_borrows.visit_tags(visit);
         intptrcast.visit_tags(visit);
@@ -756,7 +999,7 @@ fn use_addr_for_alignment_check(ecx: &MiriInterpCx<'mir, 'tcx>) -> bool {
 
     #[inline(always)]
     fn enforce_validity(ecx: &MiriInterpCx<'mir, 'tcx>) -> bool {
-        ecx.machine.validate
+        ecx.machine.validate && !ecx.in_relaxed_crate()
     }
 
     #[inline(always)]
@@ -816,9 +1059,52 @@ fn assert_panic(
         ecx.assert_panic(msg, unwind)
     }
 
+    fn abort(ecx: &mut MiriInterpCx<'mir, 'tcx>, msg: String) -> InterpResult<'tcx, !> {
+        let frame = ecx.frame();
+        let at_abort_terminator = frame.loc.map_or(false, |loc| {
+            matches!(
+                frame.body.basic_blocks[loc.block].terminator().kind,
+                mir::TerminatorKind::Abort
+            )
+        });
+        if !at_abort_terminator {
+            // This is one of the intrinsics that abort.
+            throw_machine_stop!(TerminationInfo::Abort(msg))
+        }
+        // The only way to reach an `Abort` terminator is a panic trying to unwind out of a
+        // function that cannot unwind, so we can tell the user which function that was.
+        let def_id = frame.instance.def_id();
+        let nounwind_abi = match ecx.tcx.def_kind(def_id) {
+            DefKind::Fn | DefKind::AssocFn =>
+                match ecx.tcx.fn_sig(def_id).abi() {
+                    abi @ (Abi::C { unwind: false }
+                    | Abi::System { unwind: false }
+                    | Abi::Cdecl { unwind: false }
+                    | Abi::Stdcall { unwind: false }
+                    | Abi::Fastcall { unwind: false }
+                    | Abi::Vectorcall { unwind: false }
+                    | Abi::Thiscall { unwind: false }
+                    | Abi::Aapcs { unwind: false }
+                    | Abi::Win64 { unwind: false }
+                    | Abi::SysV64 { unwind: false }) => Some(abi),
+                    _ => None,
+                },
+            _ => None,
+        };
+        throw_machine_stop!(TerminationInfo::UnwindAbort {
+            function: ecx.tcx.def_path_str(def_id),
+            nounwind_abi,
+        })
+    }
+
     #[inline(always)]
-    fn abort(_ecx: &mut MiriInterpCx<'mir, 'tcx>, msg: String) -> InterpResult<'tcx, !> {
-        throw_machine_stop!(TerminationInfo::Abort(msg))
+    fn eval_inline_asm(
+        ecx: &mut MiriInterpCx<'mir, 'tcx>,
+        template: &'tcx [InlineAsmTemplatePiece],
+        operands: &[mir::InlineAsmOperand<'tcx>],
+        options: InlineAsmOptions,
+    ) -> InterpResult<'tcx> {
+        ecx.emulate_inline_asm(template, operands, options)
     }
 
     #[inline(always)]
@@ -884,6 +1170,7 @@ fn adjust_allocation<'b>(
         kind: Option<MemoryKind<Self::MemoryKind>>,
     ) -> InterpResult<'tcx, Cow<'b, Allocation<Self::Provenance, Self::AllocExtra>>> {
         let kind = kind.expect("we set our STATIC_KIND so this cannot be None");
+        intptrcast::GlobalStateInner::check_alloc_fits(ecx, id, alloc.size(), alloc.align)?;
         if ecx.machine.tracked_alloc_ids.contains(&id) {
             ecx.emit_diagnostic(NonHaltingDiagnostic::CreatedAlloc(
                 id,
@@ -893,6 +1180,23 @@ fn adjust_allocation<'b>(
             ));
         }
 
+        if let MemoryKind::Machine(
+            kind @ (MiriMemoryKind::Rust
+            | MiriMemoryKind::Miri
+            | MiriMemoryKind::C
+            | MiriMemoryKind::WinHeap),
+        ) = kind
+        {
+            let info = HeapAllocInfo {
+                kind,
+                size: alloc.size(),
+                align: alloc.align,
+                allocated: ecx.machine.current_span().get(),
+                deallocated: None,
+            };
+            ecx.machine.heap_allocs.borrow_mut().insert(id, info);
+        }
+
         let alloc = alloc.into_owned();
         let stacks = ecx.machine.stacked_borrows.as_ref().map(|stacked_borrows| {
             Stacks::new_allocation(
@@ -1069,6 +1373,14 @@ fn before_memory_deallocation(
         if machine.tracked_alloc_ids.contains(&alloc_id) {
             machine.emit_diagnostic(NonHaltingDiagnostic::FreedAlloc(alloc_id));
         }
+        if let Some(mut info) = machine.heap_allocs.get_mut().remove(&alloc_id) {
+            info.deallocated = Some(machine.current_span().get());
+            let freed = machine.freed_heap_allocs.get_mut();
+            if freed.len() == FREED_HEAP_ALLOCS_LIMIT {
+                freed.pop_front();
+            }
+            freed.push_back((alloc_id, info));
+        }
         if let Some(data_race) = &mut alloc_extra.data_race {
             data_race.deallocate(
                 alloc_id,
@@ -1097,7 +1409,11 @@ fn retag(
         kind: mir::RetagKind,
         place: &PlaceTy<'tcx, Provenance>,
     ) -> InterpResult<'tcx> {
-        if ecx.machine.stacked_borrows.is_some() { ecx.retag(kind, place) } else { Ok(()) }
+        if ecx.machine.stacked_borrows.is_some() && !ecx.in_relaxed_crate() {
+            ecx.retag(kind, place)
+        } else {
+            Ok(())
+        }
     }
 
     #[inline(always)]
@@ -1122,10 +1438,19 @@ fn init_frame_extra(
 
         let stacked_borrows = ecx.machine.stacked_borrows.as_ref();
 
+        // Exemption from isolation is inherited by everything called from an exempt function,
+        // so we only need to look at the attributes of the function being called.
+        let isolation_exempt = ecx
+            .active_thread_stack()
+            .last()
+            .map_or(false, |caller| caller.extra.isolation_exempt)
+            || ecx.is_isolation_exempt(frame.instance.def_id())?;
+
         let extra = FrameData {
             stacked_borrows: stacked_borrows.map(|sb| sb.borrow_mut().new_frame(&ecx.machine)),
             catch_unwind: None,
             timing,
+            isolation_exempt,
         };
         Ok(frame.with_extra(extra))
     }
@@ -1144,6 +1469,7 @@ fn stack_mut<'a>(
 
     fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
         ecx.machine.basic_block_count += 1u64; // a u64 that is only incremented by 1 will "never" overflow
+        ecx.active_thread_mut().basic_block_count += 1;
         ecx.machine.since_gc += 1;
         // Possibly report our progress.
         if let Some(report_progress) = ecx.machine.report_progress {
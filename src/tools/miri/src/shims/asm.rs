@@ -0,0 +1,67 @@
+//! Inline assembly. We cannot execute machine instructions, but `asm!` blocks without any are
+//! common: they serve as compiler barriers or keep values alive, and only limit what the compiler
+//! may assume about the surrounding code. For those, we give the operands the effect they have
+//! when no instruction touches the registers.
+
+use rustc_ast::{InlineAsmOptions, InlineAsmTemplatePiece};
+use rustc_middle::mir;
+
+use crate::*;
+
+/// Whether an assembly template contains anything but whitespace and comments.
+fn contains_instructions(mut template: &str) -> bool {
+    loop {
+        template = template.trim_start();
+        if let Some(rest) = template.strip_prefix("/*") {
+            template = rest.split_once("*/").map_or("", |(_comment, rest)| rest);
+        } else if template.starts_with("//") || template.starts_with('#') {
+            template = template.split_once('\n').map_or("", |(_comment, rest)| rest);
+        } else {
+            return !template.is_empty();
+        }
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    fn emulate_inline_asm(
+        &mut self,
+        template: &'tcx [InlineAsmTemplatePiece],
+        operands: &[mir::InlineAsmOperand<'tcx>],
+        // Without instructions, a block that may access memory is just a compiler barrier, which
+        // does not affect execution. `noreturn` is checked by the caller.
+        _options: InlineAsmOptions,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+
+        // Placeholders are rendered as `{N}`, so they only go unnoticed inside comments.
+        if contains_instructions(&InlineAsmTemplatePiece::to_string(template)) {
+            throw_unsup_format!(
+                "inline assembly is only supported if it does not contain any instructions"
+            );
+        }
+
+        for operand in operands {
+            match operand {
+                mir::InlineAsmOperand::Out { place: Some(place), .. } => {
+                    // Nothing wrote the register, so we do not know what it contains.
+                    let place = this.eval_place(*place)?;
+                    this.write_uninit(&place)?;
+                }
+                mir::InlineAsmOperand::InOut { in_value, out_place: Some(out_place), .. } => {
+                    // The register still contains the input. Its type may differ from the output.
+                    let in_value = this.eval_operand(in_value, None)?;
+                    let out_place = this.eval_place(*out_place)?;
+                    this.copy_op(&in_value, &out_place, /*allow_transmute*/ true)?;
+                }
+                mir::InlineAsmOperand::In { .. }
+                | mir::InlineAsmOperand::Out { place: None, .. }
+                | mir::InlineAsmOperand::InOut { out_place: None, .. }
+                | mir::InlineAsmOperand::Const { .. }
+                | mir::InlineAsmOperand::SymFn { .. }
+                | mir::InlineAsmOperand::SymStatic { .. } => {}
+            }
+        }
+        Ok(())
+    }
+}
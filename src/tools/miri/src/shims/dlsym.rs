@@ -22,7 +22,7 @@ pub fn from_str<'tcx>(name: &[u8], target_os: &str) -> InterpResult<'tcx, Option
             target if target_os_is_unix(target) =>
                 unix::Dlsym::from_str(name, target)?.map(Dlsym::Posix),
             "windows" => windows::Dlsym::from_str(name)?.map(Dlsym::Windows),
-            os => bug!("dlsym not implemented for target_os {}", os),
+            os => throw_unsup_format!("`dlsym` is not supported on target OS `{}`", os),
         })
     }
 }
@@ -1,6 +1,7 @@
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::io::ErrorKind;
+use std::iter;
 use std::mem;
 
 use rustc_const_eval::interpret::Pointer;
@@ -8,9 +9,36 @@
 use rustc_middle::ty::layout::LayoutOf;
 use rustc_target::abi::Size;
 
-use crate::helpers::target_os_is_unix;
+use crate::helpers::target_os_has_posix_env;
 use crate::*;
 
+/// The process ID we report when isolation is enabled.
+const FAKE_PID: u32 = 1000;
+
+/// Returns the host's `(nodename, release, version)` as reported by `uname`.
+#[cfg(unix)]
+fn host_uname() -> Option<(String, String, String)> {
+    use std::ffi::CStr;
+
+    let mut buf = mem::MaybeUninit::<libc::utsname>::uninit();
+    // SAFETY: `uname` only writes to the buffer we pass in.
+    if unsafe { libc::uname(buf.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    // SAFETY: `uname` succeeded, so it initialized the buffer with nul-terminated strings.
+    let buf = unsafe { buf.assume_init() };
+    let field = |field: &[libc::c_char]| {
+        // SAFETY: see above.
+        unsafe { CStr::from_ptr(field.as_ptr()) }.to_string_lossy().into_owned()
+    };
+    Some((field(&buf.nodename), field(&buf.release), field(&buf.version)))
+}
+
+#[cfg(not(unix))]
+fn host_uname() -> Option<(String, String, String)> {
+    None
+}
+
 /// Check whether an operation that writes to a target buffer was successful.
 /// Accordingly select return value.
 /// Local helper function to be used in Windows shims.
@@ -26,6 +54,35 @@ fn windows_check_buffer_size((success, len): (bool, u64)) -> u32 {
     }
 }
 
+/// Checks whether the environment variable `name` matches `pattern`, in which `*` matches any
+/// sequence of characters and `?` matches any single character.
+fn env_var_matches(pattern: &str, name: &OsStr) -> bool {
+    fn glob_matches(pattern: &str, name: &str) -> bool {
+        let mut pattern_chars = pattern.chars();
+        match pattern_chars.next() {
+            None => name.is_empty(),
+            Some('*') => {
+                let rest = pattern_chars.as_str();
+                name.char_indices()
+                    .map(|(idx, _)| idx)
+                    .chain(iter::once(name.len()))
+                    .any(|idx| glob_matches(rest, &name[idx..]))
+            }
+            Some(pattern_char) => {
+                let mut name_chars = name.chars();
+                match name_chars.next() {
+                    Some(name_char) if pattern_char == '?' || pattern_char == name_char =>
+                        glob_matches(pattern_chars.as_str(), name_chars.as_str()),
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    // Names that are not valid UTF-8 can only be forwarded by disabling isolation.
+    name.to_str().map_or(false, |name| glob_matches(pattern, name))
+}
+
 #[derive(Default)]
 pub struct EnvVars<'tcx> {
     /// Stores pointers to the environment variables. These variables must be stored as
@@ -52,28 +109,25 @@ pub(crate) fn init<'mir>(
         ecx: &mut InterpCx<'mir, 'tcx, MiriMachine<'mir, 'tcx>>,
         config: &MiriConfig,
     ) -> InterpResult<'tcx> {
-        let target_os = ecx.tcx.sess.target.os.as_ref();
-
+        let forward_all =
+            ecx.machine.communicate(IsolationClass::Env) || config.forward_all_env_vars;
         // Skip the loop entirely if we don't want to forward anything.
-        if ecx.machine.communicate() || !config.forwarded_env_vars.is_empty() {
+        if forward_all || !config.forwarded_env_vars.is_empty() {
             for (name, value) in &config.env {
-                let forward = ecx.machine.communicate()
-                    || config.forwarded_env_vars.iter().any(|v| **v == *name);
+                let forward = (forward_all
+                    || config.forwarded_env_vars.iter().any(|pat| env_var_matches(pat, name)))
+                    && !config.excluded_env_vars.iter().any(|pat| env_var_matches(pat, name))
+                    && !config.synthetic_env_vars.iter().any(|(synthetic, _)| synthetic == name);
                 if forward {
-                    let var_ptr = match target_os {
-                        target if target_os_is_unix(target) =>
-                            alloc_env_var_as_c_str(name.as_ref(), value.as_ref(), ecx)?,
-                        "windows" => alloc_env_var_as_wide_str(name.as_ref(), value.as_ref(), ecx)?,
-                        unsupported =>
-                            throw_unsup_format!(
-                                "environment support for target OS `{}` not yet available",
-                                unsupported
-                            ),
-                    };
+                    let var_ptr = alloc_env_var(name, value, ecx)?;
                     ecx.machine.env_vars.map.insert(name.clone(), var_ptr);
                 }
             }
         }
+        for (name, value) in &config.synthetic_env_vars {
+            let var_ptr = alloc_env_var(name, value, ecx)?;
+            ecx.machine.env_vars.map.insert(name.clone(), var_ptr);
+        }
         ecx.update_environ()
     }
 
@@ -93,6 +147,23 @@ pub(crate) fn cleanup<'mir>(
     }
 }
 
+fn alloc_env_var<'mir, 'tcx>(
+    name: &OsStr,
+    value: &OsStr,
+    ecx: &mut InterpCx<'mir, 'tcx, MiriMachine<'mir, 'tcx>>,
+) -> InterpResult<'tcx, Pointer<Option<Provenance>>> {
+    match ecx.tcx.sess.target.os.as_ref() {
+        target_os if target_os_has_posix_env(target_os) =>
+            alloc_env_var_as_c_str(name, value, ecx),
+        "windows" => alloc_env_var_as_wide_str(name, value, ecx),
+        unsupported =>
+            throw_unsup_format!(
+                "environment support for target OS `{}` not yet available",
+                unsupported
+            ),
+    }
+}
+
 fn alloc_env_var_as_c_str<'mir, 'tcx>(
     name: &OsStr,
     value: &OsStr,
@@ -122,7 +193,7 @@ fn getenv(
         name_op: &OpTy<'tcx, Provenance>,
     ) -> InterpResult<'tcx, Pointer<Option<Provenance>>> {
         let this = self.eval_context_mut();
-        this.assert_target_os_is_unix("getenv");
+        this.assert_target_os_has_posix_env("getenv");
 
         let name_ptr = this.read_pointer(name_op)?;
         let name = this.read_os_str_from_c_str(name_ptr)?;
@@ -218,7 +289,7 @@ fn setenv(
         value_op: &OpTy<'tcx, Provenance>,
     ) -> InterpResult<'tcx, i32> {
         let this = self.eval_context_mut();
-        this.assert_target_os_is_unix("setenv");
+        this.assert_target_os_has_posix_env("setenv");
 
         let name_ptr = this.read_pointer(name_op)?;
         let value_ptr = this.read_pointer(value_op)?;
@@ -288,7 +359,7 @@ fn SetEnvironmentVariableW(
 
     fn unsetenv(&mut self, name_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i32> {
         let this = self.eval_context_mut();
-        this.assert_target_os_is_unix("unsetenv");
+        this.assert_target_os_has_posix_env("unsetenv");
 
         let name_ptr = this.read_pointer(name_op)?;
         let mut success = None;
@@ -318,12 +389,12 @@ fn getcwd(
         size_op: &OpTy<'tcx, Provenance>,
     ) -> InterpResult<'tcx, Pointer<Option<Provenance>>> {
         let this = self.eval_context_mut();
-        this.assert_target_os_is_unix("getcwd");
+        this.assert_target_os_has_posix_env("getcwd");
 
         let buf = this.read_pointer(buf_op)?;
         let size = this.read_scalar(size_op)?.to_machine_usize(&*this.tcx)?;
 
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::Env) {
             this.reject_in_isolation("`getcwd`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
             return Ok(Pointer::null());
@@ -356,7 +427,7 @@ fn GetCurrentDirectoryW(
         let size = u64::from(this.read_scalar(size_op)?.to_u32()?);
         let buf = this.read_pointer(buf_op)?;
 
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::Env) {
             this.reject_in_isolation("`GetCurrentDirectoryW`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
             return Ok(Scalar::from_u32(0));
@@ -375,11 +446,11 @@ fn GetCurrentDirectoryW(
 
     fn chdir(&mut self, path_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i32> {
         let this = self.eval_context_mut();
-        this.assert_target_os_is_unix("chdir");
+        this.assert_target_os_has_posix_env("chdir");
 
         let path = this.read_path_from_c_str(this.read_pointer(path_op)?)?;
 
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::Env) {
             this.reject_in_isolation("`chdir`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
 
@@ -407,7 +478,7 @@ fn SetCurrentDirectoryW(
 
         let path = this.read_path_from_wide_str(this.read_pointer(path_op)?)?;
 
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::Env) {
             this.reject_in_isolation("`SetCurrentDirectoryW`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
 
@@ -459,17 +530,79 @@ fn update_environ(&mut self) -> InterpResult<'tcx> {
         Ok(())
     }
 
+    /// The process ID reported to the program. Under isolation we make up a fixed one.
+    fn process_id(&self) -> u32 {
+        let this = self.eval_context_ref();
+        if this.communicate(IsolationClass::Env) { std::process::id() } else { FAKE_PID }
+    }
+
     fn getpid(&mut self) -> InterpResult<'tcx, i32> {
         let this = self.eval_context_mut();
         this.assert_target_os_is_unix("getpid");
 
-        this.check_no_isolation("`getpid`")?;
-
         // The reason we need to do this wacky of a conversion is because
         // `libc::getpid` returns an i32, however, `std::process::id()` return an u32.
         // So we un-do the conversion that stdlib does and turn it back into an i32.
         #[allow(clippy::cast_possible_wrap)]
-        Ok(std::process::id() as i32)
+        Ok(this.process_id() as i32)
+    }
+
+    fn linux_gettid(&mut self) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_ref();
+        this.assert_target_os("linux", "gettid");
+
+        // Like on a real Linux system, the main thread's TID is the PID, and the other threads'
+        // TIDs follow. We base them on our `ThreadId`s so they are unique and deterministic.
+        let tid = this.process_id().wrapping_add(this.get_active_thread().to_u32());
+        #[allow(clippy::cast_possible_wrap)]
+        Ok(tid as i32)
+    }
+
+    fn uname(&mut self, buf_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+        this.assert_target_os_is_unix("uname");
+
+        let buf = this.deref_operand(buf_op)?;
+
+        let target_os = &*this.tcx.sess.target.os;
+        let sysname = match target_os {
+            "linux" | "android" => "Linux",
+            "macos" => "Darwin",
+            "freebsd" => "FreeBSD",
+            _ => throw_unsup_format!("`uname` is not supported on {target_os}"),
+        };
+        let machine = match (target_os, &*this.tcx.sess.target.arch) {
+            ("macos", "aarch64") => "arm64",
+            (_, arch) => arch,
+        };
+        // The remaining fields describe the host, so we only report them without isolation.
+        let host = if this.communicate(IsolationClass::Env) { host_uname() } else { None };
+        let (nodename, release, version) = host.unwrap_or_else(|| {
+            ("miri".to_owned(), "0.0.0".to_owned(), "#1 Miri".to_owned())
+        });
+
+        let mut fields = vec![
+            ("sysname", sysname.to_owned()),
+            ("nodename", nodename),
+            ("release", release),
+            ("version", version),
+            ("machine", machine.to_owned()),
+        ];
+        if matches!(target_os, "linux" | "android") {
+            fields.push(("domainname", "(none)".to_owned()));
+        }
+        for (name, value) in fields {
+            let field = this.mplace_field_named(&buf, name)?;
+            let size = field.layout.size.bytes();
+            let (written, _) = this.write_os_str_to_c_str(OsStr::new(&value), field.ptr, size)?;
+            if !written {
+                // Overly long values are truncated, which is fine for these informative strings.
+                let len = usize::try_from(size.checked_sub(1).unwrap()).unwrap();
+                this.write_c_str(&value.as_bytes()[..len], field.ptr, size)?;
+            }
+        }
+
+        Ok(0)
     }
 
     #[allow(non_snake_case)]
@@ -477,8 +610,6 @@ fn GetCurrentProcessId(&mut self) -> InterpResult<'tcx, u32> {
         let this = self.eval_context_mut();
         this.assert_target_os("windows", "GetCurrentProcessId");
 
-        this.check_no_isolation("`GetCurrentProcessId`")?;
-
-        Ok(std::process::id())
+        Ok(this.process_id())
     }
 }
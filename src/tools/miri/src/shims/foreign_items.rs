@@ -1,4 +1,4 @@
-use std::{collections::hash_map::Entry, io::Write, iter};
+use std::{collections::hash_map::Entry, iter};
 
 use log::trace;
 
@@ -6,23 +6,20 @@
 use rustc_ast::expand::allocator::AllocatorKind;
 use rustc_hir::{
     def::DefKind,
-    def_id::{CrateNum, DefId, LOCAL_CRATE},
-};
-use rustc_middle::middle::{
-    codegen_fn_attrs::CodegenFnAttrFlags, dependency_format::Linkage,
-    exported_symbols::ExportedSymbol,
+    def_id::{CrateNum, DefId},
 };
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir;
 use rustc_middle::ty;
-use rustc_session::config::CrateType;
-use rustc_span::Symbol;
+use rustc_span::{sym, Symbol};
 use rustc_target::{
     abi::{Align, Size},
-    spec::abi::Abi,
+    spec::{abi::Abi, PanicStrategy},
 };
 
 use super::backtrace::EvalContextExt as _;
-use crate::helpers::{convert::Truncate, target_os_is_unix};
+use super::unix::signal::EvalContextExt as _;
+use crate::helpers::{check_arg_count, convert::Truncate, iter_exported_symbols, target_os_is_unix};
 use crate::*;
 
 /// Returned by `emulate_foreign_item_by_name`.
@@ -44,10 +41,16 @@ fn min_align(&self, size: u64, kind: MiriMemoryKind) -> Align {
         let this = self.eval_context_ref();
         // List taken from `library/std/src/sys/common/alloc.rs`.
         // This list should be kept in sync with the one from libstd.
-        let min_align = match this.tcx.sess.target.arch.as_ref() {
-            "x86" | "arm" | "mips" | "powerpc" | "powerpc64" | "asmjs" | "wasm32" => 8,
-            "x86_64" | "aarch64" | "mips64" | "s390x" | "sparc64" => 16,
-            arch => bug!("Unsupported target architecture: {}", arch),
+        let target = &this.tcx.sess.target;
+        let min_align = match target.arch.as_ref() {
+            "riscv32" | "xtensa" if target.os == "espidf" => 4,
+            "x86" | "arm" | "mips" | "powerpc" | "powerpc64" | "sparc" | "asmjs" | "wasm32"
+            | "hexagon" | "riscv32" | "xtensa" => 8,
+            "x86_64" | "aarch64" | "mips64" | "s390x" | "sparc64" | "riscv64" | "wasm64" => 16,
+            // libstd does not support allocation on the remaining targets (e.g. AVR or MSP430),
+            // so there is nothing to be in sync with. Use the pointer size like most C
+            // allocators for these targets do.
+            _ => this.pointer_size().bytes(),
         };
         // Windows always aligns, even small allocations.
         // Source: <https://support.microsoft.com/en-us/help/286470/how-to-use-pageheap-exe-in-windows-xp-windows-2000-and-windows-server>
@@ -100,11 +103,85 @@ fn free(
     ) -> InterpResult<'tcx> {
         let this = self.eval_context_mut();
         if !this.ptr_is_null(ptr)? {
+            this.check_heap_dealloc(ptr, kind, None)?;
             this.deallocate_ptr(ptr, None, kind.into())?;
         }
         Ok(())
     }
 
+    /// Returns the number of bytes that can be used through `ptr`, which must point to the start
+    /// of a live allocation of the given kind. This is the size that was requested from the
+    /// allocator: any further bytes would be out of bounds for Miri.
+    fn malloc_size(
+        &self,
+        ptr: Pointer<Option<Provenance>>,
+        kind: MiriMemoryKind,
+        name: &str,
+    ) -> InterpResult<'tcx, u64> {
+        let this = self.eval_context_ref();
+        let (alloc_id, offset, _) = this.ptr_get_alloc_id(ptr)?;
+        let alloc_kind = this.memory.alloc_map().get(alloc_id).map(|(kind, _)| *kind);
+        if offset != Size::ZERO || alloc_kind != Some(kind.into()) {
+            throw_ub_format!(
+                "`{name}` called on a pointer that does not point to the start of a live {kind} allocation"
+            );
+        }
+        Ok(this.get_alloc_info(alloc_id).0.bytes())
+    }
+
+    /// Checks that `ptr` may be deallocated (or reallocated) by the given allocator, with the
+    /// given layout if the allocator takes one. This catches the same mistakes as
+    /// `deallocate_ptr`, but the error also points to where the memory was allocated and, if it
+    /// was already deallocated, where that happened.
+    fn check_heap_dealloc(
+        &self,
+        ptr: Pointer<Option<Provenance>>,
+        kind: MiriMemoryKind,
+        layout: Option<(Size, Align)>,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_ref();
+        let Ok((alloc_id, offset, _)) = this.ptr_try_get_alloc_id(ptr) else {
+            return Ok(());
+        };
+        let heap_allocs = this.machine.heap_allocs.borrow();
+        let freed_heap_allocs = this.machine.freed_heap_allocs.borrow();
+        let Some(info) = heap_allocs.get(&alloc_id).or_else(|| {
+            freed_heap_allocs.iter().find(|(id, _)| *id == alloc_id).map(|(_, info)| info)
+        }) else {
+            // Not heap memory, or freed too long ago; `deallocate_ptr` will tell what is wrong
+            // with it.
+            return Ok(());
+        };
+        let msg = if info.deallocated.is_some() {
+            format!("deallocating {alloc_id:?}, which was already deallocated")
+        } else if offset != Size::ZERO {
+            // The generic error already shows the offending pointer.
+            return Ok(());
+        } else if info.kind != kind {
+            format!(
+                "deallocating {alloc_id:?}, which is {} memory, using {kind} deallocation operation",
+                info.kind
+            )
+        } else if layout.map_or(false, |layout| layout != (info.size, info.align)) {
+            let (size, align) = layout.unwrap();
+            format!(
+                "incorrect layout on deallocation: {alloc_id:?} has size {} and alignment {}, but gave size {} and alignment {}",
+                info.size.bytes(),
+                info.align.bytes(),
+                size.bytes(),
+                align.bytes(),
+            )
+        } else {
+            return Ok(());
+        };
+        throw_machine_stop!(TerminationInfo::HeapMisuse {
+            msg,
+            alloc_id,
+            allocated: (info.allocated.data(), info.kind),
+            deallocated: info.deallocated.map(|span| span.data()),
+        })
+    }
+
     fn realloc(
         &mut self,
         old_ptr: Pointer<Option<Provenance>>,
@@ -122,6 +199,7 @@ fn realloc(
                 Ok(new_ptr.into())
             }
         } else {
+            this.check_heap_dealloc(old_ptr, kind, None)?;
             if new_size == 0 {
                 this.deallocate_ptr(old_ptr, None, kind.into())?;
                 Ok(Pointer::null())
@@ -154,74 +232,48 @@ fn lookup_exported_symbol(
             Entry::Vacant(e) => {
                 // Find it if it was not cached.
                 let mut instance_and_crate: Option<(ty::Instance<'_>, CrateNum)> = None;
-                // `dependency_formats` includes all the transitive informations needed to link a crate,
-                // which is what we need here since we need to dig out `exported_symbols` from all transitive
-                // dependencies.
-                let dependency_formats = tcx.dependency_formats(());
-                let dependency_format = dependency_formats
-                    .iter()
-                    .find(|(crate_type, _)| *crate_type == CrateType::Executable)
-                    .expect("interpreting a non-executable crate");
-                for cnum in iter::once(LOCAL_CRATE).chain(
-                    dependency_format.1.iter().enumerate().filter_map(|(num, &linkage)| {
-                        // We add 1 to the number because that's what rustc also does everywhere it
-                        // calls `CrateNum::new`...
-                        #[allow(clippy::integer_arithmetic)]
-                        (linkage != Linkage::NotLinked).then_some(CrateNum::new(num + 1))
-                    }),
-                ) {
-                    // We can ignore `_export_info` here: we are a Rust crate, and everything is exported
-                    // from a Rust crate.
-                    for &(symbol, _export_info) in tcx.exported_symbols(cnum) {
-                        if let ExportedSymbol::NonGeneric(def_id) = symbol {
-                            let attrs = tcx.codegen_fn_attrs(def_id);
-                            let symbol_name = if let Some(export_name) = attrs.export_name {
-                                export_name
-                            } else if attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE) {
-                                tcx.item_name(def_id)
+                iter_exported_symbols(tcx, |cnum, def_id| {
+                    let attrs = tcx.codegen_fn_attrs(def_id);
+                    let symbol_name = if let Some(export_name) = attrs.export_name {
+                        export_name
+                    } else if attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE) {
+                        tcx.item_name(def_id)
+                    } else {
+                        // Skip over items without an explicitly defined symbol name.
+                        return Ok(());
+                    };
+                    if symbol_name == link_name {
+                        if let Some((original_instance, original_cnum)) = instance_and_crate {
+                            // Make sure we are consistent wrt what is 'first' and 'second'.
+                            let original_span = tcx.def_span(original_instance.def_id()).data();
+                            let span = tcx.def_span(def_id).data();
+                            if original_span < span {
+                                throw_machine_stop!(TerminationInfo::MultipleSymbolDefinitions {
+                                    link_name,
+                                    first: original_span,
+                                    first_crate: tcx.crate_name(original_cnum),
+                                    second: span,
+                                    second_crate: tcx.crate_name(cnum),
+                                });
                             } else {
-                                // Skip over items without an explicitly defined symbol name.
-                                continue;
-                            };
-                            if symbol_name == link_name {
-                                if let Some((original_instance, original_cnum)) = instance_and_crate
-                                {
-                                    // Make sure we are consistent wrt what is 'first' and 'second'.
-                                    let original_span =
-                                        tcx.def_span(original_instance.def_id()).data();
-                                    let span = tcx.def_span(def_id).data();
-                                    if original_span < span {
-                                        throw_machine_stop!(
-                                            TerminationInfo::MultipleSymbolDefinitions {
-                                                link_name,
-                                                first: original_span,
-                                                first_crate: tcx.crate_name(original_cnum),
-                                                second: span,
-                                                second_crate: tcx.crate_name(cnum),
-                                            }
-                                        );
-                                    } else {
-                                        throw_machine_stop!(
-                                            TerminationInfo::MultipleSymbolDefinitions {
-                                                link_name,
-                                                first: span,
-                                                first_crate: tcx.crate_name(cnum),
-                                                second: original_span,
-                                                second_crate: tcx.crate_name(original_cnum),
-                                            }
-                                        );
-                                    }
-                                }
-                                if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) {
-                                    throw_ub_format!(
-                                        "attempt to call an exported symbol that is not defined as a function"
-                                    );
-                                }
-                                instance_and_crate = Some((ty::Instance::mono(tcx, def_id), cnum));
+                                throw_machine_stop!(TerminationInfo::MultipleSymbolDefinitions {
+                                    link_name,
+                                    first: span,
+                                    first_crate: tcx.crate_name(cnum),
+                                    second: original_span,
+                                    second_crate: tcx.crate_name(original_cnum),
+                                });
                             }
                         }
+                        if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) {
+                            throw_ub_format!(
+                                "attempt to call an exported symbol that is not defined as a function"
+                            );
+                        }
+                        instance_and_crate = Some((ty::Instance::mono(tcx, def_id), cnum));
                     }
-                }
+                    Ok(())
+                })?;
 
                 e.insert(instance_and_crate.map(|ic| ic.0))
             }
@@ -251,6 +303,25 @@ fn emulate_foreign_item(
         let link_name = this.item_link_name(def_id);
         let tcx = this.tcx.tcx;
 
+        // Non-local jumps never return, but they are not always declared as diverging.
+        if matches!(link_name.as_str(), "longjmp" | "_longjmp" | "siglongjmp") {
+            let [env, _val] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+            this.longjmp(link_name, env)?;
+        }
+
+        // With `-C panic=abort`, the panic runtime just aborts. We stop before it does, so that we
+        // can point at the panic instead of at the internals of `panic_abort`.
+        if link_name.as_str() == "__rust_start_panic"
+            && tcx.sess.panic_strategy() == PanicStrategy::Abort
+        {
+            // The panic runtime defines this symbol, so we do not use `check_shim`.
+            this.check_abi(abi, Abi::Rust)?;
+            let [_payload] = check_arg_count(args)?;
+            let span = this.machine.current_span().get();
+            let location = (!span.is_dummy()).then(|| span.data());
+            throw_machine_stop!(TerminationInfo::PanicAbort { location });
+        }
+
         // First: functions that diverge.
         let ret = match ret {
             None =>
@@ -274,6 +345,23 @@ fn emulate_foreign_item(
                             panic_impl_instance,
                         )));
                     }
+                    // rustc generates this to call the `#[alloc_error_handler]` if there is one, or
+                    // the default implementation in `alloc` otherwise.
+                    "__rust_alloc_error_handler" => {
+                        // As for `panic_impl`, argument count checking happens in the handler.
+                        this.check_abi_and_shim_symbol_clash(abi, Abi::Rust, link_name)?;
+                        let Some(handler_kind) = tcx.alloc_error_handler_kind(()) else {
+                            // In real code, this symbol does not exist without an allocator.
+                            throw_unsup_format!(
+                                "can't call (diverging) foreign function: {link_name}"
+                            );
+                        };
+                        let handler = Symbol::intern(&handler_kind.fn_name(sym::oom));
+                        let body = this
+                            .lookup_exported_symbol(handler)?
+                            .expect("the alloc error handler should be present with an allocator");
+                        return Ok(Some(body));
+                    }
                     #[rustfmt::skip]
                     | "exit"
                     | "ExitProcess"
@@ -290,14 +378,13 @@ fn emulate_foreign_item(
                     }
                     "abort" => {
                         let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
-                        throw_machine_stop!(TerminationInfo::Abort(
-                            "the program aborted execution".to_owned()
-                        ))
+                        throw_machine_stop!(TerminationInfo::Abort(this.abort_message("`abort`")))
                     }
                     _ => {
                         if let Some(body) = this.lookup_exported_symbol(link_name)? {
                             return Ok(Some(body));
                         }
+                        this.machine.record_shim_call(link_name, Some("not supported by Miri"));
                         this.handle_unsupported(format!(
                             "can't call (diverging) foreign function: {}",
                             link_name
@@ -309,10 +396,19 @@ fn emulate_foreign_item(
         };
 
         // Second: functions that return immediately.
-        match this.emulate_foreign_item_by_name(link_name, abi, args, dest)? {
+        this.machine.current_shim = Some(link_name);
+        let res = this.emulate_foreign_item_by_name(link_name, abi, args, dest)?;
+        if matches!(res, EmulateByNameResult::NeedsJumping | EmulateByNameResult::AlreadyJumped) {
+            this.machine.record_shim_call(link_name, None);
+        }
+        this.machine.current_shim = None;
+        match res {
             EmulateByNameResult::NeedsJumping => {
                 trace!("{:?}", this.dump_place(**dest));
-                this.go_to_block(ret);
+                // If the function sent a signal to the program, its handler runs before we return.
+                if !this.deliver_pending_signal(ret)? {
+                    this.go_to_block(ret);
+                }
             }
             EmulateByNameResult::AlreadyJumped => (),
             EmulateByNameResult::MirBody(mir, instance) => return Ok(Some((mir, instance))),
@@ -321,6 +417,7 @@ fn emulate_foreign_item(
                     return Ok(Some(body));
                 }
 
+                this.machine.record_shim_call(link_name, Some("not supported by Miri"));
                 this.handle_unsupported(format!("can't call foreign function: {link_name}"))?;
                 return Ok(None);
             }
@@ -475,10 +572,10 @@ fn emulate_foreign_item_by_name(
                 let len = len.to_machine_usize(this)?;
                 let msg = this.read_bytes_ptr_strip_provenance(ptr, Size::from_bytes(len))?;
 
-                // Note: we're ignoring errors writing to host stdout/stderr.
+                // Note: we're ignoring errors writing to stdout/stderr.
                 let _ignore = match link_name.as_str() {
-                    "miri_write_to_stdout" => std::io::stdout().write_all(msg),
-                    "miri_write_to_stderr" => std::io::stderr().write_all(msg),
+                    "miri_write_to_stdout" => this.machine.stdout.write(msg),
+                    "miri_write_to_stderr" => this.machine.stderr.write(msg),
                     _ => unreachable!(),
                 };
             }
@@ -578,11 +675,9 @@ fn emulate_foreign_item_by_name(
                     };
 
                     // No need to check old_size/align; we anyway check that they match the allocation.
-                    this.deallocate_ptr(
-                        ptr,
-                        Some((Size::from_bytes(old_size), Align::from_bytes(align).unwrap())),
-                        memory_kind.into(),
-                    )
+                    let layout = (Size::from_bytes(old_size), Align::from_bytes(align).unwrap());
+                    this.check_heap_dealloc(ptr, memory_kind, Some(layout))?;
+                    this.deallocate_ptr(ptr, Some(layout), memory_kind.into())
                 };
 
                 match link_name.as_str() {
@@ -606,9 +701,11 @@ fn emulate_foreign_item_by_name(
                     Self::check_alloc_request(new_size, align)?;
 
                     let align = Align::from_bytes(align).unwrap();
+                    let old_layout = (Size::from_bytes(old_size), align);
+                    this.check_heap_dealloc(ptr, MiriMemoryKind::Rust, Some(old_layout))?;
                     let new_ptr = this.reallocate_ptr(
                         ptr,
-                        Some((Size::from_bytes(old_size), align)),
+                        Some(old_layout),
                         Size::from_bytes(new_size),
                         align,
                         MiriMemoryKind::Rust.into(),
@@ -831,9 +928,8 @@ fn emulate_foreign_item_by_name(
                 let sum_field = this.place_field(dest, 1)?;
                 this.write_scalar(Scalar::from_u64(sum), &sum_field)?;
             }
-            "llvm.x86.sse2.pause" if this.tcx.sess.target.arch == "x86" || this.tcx.sess.target.arch == "x86_64" => {
-                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
-                this.yield_active_thread();
+            name if name.starts_with("llvm.x86.sse2.") && (this.tcx.sess.target.arch == "x86" || this.tcx.sess.target.arch == "x86_64") => {
+                return shims::x86::sse2::EvalContextExt::emulate_x86_sse2_intrinsic(this, link_name, abi, args, dest);
             }
             "llvm.aarch64.isb" if this.tcx.sess.target.arch == "aarch64" => {
                 let [arg] = this.check_shim(abi, Abi::Unadjusted, link_name, args)?;
@@ -852,6 +948,7 @@ fn emulate_foreign_item_by_name(
             _ => match this.tcx.sess.target.os.as_ref() {
                 target if target_os_is_unix(target) => return shims::unix::foreign_items::EvalContextExt::emulate_foreign_item_by_name(this, link_name, abi, args, dest),
                 "windows" => return shims::windows::foreign_items::EvalContextExt::emulate_foreign_item_by_name(this, link_name, abi, args, dest),
+                "wasi" => return shims::wasi::foreign_items::EvalContextExt::emulate_foreign_item_by_name(this, link_name, abi, args, dest),
                 target => throw_unsup_format!("the target `{}` is not supported", target),
             }
         };
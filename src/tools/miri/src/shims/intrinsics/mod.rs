@@ -11,6 +11,7 @@
     mir,
     ty::{self, FloatTy, Ty},
 };
+use rustc_span::sym;
 use rustc_target::abi::{Integer, Size};
 
 use crate::*;
@@ -30,6 +31,11 @@ fn call_intrinsic(
     ) -> InterpResult<'tcx> {
         let this = self.eval_context_mut();
 
+        // The core engine would handle this, but we can tell the user why the program aborted.
+        if this.tcx.item_name(instance.def_id()) == sym::abort {
+            throw_machine_stop!(TerminationInfo::Abort(this.abort_message("the `abort` intrinsic")))
+        }
+
         // See if the core engine can handle this intrinsic.
         if this.emulate_intrinsic(instance, args, dest, ret)? {
             return Ok(());
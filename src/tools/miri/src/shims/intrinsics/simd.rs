@@ -24,7 +24,14 @@ fn emulate_simd_intrinsic(
             | "floor"
             | "round"
             | "trunc"
-            | "fsqrt" => {
+            | "fsqrt"
+            | "fsin"
+            | "fcos"
+            | "fexp"
+            | "fexp2"
+            | "flog"
+            | "flog10"
+            | "flog2" => {
                 let [op] = check_arg_count(args)?;
                 let (op, op_len) = this.operand_to_simd(op)?;
                 let (dest, dest_len) = this.place_to_simd(dest)?;
@@ -38,6 +45,13 @@ enum HostFloatOp {
                     Round,
                     Trunc,
                     Sqrt,
+                    Sin,
+                    Cos,
+                    Exp,
+                    Exp2,
+                    Log,
+                    Log10,
+                    Log2,
                 }
                 #[derive(Copy, Clone)]
                 enum Op {
@@ -53,6 +67,13 @@ enum Op {
                     "round" => Op::HostOp(HostFloatOp::Round),
                     "trunc" => Op::HostOp(HostFloatOp::Trunc),
                     "fsqrt" => Op::HostOp(HostFloatOp::Sqrt),
+                    "fsin" => Op::HostOp(HostFloatOp::Sin),
+                    "fcos" => Op::HostOp(HostFloatOp::Cos),
+                    "fexp" => Op::HostOp(HostFloatOp::Exp),
+                    "fexp2" => Op::HostOp(HostFloatOp::Exp2),
+                    "flog" => Op::HostOp(HostFloatOp::Log),
+                    "flog10" => Op::HostOp(HostFloatOp::Log10),
+                    "flog2" => Op::HostOp(HostFloatOp::Log2),
                     _ => unreachable!(),
                 };
 
@@ -86,6 +107,13 @@ enum Op {
                                         HostFloatOp::Round => f.round(),
                                         HostFloatOp::Trunc => f.trunc(),
                                         HostFloatOp::Sqrt => f.sqrt(),
+                                        HostFloatOp::Sin => f.sin(),
+                                        HostFloatOp::Cos => f.cos(),
+                                        HostFloatOp::Exp => f.exp(),
+                                        HostFloatOp::Exp2 => f.exp2(),
+                                        HostFloatOp::Log => f.ln(),
+                                        HostFloatOp::Log10 => f.log10(),
+                                        HostFloatOp::Log2 => f.log2(),
                                     };
                                     Scalar::from_u32(res.to_bits())
                                 }
@@ -97,6 +125,13 @@ enum Op {
                                         HostFloatOp::Round => f.round(),
                                         HostFloatOp::Trunc => f.trunc(),
                                         HostFloatOp::Sqrt => f.sqrt(),
+                                        HostFloatOp::Sin => f.sin(),
+                                        HostFloatOp::Cos => f.cos(),
+                                        HostFloatOp::Exp => f.exp(),
+                                        HostFloatOp::Exp2 => f.exp2(),
+                                        HostFloatOp::Log => f.ln(),
+                                        HostFloatOp::Log10 => f.log10(),
+                                        HostFloatOp::Log2 => f.log2(),
                                     };
                                     Scalar::from_u64(res.to_bits())
                                 }
@@ -261,6 +296,69 @@ enum Op {
                     this.write_scalar(val, &dest.into())?;
                 }
             }
+            "fpow" => {
+                let [base, exp] = check_arg_count(args)?;
+                let (base, base_len) = this.operand_to_simd(base)?;
+                let (exp, exp_len) = this.operand_to_simd(exp)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(dest_len, base_len);
+                assert_eq!(dest_len, exp_len);
+
+                for i in 0..dest_len {
+                    let base = this.read_scalar(&this.mplace_index(&base, i)?.into())?;
+                    let exp = this.read_scalar(&this.mplace_index(&exp, i)?.into())?;
+                    let dest = this.mplace_index(&dest, i)?;
+
+                    // FIXME using host floats
+                    let ty::Float(float_ty) = dest.layout.ty.kind() else {
+                        span_bug!(this.cur_span(), "{} operand is not a float", intrinsic_name)
+                    };
+                    let val = match float_ty {
+                        FloatTy::F32 => {
+                            let base = f32::from_bits(base.to_u32()?);
+                            let exp = f32::from_bits(exp.to_u32()?);
+                            Scalar::from_u32(base.powf(exp).to_bits())
+                        }
+                        FloatTy::F64 => {
+                            let base = f64::from_bits(base.to_u64()?);
+                            let exp = f64::from_bits(exp.to_u64()?);
+                            Scalar::from_u64(base.powf(exp).to_bits())
+                        }
+                    };
+                    this.write_scalar(val, &dest.into())?;
+                }
+            }
+            "fpowi" => {
+                let [base, exp] = check_arg_count(args)?;
+                let (base, base_len) = this.operand_to_simd(base)?;
+                // The exponent is a single `i32` for all lanes.
+                let exp = this.read_scalar(exp)?.to_i32()?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(dest_len, base_len);
+
+                for i in 0..dest_len {
+                    let base = this.read_scalar(&this.mplace_index(&base, i)?.into())?;
+                    let dest = this.mplace_index(&dest, i)?;
+
+                    // FIXME using host floats
+                    let ty::Float(float_ty) = dest.layout.ty.kind() else {
+                        span_bug!(this.cur_span(), "{} operand is not a float", intrinsic_name)
+                    };
+                    let val = match float_ty {
+                        FloatTy::F32 => {
+                            let base = f32::from_bits(base.to_u32()?);
+                            Scalar::from_u32(base.powi(exp).to_bits())
+                        }
+                        FloatTy::F64 => {
+                            let base = f64::from_bits(base.to_u64()?);
+                            Scalar::from_u64(base.powi(exp).to_bits())
+                        }
+                    };
+                    this.write_scalar(val, &dest.into())?;
+                }
+            }
             #[rustfmt::skip]
             | "reduce_and"
             | "reduce_or"
@@ -268,7 +366,11 @@ enum Op {
             | "reduce_any"
             | "reduce_all"
             | "reduce_max"
-            | "reduce_min" => {
+            | "reduce_min"
+            | "reduce_max_nanless"
+            | "reduce_min_nanless"
+            | "reduce_add_unordered"
+            | "reduce_mul_unordered" => {
                 use mir::BinOp;
 
                 let [op] = check_arg_count(args)?;
@@ -289,19 +391,32 @@ enum Op {
                     "reduce_xor" => Op::MirOp(BinOp::BitXor),
                     "reduce_any" => Op::MirOpBool(BinOp::BitOr),
                     "reduce_all" => Op::MirOpBool(BinOp::BitAnd),
-                    "reduce_max" => Op::Max,
-                    "reduce_min" => Op::Min,
+                    "reduce_max" | "reduce_max_nanless" => Op::Max,
+                    "reduce_min" | "reduce_min_nanless" => Op::Min,
+                    // We just add or multiply the lanes in order, which is one of the orders the
+                    // unordered reductions may use.
+                    "reduce_add_unordered" => Op::MirOp(BinOp::Add),
+                    "reduce_mul_unordered" => Op::MirOp(BinOp::Mul),
                     _ => unreachable!(),
                 };
+                // The `nanless` reductions may assume that no lane is NaN.
+                let check_nanless = |op: &ImmTy<'tcx, Provenance>, i: u64| -> InterpResult<'tcx> {
+                    if intrinsic_name.ends_with("_nanless") && float_is_nan(op)? {
+                        throw_ub_format!("`simd_{intrinsic_name}` encountered NaN in SIMD lane {i}");
+                    }
+                    Ok(())
+                };
 
                 // Initialize with first lane, then proceed with the rest.
                 let mut res = this.read_immediate(&this.mplace_index(&op, 0)?.into())?;
+                check_nanless(&res, 0)?;
                 if matches!(which, Op::MirOpBool(_)) {
                     // Convert to `bool` scalar.
                     res = imm_from_bool(simd_element_to_bool(res)?);
                 }
                 for i in 1..op_len {
                     let op = this.read_immediate(&this.mplace_index(&op, i)?.into())?;
+                    check_nanless(&op, i)?;
                     res = match which {
                         Op::MirOp(mir_op) => {
                             this.binary_op(mir_op, &res, &op)?
@@ -421,7 +536,11 @@ enum Op {
                 }
             }
             #[rustfmt::skip]
-            "cast" | "as" => {
+            | "cast"
+            | "as"
+            | "cast_ptr"
+            | "expose_addr"
+            | "from_exposed_addr" => {
                 let [op] = check_arg_count(args)?;
                 let (op, op_len) = this.operand_to_simd(op)?;
                 let (dest, dest_len) = this.place_to_simd(dest)?;
@@ -435,6 +554,13 @@ enum Op {
                     let dest = this.mplace_index(&dest, i)?;
 
                     let val = match (op.layout.ty.kind(), dest.layout.ty.kind()) {
+                        // Pointer casts, which have their own intrinsics
+                        (ty::RawPtr(..), ty::RawPtr(..)) if intrinsic_name == "cast_ptr" =>
+                            this.ptr_to_ptr(&op, dest.layout.ty)?,
+                        (ty::RawPtr(..), ty::Int(_) | ty::Uint(_)) if intrinsic_name == "expose_addr" =>
+                            this.pointer_expose_address_cast(&op, dest.layout.ty)?,
+                        (ty::Int(_) | ty::Uint(_), ty::RawPtr(..)) if intrinsic_name == "from_exposed_addr" =>
+                            this.pointer_from_exposed_address_cast(&op, dest.layout.ty)?,
                         // Int-to-(int|float): always safe
                         (ty::Int(_) | ty::Uint(_), ty::Int(_) | ty::Uint(_) | ty::Float(_)) =>
                             this.int_to_int_or_float(&op, dest.layout.ty)?,
@@ -459,7 +585,15 @@ enum Op {
                     this.write_immediate(val, &dest.into())?;
                 }
             }
-            "shuffle" => {
+            #[rustfmt::skip]
+            | "shuffle"
+            // The older intrinsics have the number of output lanes in their name.
+            | "shuffle2"
+            | "shuffle4"
+            | "shuffle8"
+            | "shuffle16"
+            | "shuffle32"
+            | "shuffle64" => {
                 let [left, right, index] = check_arg_count(args)?;
                 let (left, left_len) = this.operand_to_simd(left)?;
                 let (right, right_len) = this.operand_to_simd(right)?;
@@ -594,6 +728,18 @@ fn simd_bitmask_index(idx: u32, vec_len: u32, endianess: Endian) -> u32 {
     }
 }
 
+fn float_is_nan<'tcx>(op: &ImmTy<'tcx, Provenance>) -> InterpResult<'tcx, bool> {
+    let ty::Float(float_ty) = op.layout.ty.kind() else {
+        // Only floats can be NaN.
+        return Ok(false);
+    };
+    let op = op.to_scalar();
+    Ok(match float_ty {
+        FloatTy::F32 => op.to_f32()?.is_nan(),
+        FloatTy::F64 => op.to_f64()?.is_nan(),
+    })
+}
+
 fn fmax_op<'tcx>(
     left: &ImmTy<'tcx, Provenance>,
     right: &ImmTy<'tcx, Provenance>,
@@ -6,12 +6,17 @@
 pub mod foreign_items;
 pub mod intrinsics;
 pub mod unix;
+pub mod wasi;
 pub mod windows;
+pub mod x86;
 
+pub mod asm;
 pub mod dlsym;
 pub mod env;
 pub mod os_str;
+pub mod output;
 pub mod panic;
+pub mod setjmp;
 pub mod time;
 pub mod tls;
 
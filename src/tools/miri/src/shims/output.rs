@@ -0,0 +1,100 @@
+//! Handling of the interpreted program's standard output and standard error.
+
+use std::fs::File;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// Where the interpreted program's standard output or standard error should go.
+#[derive(Clone, Debug, Default)]
+pub enum OutputTarget {
+    /// Write to the corresponding stream of the host.
+    #[default]
+    Host,
+    /// Silently discard all output.
+    Discard,
+    /// Write to the given file instead of the host stream.
+    File(PathBuf),
+    /// Write to the given file *and* to the host stream.
+    Tee(PathBuf),
+    /// Append all output to the given buffer, so that it can be inspected after the fact. This is
+    /// how `-Zmiri-test-jobs` keeps the output of tests that run at the same time apart.
+    Capture(Arc<Mutex<Vec<u8>>>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StdStream {
+    Stdout,
+    Stderr,
+}
+
+/// An `OutputTarget` that is ready to be written to.
+#[derive(Clone, Debug)]
+pub struct OutputStream {
+    stream: StdStream,
+    /// Whether to forward the output to the host stream.
+    host: bool,
+    /// The file to write the output to, if any.
+    file: Option<Rc<File>>,
+    /// The buffer to append the output to, if any.
+    capture: Option<Arc<Mutex<Vec<u8>>>>,
+}
+
+impl OutputStream {
+    pub fn new(stream: StdStream, target: &OutputTarget) -> io::Result<Self> {
+        let mut res = OutputStream { stream, host: false, file: None, capture: None };
+        match target {
+            OutputTarget::Host => res.host = true,
+            OutputTarget::Discard => {}
+            OutputTarget::File(path) => res.file = Some(Rc::new(File::create(path)?)),
+            OutputTarget::Tee(path) => {
+                res.host = true;
+                res.file = Some(Rc::new(File::create(path)?));
+            }
+            OutputTarget::Capture(buffer) => res.capture = Some(Arc::clone(buffer)),
+        }
+        Ok(res)
+    }
+
+    pub fn stream(&self) -> StdStream {
+        self.stream
+    }
+
+    /// Whether this stream forwards to the host stream and nowhere else.
+    pub fn is_host_only(&self) -> bool {
+        self.host && self.file.is_none() && self.capture.is_none()
+    }
+
+    /// Writes all of `bytes` to this stream. We do not add any buffering, so that a `write` of the
+    /// interpreted program corresponds to a `write` on the host.
+    pub fn write(&self, bytes: &[u8]) -> io::Result<usize> {
+        if let Some(file) = &self.file {
+            (&**file).write_all(bytes)?;
+        }
+        if let Some(capture) = &self.capture {
+            capture.lock().unwrap().extend_from_slice(bytes);
+        }
+        if self.host {
+            match self.stream {
+                StdStream::Stdout => {
+                    let mut stdout = io::stdout();
+                    stdout.write_all(bytes)?;
+                    // Stdout is buffered, flush to make sure it appears on the screen.
+                    stdout.flush()?;
+                }
+                StdStream::Stderr => io::stderr().write_all(bytes)?,
+            }
+        }
+        Ok(bytes.len())
+    }
+
+    /// Whether this stream ends up on a host terminal.
+    pub fn is_tty(&self) -> bool {
+        self.host
+            && match self.stream {
+                StdStream::Stdout => io::stdout().is_terminal(),
+                StdStream::Stderr => io::stderr().is_terminal(),
+            }
+    }
+}
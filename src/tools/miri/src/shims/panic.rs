@@ -233,4 +233,17 @@ fn assert_panic(
         }
         Ok(())
     }
+
+    /// Describes why the program aborted when the active thread called `what`. The runtime aborts
+    /// when a thread panics while it is already unwinding, so we report that as the reason.
+    fn abort_message(&self, what: &str) -> String {
+        let this = self.eval_context_ref();
+        if this.active_thread_ref().panic_payload.is_some() {
+            format!(
+                "the program aborted execution because a thread panicked while it was already unwinding from a panic"
+            )
+        } else {
+            format!("the program aborted execution by calling {what}")
+        }
+    }
 }
@@ -0,0 +1,63 @@
+//! `setjmp` and `longjmp`. A non-local jump skips stack frames without unwinding them, which we
+//! cannot emulate. But `longjmp` is often only called on error paths, so `setjmp` just remembers
+//! where it was called and returns 0. That lets us explain what a `longjmp` would have skipped.
+
+use rustc_span::{SpanData, Symbol};
+
+use crate::*;
+
+/// A `setjmp` call that a `longjmp` could return to.
+#[derive(Debug)]
+pub struct SetjmpCall {
+    thread: ThreadId,
+    /// The number of frames on the stack of `thread` when `setjmp` was called.
+    depth: usize,
+    span: SpanData,
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    fn setjmp(&mut self, env_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let env = this.read_pointer(env_op)?;
+        let call = SetjmpCall {
+            thread: this.get_active_thread(),
+            depth: this.active_thread_stack().len(),
+            span: this.cur_span().data(),
+        };
+        this.machine.setjmp_calls.insert(env.addr().bytes(), call);
+        // This is the direct return, there will not be a second one.
+        Ok(0)
+    }
+
+    fn longjmp(
+        &mut self,
+        link_name: Symbol,
+        env_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, !> {
+        let this = self.eval_context_mut();
+
+        let env = this.read_pointer(env_op)?;
+        let stack = this.active_thread_stack();
+        let call = this.machine.setjmp_calls.get(&env.addr().bytes()).filter(|call| {
+            call.thread == this.get_active_thread() && call.depth <= stack.len()
+        });
+        let (target, skipped) = match call {
+            Some(call) => {
+                // The frame that called `setjmp` stays, everything above it would be skipped.
+                let skipped = stack[call.depth..]
+                    .iter()
+                    .rev()
+                    .map(|frame| {
+                        let def_id = frame.instance.def_id();
+                        (this.tcx.def_path_str(def_id), this.tcx.def_span(def_id).data())
+                    })
+                    .collect();
+                (Some(call.span), skipped)
+            }
+            None => (None, vec![]),
+        };
+        throw_machine_stop!(TerminationInfo::NonLocalJump { link_name, target, skipped })
+    }
+}
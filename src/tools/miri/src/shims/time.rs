@@ -1,5 +1,9 @@
 use std::time::{Duration, SystemTime};
 
+use rustc_middle::ty::layout::TyAndLayout;
+use rustc_target::abi::Size;
+
+use crate::clock::cpu_time_for_basic_blocks;
 use crate::concurrency::thread::MachineCallback;
 use crate::*;
 
@@ -9,6 +13,23 @@ pub fn system_time_to_duration<'tcx>(time: &SystemTime) -> InterpResult<'tcx, Du
         .map_err(|_| err_unsup_format!("times before the Unix epoch are not supported").into())
 }
 
+impl<'mir, 'tcx: 'mir> EvalContextExtPrivate<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+trait EvalContextExtPrivate<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Returns the time elapsed since the Unix epoch for the shim `name`, which reads the host's
+    /// real-time clock. If isolation rejects that, the made-up real time starts at the Unix epoch
+    /// when the program starts and then advances with the monotonic clock.
+    fn realtime_since_epoch(&self, name: &str) -> InterpResult<'tcx, Duration> {
+        let this = self.eval_context_ref();
+        match this.isolated_op(IsolationClass::Clock) {
+            IsolatedOp::Allow => system_time_to_duration(&SystemTime::now()),
+            IsolatedOp::Reject(reject_with) => {
+                this.fake_in_isolation(name, reject_with)?;
+                Ok(this.machine.clock.now().duration_since(this.machine.clock.anchor()))
+            }
+        }
+    }
+}
+
 impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
 pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
     fn clock_gettime(
@@ -35,14 +56,28 @@ fn clock_gettime(
         // The second kind is MONOTONIC clocks for which 0 is an arbitrary time point, but they are
         // never allowed to go backwards. We don't need to do any additonal monotonicity
         // enforcement because std::time::Instant already guarantees that it is monotonic.
-        let relative_clocks =
-            [this.eval_libc_i32("CLOCK_MONOTONIC")?, this.eval_libc_i32("CLOCK_MONOTONIC_COARSE")?];
+        // MONOTONIC_RAW is not subject to NTP adjustments, and BOOTTIME also counts the time the
+        // system was suspended; neither distinction is observable for the interpreted program.
+        let relative_clocks = [
+            this.eval_libc_i32("CLOCK_MONOTONIC")?,
+            this.eval_libc_i32("CLOCK_MONOTONIC_COARSE")?,
+            this.eval_libc_i32("CLOCK_MONOTONIC_RAW")?,
+            this.eval_libc_i32("CLOCK_BOOTTIME")?,
+        ];
+        // Finally, there are CPU-time clocks for the process and the calling thread. We have no
+        // meaningful notion of host CPU time for the interpreted program, so we derive them from
+        // the number of basic blocks executed so far; that also keeps them deterministic.
+        let process_cpu_clock = this.eval_libc_i32("CLOCK_PROCESS_CPUTIME_ID")?;
+        let thread_cpu_clock = this.eval_libc_i32("CLOCK_THREAD_CPUTIME_ID")?;
 
         let duration = if absolute_clocks.contains(&clk_id) {
-            this.check_no_isolation("`clock_gettime` with `REALTIME` clocks")?;
-            system_time_to_duration(&SystemTime::now())?
+            this.realtime_since_epoch("`clock_gettime` with `REALTIME` clocks")?
         } else if relative_clocks.contains(&clk_id) {
             this.machine.clock.now().duration_since(this.machine.clock.anchor())
+        } else if clk_id == process_cpu_clock {
+            cpu_time_for_basic_blocks(this.machine.basic_block_count)
+        } else if clk_id == thread_cpu_clock {
+            cpu_time_for_basic_blocks(this.active_thread_ref().basic_block_count)
         } else {
             let einval = this.eval_libc("EINVAL")?;
             this.set_last_error(einval)?;
@@ -65,7 +100,6 @@ fn gettimeofday(
         let this = self.eval_context_mut();
 
         this.assert_target_os_is_unix("gettimeofday");
-        this.check_no_isolation("`gettimeofday`")?;
 
         // Using tz is obsolete and should always be null
         let tz = this.read_pointer(tz_op)?;
@@ -75,7 +109,7 @@ fn gettimeofday(
             return Ok(-1);
         }
 
-        let duration = system_time_to_duration(&SystemTime::now())?;
+        let duration = this.realtime_since_epoch("`gettimeofday`")?;
         let tv_sec = duration.as_secs();
         let tv_usec = duration.subsec_micros();
 
@@ -84,15 +118,17 @@ fn gettimeofday(
         Ok(0)
     }
 
+    /// Implements both `GetSystemTimeAsFileTime` and `GetSystemTimePreciseAsFileTime`; we do not
+    /// distinguish between the two precisions.
     #[allow(non_snake_case, clippy::integer_arithmetic)]
     fn GetSystemTimeAsFileTime(
         &mut self,
+        shim_name: &str,
         LPFILETIME_op: &OpTy<'tcx, Provenance>,
     ) -> InterpResult<'tcx> {
         let this = self.eval_context_mut();
 
-        this.assert_target_os("windows", "GetSystemTimeAsFileTime");
-        this.check_no_isolation("`GetSystemTimeAsFileTime`")?;
+        this.assert_target_os("windows", shim_name);
 
         let NANOS_PER_SEC = this.eval_windows_u64("time", "NANOS_PER_SEC")?;
         let INTERVALS_PER_SEC = this.eval_windows_u64("time", "INTERVALS_PER_SEC")?;
@@ -100,7 +136,7 @@ fn GetSystemTimeAsFileTime(
         let NANOS_PER_INTERVAL = NANOS_PER_SEC / INTERVALS_PER_SEC;
         let SECONDS_TO_UNIX_EPOCH = INTERVALS_TO_UNIX_EPOCH / INTERVALS_PER_SEC;
 
-        let duration = system_time_to_duration(&SystemTime::now())?
+        let duration = this.realtime_since_epoch(&format!("`{shim_name}`"))?
             + Duration::from_secs(SECONDS_TO_UNIX_EPOCH);
         let duration_ticks = u64::try_from(duration.as_nanos() / u128::from(NANOS_PER_INTERVAL))
             .map_err(|_| err_unsup_format!("programs running more than 2^64 Windows ticks after the Windows epoch are not supported"))?;
@@ -158,10 +194,13 @@ fn QueryPerformanceFrequency(
         Ok(Scalar::from_i32(-1)) // Return non-zero on success
     }
 
-    fn mach_absolute_time(&self) -> InterpResult<'tcx, Scalar<Provenance>> {
+    /// Implements `mach_absolute_time` and its siblings. The "continuous" variants also count the
+    /// time the system was asleep, and the "approximate" variants trade precision for speed; none
+    /// of that is observable for the interpreted program, so they all share the same clock.
+    fn mach_absolute_time(&self, shim_name: &str) -> InterpResult<'tcx, Scalar<Provenance>> {
         let this = self.eval_context_ref();
 
-        this.assert_target_os("macos", "mach_absolute_time");
+        this.assert_target_os("macos", shim_name);
 
         // This returns a u64, with time units determined dynamically by `mach_timebase_info`.
         // We return plain nanoseconds.
@@ -215,6 +254,8 @@ fn nanosleep(
 
         let active_thread = this.get_active_thread();
         this.block_thread(active_thread);
+        // Even if the timeout has already passed, sleeping gives other threads a chance to run.
+        this.yield_active_thread();
 
         this.register_timeout_callback(
             active_thread,
@@ -238,6 +279,8 @@ fn Sleep(&mut self, timeout: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx> {
 
         let active_thread = this.get_active_thread();
         this.block_thread(active_thread);
+        // Even if the timeout has already passed, sleeping gives other threads a chance to run.
+        this.yield_active_thread();
 
         this.register_timeout_callback(
             active_thread,
@@ -247,6 +290,120 @@ fn Sleep(&mut self, timeout: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx> {
 
         Ok(())
     }
+
+    /// The WASI equivalent of `clock_gettime`. It takes its pointer argument as an integer and
+    /// returns an errno value.
+    fn wasi_clock_time_get(
+        &mut self,
+        clk_id_op: &OpTy<'tcx, Provenance>,
+        precision_op: &OpTy<'tcx, Provenance>,
+        time_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        this.assert_target_os("wasi", "clock_time_get");
+
+        let clk_id = this.read_scalar(clk_id_op)?.to_u32()?;
+        // We can always be as precise as requested.
+        let _precision = this.read_scalar(precision_op)?.to_u64()?;
+        let time_ptr = this.read_pointer_from_int(time_op)?;
+
+        // The clock IDs are fixed by the WASI ABI: `REALTIME`, `MONOTONIC`, `PROCESS_CPUTIME_ID`
+        // and `THREAD_CPUTIME_ID`. They behave like their Linux counterparts.
+        let duration = match clk_id {
+            0 => this.realtime_since_epoch("`clock_time_get` with the `REALTIME` clock")?,
+            1 => this.machine.clock.now().duration_since(this.machine.clock.anchor()),
+            2 => cpu_time_for_basic_blocks(this.machine.basic_block_count),
+            3 => cpu_time_for_basic_blocks(this.active_thread_ref().basic_block_count),
+            _ => return this.eval_libc_i32("EINVAL"),
+        };
+
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let time_place = MPlaceTy::from_aligned_ptr(time_ptr, this.machine.layouts.u64);
+        this.write_scalar(Scalar::from_u64(nanos), &time_place.into())?;
+        Ok(0)
+    }
+
+    /// Implements WASI's `poll_oneoff` for the only use std has for it: waiting for a single
+    /// timeout on the monotonic clock, which is how `std::thread::sleep` is implemented.
+    fn wasi_poll_oneoff(
+        &mut self,
+        in_op: &OpTy<'tcx, Provenance>,
+        out_op: &OpTy<'tcx, Provenance>,
+        nsubscriptions_op: &OpTy<'tcx, Provenance>,
+        nevents_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        this.assert_target_os("wasi", "poll_oneoff");
+
+        let subscription = this.read_pointer_from_int(in_op)?;
+        let event = this.read_pointer_from_int(out_op)?;
+        let nsubscriptions = this.read_scalar(nsubscriptions_op)?.to_u32()?;
+        let nevents = this.read_pointer_from_int(nevents_op)?;
+
+        if nsubscriptions != 1 {
+            throw_unsup_format!("`poll_oneoff` is only supported for a single subscription");
+        }
+        // The layout of `subscription` and `event` is fixed by the WASI ABI.
+        let layouts = &this.machine.layouts;
+        let userdata = wasi_field(this, subscription, 0, layouts.u64)?;
+        let tag = wasi_field(this, subscription, 8, layouts.u8)?;
+        let clk_id = wasi_field(this, subscription, 16, layouts.u32)?;
+        let timeout = wasi_field(this, subscription, 24, layouts.u64)?;
+        let flags = wasi_field(this, subscription, 40, layouts.u16)?;
+        let event_userdata = wasi_field(this, event, 0, layouts.u64)?;
+        let event_error = wasi_field(this, event, 8, layouts.u16)?;
+        let event_type = wasi_field(this, event, 10, layouts.u8)?;
+        let nevents = MPlaceTy::from_aligned_ptr(nevents, layouts.usize);
+
+        let userdata = this.read_scalar(&userdata.into())?;
+        let tag = this.read_scalar(&tag.into())?.to_u8()?;
+        let clk_id = this.read_scalar(&clk_id.into())?.to_u32()?;
+        let timeout = this.read_scalar(&timeout.into())?.to_u64()?;
+        let flags = this.read_scalar(&flags.into())?.to_u16()?;
+        // Tag 0 is `EVENTTYPE_CLOCK`, clock 1 is `MONOTONIC`, and flag 1 is
+        // `SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME`.
+        if tag != 0 || clk_id != 1 {
+            throw_unsup_format!("`poll_oneoff` is only supported for waiting on the monotonic clock");
+        }
+        let timeout = Duration::from_nanos(timeout);
+        let timeout_time = if flags & 1 != 0 {
+            this.machine.clock.anchor().checked_add(timeout)
+        } else {
+            this.machine.clock.now().checked_add(timeout)
+        };
+        // If the timeout overflows, sleep for an hour. Waking up early is always acceptable.
+        let timeout_time = timeout_time.unwrap_or_else(|| {
+            this.machine.clock.now().checked_add(Duration::from_secs(3600)).unwrap()
+        });
+
+        // We report the event right away; the thread cannot observe it before it wakes up.
+        this.write_scalar(userdata, &event_userdata.into())?;
+        this.write_scalar(Scalar::from_u16(0), &event_error.into())?;
+        this.write_scalar(Scalar::from_u8(tag), &event_type.into())?;
+        this.write_scalar(Scalar::from_machine_usize(1, this), &nevents.into())?;
+
+        let active_thread = this.get_active_thread();
+        this.block_thread(active_thread);
+        this.register_timeout_callback(
+            active_thread,
+            Time::Monotonic(timeout_time),
+            Box::new(UnblockCallback { thread_to_unblock: active_thread }),
+        );
+
+        Ok(0)
+    }
+}
+
+/// Returns the field at `offset` of a WASI struct that `ptr` points to.
+fn wasi_field<'tcx>(
+    ecx: &MiriInterpCx<'_, 'tcx>,
+    ptr: Pointer<Option<Provenance>>,
+    offset: u64,
+    layout: TyAndLayout<'tcx>,
+) -> InterpResult<'tcx, MPlaceTy<'tcx, Provenance>> {
+    Ok(MPlaceTy::from_aligned_ptr(ptr.offset(Size::from_bytes(offset), ecx)?, layout))
 }
 
 struct UnblockCallback {
@@ -5,12 +5,16 @@
 use std::collections::BTreeMap;
 
 use log::trace;
+use rand::rngs::StdRng;
 
 use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_middle::ty;
 use rustc_target::abi::{HasDataLayout, Size};
 use rustc_target::spec::abi::Abi;
 
+use crate::concurrency::thread::MAIN_THREAD;
+use crate::helpers::iter_exported_symbols;
 use crate::*;
 
 pub type TlsKey = u128;
@@ -29,6 +33,11 @@ struct RunningDtorsState {
     /// have not tried to retrieve a TLS destructor yet or that we already tried
     /// all keys.
     last_dtor_key: Option<TlsKey>,
+    /// With `-Zmiri-shuffle-tls-dtors`, the keys that have not been tried yet in the current round,
+    /// in reverse order of when they will be tried. `None` means that no round is in progress.
+    shuffled_keys: Option<Vec<TlsKey>>,
+    /// On Windows, the index of the next TLS callback to run.
+    next_windows_callback: usize,
 }
 
 #[derive(Debug)]
@@ -47,6 +56,10 @@ pub struct TlsData<'tcx> {
     /// specific thread, it means that we are in the "destruct" phase, during
     /// which some operations are UB.
     dtors_running: FxHashMap<ThreadId, RunningDtorsState>,
+
+    /// The TLS callbacks registered in the magic Windows linker section, in the order in which
+    /// they run. Looked up the first time a thread exits.
+    windows_tls_callbacks: Option<Vec<ty::Instance<'tcx>>>,
 }
 
 impl<'tcx> Default for TlsData<'tcx> {
@@ -56,6 +69,7 @@ fn default() -> Self {
             keys: Default::default(),
             macos_thread_dtors: Default::default(),
             dtors_running: Default::default(),
+            windows_tls_callbacks: None,
         }
     }
 }
@@ -70,13 +84,14 @@ pub fn create_tls_key(
         max_size: Size,
     ) -> InterpResult<'tcx, TlsKey> {
         let new_key = self.next_key;
-        self.next_key += 1;
-        self.keys.try_insert(new_key, TlsEntry { data: Default::default(), dtor }).unwrap();
-        trace!("New TLS key allocated: {} with dtor {:?}", new_key, dtor);
-
+        // Check this before handing out the key, so that a key that does not fit the target's
+        // key type (e.g. a 16-bit `pthread_key_t`) is never registered.
         if max_size.bits() < 128 && new_key >= (1u128 << max_size.bits()) {
             throw_unsup_format!("we ran out of TLS key space");
         }
+        self.next_key += 1;
+        self.keys.try_insert(new_key, TlsEntry { data: Default::default(), dtor }).unwrap();
+        trace!("New TLS key allocated: {} with dtor {:?}", new_key, dtor);
         Ok(new_key)
     }
 
@@ -193,8 +208,7 @@ fn fetch_tls_dtor(
         // time. That's why we have `key` to indicate how far we got in the current iteration. If we
         // return `None`, `schedule_next_pthread_tls_dtor` will re-try with `ket` set to `None` to
         // start the next round.
-        // TODO: In the future, we might consider randomizing destructor order, but we still have to
-        // uphold this requirement.
+        // `fetch_shuffled_tls_dtor` upholds the same requirement with a random order.
         for (&key, TlsEntry { data, dtor }) in thread_local.range_mut((start, Unbounded)) {
             match data.entry(thread_id) {
                 BTreeEntry::Occupied(entry) => {
@@ -211,6 +225,36 @@ fn fetch_tls_dtor(
         None
     }
 
+    /// Like `fetch_tls_dtor`, but tries the keys in a random order, which is picked anew at the
+    /// start of every round. Returns `None` at the end of a round; the next call then starts a
+    /// new round.
+    fn fetch_shuffled_tls_dtor(
+        &mut self,
+        thread_id: ThreadId,
+        rng: &mut StdRng,
+    ) -> Option<(ty::Instance<'tcx>, Scalar<Provenance>, TlsKey)> {
+        use rand::seq::SliceRandom;
+
+        let keys = &mut self.keys;
+        let state = self.dtors_running.get_mut(&thread_id).unwrap();
+        let pending = state.shuffled_keys.get_or_insert_with(|| {
+            let mut round: Vec<TlsKey> = keys.keys().copied().collect();
+            round.shuffle(rng);
+            round
+        });
+        while let Some(key) = pending.pop() {
+            // The key might have been deleted by a destructor that ran earlier in this round.
+            let Some(TlsEntry { data, dtor }) = keys.get_mut(&key) else { continue };
+            if let Some(dtor) = dtor {
+                if let Some(data_scalar) = data.remove(&thread_id) {
+                    return Some((*dtor, data_scalar, key));
+                }
+            }
+        }
+        state.shuffled_keys = None;
+        None
+    }
+
     /// Set that dtors are running for `thread`. It is guaranteed not to change
     /// the existing values stored in `dtors_running` for this thread. Returns
     /// `true` if dtors for `thread` are already running.
@@ -220,7 +264,11 @@ fn set_dtors_running_for_thread(&mut self, thread: ThreadId) -> bool {
             HashMapEntry::Vacant(entry) => {
                 // We cannot just do `self.dtors_running.insert` because that
                 // would overwrite `last_dtor_key` with `None`.
-                entry.insert(RunningDtorsState { last_dtor_key: None });
+                entry.insert(RunningDtorsState {
+                    last_dtor_key: None,
+                    shuffled_keys: None,
+                    next_windows_callback: 0,
+                });
                 false
             }
         }
@@ -237,7 +285,13 @@ fn delete_all_thread_tls(&mut self, thread_id: ThreadId) {
 
 impl VisitTags for TlsData<'_> {
     fn visit_tags(&self, visit: &mut dyn FnMut(SbTag)) {
-        let TlsData { keys, macos_thread_dtors, next_key: _, dtors_running: _ } = self;
+        let TlsData {
+            keys,
+            macos_thread_dtors,
+            next_key: _,
+            dtors_running: _,
+            windows_tls_callbacks: _,
+        } = self;
 
         for scalar in keys.values().flat_map(|v| v.data.values()) {
             scalar.visit_tags(visit);
@@ -250,30 +304,96 @@ fn visit_tags(&self, visit: &mut dyn FnMut(SbTag)) {
 
 impl<'mir, 'tcx: 'mir> EvalContextPrivExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
 trait EvalContextPrivExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
-    /// Schedule TLS destructors for Windows.
-    /// On windows, TLS destructors are managed by std.
-    fn schedule_windows_tls_dtors(&mut self) -> InterpResult<'tcx> {
+    /// Finds the TLS callbacks that are registered by placing a pointer to them in one of the
+    /// `.CRT$XL?` linker sections. The loader runs the callbacks between the `.CRT$XLA` and
+    /// `.CRT$XLZ` markers, sorted by section name; within a section, we use the crate order.
+    fn lookup_windows_tls_callbacks(&mut self) -> InterpResult<'tcx, Vec<ty::Instance<'tcx>>> {
+        let this = self.eval_context_mut();
+        let tcx = this.tcx.tcx;
+
+        let mut statics = Vec::new();
+        let mut check_static = |def_id: DefId| {
+            if !tcx.is_static(def_id) {
+                return;
+            }
+            if let Some(section) = tcx.codegen_fn_attrs(def_id).link_section {
+                let name = section.as_str();
+                if name.starts_with(".CRT$XL") && name > ".CRT$XLA" && name < ".CRT$XLZ" {
+                    statics.push((section, def_id));
+                }
+            }
+        };
+        // The exported symbols of the local crate only contain the items exported by name, but
+        // callbacks are usually registered with a `#[used]` static that is not `#[no_mangle]`.
+        // So we look at all the statics of the local crate instead.
+        for item_id in tcx.hir().items() {
+            check_static(item_id.owner_id.to_def_id());
+        }
+        iter_exported_symbols(tcx, |cnum, def_id| {
+            if cnum != LOCAL_CRATE {
+                check_static(def_id);
+            }
+            Ok(())
+        })?;
+        // This is a stable sort, so it keeps the crate order within a section.
+        statics.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+        let mut callbacks = Vec::new();
+        for (_, def_id) in statics {
+            let instance = ty::Instance::mono(tcx, def_id);
+            let place = this.eval_global(GlobalId { instance, promoted: None }, None)?;
+            // A section can hold a single callback or an array of them. Null entries are skipped,
+            // like the loader does.
+            let entries = if let ty::Array(..) = place.layout.ty.kind() {
+                let len = place.len(this)?;
+                (0..len)
+                    .map(|i| this.mplace_field(&place, usize::try_from(i).unwrap()))
+                    .collect::<InterpResult<'tcx, Vec<_>>>()?
+            } else {
+                vec![place]
+            };
+            for entry in entries {
+                let ptr = this.read_pointer(&entry.into())?;
+                if !this.ptr_is_null(ptr)? {
+                    callbacks.push(this.get_ptr_fn(ptr)?.as_instance()?);
+                }
+            }
+        }
+        Ok(callbacks)
+    }
+
+    /// Schedule the next TLS callback for Windows. Returns `true` if found a callback to
+    /// schedule, and `false` otherwise.
+    fn schedule_next_windows_tls_callback(&mut self) -> InterpResult<'tcx, bool> {
         let this = self.eval_context_mut();
         let active_thread = this.get_active_thread();
 
         // Windows has a special magic linker section that is run on certain events.
-        // Instead of searching for that section and supporting arbitrary hooks in there
-        // (that would be basically https://github.com/rust-lang/miri/issues/450),
-        // we specifically look up the static in libstd that we know is placed
-        // in that section.
-        let thread_callback =
-            this.eval_windows("thread_local_key", "p_thread_callback")?.to_pointer(this)?;
-        let thread_callback = this.get_ptr_fn(thread_callback)?.as_instance()?;
-
-        // FIXME: Technically, the reason should be `DLL_PROCESS_DETACH` when the main thread exits
-        // but std treats both the same.
-        let reason = this.eval_windows("c", "DLL_THREAD_DETACH")?;
+        if this.machine.tls.windows_tls_callbacks.is_none() {
+            let callbacks = this.lookup_windows_tls_callbacks()?;
+            this.machine.tls.windows_tls_callbacks = Some(callbacks);
+        }
+        let state = this.machine.tls.dtors_running.get_mut(&active_thread).unwrap();
+        let index = state.next_windows_callback;
+        let Some(&callback) = this.machine.tls.windows_tls_callbacks.as_ref().unwrap().get(index)
+        else {
+            return Ok(false);
+        };
+        state.next_windows_callback = index.checked_add(1).unwrap();
+        trace!("Running TLS callback {:?} on {:?}", callback, active_thread);
+
+        // The main thread exiting ends the process.
+        let reason = if active_thread == MAIN_THREAD {
+            this.eval_windows("c", "DLL_PROCESS_DETACH")?
+        } else {
+            this.eval_windows("c", "DLL_THREAD_DETACH")?
+        };
 
         // The signature of this function is `unsafe extern "system" fn(h: c::LPVOID, dwReason: c::DWORD, pv: c::LPVOID)`.
         // FIXME: `h` should be a handle to the current module and what `pv` should be is unknown
         // but both are ignored by std
         this.call_function(
-            thread_callback,
+            callback,
             Abi::System { unwind: false },
             &[Scalar::null_ptr(this).into(), reason.into(), Scalar::null_ptr(this).into()],
             None,
@@ -281,7 +401,7 @@ fn schedule_windows_tls_dtors(&mut self) -> InterpResult<'tcx> {
         )?;
 
         this.enable_thread(active_thread);
-        Ok(())
+        Ok(true)
     }
 
     /// Schedule the MacOS thread destructor of the thread local storage to be
@@ -321,11 +441,22 @@ fn schedule_next_pthread_tls_dtor(&mut self) -> InterpResult<'tcx, bool> {
 
         assert!(this.has_terminated(active_thread), "running TLS dtors for non-terminated thread");
         // Fetch next dtor after `key`.
-        let last_key = this.machine.tls.dtors_running[&active_thread].last_dtor_key;
-        let dtor = match this.machine.tls.fetch_tls_dtor(last_key, active_thread) {
-            dtor @ Some(_) => dtor,
-            // We ran each dtor once, start over from the beginning.
-            None => this.machine.tls.fetch_tls_dtor(None, active_thread),
+        let dtor = if this.machine.shuffle_tls_dtors {
+            // Like the randomness of the program, the order only depends on the seed and the
+            // thread, not on how the threads were interleaved.
+            let rng = this.machine.threads.active_thread_rng(this.machine.seed);
+            match this.machine.tls.fetch_shuffled_tls_dtor(active_thread, rng) {
+                dtor @ Some(_) => dtor,
+                // We ran each dtor once, start over with a new order.
+                None => this.machine.tls.fetch_shuffled_tls_dtor(active_thread, rng),
+            }
+        } else {
+            let last_key = this.machine.tls.dtors_running[&active_thread].last_dtor_key;
+            match this.machine.tls.fetch_tls_dtor(last_key, active_thread) {
+                dtor @ Some(_) => dtor,
+                // We ran each dtor once, start over from the beginning.
+                None => this.machine.tls.fetch_tls_dtor(None, active_thread),
+            }
         };
         if let Some((instance, ptr, key)) = dtor {
             this.machine.tls.dtors_running.get_mut(&active_thread).unwrap().last_dtor_key =
@@ -369,20 +500,17 @@ fn schedule_next_tls_dtor_for_active_thread(&mut self) -> InterpResult<'tcx> {
         let active_thread = this.get_active_thread();
         trace!("schedule_next_tls_dtor_for_active_thread on thread {:?}", active_thread);
 
-        if !this.machine.tls.set_dtors_running_for_thread(active_thread) {
-            // This is the first time we got asked to schedule a destructor. The
-            // Windows schedule destructor function must be called exactly once,
-            // this is why it is in this block.
-            if this.tcx.sess.target.os == "windows" {
-                // On Windows, we signal that the thread quit by starting the
-                // relevant function, reenabling the thread, and going back to
-                // the scheduler.
-                this.schedule_windows_tls_dtors()?;
+        this.machine.tls.set_dtors_running_for_thread(active_thread);
+        // The dtors make some progress each time around the scheduler loop,
+        // until they return `false` to indicate that they are done.
+
+        if this.tcx.sess.target.os == "windows" {
+            // On Windows, we signal that the thread quit by running the TLS callbacks one after
+            // the other, reenabling the thread each time and going back to the scheduler.
+            if this.schedule_next_windows_tls_callback()? {
                 return Ok(());
             }
         }
-        // The remaining dtors make some progress each time around the scheduler loop,
-        // until they return `false` to indicate that they are done.
 
         // The macOS thread wide destructor runs "before any TLS slots get
         // freed", so do that first.
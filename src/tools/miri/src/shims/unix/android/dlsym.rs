@@ -2,6 +2,7 @@
 
 use crate::helpers::check_arg_count;
 use crate::*;
+use shims::unix::signal::EvalContextExt as _;
 
 #[derive(Debug, Copy, Clone)]
 #[allow(non_camel_case_types)]
@@ -36,14 +37,9 @@ fn call_dlsym(
 
         match dlsym {
             Dlsym::signal => {
-                if !this.frame_in_std() {
-                    throw_unsup_format!(
-                        "`signal` support is crude and just enough for libstd to work"
-                    );
-                }
-
-                let [_sig, _func] = check_arg_count(args)?;
-                this.write_null(dest)?;
+                let [sig, handler] = check_arg_count(args)?;
+                let result = this.signal(sig, handler)?;
+                this.write_scalar(result, dest)?;
             }
         }
 
@@ -10,6 +10,8 @@
 use crate::*;
 use shims::foreign_items::EmulateByNameResult;
 use shims::unix::fs::EvalContextExt as _;
+use shims::unix::signal::EvalContextExt as _;
+use shims::unix::spawn::EvalContextExt as _;
 use shims::unix::sync::EvalContextExt as _;
 use shims::unix::thread::EvalContextExt as _;
 
@@ -74,6 +76,13 @@ fn emulate_foreign_item_by_name(
                 let result = this.fcntl(args)?;
                 this.write_scalar(Scalar::from_i32(result), dest)?;
             }
+            "ioctl" => {
+                // `ioctl` is variadic. The argument count is checked based on the request in
+                // `this.ioctl()`, so we do not use `check_shim` here.
+                this.check_abi_and_shim_symbol_clash(abi, Abi::C { unwind: false }, link_name)?;
+                let result = this.ioctl(args)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
             "read" => {
                 let [fd, buf, count] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
                 let fd = this.read_scalar(fd)?.to_i32()?;
@@ -206,6 +215,46 @@ fn emulate_foreign_item_by_name(
                     this.write_null(dest)?;
                 }
             }
+            "aligned_alloc" => {
+                let [align, size] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let align = this.read_scalar(align)?.to_machine_usize(this)?;
+                let size = this.read_scalar(size)?.to_machine_usize(this)?;
+                // C11 makes it UB to pass an alignment the implementation does not support, and we
+                // support exactly the powers of 2. (C17 turned this into a NULL return, but we
+                // would rather tell the program about its mistake.) C11 also requires `size` to
+                // be a multiple of `align`, but C17 dropped that and common allocators do not
+                // care, so neither do we.
+                if !align.is_power_of_two() {
+                    throw_ub_format!(
+                        "`aligned_alloc` called with an alignment of {align}, which is not a power of 2"
+                    );
+                }
+                if size == 0 {
+                    this.write_null(dest)?;
+                } else {
+                    // Like `malloc`, we guarantee an alignment suitable for any fundamental type.
+                    let align = align.max(this.min_align(size, MiriMemoryKind::C).bytes());
+                    let ptr = this.allocate_ptr(
+                        Size::from_bytes(size),
+                        Align::from_bytes(align).unwrap(),
+                        MiriMemoryKind::C.into(),
+                    )?;
+                    this.write_pointer(ptr, dest)?;
+                }
+            }
+            "malloc_usable_size" | "malloc_size"
+                if (link_name.as_str() == "malloc_size") == (this.tcx.sess.target.os == "macos") =>
+            {
+                // macOS calls this `malloc_size`, everyone else `malloc_usable_size`.
+                let [ptr] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let ptr = this.read_pointer(ptr)?;
+                let size = if this.ptr_is_null(ptr)? {
+                    0
+                } else {
+                    this.malloc_size(ptr, MiriMemoryKind::C, link_name.as_str())?
+                };
+                this.write_scalar(Scalar::from_machine_usize(size, this), dest)?;
+            }
 
             // Dynamic symbol loading
             "dlsym" => {
@@ -228,7 +277,7 @@ fn emulate_foreign_item_by_name(
                 // FIXME: Which of these are POSIX, and which are GNU/Linux?
                 // At least the names seem to all also exist on macOS.
                 let sysconfs: &[(&str, fn(&MiriInterpCx<'_, '_>) -> Scalar<Provenance>)] = &[
-                    ("_SC_PAGESIZE", |this| Scalar::from_int(PAGE_SIZE, this.pointer_size())),
+                    ("_SC_PAGESIZE", |this| Scalar::from_int(this.machine.page_size, this.pointer_size())),
                     ("_SC_NPROCESSORS_CONF", |this| Scalar::from_int(this.machine.num_cpus, this.pointer_size())),
                     ("_SC_NPROCESSORS_ONLN", |this| Scalar::from_int(this.machine.num_cpus, this.pointer_size())),
                     // 512 seems to be a reasonable default. The value is not critical, in
@@ -448,6 +497,116 @@ fn emulate_foreign_item_by_name(
                 this.write_scalar(Scalar::from_i32(result), dest)?;
             }
 
+            // Process management
+            "posix_spawnattr_init" | "posix_spawn_file_actions_init" => {
+                let [obj] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.posix_spawn_init(link_name.as_str(), obj)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "posix_spawnattr_destroy" | "posix_spawn_file_actions_destroy" => {
+                let [obj] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.deref_operand(obj)?;
+                this.write_null(dest)?;
+            }
+            "posix_spawnattr_setflags" => {
+                let [attr, flags] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.deref_operand(attr)?;
+                let flags = i32::from(this.read_scalar(flags)?.to_i16()?);
+                // We only support resetting signal dispositions, which `posix_spawnattr_setsigdefault`
+                // ignores, see there.
+                let setsigdef = this.eval_libc_i32("POSIX_SPAWN_SETSIGDEF")?;
+                if flags & !setsigdef != 0 {
+                    throw_unsup_format!("unsupported `posix_spawnattr_setflags` flags: {:#x}", flags);
+                }
+                this.write_null(dest)?;
+            }
+            "posix_spawnattr_setsigdefault" => {
+                let [attr, sigdefault] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.deref_operand(attr)?;
+                this.deref_operand(sigdefault)?;
+                // Processes spawned on the host get the dispositions of the host Miri process, which
+                // are the defaults (except for `SIGPIPE`, which the host standard library resets),
+                // so there is nothing to do.
+                this.write_null(dest)?;
+            }
+            "posix_spawn_file_actions_adddup2" => {
+                let [file_actions, fd, newfd] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.deref_operand(file_actions)?;
+                this.read_scalar(fd)?.to_i32()?;
+                this.read_scalar(newfd)?.to_i32()?;
+                // Our file descriptors are not host file descriptors, so we cannot hand them to the
+                // spawned process. Only inherited standard streams are supported.
+                let enosys = this.eval_libc("ENOSYS")?;
+                this.write_scalar(enosys, dest)?;
+            }
+            "posix_spawn" | "posix_spawnp" => {
+                let [pid, file, file_actions, attr, argv, envp] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.posix_spawn(link_name.as_str(), pid, file, file_actions, attr, argv, envp)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "fork" | "vfork" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                // We cannot duplicate the interpreter, so we pretend the system does not support this.
+                let result = this.unsupported_process_op(link_name.as_str())?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "execv" | "execvp" => {
+                let [path, argv] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.read_pointer(path)?;
+                this.read_pointer(argv)?;
+                let result = this.unsupported_process_op(link_name.as_str())?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "execve" => {
+                let [path, argv, envp] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.read_pointer(path)?;
+                this.read_pointer(argv)?;
+                this.read_pointer(envp)?;
+                let result = this.unsupported_process_op(link_name.as_str())?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "waitpid" => {
+                let [pid, status, options] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.waitpid(pid, status, options)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "kill" => {
+                let [pid, sig] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.kill(pid, sig)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+
+            // Signals
+            "sigaction" => {
+                let [sig, act, oldact] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.sigaction(sig, act, oldact)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "signal" => {
+                let [sig, handler] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.signal(sig, handler)?;
+                this.write_scalar(result, dest)?;
+            }
+            "raise" => {
+                let [sig] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.raise(sig)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+
+            // Non-local jumps (`longjmp` is handled in the platform-independent code)
+            "setjmp" | "_setjmp" => {
+                let [env] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.setjmp(env)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "sigsetjmp" | "__sigsetjmp" => {
+                let [env, savemask] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                // We never jump back, so there is no signal mask to restore.
+                this.read_scalar(savemask)?.to_i32()?;
+                let result = this.setjmp(env)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+
             // Miscellaneous
             "isatty" => {
                 let [fd] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
@@ -482,6 +641,21 @@ fn emulate_foreign_item_by_name(
                 let result = this.getpid()?;
                 this.write_scalar(Scalar::from_i32(result), dest)?;
             }
+            "uname" => {
+                let [buf] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.uname(buf)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "getentropy" => {
+                let [buf, bufsize] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                getentropy(this, buf, bufsize, dest)?;
+            }
+            "arc4random_buf" if matches!(&*this.tcx.sess.target.os, "macos" | "freebsd") => {
+                let [buf, nbytes] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let buf = this.read_pointer(buf)?;
+                let nbytes = this.read_scalar(nbytes)?.to_machine_usize(this)?;
+                this.gen_random(buf, nbytes)?;
+            }
 
             // Incomplete shims that we "stub out" just to get pre-main initialization code to work.
             // These shims are enabled only when the caller is in the standard library.
@@ -490,7 +664,7 @@ fn emulate_foreign_item_by_name(
                 let [_attr, guard_size] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
                 let guard_size = this.deref_operand(guard_size)?;
                 let guard_size_layout = this.libc_ty_layout("size_t")?;
-                this.write_scalar(Scalar::from_uint(crate::PAGE_SIZE, guard_size_layout.size), &guard_size.into())?;
+                this.write_scalar(Scalar::from_uint(this.machine.page_size, guard_size_layout.size), &guard_size.into())?;
 
                 // Return success (`0`).
                 this.write_null(dest)?;
@@ -531,19 +705,55 @@ fn emulate_foreign_item_by_name(
                 this.write_null(dest)?;
             }
 
-            | "signal"
-            | "sigaltstack"
+            "sigaltstack"
             if this.frame_in_std() => {
                 let [_, _] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
                 this.write_null(dest)?;
             }
-            | "sigaction"
-            | "mprotect"
+            "mprotect"
             if this.frame_in_std() => {
                 let [_, _, _] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
                 this.write_null(dest)?;
             }
 
+            "sigemptyset"
+            if this.frame_in_std() => {
+                let [set] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let set = this.deref_operand(set)?;
+                let sigset_layout = this.libc_ty_layout("sigset_t")?;
+                this.write_bytes_ptr(set.ptr, std::iter::repeat(0u8).take(sigset_layout.size.bytes_usize()))?;
+                this.write_null(dest)?;
+            }
+            "sigaddset"
+            if this.frame_in_std() => {
+                // This is only used to set up the signal set for `posix_spawnattr_setsigdefault`,
+                // which ignores it.
+                let [set, signum] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.deref_operand(set)?;
+                this.read_scalar(signum)?.to_i32()?;
+                this.write_null(dest)?;
+            }
+            "pipe" | "pipe2"
+            if this.frame_in_std() => {
+                // The standard library only uses pipes to communicate with child processes. We do
+                // not support those pipes, so we make this fail and let `std::process::Command`
+                // report an error.
+                match link_name.as_str() {
+                    "pipe" => {
+                        let [fds] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                        this.read_pointer(fds)?;
+                    }
+                    "pipe2" => {
+                        let [fds, flags] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                        this.read_pointer(fds)?;
+                        this.read_scalar(flags)?.to_i32()?;
+                    }
+                    _ => unreachable!(),
+                }
+                let result = this.unsupported_process_op(link_name.as_str())?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+
             "getuid"
             if this.frame_in_std() => {
                 let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
@@ -554,7 +764,7 @@ fn emulate_foreign_item_by_name(
             "getpwuid_r" if this.frame_in_std() => {
                 let [uid, pwd, buf, buflen, result] =
                     this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
-                this.check_no_isolation("`getpwuid_r`")?;
+                this.check_no_isolation(IsolationClass::Env, "`getpwuid_r`")?;
 
                 let uid = this.read_scalar(uid)?.to_u32()?;
                 let pwd = this.deref_operand(pwd)?;
@@ -603,3 +813,27 @@ fn emulate_foreign_item_by_name(
         Ok(EmulateByNameResult::NeedsJumping)
     }
 }
+
+/// Shims `getentropy`, which is available (as a function or via `dlsym`) on all Unix targets we
+/// support.
+pub(super) fn getentropy<'tcx>(
+    this: &mut MiriInterpCx<'_, 'tcx>,
+    buf: &OpTy<'tcx, Provenance>,
+    bufsize: &OpTy<'tcx, Provenance>,
+    dest: &PlaceTy<'tcx, Provenance>,
+) -> InterpResult<'tcx> {
+    let buf = this.read_pointer(buf)?;
+    let bufsize = this.read_scalar(bufsize)?.to_machine_usize(this)?;
+
+    // Requests for more than 256 bytes are rejected with EIO.
+    if bufsize > 256 {
+        let eio = this.eval_libc("EIO")?;
+        this.set_last_error(eio)?;
+        this.write_scalar(Scalar::from_i32(-1), dest)?;
+        return Ok(());
+    }
+
+    this.gen_random(buf, bufsize)?;
+    this.write_null(dest)?;
+    Ok(())
+}
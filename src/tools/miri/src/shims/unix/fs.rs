@@ -73,6 +73,8 @@ fn as_unix_host_fd(&self) -> Option<i32> {
     }
 }
 
+// Isolation was checked when the file was opened, and the host enforces the access mode it was
+// opened with, so the operations on an open file do not check it again.
 impl FileDescriptor for FileHandle {
     fn name(&self) -> &'static str {
         "FILE"
@@ -84,36 +86,32 @@ fn as_file_handle<'tcx>(&self) -> InterpResult<'tcx, &FileHandle> {
 
     fn read<'tcx>(
         &mut self,
-        communicate_allowed: bool,
+        _communicate_allowed: bool,
         bytes: &mut [u8],
     ) -> InterpResult<'tcx, io::Result<usize>> {
-        assert!(communicate_allowed, "isolation should have prevented even opening a file");
         Ok(self.file.read(bytes))
     }
 
     fn write<'tcx>(
         &self,
-        communicate_allowed: bool,
+        _communicate_allowed: bool,
         bytes: &[u8],
     ) -> InterpResult<'tcx, io::Result<usize>> {
-        assert!(communicate_allowed, "isolation should have prevented even opening a file");
         Ok((&mut &self.file).write(bytes))
     }
 
     fn seek<'tcx>(
         &mut self,
-        communicate_allowed: bool,
+        _communicate_allowed: bool,
         offset: SeekFrom,
     ) -> InterpResult<'tcx, io::Result<u64>> {
-        assert!(communicate_allowed, "isolation should have prevented even opening a file");
         Ok(self.file.seek(offset))
     }
 
     fn close<'tcx>(
         self: Box<Self>,
-        communicate_allowed: bool,
+        _communicate_allowed: bool,
     ) -> InterpResult<'tcx, io::Result<i32>> {
-        assert!(communicate_allowed, "isolation should have prevented even opening a file");
         // We sync the file if it was opened in a mode different than read-only.
         if self.writable {
             // `File::sync_all` does the checks that are done when closing a file. We do this to
@@ -182,45 +180,12 @@ fn is_tty(&self) -> bool {
     }
 }
 
-impl FileDescriptor for io::Stdout {
+impl FileDescriptor for OutputStream {
     fn name(&self) -> &'static str {
-        "stdout"
-    }
-
-    fn write<'tcx>(
-        &self,
-        _communicate_allowed: bool,
-        bytes: &[u8],
-    ) -> InterpResult<'tcx, io::Result<usize>> {
-        // We allow writing to stderr even with isolation enabled.
-        let result = Write::write(&mut { self }, bytes);
-        // Stdout is buffered, flush to make sure it appears on the
-        // screen.  This is the write() syscall of the interpreted
-        // program, we want it to correspond to a write() syscall on
-        // the host -- there is no good in adding extra buffering
-        // here.
-        io::stdout().flush().unwrap();
-
-        Ok(result)
-    }
-
-    fn dup(&mut self) -> io::Result<Box<dyn FileDescriptor>> {
-        Ok(Box::new(io::stdout()))
-    }
-
-    #[cfg(unix)]
-    fn as_unix_host_fd(&self) -> Option<i32> {
-        Some(libc::STDOUT_FILENO)
-    }
-
-    fn is_tty(&self) -> bool {
-        self.is_terminal()
-    }
-}
-
-impl FileDescriptor for io::Stderr {
-    fn name(&self) -> &'static str {
-        "stderr"
+        match self.stream() {
+            StdStream::Stdout => "stdout",
+            StdStream::Stderr => "stderr",
+        }
     }
 
     fn write<'tcx>(
@@ -228,48 +193,27 @@ fn write<'tcx>(
         _communicate_allowed: bool,
         bytes: &[u8],
     ) -> InterpResult<'tcx, io::Result<usize>> {
-        // We allow writing to stderr even with isolation enabled.
-        // No need to flush, stderr is not buffered.
-        Ok(Write::write(&mut { self }, bytes))
+        // We allow writing to stdout and stderr even with isolation enabled.
+        Ok(OutputStream::write(self, bytes))
     }
 
     fn dup(&mut self) -> io::Result<Box<dyn FileDescriptor>> {
-        Ok(Box::new(io::stderr()))
+        Ok(Box::new(self.clone()))
     }
 
     #[cfg(unix)]
     fn as_unix_host_fd(&self) -> Option<i32> {
-        Some(libc::STDERR_FILENO)
-    }
-
-    fn is_tty(&self) -> bool {
-        self.is_terminal()
-    }
-}
-
-#[derive(Debug)]
-struct NullOutput;
-
-impl FileDescriptor for NullOutput {
-    fn name(&self) -> &'static str {
-        "stderr and stdout"
-    }
-
-    fn write<'tcx>(
-        &self,
-        _communicate_allowed: bool,
-        bytes: &[u8],
-    ) -> InterpResult<'tcx, io::Result<usize>> {
-        // We just don't write anything, but report to the user that we did.
-        Ok(Ok(bytes.len()))
-    }
-
-    fn dup(&mut self) -> io::Result<Box<dyn FileDescriptor>> {
-        Ok(Box::new(NullOutput))
+        if !self.is_host_only() {
+            return None;
+        }
+        Some(match self.stream() {
+            StdStream::Stdout => libc::STDOUT_FILENO,
+            StdStream::Stderr => libc::STDERR_FILENO,
+        })
     }
 
     fn is_tty(&self) -> bool {
-        false
+        OutputStream::is_tty(self)
     }
 }
 
@@ -285,16 +229,11 @@ fn visit_tags(&self, _visit: &mut dyn FnMut(SbTag)) {
 }
 
 impl FileHandler {
-    pub(crate) fn new(mute_stdout_stderr: bool) -> FileHandler {
+    pub(crate) fn new(stdout: &OutputStream, stderr: &OutputStream) -> FileHandler {
         let mut handles: BTreeMap<_, Box<dyn FileDescriptor>> = BTreeMap::new();
         handles.insert(0i32, Box::new(io::stdin()));
-        if mute_stdout_stderr {
-            handles.insert(1i32, Box::new(NullOutput));
-            handles.insert(2i32, Box::new(NullOutput));
-        } else {
-            handles.insert(1i32, Box::new(io::stdout()));
-            handles.insert(2i32, Box::new(io::stderr()));
-        }
+        handles.insert(1i32, Box::new(stdout.clone()));
+        handles.insert(2i32, Box::new(stderr.clone()));
         FileHandler { handles }
     }
 
@@ -388,6 +327,20 @@ fn handle_not_found<T: From<i32>>(&mut self) -> InterpResult<'tcx, T> {
         Ok((-1).into())
     }
 
+    /// Whether `fd` refers to a terminal. Under isolation, no file descriptor is a terminal,
+    /// unless `-Zmiri-force-tty` says otherwise for the standard streams.
+    fn fd_is_tty(&self, fd: i32) -> bool {
+        let this = self.eval_context_ref();
+        let handle = match this.machine.file_handler.handles.get(&fd) {
+            Some(handle) => handle,
+            None => return false,
+        };
+        match (fd, this.machine.force_tty) {
+            (0..=2, Some(force_tty)) => force_tty,
+            _ => this.communicate(IsolationClass::Env) && handle.is_tty(),
+        }
+    }
+
     fn file_type_to_d_type(
         &mut self,
         file_type: std::io::Result<FileType>,
@@ -614,11 +567,19 @@ fn open(&mut self, args: &[OpTy<'tcx, Provenance>]) -> InterpResult<'tcx, i32> {
 
         let path = this.read_path_from_c_str(path)?;
 
-        // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
-            this.reject_in_isolation("`open`", reject_with)?;
-            this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
-            return Ok(-1);
+        // Reject if isolation is enabled. Reading the file and modifying it (which opening it for
+        // writing, creating it, or truncating it may do) are isolated separately.
+        let modifies = writable || flag & (o_creat | o_trunc) != 0;
+        let classes = [
+            (access_mode != o_wronly, IsolationClass::FsRead),
+            (modifies, IsolationClass::FsWrite),
+        ];
+        for (_, class) in classes.into_iter().filter(|&(needed, _)| needed) {
+            if let IsolatedOp::Reject(reject_with) = this.isolated_op(class) {
+                this.reject_in_isolation("`open`", reject_with)?;
+                this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
+                return Ok(-1);
+            }
         }
 
         let fd = options.open(path).map(|file| {
@@ -642,7 +603,7 @@ fn fcntl(&mut self, args: &[OpTy<'tcx, Provenance>]) -> InterpResult<'tcx, i32>
         let cmd = this.read_scalar(&args[1])?.to_i32()?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`fcntl`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
             return Ok(-1);
@@ -703,6 +664,68 @@ fn fcntl(&mut self, args: &[OpTy<'tcx, Provenance>]) -> InterpResult<'tcx, i32>
         }
     }
 
+    fn ioctl(&mut self, args: &[OpTy<'tcx, Provenance>]) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        if args.len() < 2 {
+            throw_ub_format!(
+                "incorrect number of arguments for ioctl: got {}, expected at least 2",
+                args.len()
+            );
+        }
+        let fd = this.read_scalar(&args[0])?.to_i32()?;
+        // The type of the request argument differs between targets (and libcs), but the request
+        // constants always have that same type.
+        let request_size = args[1].layout.size;
+        let request = this.read_scalar(&args[1])?.to_bits(request_size)?;
+        let is_request =
+            |this: &MiriInterpCx<'mir, 'tcx>, name: &str| -> InterpResult<'tcx, bool> {
+                Ok(this.eval_libc(name)?.to_bits(request_size)? == request)
+            };
+
+        if !this.machine.file_handler.handles.contains_key(&fd) {
+            return this.handle_not_found();
+        }
+
+        if is_request(this, "TIOCGWINSZ")? {
+            if args.len() < 3 {
+                throw_ub_format!(
+                    "incorrect number of arguments for ioctl with request=`TIOCGWINSZ`: got {}, expected at least 3",
+                    args.len()
+                );
+            }
+            if !this.fd_is_tty(fd) {
+                let enotty = this.eval_libc("ENOTTY")?;
+                this.set_last_error(enotty)?;
+                return Ok(-1);
+            }
+            // We do not query the host terminal, but report the classic 80x24 size.
+            let winsize = this.deref_operand(&args[2])?;
+            this.write_int_fields_named(
+                &[("ws_row", 24), ("ws_col", 80), ("ws_xpixel", 0), ("ws_ypixel", 0)],
+                &winsize,
+            )?;
+            Ok(0)
+        } else if is_request(this, "FIONBIO")? {
+            if args.len() < 3 {
+                throw_ub_format!(
+                    "incorrect number of arguments for ioctl with request=`FIONBIO`: got {}, expected at least 3",
+                    args.len()
+                );
+            }
+            // We do not support non-blocking I/O, but none of the file descriptors we support
+            // can actually block, so there is no difference between the two modes.
+            let _nonblocking = this.read_scalar(&this.deref_operand(&args[2])?.into())?.to_i32()?;
+            Ok(0)
+        } else if is_request(this, "FIOCLEX")? || is_request(this, "FIONCLEX")? {
+            // Like for `fcntl`, we always assume the FD_CLOEXEC flag is set for every open file;
+            // since exec() isn't supported, the flag does not make a difference.
+            Ok(0)
+        } else {
+            throw_unsup_format!("the {:#x} request is not supported for `ioctl`", request);
+        }
+    }
+
     fn close(&mut self, fd_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, Scalar<Provenance>> {
         let this = self.eval_context_mut();
 
@@ -710,7 +733,7 @@ fn close(&mut self, fd_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, Scalar
 
         Ok(Scalar::from_i32(
             if let Some(file_descriptor) = this.machine.file_handler.handles.remove(&fd) {
-                let result = file_descriptor.close(this.machine.communicate())?;
+                let result = file_descriptor.close(this.communicate(IsolationClass::FsRead))?;
                 this.try_unwrap_io_result(result)?
             } else {
                 this.handle_not_found()?
@@ -743,7 +766,7 @@ fn read(
         let count = count
             .min(u64::try_from(this.machine_isize_max()).unwrap())
             .min(u64::try_from(isize::MAX).unwrap());
-        let communicate = this.machine.communicate();
+        let communicate = this.communicate(IsolationClass::FsRead);
 
         if let Some(file_descriptor) = this.machine.file_handler.handles.get_mut(&fd) {
             trace!("read: FD mapped to {:?}", file_descriptor);
@@ -796,7 +819,7 @@ fn write(
         let count = count
             .min(u64::try_from(this.machine_isize_max()).unwrap())
             .min(u64::try_from(isize::MAX).unwrap());
-        let communicate = this.machine.communicate();
+        let communicate = this.communicate(IsolationClass::FsWrite);
 
         if let Some(file_descriptor) = this.machine.file_handler.handles.get(&fd) {
             let bytes = this.read_bytes_ptr_strip_provenance(buf, Size::from_bytes(count))?;
@@ -834,7 +857,7 @@ fn lseek64(
             return Ok(Scalar::from_i64(-1));
         };
 
-        let communicate = this.machine.communicate();
+        let communicate = this.communicate(IsolationClass::FsRead);
         Ok(Scalar::from_i64(
             if let Some(file_descriptor) = this.machine.file_handler.handles.get_mut(&fd) {
                 let result = file_descriptor
@@ -853,7 +876,7 @@ fn unlink(&mut self, path_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i32
         let path = this.read_path_from_c_str(this.read_pointer(path_op)?)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`unlink`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
             return Ok(-1);
@@ -884,7 +907,7 @@ fn create_link(src: &Path, dst: &Path) -> std::io::Result<()> {
         let linkpath = this.read_path_from_c_str(this.read_pointer(linkpath_op)?)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`symlink`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
             return Ok(-1);
@@ -906,7 +929,7 @@ fn macos_stat(
         let path = this.read_path_from_c_str(path_scalar)?.into_owned();
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`stat`", reject_with)?;
             let eacc = this.eval_libc("EACCES")?;
             this.set_last_error(eacc)?;
@@ -935,7 +958,7 @@ fn macos_lstat(
         let path = this.read_path_from_c_str(path_scalar)?.into_owned();
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`lstat`", reject_with)?;
             let eacc = this.eval_libc("EACCES")?;
             this.set_last_error(eacc)?;
@@ -962,7 +985,7 @@ fn macos_fstat(
         let fd = this.read_scalar(fd_op)?.to_i32()?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`fstat`", reject_with)?;
             // Set error code as "EBADF" (bad fd)
             return Ok(Scalar::from_i32(this.handle_not_found()?));
@@ -1037,7 +1060,7 @@ fn linux_statx(
         }
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`statx`", reject_with)?;
             let ecode = if path.is_absolute() || dirfd == this.eval_libc_i32("AT_FDCWD")? {
                 // since `path` is provided, either absolute or
@@ -1190,7 +1213,7 @@ fn rename(
         let newpath = this.read_path_from_c_str(newpath_ptr)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`rename`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
             return Ok(-1);
@@ -1218,7 +1241,7 @@ fn mkdir(
         let path = this.read_path_from_c_str(this.read_pointer(path_op)?)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`mkdir`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
             return Ok(-1);
@@ -1246,7 +1269,7 @@ fn rmdir(&mut self, path_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i32>
         let path = this.read_path_from_c_str(this.read_pointer(path_op)?)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`rmdir`", reject_with)?;
             this.set_last_error_from_io_error(ErrorKind::PermissionDenied)?;
             return Ok(-1);
@@ -1266,7 +1289,7 @@ fn opendir(
         let name = this.read_path_from_c_str(this.read_pointer(name_op)?)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`opendir`", reject_with)?;
             let eacc = this.eval_libc("EACCES")?;
             this.set_last_error(eacc)?;
@@ -1302,7 +1325,7 @@ fn linux_readdir64(
         let dirp = this.read_scalar(dirp_op)?.to_machine_usize(this)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`readdir`", reject_with)?;
             let eacc = this.eval_libc("EBADF")?;
             this.set_last_error(eacc)?;
@@ -1394,7 +1417,7 @@ fn macos_readdir_r(
         let dirp = this.read_scalar(dirp_op)?.to_machine_usize(this)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`readdir_r`", reject_with)?;
             // Set error code as "EBADF" (bad fd)
             return Ok(Scalar::from_i32(this.handle_not_found()?));
@@ -1487,7 +1510,7 @@ fn closedir(&mut self, dirp_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i
         let dirp = this.read_scalar(dirp_op)?.to_machine_usize(this)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`closedir`", reject_with)?;
             // Set error code as "EBADF" (bad fd)
             return this.handle_not_found();
@@ -1513,7 +1536,7 @@ fn ftruncate64(
         let length = this.read_scalar(length_op)?.to_i64()?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`ftruncate64`", reject_with)?;
             // Set error code as "EBADF" (bad fd)
             return Ok(Scalar::from_i32(this.handle_not_found()?));
@@ -1555,7 +1578,7 @@ fn fsync(&mut self, fd_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i32> {
         let fd = this.read_scalar(fd_op)?.to_i32()?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`fsync`", reject_with)?;
             // Set error code as "EBADF" (bad fd)
             return this.handle_not_found();
@@ -1577,7 +1600,7 @@ fn fdatasync(&mut self, fd_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i3
         let fd = this.read_scalar(fd_op)?.to_i32()?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`fdatasync`", reject_with)?;
             // Set error code as "EBADF" (bad fd)
             return this.handle_not_found();
@@ -1622,7 +1645,7 @@ fn sync_file_range(
         }
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`sync_file_range`", reject_with)?;
             // Set error code as "EBADF" (bad fd)
             return Ok(Scalar::from_i32(this.handle_not_found()?));
@@ -1651,7 +1674,7 @@ fn readlink(
         let bufsize = this.read_scalar(bufsize_op)?.to_machine_usize(this)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`readlink`", reject_with)?;
             let eacc = this.eval_libc("EACCES")?;
             this.set_last_error(eacc)?;
@@ -1691,11 +1714,9 @@ fn isatty(
         let this = self.eval_context_mut();
         // "returns 1 if fd is an open file descriptor referring to a terminal;
         // otherwise 0 is returned, and errno is set to indicate the error"
-        if matches!(this.machine.isolated_op, IsolatedOp::Allow) {
-            let fd = this.read_scalar(miri_fd)?.to_i32()?;
-            if this.machine.file_handler.handles.get(&fd).map(|fd| fd.is_tty()) == Some(true) {
-                return Ok(Scalar::from_i32(1));
-            }
+        let fd = this.read_scalar(miri_fd)?.to_i32()?;
+        if this.fd_is_tty(fd) {
+            return Ok(Scalar::from_i32(1));
         }
         // Fallback when the FD was not found or isolation is enabled.
         let enotty = this.eval_libc("ENOTTY")?;
@@ -1715,7 +1736,7 @@ fn realpath(
         let processed_ptr = this.read_pointer(processed_path_op)?;
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsRead) {
             this.reject_in_isolation("`realpath`", reject_with)?;
             let eacc = this.eval_libc("EACCES")?;
             this.set_last_error(eacc)?;
@@ -1790,7 +1811,7 @@ fn mkstemp(&mut self, template_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx
         let template_bytes = template.as_mut_slice();
 
         // Reject if isolation is enabled.
-        if let IsolatedOp::Reject(reject_with) = this.machine.isolated_op {
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::FsWrite) {
             this.reject_in_isolation("`mkstemp`", reject_with)?;
             let eacc = this.eval_libc("EACCES")?;
             this.set_last_error(eacc)?;
@@ -1846,7 +1867,7 @@ fn mkstemp(&mut self, template_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx
 
         // If the generated file already exists, we will try again `max_attempts` many times.
         for _ in 0..max_attempts {
-            let rng = this.machine.rng.get_mut();
+            let rng = this.active_thread_rng();
 
             // Generate a random unique suffix.
             let unique_suffix = SUBSTITUTIONS.choose_multiple(rng, 6).collect::<String>();
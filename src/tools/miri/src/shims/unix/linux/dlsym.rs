@@ -1,9 +1,17 @@
+use std::ffi::OsStr;
+
 use rustc_middle::mir;
 
+use log::trace;
+
 use crate::*;
+use helpers::check_arg_count;
 
 #[derive(Debug, Copy, Clone)]
-pub enum Dlsym {}
+#[allow(non_camel_case_types)]
+pub enum Dlsym {
+    gnu_get_libc_version,
+}
 
 impl Dlsym {
     // Returns an error for unsupported symbols, and None if this symbol
@@ -13,6 +21,8 @@ pub fn from_str<'tcx>(name: &str) -> InterpResult<'tcx, Option<Dlsym>> {
             "__pthread_get_minstack" => None,
             "getrandom" => None, // std falls back to syscall(SYS_getrandom, ...) when this is NULL.
             "statx" => None,     // std falls back to syscall(SYS_statx, ...) when this is NULL.
+            // std needs this to decide whether it can use `posix_spawn` for `Command`.
+            "gnu_get_libc_version" => Some(Dlsym::gnu_get_libc_version),
             _ => throw_unsup_format!("unsupported Linux dlsym: {}", name),
         })
     }
@@ -23,18 +33,26 @@ pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
     fn call_dlsym(
         &mut self,
         dlsym: Dlsym,
-        _args: &[OpTy<'tcx, Provenance>],
-        _dest: &PlaceTy<'tcx, Provenance>,
+        args: &[OpTy<'tcx, Provenance>],
+        dest: &PlaceTy<'tcx, Provenance>,
         ret: Option<mir::BasicBlock>,
     ) -> InterpResult<'tcx> {
         let this = self.eval_context_mut();
-        let _ret = ret.expect("we don't support any diverging dlsym");
+        let ret = ret.expect("we don't support any diverging dlsym");
         assert!(this.tcx.sess.target.os == "linux");
 
-        match dlsym {}
+        match dlsym {
+            Dlsym::gnu_get_libc_version => {
+                let [] = check_arg_count(args)?;
+                // Pretend to be a reasonably recent glibc.
+                let version = this
+                    .alloc_os_str_as_c_str(OsStr::new("2.36"), MiriMemoryKind::Machine.into())?;
+                this.write_pointer(version, dest)?;
+            }
+        }
 
-        //trace!("{:?}", this.dump_place(**dest));
-        //this.go_to_block(ret);
-        //Ok(())
+        trace!("{:?}", this.dump_place(**dest));
+        this.go_to_block(ret);
+        Ok(())
     }
 }
@@ -103,6 +103,8 @@ fn emulate_foreign_item_by_name(
 
                 let sys_futex = this.eval_libc("SYS_futex")?.to_machine_usize(this)?;
 
+                let sys_gettid = this.eval_libc("SYS_gettid")?.to_machine_usize(this)?;
+
                 if args.is_empty() {
                     throw_ub_format!(
                         "incorrect number of arguments for syscall: got 0, expected at least 1"
@@ -139,6 +141,10 @@ fn emulate_foreign_item_by_name(
                     id if id == sys_futex => {
                         futex(this, &args[1..], dest)?;
                     }
+                    id if id == sys_gettid => {
+                        let result = this.linux_gettid()?;
+                        this.write_scalar(Scalar::from_machine_isize(result.into(), this), dest)?;
+                    }
                     id => {
                         this.handle_unsupported(format!("can't execute syscall with ID {id}"))?;
                         return Ok(EmulateByNameResult::AlreadyJumped);
@@ -147,6 +153,11 @@ fn emulate_foreign_item_by_name(
             }
 
             // Miscelanneous
+            "gettid" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.linux_gettid()?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
             "getrandom" => {
                 let [ptr, len, flags] =
                     this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
@@ -92,6 +92,7 @@ pub fn futex<'tcx>(
             } else {
                 if op & futex_realtime != 0 {
                     this.check_no_isolation(
+                        IsolationClass::Clock,
                         "`futex` syscall with `op=FUTEX_WAIT` and non-null timeout with `FUTEX_CLOCK_REALTIME`",
                     )?;
                 }
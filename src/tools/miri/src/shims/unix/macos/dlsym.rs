@@ -4,6 +4,7 @@
 
 use crate::*;
 use helpers::check_arg_count;
+use shims::unix::foreign_items::getentropy;
 
 #[derive(Debug, Copy, Clone)]
 #[allow(non_camel_case_types)]
@@ -37,11 +38,8 @@ fn call_dlsym(
 
         match dlsym {
             Dlsym::getentropy => {
-                let [ptr, len] = check_arg_count(args)?;
-                let ptr = this.read_pointer(ptr)?;
-                let len = this.read_scalar(len)?.to_machine_usize(this)?;
-                this.gen_random(ptr, len)?;
-                this.write_null(dest)?;
+                let [buf, bufsize] = check_arg_count(args)?;
+                getentropy(this, buf, bufsize, dest)?;
             }
         }
 
@@ -92,9 +92,12 @@ fn emulate_foreign_item_by_name(
             }
 
             // Time related shims
-            "mach_absolute_time" => {
+            "mach_absolute_time"
+            | "mach_approximate_time"
+            | "mach_continuous_time"
+            | "mach_continuous_approximate_time" => {
                 let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
-                let result = this.mach_absolute_time()?;
+                let result = this.mach_absolute_time(link_name.as_str())?;
                 this.write_scalar(result, dest)?;
             }
 
@@ -122,7 +125,7 @@ fn emulate_foreign_item_by_name(
             "_NSGetExecutablePath" => {
                 let [buf, bufsize] =
                     this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
-                this.check_no_isolation("`_NSGetExecutablePath`")?;
+                this.check_no_isolation(IsolationClass::Env, "`_NSGetExecutablePath`")?;
 
                 let buf_ptr = this.read_pointer(buf)?;
                 let bufsize = this.deref_operand(bufsize)?;
@@ -1,7 +1,9 @@
 pub mod dlsym;
 pub mod foreign_items;
+pub mod fs;
+pub mod signal;
 
-mod fs;
+mod spawn;
 mod sync;
 mod thread;
 
@@ -0,0 +1,292 @@
+use std::iter;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir;
+use rustc_target::spec::abi::Abi;
+
+use crate::*;
+
+/// What the program registered for a signal with `sigaction` or `signal`.
+#[derive(Clone, Debug)]
+struct SignalAction {
+    /// `SIG_DFL`, `SIG_IGN`, or the function to call.
+    handler: Pointer<Option<Provenance>>,
+    /// The raw bits of `sa_flags`.
+    flags: u64,
+    /// The raw bytes of `sa_mask`. We only keep them to hand them back in `sigaction`: handlers
+    /// only ever run synchronously when `raise` or `kill` return, so there is nothing to block.
+    mask: Vec<u8>,
+}
+
+/// The signal dispositions of the interpreted process.
+#[derive(Debug, Default)]
+pub struct SignalHandlers {
+    /// Signals without an entry have their default disposition.
+    actions: FxHashMap<i32, SignalAction>,
+    /// A signal the program sent to itself that still has to be delivered. This happens when the
+    /// foreign function that sent it returns, see `deliver_pending_signal`.
+    pending: Option<i32>,
+}
+
+impl VisitTags for SignalHandlers {
+    fn visit_tags(&self, visit: &mut dyn FnMut(SbTag)) {
+        let SignalHandlers { actions, pending: _ } = self;
+
+        for action in actions.values() {
+            action.handler.visit_tags(visit);
+        }
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExtPrivate<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+trait EvalContextExtPrivate<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    fn is_valid_signal(&self, sig: i32) -> bool {
+        let this = self.eval_context_ref();
+        // This includes the real-time signals.
+        let max = match &*this.tcx.sess.target.os {
+            "macos" => 31,
+            "freebsd" => 128,
+            _ => 64,
+        };
+        (1..=max).contains(&sig)
+    }
+
+    /// Whether `sig` is one of the signals whose disposition cannot be changed.
+    fn is_uncatchable_signal(&self, sig: i32) -> InterpResult<'tcx, bool> {
+        let this = self.eval_context_ref();
+        Ok(sig == this.eval_libc_i32("SIGKILL")? || sig == this.eval_libc_i32("SIGSTOP")?)
+    }
+
+    /// Returns whether any of the given signal names refers to `sig`.
+    fn signal_is_one_of(&self, sig: i32, names: &[&str]) -> InterpResult<'tcx, bool> {
+        let this = self.eval_context_ref();
+        for name in names {
+            if sig == this.eval_libc_i32(name)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Gets the value of an `SA_*` constant as the bits it has in `sa_flags`.
+    fn sa_flag(&self, name: &str) -> InterpResult<'tcx, u64> {
+        let this = self.eval_context_ref();
+        let flag = this.eval_libc(name)?.assert_int();
+        Ok(u64::try_from(flag.assert_bits(flag.size())).unwrap())
+    }
+
+    fn signal_action(&self, sig: i32) -> InterpResult<'tcx, SignalAction> {
+        let this = self.eval_context_ref();
+        if let Some(action) = this.machine.signal_handlers.actions.get(&sig) {
+            return Ok(action.clone());
+        }
+        let sig_dfl = this.eval_libc("SIG_DFL")?.to_machine_usize(this)?;
+        let sigset_layout = this.libc_ty_layout("sigset_t")?;
+        Ok(SignalAction {
+            handler: Pointer::from_addr(sig_dfl),
+            flags: 0,
+            mask: vec![0; sigset_layout.size.bytes_usize()],
+        })
+    }
+
+    fn read_sigaction(
+        &self,
+        place: &MPlaceTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, SignalAction> {
+        let this = self.eval_context_ref();
+
+        let handler = this.read_pointer(&this.mplace_field_named(place, "sa_sigaction")?.into())?;
+        let mask_place = this.mplace_field_named(place, "sa_mask")?;
+        let mask = this.read_bytes_ptr_strip_provenance(mask_place.ptr, mask_place.layout.size)?;
+        let mask = mask.to_owned();
+        let flags_place = this.mplace_field_named(place, "sa_flags")?;
+        let flags = this.read_scalar(&flags_place.into())?.to_bits(flags_place.layout.size)?;
+        Ok(SignalAction { handler, flags: u64::try_from(flags).unwrap(), mask })
+    }
+
+    fn write_sigaction(
+        &mut self,
+        place: &MPlaceTy<'tcx, Provenance>,
+        action: &SignalAction,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+
+        let handler_place = this.mplace_field_named(place, "sa_sigaction")?;
+        this.write_pointer(action.handler, &handler_place.into())?;
+        let mask_place = this.mplace_field_named(place, "sa_mask")?;
+        this.write_bytes_ptr(mask_place.ptr, action.mask.iter().copied())?;
+        let flags_place = this.mplace_field_named(place, "sa_flags")?;
+        let flags = Scalar::from_uint(action.flags, flags_place.layout.size);
+        this.write_scalar(flags, &flags_place.into())?;
+        if matches!(&*this.tcx.sess.target.os, "linux" | "android") {
+            // We never hand out a restorer.
+            let restorer_place = this.mplace_field_named(place, "sa_restorer")?;
+            this.write_null(&restorer_place.into())?;
+        }
+        Ok(())
+    }
+
+    /// Allocates the `siginfo_t` that is passed to `SA_SIGINFO` handlers.
+    fn alloc_siginfo(&mut self, sig: i32) -> InterpResult<'tcx, MPlaceTy<'tcx, Provenance>> {
+        let this = self.eval_context_mut();
+
+        let siginfo_layout = this.libc_ty_layout("siginfo_t")?;
+        let siginfo = this.allocate(siginfo_layout, MiriMemoryKind::Machine.into())?;
+        // All other fields are 0, which makes `si_code` `SI_USER`.
+        this.write_bytes_ptr(
+            siginfo.ptr,
+            iter::repeat(0u8).take(siginfo_layout.size.bytes_usize()),
+        )?;
+        let signo_place = this.mplace_field_named(&siginfo, "si_signo")?;
+        this.write_scalar(Scalar::from_i32(sig), &signo_place.into())?;
+        Ok(siginfo)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    fn sigaction(
+        &mut self,
+        sig_op: &OpTy<'tcx, Provenance>,
+        act_op: &OpTy<'tcx, Provenance>,
+        oldact_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let sig = this.read_scalar(sig_op)?.to_i32()?;
+        let act = this.read_pointer(act_op)?;
+        let oldact = this.read_pointer(oldact_op)?;
+
+        if !this.is_valid_signal(sig)
+            || (!this.ptr_is_null(act)? && this.is_uncatchable_signal(sig)?)
+        {
+            let einval = this.eval_libc("EINVAL")?;
+            this.set_last_error(einval)?;
+            return Ok(-1);
+        }
+
+        // Read the new action before writing the old one, the program may pass the same struct.
+        let new_action = if this.ptr_is_null(act)? {
+            None
+        } else {
+            Some(this.read_sigaction(&this.deref_operand(act_op)?)?)
+        };
+        if !this.ptr_is_null(oldact)? {
+            let old_action = this.signal_action(sig)?;
+            this.write_sigaction(&this.deref_operand(oldact_op)?, &old_action)?;
+        }
+        if let Some(new_action) = new_action {
+            this.machine.signal_handlers.actions.insert(sig, new_action);
+        }
+        Ok(0)
+    }
+
+    fn signal(
+        &mut self,
+        sig_op: &OpTy<'tcx, Provenance>,
+        handler_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, Scalar<Provenance>> {
+        let this = self.eval_context_mut();
+
+        let sig = this.read_scalar(sig_op)?.to_i32()?;
+        let handler = this.read_pointer(handler_op)?;
+
+        if !this.is_valid_signal(sig) || this.is_uncatchable_signal(sig)? {
+            let einval = this.eval_libc("EINVAL")?;
+            this.set_last_error(einval)?;
+            return this.eval_libc("SIG_ERR");
+        }
+
+        let old_action = this.signal_action(sig)?;
+        // Both glibc and macOS give `signal` BSD semantics, which is what `SA_RESTART` stands for.
+        let new_action =
+            SignalAction { handler, flags: this.sa_flag("SA_RESTART")?, mask: old_action.mask };
+        this.machine.signal_handlers.actions.insert(sig, new_action);
+        Ok(Scalar::from_maybe_pointer(old_action.handler, this))
+    }
+
+    fn raise(&mut self, sig_op: &OpTy<'tcx, Provenance>) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let sig = this.read_scalar(sig_op)?.to_i32()?;
+        this.send_signal_to_self(sig)
+    }
+
+    /// Sends `sig` to the interpreted process, as `raise` and `kill` do. Handlers are not called
+    /// right away, but when the foreign function that sent the signal returns.
+    fn send_signal_to_self(&mut self, sig: i32) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        if sig == 0 {
+            // This just checks whether we could send a signal.
+            return Ok(0);
+        }
+        if !this.is_valid_signal(sig) {
+            let einval = this.eval_libc("EINVAL")?;
+            this.set_last_error(einval)?;
+            return Ok(-1);
+        }
+
+        let handler = this.signal_action(sig)?.handler.addr().bytes();
+        if handler == this.eval_libc("SIG_IGN")?.to_machine_usize(this)? {
+            return Ok(0);
+        }
+        if handler == this.eval_libc("SIG_DFL")?.to_machine_usize(this)? {
+            if this.signal_is_one_of(sig, &["SIGCHLD", "SIGURG", "SIGWINCH", "SIGCONT"])? {
+                // The default action for these is to do nothing.
+                return Ok(0);
+            }
+            if this.signal_is_one_of(sig, &["SIGSTOP", "SIGTSTP", "SIGTTIN", "SIGTTOU"])? {
+                throw_unsup_format!("stopping the process with signal {} is not supported", sig);
+            }
+            if sig == this.eval_libc_i32("SIGABRT")? {
+                throw_machine_stop!(TerminationInfo::Abort(
+                    "the program aborted execution by raising `SIGABRT`".to_owned()
+                ));
+            }
+            throw_machine_stop!(TerminationInfo::TerminatedBySignal(sig));
+        }
+
+        this.machine.signal_handlers.pending = Some(sig);
+        Ok(0)
+    }
+
+    /// If the foreign function that just finished sent a signal, pushes a frame for its handler
+    /// that returns to `ret` (instead of jumping there directly) and returns `true`.
+    fn deliver_pending_signal(&mut self, ret: mir::BasicBlock) -> InterpResult<'tcx, bool> {
+        let this = self.eval_context_mut();
+
+        let Some(sig) = this.machine.signal_handlers.pending.take() else {
+            return Ok(false);
+        };
+        let action = this.signal_action(sig)?;
+        if action.flags & this.sa_flag("SA_RESETHAND")? != 0 {
+            this.machine.signal_handlers.actions.remove(&sig);
+        }
+
+        let handler = match action.handler.into_parts() {
+            (Some(_), _) => action.handler,
+            // `sighandler_t` is an integer type, so the handler usually lost its provenance when
+            // the program cast it. The C library casts it back to a function pointer.
+            (None, addr) =>
+                crate::intptrcast::GlobalStateInner::ptr_from_addr_cast(this, addr.bytes())?,
+        };
+        let instance = this.get_ptr_fn(handler)?.as_instance()?;
+
+        let mut args: Vec<Immediate<Provenance>> = vec![Scalar::from_i32(sig).into()];
+        if action.flags & this.sa_flag("SA_SIGINFO")? != 0 {
+            let siginfo = this.alloc_siginfo(sig)?;
+            args.push(Scalar::from_maybe_pointer(siginfo.ptr, this).into());
+            // We do not provide a `ucontext_t`.
+            args.push(Scalar::null_ptr(this).into());
+        }
+        this.call_function(
+            instance,
+            Abi::C { unwind: false },
+            &args,
+            None,
+            StackPopCleanup::Goto { ret: Some(ret), unwind: StackPopUnwind::NotAllowed },
+        )?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,237 @@
+use std::ffi::OsString;
+use std::process::{Command, ExitStatus};
+
+use crate::*;
+use shims::os_str::{bytes_to_os_str, os_str_to_bytes};
+use shims::unix::signal::EvalContextExt as _;
+
+/// Converts the exit status of a host process into the `int` status `waitpid` reports.
+fn wait_status(status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        // All Unix targets we support use the same encoding as the host.
+        use std::os::unix::process::ExitStatusExt;
+        status.into_raw()
+    }
+    #[cfg(not(unix))]
+    {
+        // Report a normal exit with the given code, which is how `WIFEXITED` statuses are encoded.
+        (status.code().unwrap_or(1) & 0xff).checked_shl(8).unwrap()
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExtPrivate<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+trait EvalContextExtPrivate<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Checks whether the program may spawn a process on the host. If it may not, returns the
+    /// error number that the spawning function should return.
+    fn reject_process_spawning(&mut self, name: &str) -> InterpResult<'tcx, Option<i32>> {
+        let this = self.eval_context_mut();
+
+        if !this.machine.process_passthrough {
+            // We cannot emulate other processes, so we let the program handle this like any other
+            // failure to spawn a process.
+            this.machine.record_shim_fallback("made to fail since Miri cannot emulate processes");
+            this.emit_diagnostic(NonHaltingDiagnostic::UnsupportedProcessOp {
+                op: format!("`{name}`"),
+                spawn: true,
+            });
+            return Ok(Some(this.eval_libc_i32("ENOSYS")?));
+        }
+        if let IsolatedOp::Reject(reject_with) = this.isolated_op(IsolationClass::Process) {
+            this.reject_in_isolation(&format!("`{name}`"), reject_with)?;
+            return Ok(Some(this.eval_libc_i32("EACCES")?));
+        }
+        Ok(None)
+    }
+
+    /// Reads a NULL-terminated array of C strings, such as `argv` or `envp`.
+    fn read_c_str_array(
+        &self,
+        mut ptr: Pointer<Option<Provenance>>,
+    ) -> InterpResult<'tcx, Vec<OsString>> {
+        let this = self.eval_context_ref();
+        let ptr_layout = this.machine.layouts.const_raw_ptr;
+
+        let mut strs = Vec::new();
+        loop {
+            let place = MPlaceTy::from_aligned_ptr(ptr, ptr_layout);
+            let str_ptr = this.read_pointer(&place.into())?;
+            if this.ptr_is_null(str_ptr)? {
+                return Ok(strs);
+            }
+            strs.push(this.read_os_str_from_c_str(str_ptr)?.to_owned());
+            ptr = ptr.offset(ptr_layout.size, this)?;
+        }
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Reports that the program called a process management function we cannot emulate, and makes
+    /// it fail with `ENOSYS` so that the program can go down its error path.
+    fn unsupported_process_op(&mut self, name: &str) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        this.machine.record_shim_fallback("made to fail since Miri cannot emulate processes");
+        this.emit_diagnostic(NonHaltingDiagnostic::UnsupportedProcessOp {
+            op: format!("`{name}`"),
+            spawn: false,
+        });
+        let enosys = this.eval_libc("ENOSYS")?;
+        this.set_last_error(enosys)?;
+        Ok(-1)
+    }
+
+    /// Implements `posix_spawnattr_init` and `posix_spawn_file_actions_init`. We do not need to
+    /// store anything in the objects, but this is the first thing `std::process::Command` calls, so
+    /// this is where we decide whether spawning processes is going to work at all.
+    fn posix_spawn_init(
+        &mut self,
+        name: &str,
+        obj_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        this.deref_operand(obj_op)?;
+        if let Some(err) = this.reject_process_spawning(name)? {
+            return Ok(err);
+        }
+        Ok(0)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn posix_spawn(
+        &mut self,
+        name: &str,
+        pid_op: &OpTy<'tcx, Provenance>,
+        file_op: &OpTy<'tcx, Provenance>,
+        file_actions_op: &OpTy<'tcx, Provenance>,
+        attr_op: &OpTy<'tcx, Provenance>,
+        argv_op: &OpTy<'tcx, Provenance>,
+        envp_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let pid = this.read_pointer(pid_op)?;
+        let file = this.read_os_str_from_c_str(this.read_pointer(file_op)?)?.to_owned();
+        // We do not support any file actions or attributes (see the shims for the functions that
+        // set them), so there is nothing to do with these.
+        this.read_pointer(file_actions_op)?;
+        this.read_pointer(attr_op)?;
+        let argv = this.read_c_str_array(this.read_pointer(argv_op)?)?;
+        let envp = this.read_pointer(envp_op)?;
+        let env = if this.ptr_is_null(envp)? { None } else { Some(this.read_c_str_array(envp)?) };
+
+        if let Some(err) = this.reject_process_spawning(name)? {
+            return Ok(err);
+        }
+
+        let mut cmd = Command::new(file);
+        // `argv[0]` is the program name, which `Command` sets by itself.
+        cmd.args(argv.iter().skip(1));
+        if let Some(env) = env {
+            cmd.env_clear();
+            for var in env {
+                let var = os_str_to_bytes(&var)?;
+                // Entries without a `=` are ignored, like the C library does.
+                if let Some(pos) = var.iter().position(|&b| b == b'=') {
+                    let (name, value) = var.split_at(pos);
+                    cmd.env(bytes_to_os_str(name)?, bytes_to_os_str(&value[1..])?);
+                }
+            }
+        }
+
+        match cmd.spawn() {
+            Ok(child) => {
+                let child_pid = i32::try_from(child.id()).unwrap();
+                this.machine.child_processes.insert(child_pid, child);
+                if !this.ptr_is_null(pid)? {
+                    let pid_place = this.deref_operand(pid_op)?;
+                    this.write_scalar(Scalar::from_i32(child_pid), &pid_place.into())?;
+                }
+                Ok(0)
+            }
+            Err(e) => this.io_error_to_errnum(e.kind())?.to_i32(),
+        }
+    }
+
+    fn waitpid(
+        &mut self,
+        pid_op: &OpTy<'tcx, Provenance>,
+        status_op: &OpTy<'tcx, Provenance>,
+        options_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let pid = this.read_scalar(pid_op)?.to_i32()?;
+        let status = this.read_pointer(status_op)?;
+        let options = this.read_scalar(options_op)?.to_i32()?;
+
+        let wnohang = this.eval_libc_i32("WNOHANG")?;
+        if options & !wnohang != 0 {
+            throw_unsup_format!("unsupported `waitpid` options: {:#x}", options);
+        }
+        if pid <= 0 {
+            throw_unsup_format!("`waitpid` is only supported for a specific child process");
+        }
+
+        let Some(child) = this.machine.child_processes.get_mut(&pid) else {
+            // This is not a child of ours (or we already waited for it).
+            let echild = this.eval_libc("ECHILD")?;
+            this.set_last_error(echild)?;
+            return Ok(-1);
+        };
+        // Note that a blocking wait blocks the entire interpreter, not just the current thread.
+        let result = if options & wnohang != 0 { child.try_wait() } else { child.wait().map(Some) };
+        match result {
+            Ok(None) => Ok(0),
+            Ok(Some(exit_status)) => {
+                this.machine.child_processes.remove(&pid);
+                if !this.ptr_is_null(status)? {
+                    let status_place = this.deref_operand(status_op)?;
+                    let status = Scalar::from_i32(wait_status(exit_status));
+                    this.write_scalar(status, &status_place.into())?;
+                }
+                Ok(pid)
+            }
+            Err(e) => {
+                this.set_last_error_from_io_error(e.kind())?;
+                Ok(-1)
+            }
+        }
+    }
+
+    fn kill(
+        &mut self,
+        pid_op: &OpTy<'tcx, Provenance>,
+        sig_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let pid = this.read_scalar(pid_op)?.to_i32()?;
+        let sig = this.read_scalar(sig_op)?.to_i32()?;
+        let sigkill = this.eval_libc_i32("SIGKILL")?;
+
+        // Process group 0 is our own, and we are the only process in it.
+        if pid == 0 || u32::try_from(pid).ok() == Some(this.process_id()) {
+            return this.send_signal_to_self(sig);
+        }
+        let Some(child) = this.machine.child_processes.get_mut(&pid) else {
+            throw_unsup_format!("`kill` is only supported for child processes and the program itself");
+        };
+        if sig == 0 {
+            // This just checks whether the process exists.
+            return Ok(0);
+        }
+        if sig != sigkill {
+            throw_unsup_format!("sending signal {} to a child process is not supported", sig);
+        }
+        match child.kill() {
+            Ok(()) => Ok(0),
+            Err(e) => {
+                this.set_last_error_from_io_error(e.kind())?;
+                Ok(-1)
+            }
+        }
+    }
+}
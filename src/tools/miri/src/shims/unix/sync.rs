@@ -725,7 +725,10 @@ fn pthread_cond_timedwait(
         };
 
         let timeout_time = if clock_id == this.eval_libc_i32("CLOCK_REALTIME")? {
-            this.check_no_isolation("`pthread_cond_timedwait` with `CLOCK_REALTIME`")?;
+            this.check_no_isolation(
+                IsolationClass::Clock,
+                "`pthread_cond_timedwait` with `CLOCK_REALTIME`",
+            )?;
             Time::RealTime(SystemTime::UNIX_EPOCH.checked_add(duration).unwrap())
         } else if clock_id == this.eval_libc_i32("CLOCK_MONOTONIC")? {
             Time::Monotonic(this.machine.clock.anchor().checked_add(duration).unwrap())
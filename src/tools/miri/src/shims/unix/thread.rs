@@ -19,6 +19,10 @@ fn pthread_create(
 
         let func_arg = this.read_immediate(arg)?;
 
+        if this.spawn_should_fail() {
+            return this.eval_libc_i32("EAGAIN");
+        }
+
         this.start_thread(
             Some(thread_info_place),
             start_routine,
@@ -0,0 +1,208 @@
+use rustc_span::Symbol;
+use rustc_target::abi::Size;
+use rustc_target::spec::abi::Abi;
+
+use crate::*;
+use shims::foreign_items::EmulateByNameResult;
+use shims::unix::fs::EvalContextExt as _;
+
+impl<'mir, 'tcx: 'mir> EvalContextExtPrivate<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+trait EvalContextExtPrivate<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Returns the command-line arguments, the same ones we pass to `main`.
+    fn wasi_args(&self) -> InterpResult<'tcx, Vec<Vec<u8>>> {
+        let this = self.eval_context_ref();
+        let argc = this.machine.argc.expect("machine must be initialized");
+        let argc = this.read_scalar(&argc.into())?.to_machine_isize(this)?;
+        let argv = this.machine.argv.expect("machine must be initialized");
+        let mut argv = this.read_pointer(&argv.into())?;
+
+        let ptr_layout = this.machine.layouts.const_raw_ptr;
+        let mut args = Vec::new();
+        for _ in 0..argc {
+            let arg = this.read_pointer(&MPlaceTy::from_aligned_ptr(argv, ptr_layout).into())?;
+            args.push(this.read_c_str(arg)?.to_owned());
+            argv = argv.offset(ptr_layout.size, this)?;
+        }
+        Ok(args)
+    }
+
+    /// Implements `fd_read` and `fd_write`, which transfer data between `fd` and the buffers
+    /// described by an array of `iovec`s.
+    fn wasi_fd_transfer(
+        &mut self,
+        write: bool,
+        fd_op: &OpTy<'tcx, Provenance>,
+        iovs_op: &OpTy<'tcx, Provenance>,
+        iovs_len_op: &OpTy<'tcx, Provenance>,
+        result_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let fd = this.read_scalar(fd_op)?.to_i32()?;
+        let mut iov = this.read_pointer_from_int(iovs_op)?;
+        let iovs_len = this.read_scalar(iovs_len_op)?.to_u32()?;
+        let result = this.read_pointer_from_int(result_op)?;
+
+        // An `iovec` is a pointer to the buffer followed by its length.
+        let ptr_layout = this.machine.layouts.const_raw_ptr;
+        let usize_layout = this.machine.layouts.usize;
+        let mut total: u64 = 0;
+        for _ in 0..iovs_len {
+            let buf = this.read_pointer(&MPlaceTy::from_aligned_ptr(iov, ptr_layout).into())?;
+            let len = iov.offset(ptr_layout.size, this)?;
+            let len = this.read_scalar(&MPlaceTy::from_aligned_ptr(len, usize_layout).into())?;
+            let len = len.to_machine_usize(this)?;
+            iov = iov.offset(ptr_layout.size.checked_mul(2, this).unwrap(), this)?;
+
+            let transferred =
+                if write { this.write(fd, buf, len)? } else { this.read(fd, buf, len)? };
+            let Ok(transferred) = u64::try_from(transferred) else {
+                // The shim already set the error; WASI returns it instead.
+                return this.get_last_error()?.to_i32();
+            };
+            total = total.checked_add(transferred).unwrap();
+            if transferred < len {
+                break;
+            }
+        }
+
+        let result = MPlaceTy::from_aligned_ptr(result, usize_layout);
+        this.write_scalar(Scalar::from_machine_usize(total, this), &result.into())?;
+        Ok(0)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    fn emulate_foreign_item_by_name(
+        &mut self,
+        link_name: Symbol,
+        abi: Abi,
+        args: &[OpTy<'tcx, Provenance>],
+        dest: &PlaceTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, EmulateByNameResult<'mir, 'tcx>> {
+        let this = self.eval_context_mut();
+
+        // See `fn emulate_foreign_item_by_name` in `shims/foreign_items.rs` for the general pattern.
+        // The WASI functions (as opposed to the wasi-libc ones) take all pointers as integers and
+        // return an errno value, which is 0 on success.
+        #[rustfmt::skip]
+        match link_name.as_str() {
+            // Environment related shims. These are implemented by wasi-libc; the `environ` static
+            // is set up in `init_extern_statics` and `errno` is a thread-local static.
+            "getenv" => {
+                let [name] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.getenv(name)?;
+                this.write_pointer(result, dest)?;
+            }
+            "unsetenv" => {
+                let [name] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.unsetenv(name)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "setenv" => {
+                let [name, value, overwrite] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.read_scalar(overwrite)?.to_i32()?;
+                let result = this.setenv(name, value)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "getcwd" => {
+                let [buf, size] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.getcwd(buf, size)?;
+                this.write_pointer(result, dest)?;
+            }
+            "chdir" => {
+                let [path] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.chdir(path)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "args_sizes_get" => {
+                let [argc, buf_size] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let argc = this.read_pointer_from_int(argc)?;
+                let buf_size = this.read_pointer_from_int(buf_size)?;
+                let args = this.wasi_args()?;
+                // Every argument is stored with a trailing null byte.
+                let size: usize = args.iter().map(|arg| arg.len().checked_add(1).unwrap()).sum();
+                let usize_layout = this.machine.layouts.usize;
+                let argc = MPlaceTy::from_aligned_ptr(argc, usize_layout);
+                this.write_scalar(Scalar::from_machine_usize(u64::try_from(args.len()).unwrap(), this), &argc.into())?;
+                let buf_size = MPlaceTy::from_aligned_ptr(buf_size, usize_layout);
+                this.write_scalar(Scalar::from_machine_usize(u64::try_from(size).unwrap(), this), &buf_size.into())?;
+                this.write_null(dest)?;
+            }
+            "args_get" => {
+                let [argv, buf] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let mut argv = this.read_pointer_from_int(argv)?;
+                let mut buf = this.read_pointer_from_int(buf)?;
+                let ptr_layout = this.machine.layouts.mut_raw_ptr;
+                for mut arg in this.wasi_args()? {
+                    this.write_pointer(buf, &MPlaceTy::from_aligned_ptr(argv, ptr_layout).into())?;
+                    arg.push(0);
+                    this.write_bytes_ptr(buf, arg.iter().copied())?;
+                    buf = buf.offset(Size::from_bytes(arg.len()), this)?;
+                    argv = argv.offset(ptr_layout.size, this)?;
+                }
+                this.write_null(dest)?;
+            }
+
+            // File related shims
+            "fd_write" => {
+                let [fd, iovs, iovs_len, nwritten] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.wasi_fd_transfer(/* write */ true, fd, iovs, iovs_len, nwritten)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "fd_read" => {
+                let [fd, iovs, iovs_len, nread] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.wasi_fd_transfer(/* write */ false, fd, iovs, iovs_len, nread)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "__wasilibc_find_relpath" => {
+                let [path, abs_prefix, relative_path, relative_path_len] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.read_pointer(path)?;
+                this.read_pointer(abs_prefix)?;
+                this.read_pointer(relative_path)?;
+                this.read_scalar(relative_path_len)?.to_machine_usize(this)?;
+                // WASI programs can only access the file system through pre-opened directories,
+                // and we do not give them any.
+                let enoent = this.eval_libc("ENOENT")?;
+                this.set_last_error(enoent)?;
+                this.write_scalar(Scalar::from_i32(-1), dest)?;
+            }
+            "isatty" => {
+                let [fd] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.isatty(fd)?;
+                this.write_scalar(result, dest)?;
+            }
+
+            // Time related shims
+            "clock_time_get" => {
+                let [clk_id, precision, time] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.wasi_clock_time_get(clk_id, precision, time)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "poll_oneoff" => {
+                let [in_, out, nsubscriptions, nevents] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.wasi_poll_oneoff(in_, out, nsubscriptions, nevents)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "sched_yield" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.yield_active_thread();
+                this.write_null(dest)?;
+            }
+
+            // Miscellaneous
+            "random_get" => {
+                let [buf, len] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let buf = this.read_pointer_from_int(buf)?;
+                let len = this.read_scalar(len)?.to_machine_usize(this)?;
+                this.gen_random(buf, len)?;
+                this.write_null(dest)?;
+            }
+
+            _ => return Ok(EmulateByNameResult::NotSupported),
+        };
+
+        Ok(EmulateByNameResult::NeedsJumping)
+    }
+}
@@ -0,0 +1 @@
+pub mod foreign_items;
@@ -0,0 +1,169 @@
+use std::io::{self, IsTerminal};
+
+use rustc_target::abi::Size;
+
+use crate::*;
+
+/// The fake handles `GetStdHandle` hands out for the standard streams: they are just the
+/// `STD_*_HANDLE` constants that were passed to it.
+const STD_INPUT_HANDLE: i64 = -10;
+const STD_OUTPUT_HANDLE: i64 = -11;
+const STD_ERROR_HANDLE: i64 = -12;
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+
+#[allow(non_snake_case)]
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    fn GetStdHandle(
+        &mut self,
+        which_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, Scalar<Provenance>> {
+        let this = self.eval_context_mut();
+
+        let which = this.read_scalar(which_op)?.to_i32()?;
+        Ok(match i64::from(which) {
+            // We just make this the identity function, so we know later in `WriteFile`,
+            // `WriteConsoleW` and `NtWriteFile` which one it is. This is very fake, but libtest
+            // needs it so we cannot make it a std-only shim.
+            // FIXME: this should return real HANDLEs when io support is added
+            STD_INPUT_HANDLE | STD_OUTPUT_HANDLE | STD_ERROR_HANDLE =>
+                Scalar::from_machine_isize(which.into(), this),
+            _ => {
+                let invalid_parameter = this.eval_windows("c", "ERROR_INVALID_PARAMETER")?;
+                this.set_last_error(invalid_parameter)?;
+                // INVALID_HANDLE_VALUE
+                Scalar::from_machine_isize(-1, this)
+            }
+        })
+    }
+
+    fn GetConsoleMode(
+        &mut self,
+        console_op: &OpTy<'tcx, Provenance>,
+        mode_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let console = this.read_scalar(console_op)?.to_machine_isize(this)?;
+        let mode = this.deref_operand(mode_op)?;
+
+        let communicate = this.communicate(IsolationClass::Env);
+        let is_console = match (console, this.machine.force_tty) {
+            (STD_ERROR_HANDLE..=STD_INPUT_HANDLE, Some(force_tty)) => force_tty,
+            (STD_INPUT_HANDLE, None) => communicate && io::stdin().is_terminal(),
+            // This takes redirection of the interpreted program's output into account.
+            (STD_OUTPUT_HANDLE, None) => communicate && this.machine.stdout.is_tty(),
+            (STD_ERROR_HANDLE, None) => communicate && this.machine.stderr.is_tty(),
+            _ => false,
+        };
+        if is_console {
+            // Report the default console modes: ENABLE_PROCESSED_INPUT | ENABLE_LINE_INPUT |
+            // ENABLE_ECHO_INPUT for input, and ENABLE_PROCESSED_OUTPUT |
+            // ENABLE_WRAP_AT_EOL_OUTPUT for output.
+            let mode_val: u32 = if console == STD_INPUT_HANDLE { 0x7 } else { 0x3 };
+            this.write_scalar(Scalar::from_u32(mode_val), &mode.into())?;
+            Ok(1)
+        } else {
+            let invalid_handle = this.eval_windows("c", "ERROR_INVALID_HANDLE")?;
+            this.set_last_error(invalid_handle)?;
+            Ok(0)
+        }
+    }
+
+    fn WriteConsoleW(
+        &mut self,
+        console_op: &OpTy<'tcx, Provenance>,
+        buf_op: &OpTy<'tcx, Provenance>,
+        len_op: &OpTy<'tcx, Provenance>,
+        written_op: &OpTy<'tcx, Provenance>,
+        reserved_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let console = this.read_scalar(console_op)?.to_machine_isize(this)?;
+        let buf = this.read_pointer(buf_op)?;
+        let len = this.read_scalar(len_op)?.to_u32()?;
+        let written = this.read_pointer(written_op)?;
+        let reserved = this.read_pointer(reserved_op)?;
+
+        if !this.ptr_is_null(reserved)? {
+            throw_unsup_format!("non-null `lpReserved` in `WriteConsoleW`");
+        }
+
+        // `len` counts UTF-16 code units, not bytes.
+        let size = Size::from_bytes(u64::from(len).checked_mul(2).unwrap());
+        let bytes = this.read_bytes_ptr_strip_provenance(buf, size)?;
+        let units = bytes.chunks_exact(2).map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
+        // The console would show lone surrogates as the replacement character, so we do the same.
+        let text: String = char::decode_utf16(units)
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect();
+
+        let res = match console {
+            STD_OUTPUT_HANDLE => this.machine.stdout.write(text.as_bytes()),
+            STD_ERROR_HANDLE => this.machine.stderr.write(text.as_bytes()),
+            _ => {
+                let invalid_handle = this.eval_windows("c", "ERROR_INVALID_HANDLE")?;
+                this.set_last_error(invalid_handle)?;
+                return Ok(0);
+            }
+        };
+        if let Err(e) = res {
+            this.set_last_error_from_io_error(e.kind())?;
+            return Ok(0);
+        }
+
+        // We always write everything, so report the number of UTF-16 code units we were given.
+        if !this.ptr_is_null(written)? {
+            let written = this.deref_operand(written_op)?;
+            this.write_scalar(Scalar::from_u32(len), &written.into())?;
+        }
+        Ok(1)
+    }
+
+    fn WriteFile(
+        &mut self,
+        file_op: &OpTy<'tcx, Provenance>,
+        buf_op: &OpTy<'tcx, Provenance>,
+        len_op: &OpTy<'tcx, Provenance>,
+        written_op: &OpTy<'tcx, Provenance>,
+        overlapped_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        let file = this.read_scalar(file_op)?.to_machine_isize(this)?;
+        let buf = this.read_pointer(buf_op)?;
+        let len = this.read_scalar(len_op)?.to_u32()?;
+        let written = this.read_pointer(written_op)?;
+        let overlapped = this.read_pointer(overlapped_op)?;
+
+        if !this.ptr_is_null(overlapped)? {
+            throw_unsup_format!("non-null `lpOverlapped` in `WriteFile`");
+        }
+
+        let bytes = this.read_bytes_ptr_strip_provenance(buf, Size::from_bytes(u64::from(len)))?;
+        let res = match file {
+            STD_OUTPUT_HANDLE => this.machine.stdout.write(bytes),
+            STD_ERROR_HANDLE => this.machine.stderr.write(bytes),
+            _ =>
+                throw_unsup_format!(
+                    "on Windows, writing to anything except stdout/stderr is not supported"
+                ),
+        };
+        match res {
+            Ok(n) => {
+                // We write at most `len` bytes, which is a `u32`.
+                let n = u32::try_from(n).unwrap();
+                if !this.ptr_is_null(written)? {
+                    let written = this.deref_operand(written_op)?;
+                    this.write_scalar(Scalar::from_u32(n), &written.into())?;
+                }
+                Ok(1)
+            }
+            Err(e) => {
+                this.set_last_error_from_io_error(e.kind())?;
+                Ok(0)
+            }
+        }
+    }
+}
@@ -11,6 +11,7 @@
 
 #[derive(Debug, Copy, Clone)]
 pub enum Dlsym {
+    GetSystemTimePreciseAsFileTime,
     NtWriteFile,
     SetThreadDescription,
     WaitOnAddress,
@@ -22,7 +23,7 @@ impl Dlsym {
     // should become a NULL pointer (pretend it does not exist).
     pub fn from_str<'tcx>(name: &str) -> InterpResult<'tcx, Option<Dlsym>> {
         Ok(match name {
-            "GetSystemTimePreciseAsFileTime" => None,
+            "GetSystemTimePreciseAsFileTime" => Some(Dlsym::GetSystemTimePreciseAsFileTime),
             "NtWriteFile" => Some(Dlsym::NtWriteFile),
             "SetThreadDescription" => Some(Dlsym::SetThreadDescription),
             "WaitOnAddress" => Some(Dlsym::WaitOnAddress),
@@ -49,6 +50,11 @@ fn call_dlsym(
         this.check_abi(abi, Abi::System { unwind: false })?;
 
         match dlsym {
+            Dlsym::GetSystemTimePreciseAsFileTime => {
+                #[allow(non_snake_case)]
+                let [LPFILETIME] = check_arg_count(args)?;
+                this.GetSystemTimeAsFileTime("GetSystemTimePreciseAsFileTime", LPFILETIME)?;
+            }
             Dlsym::NtWriteFile => {
                 if !this.frame_in_std() {
                     throw_unsup_format!(
@@ -81,16 +87,12 @@ fn call_dlsym(
 
                 let written = if handle == -11 || handle == -12 {
                     // stdout/stderr
-                    use std::io::{self, Write};
-
                     let buf_cont =
                         this.read_bytes_ptr_strip_provenance(buf, Size::from_bytes(u64::from(n)))?;
-                    let res = if this.machine.mute_stdout_stderr {
-                        Ok(buf_cont.len())
-                    } else if handle == -11 {
-                        io::stdout().write(buf_cont)
+                    let res = if handle == -11 {
+                        this.machine.stdout.write(buf_cont)
                     } else {
-                        io::stderr().write(buf_cont)
+                        this.machine.stderr.write(buf_cont)
                     };
                     // We write at most `n` bytes, which is a `u32`, so we cannot have written more than that.
                     res.ok().map(|n| u32::try_from(n).unwrap())
@@ -6,7 +6,9 @@
 
 use crate::*;
 use shims::foreign_items::EmulateByNameResult;
+use shims::windows::console::EvalContextExt as _;
 use shims::windows::handle::{EvalContextExt as _, Handle, PseudoHandle};
+use shims::windows::heap::EvalContextExt as _;
 use shims::windows::sync::EvalContextExt as _;
 use shims::windows::thread::EvalContextExt as _;
 
@@ -70,35 +72,35 @@ fn emulate_foreign_item_by_name(
             }
 
             // Allocation
+            "GetProcessHeap" => {
+                let [] = this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
+                let heap = this.GetProcessHeap();
+                this.write_scalar(heap, dest)?;
+            }
             "HeapAlloc" => {
-                let [handle, flags, size] =
+                let [heap, flags, size] =
                     this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                this.read_scalar(handle)?.to_machine_isize(this)?;
-                let flags = this.read_scalar(flags)?.to_u32()?;
-                let size = this.read_scalar(size)?.to_machine_usize(this)?;
-                let zero_init = (flags & 0x00000008) != 0; // HEAP_ZERO_MEMORY
-                let res = this.malloc(size, zero_init, MiriMemoryKind::WinHeap)?;
+                let res = this.HeapAlloc(heap, flags, size)?;
                 this.write_pointer(res, dest)?;
             }
             "HeapFree" => {
-                let [handle, flags, ptr] =
+                let [heap, flags, ptr] =
                     this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                this.read_scalar(handle)?.to_machine_isize(this)?;
-                this.read_scalar(flags)?.to_u32()?;
-                let ptr = this.read_pointer(ptr)?;
-                this.free(ptr, MiriMemoryKind::WinHeap)?;
-                this.write_scalar(Scalar::from_i32(1), dest)?;
+                let res = this.HeapFree(heap, flags, ptr)?;
+                this.write_scalar(Scalar::from_i32(res), dest)?;
             }
             "HeapReAlloc" => {
-                let [handle, flags, ptr, size] =
+                let [heap, flags, ptr, size] =
                     this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                this.read_scalar(handle)?.to_machine_isize(this)?;
-                this.read_scalar(flags)?.to_u32()?;
-                let ptr = this.read_pointer(ptr)?;
-                let size = this.read_scalar(size)?.to_machine_usize(this)?;
-                let res = this.realloc(ptr, size, MiriMemoryKind::WinHeap)?;
+                let res = this.HeapReAlloc(heap, flags, ptr, size)?;
                 this.write_pointer(res, dest)?;
             }
+            "HeapSize" => {
+                let [heap, flags, ptr] =
+                    this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
+                let size = this.HeapSize(heap, flags, ptr)?;
+                this.write_scalar(Scalar::from_machine_usize(size, this), dest)?;
+            }
 
             // errno
             "SetLastError" => {
@@ -155,18 +157,62 @@ fn emulate_foreign_item_by_name(
                     })
                     .collect();
 
+                // Set processor architecture.
+                // The `PROCESSOR_ARCHITECTURE_*` constants are not in std, so we hard-code them.
+                let arch: u16 = match &*this.tcx.sess.target.arch {
+                    "x86" => 0,
+                    "arm" => 5,
+                    "x86_64" => 9,
+                    "aarch64" => 12,
+                    _ => 0xffff, // PROCESSOR_ARCHITECTURE_UNKNOWN
+                };
+                let processor_arch = system_info.offset(field_offsets[0], word_layout, &this.tcx)?;
+                this.write_scalar(Scalar::from_u16(arch), &processor_arch.into())?;
                 // Set page size.
                 let page_size = system_info.offset(field_offsets[2], dword_layout, &this.tcx)?;
                 this.write_scalar(
-                    Scalar::from_int(PAGE_SIZE, dword_layout.size),
+                    Scalar::from_int(this.machine.page_size, dword_layout.size),
                     &page_size.into(),
                 )?;
+                // Set the range of application addresses. We do not actually restrict where
+                // allocations live, so just report the range of user-mode addresses that Windows
+                // reports: everything from 64 KiB to the end of the lower 2 GiB on 32-bit targets
+                // and of the lower 8 TiB on 64-bit targets, except for the last 64 KiB.
+                let min_addr = system_info.offset(field_offsets[3], usize_layout, &this.tcx)?;
+                this.write_scalar(
+                    Scalar::from_machine_usize(0x10000, this),
+                    &min_addr.into(),
+                )?;
+                let max_addr = system_info.offset(field_offsets[4], usize_layout, &this.tcx)?;
+                let max_addr_val: u64 =
+                    if this.pointer_size().bits() == 32 { 0x7FFE_FFFF } else { 0x7FF_FFFE_FFFF };
+                this.write_scalar(
+                    Scalar::from_machine_usize(max_addr_val, this),
+                    &max_addr.into(),
+                )?;
+                // Set the mask of active processors (the first `num_cpus` processors).
+                let cpu_mask = system_info.offset(field_offsets[5], usize_layout, &this.tcx)?;
+                let cpu_mask_val = 1u64
+                    .checked_shl(this.machine.num_cpus)
+                    .map_or(u64::MAX, |bit| bit.wrapping_sub(1))
+                    .min(this.machine_usize_max());
+                this.write_scalar(
+                    Scalar::from_machine_usize(cpu_mask_val, this),
+                    &cpu_mask.into(),
+                )?;
                 // Set number of processors.
                 let num_cpus = system_info.offset(field_offsets[6], dword_layout, &this.tcx)?;
                 this.write_scalar(
                     Scalar::from_int(this.machine.num_cpus, dword_layout.size),
                     &num_cpus.into(),
                 )?;
+                // Set allocation granularity, which is 64 KiB on all Windows systems (but at least
+                // one page).
+                let granularity = system_info.offset(field_offsets[8], dword_layout, &this.tcx)?;
+                this.write_scalar(
+                    Scalar::from_int(this.machine.page_size.max(0x10000), dword_layout.size),
+                    &granularity.into(),
+                )?;
             }
 
             // Thread-local storage
@@ -207,11 +253,11 @@ fn emulate_foreign_item_by_name(
             }
 
             // Time related shims
-            "GetSystemTimeAsFileTime" => {
+            "GetSystemTimeAsFileTime" | "GetSystemTimePreciseAsFileTime" => {
                 #[allow(non_snake_case)]
                 let [LPFILETIME] =
                     this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                this.GetSystemTimeAsFileTime(LPFILETIME)?;
+                this.GetSystemTimeAsFileTime(link_name.as_str(), LPFILETIME)?;
             }
             "QueryPerformanceCounter" => {
                 #[allow(non_snake_case)]
@@ -309,6 +355,11 @@ fn emulate_foreign_item_by_name(
             }
 
             // Miscellaneous
+            "GetCurrentProcessId" => {
+                let [] = this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
+                let result = this.GetCurrentProcessId()?;
+                this.write_int(result, dest)?;
+            }
             "SystemFunction036" => {
                 // This is really 'RtlGenRandom'.
                 let [ptr, len] =
@@ -362,15 +413,29 @@ fn emulate_foreign_item_by_name(
                 // FIXME: we should set last_error, but to what?
                 this.write_null(dest)?;
             }
+            "GetConsoleMode" => {
+                let [console, mode] =
+                    this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
+                let ret = this.GetConsoleMode(console, mode)?;
+                this.write_scalar(Scalar::from_i32(ret), dest)?;
+            }
             "GetStdHandle" => {
                 let [which] =
                     this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                let which = this.read_scalar(which)?.to_i32()?;
-                // We just make this the identity function, so we know later in `NtWriteFile` which
-                // one it is. This is very fake, but libtest needs it so we cannot make it a
-                // std-only shim.
-                // FIXME: this should return real HANDLEs when io support is added
-                this.write_scalar(Scalar::from_machine_isize(which.into(), this), dest)?;
+                let handle = this.GetStdHandle(which)?;
+                this.write_scalar(handle, dest)?;
+            }
+            "WriteConsoleW" => {
+                let [console, buf, len, written, reserved] =
+                    this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
+                let ret = this.WriteConsoleW(console, buf, len, written, reserved)?;
+                this.write_scalar(Scalar::from_i32(ret), dest)?;
+            }
+            "WriteFile" => {
+                let [file, buf, len, written, overlapped] =
+                    this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
+                let ret = this.WriteFile(file, buf, len, written, overlapped)?;
+                this.write_scalar(Scalar::from_i32(ret), dest)?;
             }
             "CloseHandle" => {
                 let [handle] =
@@ -381,15 +446,24 @@ fn emulate_foreign_item_by_name(
                 this.write_scalar(Scalar::from_u32(1), dest)?;
             }
 
+            // Non-local jumps (`longjmp` is handled in the platform-independent code)
+            "_setjmp" | "_setjmpex" => {
+                let [env, frame] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                // We never jump back, so we do not need to know which frame to unwind to.
+                this.read_pointer(frame)?;
+                let result = this.setjmp(env)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+
             // Threading
             "CreateThread" => {
                 let [security, stacksize, start, arg, flags, thread] =
                     this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
 
-                let thread_id =
-                    this.CreateThread(security, stacksize, start, arg, flags, thread)?;
+                let handle = this.CreateThread(security, stacksize, start, arg, flags, thread)?;
 
-                this.write_scalar(Handle::Thread(thread_id).to_scalar(this), dest)?;
+                this.write_scalar(handle.to_scalar(this), dest)?;
             }
             "WaitForSingleObject" => {
                 let [handle, timeout] =
@@ -409,12 +483,6 @@ fn emulate_foreign_item_by_name(
 
             // Incomplete shims that we "stub out" just to get pre-main initialization code to work.
             // These shims are enabled only when the caller is in the standard library.
-            "GetProcessHeap" if this.frame_in_std() => {
-                let [] = this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                // Just fake a HANDLE
-                // It's fine to not use the Handle type here because its a stub
-                this.write_int(1, dest)?;
-            }
             "GetModuleHandleA" if this.frame_in_std() => {
                 #[allow(non_snake_case)]
                 let [_lpModuleName] =
@@ -429,14 +497,6 @@ fn emulate_foreign_item_by_name(
                 // Pretend these does not exist / nothing happened, by returning zero.
                 this.write_null(dest)?;
             }
-            "GetConsoleMode" if this.frame_in_std() => {
-                let [console, mode] =
-                    this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                this.read_scalar(console)?.to_machine_isize(this)?;
-                this.deref_operand(mode)?;
-                // Indicate an error.
-                this.write_null(dest)?;
-            }
             "GetFileType" if this.frame_in_std() => {
                 #[allow(non_snake_case)]
                 let [_hFile] =
@@ -458,11 +518,6 @@ fn emulate_foreign_item_by_name(
                 // Any non zero value works for the stdlib. This is just used for stack overflows anyway.
                 this.write_int(1, dest)?;
             }
-            "GetCurrentProcessId" if this.frame_in_std() => {
-                let [] = this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                let result = this.GetCurrentProcessId()?;
-                this.write_int(result, dest)?;
-            }
             // this is only callable from std because we know that std ignores the return value
             "SwitchToThread" if this.frame_in_std() => {
                 let [] = this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
@@ -6,6 +6,8 @@
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PseudoHandle {
     CurrentThread,
+    /// The handle `GetProcessHeap` returns. It is the only heap we support.
+    ProcessHeap,
 }
 
 /// Miri representation of a Windows `HANDLE`
@@ -18,16 +20,19 @@ pub enum Handle {
 
 impl PseudoHandle {
     const CURRENT_THREAD_VALUE: u32 = 0;
+    const PROCESS_HEAP_VALUE: u32 = 1;
 
     fn value(self) -> u32 {
         match self {
             Self::CurrentThread => Self::CURRENT_THREAD_VALUE,
+            Self::ProcessHeap => Self::PROCESS_HEAP_VALUE,
         }
     }
 
     fn from_value(value: u32) -> Option<Self> {
         match value {
             Self::CURRENT_THREAD_VALUE => Some(Self::CurrentThread),
+            Self::PROCESS_HEAP_VALUE => Some(Self::ProcessHeap),
             _ => None,
         }
     }
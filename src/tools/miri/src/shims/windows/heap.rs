@@ -0,0 +1,159 @@
+use std::iter;
+
+use rustc_target::abi::Size;
+
+use crate::*;
+use shims::windows::handle::{EvalContextExt as _, Handle, PseudoHandle};
+
+// The `HeapAlloc` family's flags, from `winnt.h`.
+const HEAP_NO_SERIALIZE: u32 = 0x00000001;
+const HEAP_ZERO_MEMORY: u32 = 0x00000008;
+const HEAP_REALLOC_IN_PLACE_ONLY: u32 = 0x00000010;
+
+impl<'mir, 'tcx: 'mir> EvalContextExtPrivate<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+trait EvalContextExtPrivate<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Checks the heap handle and the flags that are passed to every function of the `HeapAlloc`
+    /// family. We only support the process heap, and no flags other than `allowed_flags`.
+    fn check_heap_args(
+        &mut self,
+        name: &str,
+        heap_op: &OpTy<'tcx, Provenance>,
+        flags_op: &OpTy<'tcx, Provenance>,
+        allowed_flags: u32,
+    ) -> InterpResult<'tcx, u32> {
+        let this = self.eval_context_mut();
+
+        let heap = this.read_scalar(heap_op)?;
+        if Handle::from_scalar(heap, this)? != Some(Handle::Pseudo(PseudoHandle::ProcessHeap)) {
+            this.invalid_handle(name)?;
+        }
+        // Miri runs one thread at a time, so there is nothing to serialize in the first place.
+        let flags = this.read_scalar(flags_op)?.to_u32()? & !HEAP_NO_SERIALIZE;
+        if flags & !allowed_flags != 0 {
+            throw_unsup_format!("unsupported flags {:#x} in `{}`", flags, name);
+        }
+        Ok(flags)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+
+#[allow(non_snake_case)]
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    fn GetProcessHeap(&mut self) -> Scalar<Provenance> {
+        let this = self.eval_context_mut();
+        Handle::Pseudo(PseudoHandle::ProcessHeap).to_scalar(this)
+    }
+
+    fn HeapAlloc(
+        &mut self,
+        heap_op: &OpTy<'tcx, Provenance>,
+        flags_op: &OpTy<'tcx, Provenance>,
+        size_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, Pointer<Option<Provenance>>> {
+        let this = self.eval_context_mut();
+
+        let flags = this.check_heap_args("HeapAlloc", heap_op, flags_op, HEAP_ZERO_MEMORY)?;
+        let size = this.read_scalar(size_op)?.to_machine_usize(this)?;
+        let zero_init = flags & HEAP_ZERO_MEMORY != 0;
+
+        // Unlike `malloc`, `HeapAlloc` returns a unique pointer even if `size` is 0.
+        let align = this.min_align(size, MiriMemoryKind::WinHeap);
+        let ptr = this.allocate_ptr(Size::from_bytes(size), align, MiriMemoryKind::WinHeap.into())?;
+        if zero_init {
+            // We just allocated this, the access is definitely in-bounds and fits into our address space.
+            this.write_bytes_ptr(
+                ptr.into(),
+                iter::repeat(0u8).take(usize::try_from(size).unwrap()),
+            )
+            .unwrap();
+        }
+        Ok(ptr.into())
+    }
+
+    fn HeapFree(
+        &mut self,
+        heap_op: &OpTy<'tcx, Provenance>,
+        flags_op: &OpTy<'tcx, Provenance>,
+        ptr_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, i32> {
+        let this = self.eval_context_mut();
+
+        this.check_heap_args("HeapFree", heap_op, flags_op, 0)?;
+        let ptr = this.read_pointer(ptr_op)?;
+        // Unlike `free`, `HeapFree` does not accept null pointers.
+        if this.ptr_is_null(ptr)? {
+            throw_ub_format!("`HeapFree` called on a null pointer");
+        }
+        this.free(ptr, MiriMemoryKind::WinHeap)?;
+        Ok(1)
+    }
+
+    fn HeapReAlloc(
+        &mut self,
+        heap_op: &OpTy<'tcx, Provenance>,
+        flags_op: &OpTy<'tcx, Provenance>,
+        ptr_op: &OpTy<'tcx, Provenance>,
+        size_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, Pointer<Option<Provenance>>> {
+        let this = self.eval_context_mut();
+
+        let flags = this.check_heap_args(
+            "HeapReAlloc",
+            heap_op,
+            flags_op,
+            HEAP_ZERO_MEMORY | HEAP_REALLOC_IN_PLACE_ONLY,
+        )?;
+        let ptr = this.read_pointer(ptr_op)?;
+        let new_size = this.read_scalar(size_op)?.to_machine_usize(this)?;
+
+        // Unlike `realloc`, this neither accepts null nor frees the memory if `new_size` is 0.
+        if this.ptr_is_null(ptr)? {
+            throw_ub_format!("`HeapReAlloc` called on a null pointer");
+        }
+        this.check_heap_dealloc(ptr, MiriMemoryKind::WinHeap, None)?;
+        let old_size = this.malloc_size(ptr, MiriMemoryKind::WinHeap, "HeapReAlloc")?;
+        if flags & HEAP_REALLOC_IN_PLACE_ONLY != 0 {
+            // We never resize allocations in place. This is a legal way for the call to fail, and
+            // it leaves the allocation untouched.
+            let not_enough_memory = this.eval_windows("c", "ERROR_NOT_ENOUGH_MEMORY")?;
+            this.set_last_error(not_enough_memory)?;
+            return Ok(Pointer::null());
+        }
+
+        let new_align = this.min_align(new_size, MiriMemoryKind::WinHeap);
+        let new_ptr = this.reallocate_ptr(
+            ptr,
+            None,
+            Size::from_bytes(new_size),
+            new_align,
+            MiriMemoryKind::WinHeap.into(),
+        )?;
+        if flags & HEAP_ZERO_MEMORY != 0 && new_size > old_size {
+            // Only the bytes beyond the old size are zeroed.
+            #[allow(clippy::integer_arithmetic)] // `new_size > old_size`
+            let grown = new_size - old_size;
+            this.write_bytes_ptr(
+                new_ptr.offset(Size::from_bytes(old_size), this)?.into(),
+                iter::repeat(0u8).take(usize::try_from(grown).unwrap()),
+            )?;
+        }
+        Ok(new_ptr.into())
+    }
+
+    fn HeapSize(
+        &mut self,
+        heap_op: &OpTy<'tcx, Provenance>,
+        flags_op: &OpTy<'tcx, Provenance>,
+        ptr_op: &OpTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, u64> {
+        let this = self.eval_context_mut();
+
+        this.check_heap_args("HeapSize", heap_op, flags_op, 0)?;
+        let ptr = this.read_pointer(ptr_op)?;
+        if this.ptr_is_null(ptr)? {
+            throw_ub_format!("`HeapSize` called on a null pointer");
+        }
+        this.malloc_size(ptr, MiriMemoryKind::WinHeap, "HeapSize")
+    }
+}
@@ -1,6 +1,8 @@
 pub mod dlsym;
 pub mod foreign_items;
 
+mod console;
 mod handle;
+mod heap;
 mod sync;
 mod thread;
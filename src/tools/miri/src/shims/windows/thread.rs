@@ -16,7 +16,7 @@ fn CreateThread(
         arg_op: &OpTy<'tcx, Provenance>,
         flags_op: &OpTy<'tcx, Provenance>,
         thread_op: &OpTy<'tcx, Provenance>,
-    ) -> InterpResult<'tcx, ThreadId> {
+    ) -> InterpResult<'tcx, Handle> {
         let this = self.eval_context_mut();
 
         let security = this.read_pointer(security_op)?;
@@ -46,13 +46,20 @@ fn CreateThread(
             throw_unsup_format!("non-null `lpThreadAttributes` in `CreateThread`")
         }
 
-        this.start_thread(
+        if this.spawn_should_fail() {
+            let not_enough_memory = this.eval_windows("c", "ERROR_NOT_ENOUGH_MEMORY")?;
+            this.set_last_error(not_enough_memory)?;
+            return Ok(Handle::Null);
+        }
+
+        let thread_id = this.start_thread(
             thread,
             start_routine,
             Abi::System { unwind: false },
             func_arg,
             this.layout_of(this.tcx.types.u32)?,
-        )
+        )?;
+        Ok(Handle::Thread(thread_id))
     }
 
     fn WaitForSingleObject(
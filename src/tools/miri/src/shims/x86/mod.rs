@@ -0,0 +1,5 @@
+//! Shims for the LLVM intrinsics that `core::arch::x86` and `core::arch::x86_64` use to implement
+//! the vendor intrinsics. The ones that `core::arch` implements with generic SIMD intrinsics do
+//! not need any support from us.
+
+pub mod sse2;
@@ -0,0 +1,578 @@
+use std::cmp::Ordering;
+
+use rustc_apfloat::{
+    ieee::{Double, Single},
+    Float, FloatConvert, Round, Status,
+};
+use rustc_middle::ty::{self, FloatTy};
+use rustc_span::Symbol;
+use rustc_target::abi::Size;
+use rustc_target::spec::abi::Abi;
+
+use crate::*;
+use shims::foreign_items::EmulateByNameResult;
+
+#[derive(Copy, Clone)]
+enum ShiftOp {
+    Left,
+    RightLogic,
+    RightArith,
+}
+
+#[derive(Copy, Clone)]
+enum FloatBinOp {
+    Min,
+    Max,
+    /// A comparison with the given `cmppd`/`cmpsd` predicate.
+    Cmp(i8),
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExtPrivate<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+trait EvalContextExtPrivate<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Applies `which` to the `f64` elements of `left` and `right`. If `low_only` is set, only the
+    /// lowest element is computed and the others are copied from `left`, which is what the `*.sd`
+    /// versions of the intrinsics do.
+    fn sse2_float_bin_op(
+        &mut self,
+        which: FloatBinOp,
+        low_only: bool,
+        left: &OpTy<'tcx, Provenance>,
+        right: &OpTy<'tcx, Provenance>,
+        dest: &PlaceTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+
+        let (left, left_len) = this.operand_to_simd(left)?;
+        let (right, right_len) = this.operand_to_simd(right)?;
+        let (dest, dest_len) = this.place_to_simd(dest)?;
+
+        assert_eq!(dest_len, left_len);
+        assert_eq!(dest_len, right_len);
+
+        for i in 0..dest_len {
+            let left = this.read_scalar(&this.mplace_index(&left, i)?.into())?;
+            let dest = this.mplace_index(&dest, i)?;
+            let res = if i == 0 || !low_only {
+                let right = this.read_scalar(&this.mplace_index(&right, i)?.into())?;
+                float_bin_op(which, left.to_f64()?, right.to_f64()?)?
+            } else {
+                left
+            };
+            this.write_scalar(res, &dest.into())?;
+        }
+        Ok(())
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Emulates the `llvm.x86.sse2.*` intrinsics behind the SSE2 functions of `core::arch` by
+    /// computing them element by element.
+    fn emulate_x86_sse2_intrinsic(
+        &mut self,
+        link_name: Symbol,
+        abi: Abi,
+        args: &[OpTy<'tcx, Provenance>],
+        dest: &PlaceTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, EmulateByNameResult<'mir, 'tcx>> {
+        let this = self.eval_context_mut();
+        // The caller has already checked the prefix.
+        let unprefixed_name = link_name.as_str().strip_prefix("llvm.x86.sse2.").unwrap();
+
+        match unprefixed_name {
+            // Used to implement `_mm_pause`.
+            "pause" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.yield_active_thread();
+            }
+            // Used to implement `_mm_lfence`, which orders loads like an acquire fence.
+            "lfence" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.atomic_fence(AtomicFenceOrd::Acquire)?;
+            }
+            // Used to implement `_mm_mfence`, which orders all memory accesses like a
+            // sequentially consistent fence.
+            "mfence" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.atomic_fence(AtomicFenceOrd::SeqCst)?;
+            }
+            // Used to implement `_mm_madd_epi16`, which multiplies the `i16` elements and adds
+            // up adjacent pairs of the products.
+            "pmadd.wd" => {
+                let [left, right] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (left, left_len) = this.operand_to_simd(left)?;
+                let (right, right_len) = this.operand_to_simd(right)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(left_len, right_len);
+                assert_eq!(dest_len.checked_mul(2).unwrap(), left_len);
+
+                for i in 0..dest_len {
+                    let j1 = i.checked_mul(2).unwrap();
+                    let j2 = j1.checked_add(1).unwrap();
+
+                    let left1 = this.read_scalar(&this.mplace_index(&left, j1)?.into())?.to_i16()?;
+                    let left2 = this.read_scalar(&this.mplace_index(&left, j2)?.into())?.to_i16()?;
+                    let right1 =
+                        this.read_scalar(&this.mplace_index(&right, j1)?.into())?.to_i16()?;
+                    let right2 =
+                        this.read_scalar(&this.mplace_index(&right, j2)?.into())?.to_i16()?;
+                    let dest = this.mplace_index(&dest, i)?;
+
+                    // The products always fit into an `i32`, but their sum wraps around if all
+                    // four inputs are `i16::MIN`.
+                    let mul1 = i32::from(left1).checked_mul(right1.into()).unwrap();
+                    let mul2 = i32::from(left2).checked_mul(right2.into()).unwrap();
+                    let res = mul1.wrapping_add(mul2);
+
+                    this.write_scalar(Scalar::from_i32(res), &dest.into())?;
+                }
+            }
+            // Used to implement `_mm_mulhi_epi16` and `_mm_mulhi_epu16`, which keep the high
+            // half of each 32-bit product.
+            "pmulh.w" | "pmulhu.w" => {
+                let [left, right] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (left, left_len) = this.operand_to_simd(left)?;
+                let (right, right_len) = this.operand_to_simd(right)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(dest_len, left_len);
+                assert_eq!(dest_len, right_len);
+
+                for i in 0..dest_len {
+                    let left = this.read_scalar(&this.mplace_index(&left, i)?.into())?;
+                    let right = this.read_scalar(&this.mplace_index(&right, i)?.into())?;
+                    let dest = this.mplace_index(&dest, i)?;
+
+                    let res = if unprefixed_name == "pmulh.w" {
+                        let mul = i32::from(left.to_i16()?).checked_mul(right.to_i16()?.into());
+                        let high = mul.unwrap().checked_shr(16).unwrap();
+                        Scalar::from_int(high, dest.layout.size)
+                    } else {
+                        let mul = u32::from(left.to_u16()?).checked_mul(right.to_u16()?.into());
+                        let high = mul.unwrap().checked_shr(16).unwrap();
+                        Scalar::from_uint(high, dest.layout.size)
+                    };
+
+                    this.write_scalar(res, &dest.into())?;
+                }
+            }
+            // Used to implement `_mm_avg_epu8` and `_mm_avg_epu16`, which compute the average of
+            // the unsigned elements, rounding up.
+            "pavg.b" | "pavg.w" => {
+                let [left, right] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (left, left_len) = this.operand_to_simd(left)?;
+                let (right, right_len) = this.operand_to_simd(right)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(dest_len, left_len);
+                assert_eq!(dest_len, right_len);
+
+                for i in 0..dest_len {
+                    let dest = this.mplace_index(&dest, i)?;
+                    let size = dest.layout.size;
+                    let left = this.read_scalar(&this.mplace_index(&left, i)?.into())?;
+                    let right = this.read_scalar(&this.mplace_index(&right, i)?.into())?;
+
+                    // The elements are at most 16 bits wide, so this cannot overflow.
+                    let sum = left.to_bits(size)?.checked_add(right.to_bits(size)?).unwrap();
+                    let res = sum.checked_add(1).unwrap().checked_shr(1).unwrap();
+
+                    this.write_scalar(Scalar::from_uint(res, size), &dest.into())?;
+                }
+            }
+            // Used to implement `_mm_sad_epu8`, which sums up the absolute differences of each
+            // group of eight `u8` elements into a `u64` element.
+            "psad.bw" => {
+                let [left, right] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (left, left_len) = this.operand_to_simd(left)?;
+                let (right, right_len) = this.operand_to_simd(right)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(left_len, right_len);
+                assert_eq!(dest_len.checked_mul(8).unwrap(), left_len);
+
+                for i in 0..dest_len {
+                    let dest = this.mplace_index(&dest, i)?;
+
+                    let mut sum: u64 = 0;
+                    for j in 0..8 {
+                        let idx = i.checked_mul(8).unwrap().checked_add(j).unwrap();
+                        let left = this.read_scalar(&this.mplace_index(&left, idx)?.into())?;
+                        let right = this.read_scalar(&this.mplace_index(&right, idx)?.into())?;
+                        let diff = left.to_u8()?.abs_diff(right.to_u8()?);
+                        sum = sum.checked_add(diff.into()).unwrap();
+                    }
+
+                    this.write_scalar(Scalar::from_u64(sum), &dest.into())?;
+                }
+            }
+            // Used to implement the `_mm_sll_*`, `_mm_srl_*`, `_mm_sra_*` functions and their
+            // `_mm_*i_*` versions, which take the count as an immediate.
+            #[rustfmt::skip]
+            | "psll.w" | "psll.d" | "psll.q"
+            | "psrl.w" | "psrl.d" | "psrl.q"
+            | "psra.w" | "psra.d"
+            | "pslli.w" | "pslli.d" | "pslli.q"
+            | "psrli.w" | "psrli.d" | "psrli.q"
+            | "psrai.w" | "psrai.d" => {
+                let [op, count] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (op, op_len) = this.operand_to_simd(op)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(dest_len, op_len);
+
+                let (which, immediate) = match unprefixed_name {
+                    "psll.w" | "psll.d" | "psll.q" => (ShiftOp::Left, false),
+                    "psrl.w" | "psrl.d" | "psrl.q" => (ShiftOp::RightLogic, false),
+                    "psra.w" | "psra.d" => (ShiftOp::RightArith, false),
+                    "pslli.w" | "pslli.d" | "pslli.q" => (ShiftOp::Left, true),
+                    "psrli.w" | "psrli.d" | "psrli.q" => (ShiftOp::RightLogic, true),
+                    "psrai.w" | "psrai.d" => (ShiftOp::RightArith, true),
+                    _ => unreachable!(),
+                };
+                let count = if immediate {
+                    // The count is an `i32`, which is treated as unsigned.
+                    u64::from(this.read_scalar(count)?.to_u32()?)
+                } else {
+                    // The count is a vector, of which only the low 64 bits are used.
+                    let (count, _) = this.operand_to_simd(count)?;
+                    let count = count.offset(Size::ZERO, this.machine.layouts.u64, this)?;
+                    this.read_scalar(&count.into())?.to_u64()?
+                };
+
+                for i in 0..dest_len {
+                    let dest = this.mplace_index(&dest, i)?;
+                    let size = dest.layout.size;
+                    let op = this.read_scalar(&this.mplace_index(&op, i)?.into())?.to_bits(size)?;
+
+                    let res = shift_element(which, op, count, size);
+
+                    this.write_scalar(Scalar::from_uint(res, size), &dest.into())?;
+                }
+            }
+            // Used to implement `_mm_packs_epi16`, `_mm_packs_epi32` and `_mm_packus_epi16`,
+            // which narrow the elements of both operands with saturation.
+            "packsswb.128" | "packssdw.128" | "packuswb.128" => {
+                let [left, right] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (left, left_len) = this.operand_to_simd(left)?;
+                let (right, right_len) = this.operand_to_simd(right)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(left_len, right_len);
+                assert_eq!(dest_len, left_len.checked_mul(2).unwrap());
+
+                let unsigned = unprefixed_name == "packuswb.128";
+
+                // The elements of `left` go into the lower half of the result, those of `right`
+                // into the upper half.
+                for i in 0..left_len {
+                    let left = this.read_immediate(&this.mplace_index(&left, i)?.into())?;
+                    let right = this.read_immediate(&this.mplace_index(&right, i)?.into())?;
+                    let left_dest = this.mplace_index(&dest, i)?;
+                    let right_dest = this.mplace_index(&dest, i.checked_add(left_len).unwrap())?;
+
+                    let left = left.to_scalar().to_int(left.layout.size)?;
+                    let right = right.to_scalar().to_int(right.layout.size)?;
+                    let left_res = saturate(left, left_dest.layout.size, unsigned);
+                    let right_res = saturate(right, right_dest.layout.size, unsigned);
+
+                    this.write_scalar(left_res, &left_dest.into())?;
+                    this.write_scalar(right_res, &right_dest.into())?;
+                }
+            }
+            // Used to implement `_mm_movemask_epi8` and `_mm_movemask_pd`, which collect the
+            // most significant bit of every element into an integer.
+            "pmovmskb.128" | "movmsk.pd" => {
+                let [op] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (op, op_len) = this.operand_to_simd(op)?;
+
+                let mut res: u32 = 0;
+                for i in 0..op_len {
+                    let op = this.read_immediate(&this.mplace_index(&op, i)?.into())?;
+                    let size = op.layout.size;
+                    let bits = op.to_scalar().to_bits(size)?;
+
+                    let sign_bit = u32::try_from(size.bits()).unwrap().checked_sub(1).unwrap();
+                    let sign = u32::try_from(bits.checked_shr(sign_bit).unwrap()).unwrap();
+                    res |= sign.checked_shl(u32::try_from(i).unwrap()).unwrap();
+                }
+
+                this.write_scalar(Scalar::from_u32(res), dest)?;
+            }
+            // Used to implement the `_mm_min_*` and `_mm_max_*` functions for `f64`. Unlike
+            // `f64::min` and `f64::max`, these return the second operand if either operand is
+            // NaN or both are zero.
+            "min.sd" | "max.sd" | "min.pd" | "max.pd" => {
+                let [left, right] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let which = if unprefixed_name.starts_with("min") {
+                    FloatBinOp::Min
+                } else {
+                    FloatBinOp::Max
+                };
+                let low_only = unprefixed_name.ends_with(".sd");
+                this.sse2_float_bin_op(which, low_only, left, right, dest)?;
+            }
+            // Used to implement the `_mm_cmp*_sd` and `_mm_cmp*_pd` functions, which produce a
+            // mask of all-0 or all-1 bits for each element.
+            "cmp.sd" | "cmp.pd" => {
+                let [left, right, imm] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let imm = this.read_scalar(imm)?.to_i8()?;
+                let low_only = unprefixed_name == "cmp.sd";
+                this.sse2_float_bin_op(FloatBinOp::Cmp(imm), low_only, left, right, dest)?;
+            }
+            // Used to implement `_mm_sqrt_sd` and `_mm_sqrt_pd`.
+            "sqrt.sd" | "sqrt.pd" => {
+                let [op] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (op, op_len) = this.operand_to_simd(op)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(dest_len, op_len);
+
+                let low_only = unprefixed_name == "sqrt.sd";
+                for i in 0..dest_len {
+                    let op = this.read_scalar(&this.mplace_index(&op, i)?.into())?;
+                    let dest = this.mplace_index(&dest, i)?;
+
+                    let res = if i == 0 || !low_only {
+                        // FIXME using host floats
+                        let op = f64::from_bits(op.to_u64()?);
+                        Scalar::from_u64(op.sqrt().to_bits())
+                    } else {
+                        op
+                    };
+
+                    this.write_scalar(res, &dest.into())?;
+                }
+            }
+            // Used to implement the `_mm_comi*_sd` and `_mm_ucomi*_sd` functions, which compare
+            // the lowest elements and return a `bool` as `i32`. The two versions only differ in
+            // whether quiet NaNs raise a floating-point exception, which we do not emulate.
+            #[rustfmt::skip]
+            | "comieq.sd" | "comilt.sd" | "comile.sd"
+            | "comigt.sd" | "comige.sd" | "comineq.sd"
+            | "ucomieq.sd" | "ucomilt.sd" | "ucomile.sd"
+            | "ucomigt.sd" | "ucomige.sd" | "ucomineq.sd" => {
+                let [left, right] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (left, _) = this.operand_to_simd(left)?;
+                let (right, _) = this.operand_to_simd(right)?;
+                let left = this.read_scalar(&this.mplace_index(&left, 0)?.into())?.to_f64()?;
+                let right = this.read_scalar(&this.mplace_index(&right, 0)?.into())?.to_f64()?;
+
+                // Like the Rust comparison operators, all comparisons except "not equal" are
+                // false if either operand is NaN.
+                let ord = left.partial_cmp(&right);
+                let res = match unprefixed_name.trim_start_matches('u') {
+                    "comieq.sd" => ord == Some(Ordering::Equal),
+                    "comilt.sd" => ord == Some(Ordering::Less),
+                    "comile.sd" => matches!(ord, Some(Ordering::Less | Ordering::Equal)),
+                    "comigt.sd" => ord == Some(Ordering::Greater),
+                    "comige.sd" => matches!(ord, Some(Ordering::Greater | Ordering::Equal)),
+                    "comineq.sd" => ord != Some(Ordering::Equal),
+                    _ => unreachable!(),
+                };
+
+                this.write_scalar(Scalar::from_i32(res.into()), dest)?;
+            }
+            // Used to implement `_mm_cvtepi32_ps`.
+            "cvtdq2ps" => {
+                let [op] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (op, op_len) = this.operand_to_simd(op)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(dest_len, op_len);
+
+                for i in 0..dest_len {
+                    let op = this.read_scalar(&this.mplace_index(&op, i)?.into())?.to_i32()?;
+                    let dest = this.mplace_index(&dest, i)?;
+
+                    let res = Single::from_i128(op.into()).value;
+
+                    this.write_scalar(Scalar::from_f32(res), &dest.into())?;
+                }
+            }
+            // Used to implement `_mm_cvtps_epi32`, `_mm_cvtpd_epi32` and their truncating
+            // `_mm_cvtt*` versions. The conversions from `f64` fill the upper half of the result
+            // with zeros.
+            "cvtps2dq" | "cvttps2dq" | "cvtpd2dq" | "cvttpd2dq" => {
+                let [op] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (op, op_len) = this.operand_to_simd(op)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert!(dest_len >= op_len);
+
+                let round = conversion_rounding(unprefixed_name);
+                for i in 0..dest_len {
+                    let dest = this.mplace_index(&dest, i)?;
+                    let res = if i < op_len {
+                        let op = this.read_immediate(&this.mplace_index(&op, i)?.into())?;
+                        float_to_int(&op, round, dest.layout.size)?
+                    } else {
+                        Scalar::from_int(0, dest.layout.size)
+                    };
+                    this.write_scalar(res, &dest.into())?;
+                }
+            }
+            // Used to implement `_mm_cvtsd_si32`, `_mm_cvtsd_si64` and their truncating
+            // `_mm_cvtt*` versions, which convert the lowest element.
+            "cvtsd2si" | "cvttsd2si" | "cvtsd2si64" | "cvttsd2si64" => {
+                let [op] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (op, _) = this.operand_to_simd(op)?;
+                let op = this.read_immediate(&this.mplace_index(&op, 0)?.into())?;
+
+                let round = conversion_rounding(unprefixed_name);
+                let res = float_to_int(&op, round, dest.layout.size)?;
+
+                this.write_scalar(res, dest)?;
+            }
+            // Used to implement `_mm_cvtsd_ss` and `_mm_cvtss_sd`, which convert the lowest
+            // element of `right` and copy the others from `left`.
+            "cvtsd2ss" | "cvtss2sd" => {
+                let [left, right] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+
+                let (left, left_len) = this.operand_to_simd(left)?;
+                let (right, _) = this.operand_to_simd(right)?;
+                let (dest, dest_len) = this.place_to_simd(dest)?;
+
+                assert_eq!(dest_len, left_len);
+
+                let right = this.read_scalar(&this.mplace_index(&right, 0)?.into())?;
+                let res = if unprefixed_name == "cvtsd2ss" {
+                    Scalar::from_f32(right.to_f64()?.convert(&mut false).value)
+                } else {
+                    Scalar::from_f64(right.to_f32()?.convert(&mut false).value)
+                };
+                this.write_scalar(res, &this.mplace_index(&dest, 0)?.into())?;
+
+                for i in 1..dest_len {
+                    this.copy_op(
+                        &this.mplace_index(&left, i)?.into(),
+                        &this.mplace_index(&dest, i)?.into(),
+                        /*allow_transmute*/ false,
+                    )?;
+                }
+            }
+            _ => return Ok(EmulateByNameResult::NotSupported),
+        }
+        Ok(EmulateByNameResult::NeedsJumping)
+    }
+}
+
+/// Shifts the `size`-sized element `val` by `count` bits. Unlike the Rust shift operators, this
+/// is fine with counts of at least the element width.
+fn shift_element(which: ShiftOp, val: u128, count: u64, size: Size) -> u128 {
+    let count = u32::try_from(count).ok().filter(|&count| u64::from(count) < size.bits());
+    match (which, count) {
+        (ShiftOp::Left, Some(count)) => size.truncate(val.checked_shl(count).unwrap()),
+        (ShiftOp::RightLogic, Some(count)) => val.checked_shr(count).unwrap(),
+        // Shifting by at least the element width shifts out all bits...
+        (ShiftOp::Left | ShiftOp::RightLogic, None) => 0,
+        (ShiftOp::RightArith, count) => {
+            // ... except for arithmetic right shifts, which fill the element with the sign bit.
+            let max_count = u32::try_from(size.bits()).unwrap().checked_sub(1).unwrap();
+            let count = count.unwrap_or(max_count);
+            #[allow(clippy::cast_possible_wrap)] // we want to reinterpret the sign-extended bits
+            let val = size.sign_extend(val) as i128;
+            #[allow(clippy::cast_sign_loss)] // we want to reinterpret the bits
+            let res = val.checked_shr(count).unwrap() as u128;
+            size.truncate(res)
+        }
+    }
+}
+
+/// Converts `val` to an integer of the given size, saturating at its bounds.
+fn saturate(val: i128, size: Size, unsigned: bool) -> Scalar<Provenance> {
+    if unsigned {
+        let max = i128::try_from(size.unsigned_int_max()).unwrap();
+        Scalar::from_uint(u128::try_from(val.clamp(0, max)).unwrap(), size)
+    } else {
+        Scalar::from_int(val.clamp(size.signed_int_min(), size.signed_int_max()), size)
+    }
+}
+
+fn float_bin_op<'tcx>(
+    which: FloatBinOp,
+    left: Double,
+    right: Double,
+) -> InterpResult<'tcx, Scalar<Provenance>> {
+    let ord = left.partial_cmp(&right);
+    Ok(match which {
+        FloatBinOp::Min => Scalar::from_f64(if ord == Some(Ordering::Less) { left } else { right }),
+        FloatBinOp::Max =>
+            Scalar::from_f64(if ord == Some(Ordering::Greater) { left } else { right }),
+        FloatBinOp::Cmp(imm) => {
+            let res = match imm {
+                // EQ
+                0 => ord == Some(Ordering::Equal),
+                // LT
+                1 => ord == Some(Ordering::Less),
+                // LE
+                2 => matches!(ord, Some(Ordering::Less | Ordering::Equal)),
+                // UNORD
+                3 => ord.is_none(),
+                // NEQ
+                4 => ord != Some(Ordering::Equal),
+                // NLT
+                5 => ord != Some(Ordering::Less),
+                // NLE
+                6 => !matches!(ord, Some(Ordering::Less | Ordering::Equal)),
+                // ORD
+                7 => ord.is_some(),
+                _ => throw_unsup_format!("invalid SSE2 floating-point comparison predicate {imm}"),
+            };
+            Scalar::from_u64(if res { u64::MAX } else { 0 })
+        }
+    })
+}
+
+/// The conversions round with the rounding mode in the `MXCSR` register, which we assume to be
+/// the default "round to nearest, ties to even". The `cvtt*` versions always truncate.
+fn conversion_rounding(unprefixed_name: &str) -> Round {
+    if unprefixed_name.starts_with("cvtt") { Round::TowardZero } else { Round::NearestTiesToEven }
+}
+
+/// Converts the float `op` to an integer of the given size. Like the hardware, this returns the
+/// "integer indefinite" value, which is the smallest integer, if `op` is NaN or out of range.
+fn float_to_int<'tcx>(
+    op: &ImmTy<'tcx, Provenance>,
+    round: Round,
+    size: Size,
+) -> InterpResult<'tcx, Scalar<Provenance>> {
+    let ty::Float(float_ty) = op.layout.ty.kind() else {
+        bug!("SSE2 conversion operand is not a float")
+    };
+    let op = op.to_scalar();
+    let res = match float_ty {
+        FloatTy::F32 => op.to_f32()?.to_i128_r(size.bits_usize(), round, &mut false),
+        FloatTy::F64 => op.to_f64()?.to_i128_r(size.bits_usize(), round, &mut false),
+    };
+    Ok(if res.status.contains(Status::INVALID_OP) {
+        Scalar::from_int(size.signed_int_min(), size)
+    } else {
+        Scalar::from_int(res.value, size)
+    })
+}
@@ -195,6 +195,12 @@ pub fn unbuild(self) -> DiagnosticCxBuilder<'span, 'ecx, 'mir, 'tcx> {
             threads: self.threads,
         }
     }
+
+    /// Returns the given violation as an error, or reports it and returns `Ok` if we continue
+    /// after errors. See `MiriMachine::recover_from_error`.
+    pub fn recover(&mut self, e: InterpError<'tcx>) -> InterpResult<'tcx> {
+        self.current_span.machine().recover_from_error(e)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -338,7 +338,9 @@ fn item_popped(
         //    which ends up about linear in the number of protected tags in the program into a
         //    constant time check (and a slow linear, because the tags in the frames aren't contiguous).
         if global.protected_tags.contains(&item.tag()) {
-            return Err(dcx.protector_error(item).into());
+            // If we continue after this, the item is simply popped like an unprotected one.
+            let err = dcx.protector_error(item);
+            return dcx.recover(err);
         }
         Ok(())
     }
@@ -358,8 +360,15 @@ fn access(
         // Two main steps: Find granting item, remove incompatible items above.
 
         // Step 1: Find granting item.
-        let granting_idx =
-            self.find_granting(access, tag, exposed_tags).map_err(|_| dcx.access_error(self))?;
+        let Ok(granting_idx) = self.find_granting(access, tag, exposed_tags) else {
+            let err = dcx.access_error(self);
+            dcx.recover(err)?;
+            // We continue after the error. Forget everything we know about this location, so that
+            // this pointer and all others derived so far may access it: we already reported the
+            // one violation, and its consequences would only lead to more reports.
+            self.set_unknown_bottom(global.next_ptr_tag);
+            return Ok(());
+        };
 
         // Step 2: Remove incompatible items above them.  Make sure we do not remove protected
         // items.  Behavior differs for reads and writes.
@@ -442,8 +451,11 @@ fn dealloc(
         exposed_tags: &FxHashSet<SbTag>,
     ) -> InterpResult<'tcx> {
         // Step 1: Make sure there is a granting item.
-        self.find_granting(AccessKind::Write, tag, exposed_tags)
-            .map_err(|_| dcx.dealloc_error())?;
+        if self.find_granting(AccessKind::Write, tag, exposed_tags).is_err() {
+            // If we continue after this, we still check the protectors below.
+            let err = dcx.dealloc_error();
+            dcx.recover(err)?;
+        }
 
         // Step 2: Consider all items removed. This checks for protectors.
         for idx in (0..self.len()).rev() {
@@ -476,9 +488,14 @@ fn grant(
 
         // Now we figure out which item grants our parent (`derived_from`) this kind of access.
         // We use that to determine where to put the new item.
-        let granting_idx = self
-            .find_granting(access, derived_from, exposed_tags)
-            .map_err(|_| dcx.grant_error(new.perm(), self))?;
+        let Ok(granting_idx) = self.find_granting(access, derived_from, exposed_tags) else {
+            let err = dcx.grant_error(new.perm(), self);
+            dcx.recover(err)?;
+            // We continue after the error. Like for a failed access, forget everything we know
+            // about this location; this also covers the new pointer.
+            self.set_unknown_bottom(global.next_ptr_tag);
+            return Ok(());
+        };
 
         // Compute where to put the new item.
         // Either way, we ensure that we insert the new item in a way such that between
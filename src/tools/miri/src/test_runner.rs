@@ -0,0 +1,276 @@
+//! Running the tests of a libtest harness separately from each other (`-Zmiri-test-jobs`).
+//!
+//! Every test is interpreted by a Miri machine of its own, and the output of each machine is
+//! captured, so that we can print the results of the tests as they come in without mixing up
+//! the output of tests that run at the same time.
+//!
+//! A `TyCtxt` cannot be shared between host threads, so the driver gives every host thread a
+//! compiler session of its own, which takes tests out of a shared `TestQueue` until none are left.
+//! The diagnostics of such a session go to a buffer, too, so that we can tell which test they
+//! belong to.
+
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::EntryFnType;
+
+use crate::*;
+
+/// The exit code libtest uses when some tests failed.
+const TESTS_FAILED_EXIT_CODE: i64 = 101;
+
+/// The exit code we use when Miri reported an error for some of the tests. This is the exit code
+/// rustc uses for errors.
+const MIRI_ERROR_EXIT_CODE: i64 = 1;
+
+/// The libtest options that take a value, so that we can tell the values apart from the test name
+/// filters.
+const OPTIONS_WITH_VALUE: &[&str] =
+    &["--color", "--format", "--logfile", "--shuffle-seed", "--skip", "--test-threads", "-Z"];
+
+/// Whether we can take care of the program with a `TestQueue`: it needs to be a libtest harness
+/// that is asked to run its tests and to print their results in the default format.
+pub fn can_run_tests_in_parallel(tcx: TyCtxt<'_>, config: &MiriConfig) -> bool {
+    // We need the output of the tests to tell how they went.
+    if config.test_jobs <= 1 || !tcx.sess.opts.test || config.mute_stdout_stderr {
+        return false;
+    }
+    // The first argument is the name of the program.
+    let mut args = config.args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        let format = if arg == "--format" {
+            args.next().map(String::as_str)
+        } else {
+            arg.strip_prefix("--format=")
+        };
+        if format.map_or(false, |format| format != "pretty" && format != "terse") {
+            return false;
+        }
+        // We can't keep the output of tests apart if they print it right away.
+        if matches!(
+            arg.as_str(),
+            "--list" | "--bench" | "--nocapture" | "--show-output" | "--logfile" | "-h" | "--help"
+        ) {
+            return false;
+        }
+    }
+    true
+}
+
+/// The tests of a harness that still have to run, and the results of those that are done.
+pub struct TestQueue {
+    /// The arguments that select a single test are these, followed by the name of the test.
+    options: Vec<String>,
+    num_tests: usize,
+    tests: Mutex<VecDeque<String>>,
+    summary: Mutex<Summary>,
+}
+
+impl TestQueue {
+    /// Asks the harness with the given entry point which tests match the filters, and prints the
+    /// header libtest prints before it runs them. Returns the exit code of the harness if it
+    /// failed to list its tests, or `None` if Miri reported an error.
+    pub fn new<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        entry_id: DefId,
+        entry_type: EntryFnType,
+        config: &MiriConfig,
+    ) -> Result<TestQueue, Option<i64>> {
+        let args = &config.args[1..];
+
+        // Interpreting the harness to list the tests is much cheaper than running them, so we do
+        // that right here. Its diagnostics are not captured, but nothing runs at the same time.
+        let mut list_args = args.to_vec();
+        list_args.push("--list".to_string());
+        let (code, listing) = run_captured(tcx, entry_id, entry_type, config, list_args);
+        if code != Some(0) {
+            print!("{listing}");
+            return Err(code);
+        }
+        // Every test is listed on a line of its own, as `<name>: test` (or `<name>: bench`, we run
+        // those once, like libtest does).
+        let tests: VecDeque<String> = listing
+            .lines()
+            .filter_map(|line| line.strip_suffix(": test").or_else(|| line.strip_suffix(": bench")))
+            .map(str::to_string)
+            .collect();
+
+        // After listing the tests, we select them by name, so we have to drop the filters.
+        let mut options = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--format" {
+                args.next();
+            } else if arg.starts_with("--format=")
+                || matches!(arg.as_str(), "--exact" | "-q" | "--quiet")
+            {
+                // We pick the format of the output ourselves.
+            } else if OPTIONS_WITH_VALUE.contains(&arg.as_str()) {
+                options.push(arg.clone());
+                options.extend(args.next().cloned());
+            } else if arg.starts_with('-') {
+                options.push(arg.clone());
+            }
+        }
+        options.extend(["--format=pretty".to_string(), "--exact".to_string()]);
+
+        println!();
+        println!("running {} test{}", tests.len(), if tests.len() == 1 { "" } else { "s" });
+        Ok(TestQueue {
+            options,
+            num_tests: tests.len(),
+            tests: Mutex::new(tests),
+            summary: Mutex::new(Summary::default()),
+        })
+    }
+
+    /// Runs tests of the queue in the given compiler session until none are left, and prints the
+    /// status line of each test as soon as it is done. `diagnostics` is the buffer the session
+    /// emits its diagnostics to.
+    pub fn run_tests<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        entry_id: DefId,
+        entry_type: EntryFnType,
+        config: &MiriConfig,
+        diagnostics: &Mutex<Vec<u8>>,
+    ) {
+        loop {
+            // Don't hold the lock while the test runs.
+            let next = self.tests.lock().unwrap().pop_front();
+            let Some(test) = next else { break };
+            let mut args = self.options.clone();
+            args.push(test.clone());
+            let (code, output) = run_captured(tcx, entry_id, entry_type, config, args);
+            let diagnostics = mem::take(&mut *diagnostics.lock().unwrap());
+            let diagnostics = String::from_utf8_lossy(&diagnostics).into_owned();
+            self.summary.lock().unwrap().record(&test, code, output, diagnostics);
+        }
+    }
+
+    /// Prints the output of the failed tests and the summary of all tests, and returns the exit
+    /// code of the harness.
+    pub fn finish(self) -> i64 {
+        let mut summary = self.summary.into_inner().unwrap();
+        // Tests are only left over if no session managed to start running them.
+        for test in self.tests.into_inner().unwrap() {
+            summary.record(&test, None, String::new(), "the test did not run\n".to_string());
+        }
+        summary.finish(self.num_tests)
+    }
+}
+
+/// Runs the harness with the given arguments and returns its exit code (or `None` if Miri reported
+/// an error), together with everything it printed.
+fn run_captured<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    entry_id: DefId,
+    entry_type: EntryFnType,
+    config: &MiriConfig,
+    args: Vec<String>,
+) -> (Option<i64>, String) {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let mut config = config.clone();
+    config.args.truncate(1);
+    config.args.extend(args);
+    config.stdout_target = OutputTarget::Capture(Arc::clone(&output));
+    config.stderr_target = OutputTarget::Capture(Arc::clone(&output));
+    let code = eval_entry(tcx, entry_id, entry_type, config);
+    let output = String::from_utf8_lossy(&output.lock().unwrap()).into_owned();
+    (code, output)
+}
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    ignored: usize,
+    /// The failed tests, with what they printed about their failure.
+    failed: Vec<(String, String)>,
+    /// The number of tests in the harness, including those that the filters leave out.
+    total: Option<usize>,
+    /// Whether Miri reported an error for one of the tests.
+    miri_error: bool,
+}
+
+impl Summary {
+    /// Records the result of running `test` on its own, and prints its status line. `diagnostics`
+    /// are the diagnostics Miri emitted while the test ran.
+    fn record(&mut self, test: &str, code: Option<i64>, output: String, diagnostics: String) {
+        let Some(code) = code else {
+            // Miri stopped the test, so the harness never got to print its result.
+            println!("test {test} ... FAILED");
+            self.miri_error = true;
+            self.failed.push((test.to_string(), format!("---- {test} ----\n{diagnostics}\n")));
+            return;
+        };
+        let status =
+            output.lines().find(|line| line.starts_with("test ") && line.contains(" ... "));
+        let outcome = status.and_then(|status| status.rsplit(" ... ").next());
+        match status {
+            Some(status) => println!("{status}"),
+            None => println!("test {test} ... FAILED"),
+        }
+        // Every run leaves out all tests but one.
+        if self.total.is_none() {
+            self.total = output
+                .lines()
+                .find_map(|line| line.strip_prefix("test result: "))
+                .and_then(|result| {
+                    result.split("; ").find_map(|part| part.strip_suffix(" filtered out"))
+                })
+                .and_then(|n| n.parse::<usize>().ok())
+                .map(|n| n + 1);
+        }
+        match outcome {
+            Some("ok") if code == 0 => self.passed += 1,
+            Some(outcome) if outcome.starts_with("ignored") && code == 0 => self.ignored += 1,
+            _ => {
+                // Only keep the part of the output that explains the failure, if we find it.
+                let failure = output
+                    .split_once("\nfailures:\n\n")
+                    .and_then(|(_, rest)| rest.split_once("\nfailures:\n"))
+                    .map_or(output.as_str(), |(failure, _)| failure);
+                self.failed.push((test.to_string(), format!("{failure}{diagnostics}")));
+            }
+        }
+    }
+
+    /// Prints the output of the failed tests and the summary of all `num_tests` tests, and
+    /// returns the exit code of the harness.
+    fn finish(mut self, num_tests: usize) -> i64 {
+        if !self.failed.is_empty() {
+            self.failed.sort();
+            println!();
+            println!("failures:");
+            println!();
+            for (_, failure) in &self.failed {
+                print!("{failure}");
+            }
+            println!();
+            println!("failures:");
+            for (test, _) in &self.failed {
+                println!("    {test}");
+            }
+        }
+        println!();
+        println!(
+            "test result: {}. {} passed; {} failed; {} ignored; 0 measured; {} filtered out",
+            if self.failed.is_empty() { "ok" } else { "FAILED" },
+            self.passed,
+            self.failed.len(),
+            self.ignored,
+            self.total.map_or(0, |total| total.saturating_sub(num_tests)),
+        );
+        println!();
+        if self.miri_error {
+            MIRI_ERROR_EXIT_CODE
+        } else if self.failed.is_empty() {
+            0
+        } else {
+            TESTS_FAILED_EXIT_CODE
+        }
+    }
+}
@@ -28,6 +28,14 @@ def normalize_stdout(str):
     str = re.sub("finished in \d+\.\d\ds", "finished in $TIME", str) # the time keeps changing, obviously
     return str
 
+def sort_test_status(str):
+    # Tests that run at the same time finish in any order.
+    lines = str.split("\n")
+    status = [i for (i, line) in enumerate(lines) if re.match("test .* \.\.\. ", line)]
+    for (i, line) in zip(status, sorted(lines[i] for i in status)):
+        lines[i] = line
+    return "\n".join(lines)
+
 def normalize_stderr(str):
     str = re.sub("Preparing a sysroot for Miri \(target: [a-z0-9_-]+\)\.\.\. done\n", "", str) # remove leading cargo-miri setup output
     return str
@@ -46,7 +54,7 @@ def check_output(actual, path, name):
     print(f"--- END diff {name} ---")
     return False
 
-def test(name, cmd, stdout_ref, stderr_ref, stdin=b'', env={}):
+def test(name, cmd, stdout_ref, stderr_ref, stdin=b'', env={}, parallel=False):
     print("Testing {}...".format(name))
     ## Call `cargo miri`, capture all output
     p_env = os.environ.copy()
@@ -61,6 +69,8 @@ def test(name, cmd, stdout_ref, stderr_ref, stdin=b'', env={}):
     (stdout, stderr) = p.communicate(input=stdin)
     stdout = normalize_stdout(stdout.decode("UTF-8"))
     stderr = normalize_stderr(stderr.decode("UTF-8"))
+    if parallel:
+        stdout = sort_test_status(stdout)
 
     stdout_matches = check_output(stdout, stdout_ref, "stdout")
     stderr_matches = check_output(stderr, stderr_ref, "stderr")
@@ -70,6 +80,53 @@ def test(name, cmd, stdout_ref, stderr_ref, stdin=b'', env={}):
         return
     fail("exit code was {}".format(p.returncode))
 
+def test_exit_code(name, cmd, exit_code, env={}):
+    print("Testing {}...".format(name))
+    p_env = os.environ.copy()
+    p_env.update(env)
+    p = subprocess.Popen(
+        cmd,
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+        env=p_env,
+    )
+    p.communicate()
+    if p.returncode != exit_code:
+        fail("exit code was {}, expected {}".format(p.returncode, exit_code))
+
+def test_grouped_output(name, cmd, sections, env={}):
+    print("Testing {}...".format(name))
+    p_env = os.environ.copy()
+    p_env.update(env)
+    p = subprocess.Popen(
+        cmd,
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+        env=p_env,
+    )
+    (stdout, stderr) = p.communicate()
+    stdout = stdout.decode("UTF-8")
+    stderr = stderr.decode("UTF-8")
+    if p.returncode == 0:
+        fail("exit code was 0, but some tests should have failed")
+    # Every failed test has a section of its own, which has to contain all of its output.
+    for (test, lines) in sections.items():
+        match = re.search(f"^---- {test} .*?(?=^----|^failures:)", stdout, re.MULTILINE | re.DOTALL)
+        missing = [line for line in lines if match is None or line not in match.group()]
+        misplaced = [line for line in lines if line in stderr]
+        if missing or misplaced:
+            print("--- BEGIN stdout ---")
+            print(stdout, end="")
+            print("--- END stdout ---")
+            print("--- BEGIN stderr ---")
+            print(stderr, end="")
+            print("--- END stderr ---")
+            fail(f"the output of `{test}` is not all in its section: {missing + misplaced}")
+
+def check_file(path, ref):
+    if not check_output(open(path).read(), ref, path):
+        fail(f"`{path}` did not match the reference")
+
 def test_no_rebuild(name, cmd, env={}):
     print("Testing {}...".format(name))
     p_env = os.environ.copy()
@@ -112,11 +169,55 @@ def test_cargo_miri_run():
         cargo_miri("run") + ["--bin", "cargo-miri-test", "--", "hello world", '"hello world"', r'he\\llo\"world'],
         "run.args.stdout.ref", "run.args.stderr.ref",
     )
+    # The files go into the target dir, so that the check at the end does not complain about them.
+    test("`cargo miri run` (redirected output)",
+        cargo_miri("run") + ["--bin", "cargo-miri-test", "--", "hello world", '"hello world"', r'he\\llo\"world'],
+        "test.stdout-empty.ref", "test.stderr-empty.ref",
+        env={'MIRIFLAGS': "-Zmiri-redirect-stdout=target/miri/redirect.stdout -Zmiri-redirect-stderr=target/miri/redirect.stderr"},
+    )
+    check_file("target/miri/redirect.stdout", "run.args.stdout.ref")
+    check_file("target/miri/redirect.stderr", "run.args.stderr.ref")
+    test("`cargo miri run` (teed output)",
+        cargo_miri("run") + ["--bin", "cargo-miri-test", "--", "hello world", '"hello world"', r'he\\llo\"world'],
+        "run.args.stdout.ref", "run.args.stderr.ref",
+        env={'MIRIFLAGS': "-Zmiri-tee-stdout=target/miri/tee.stdout -Zmiri-tee-stderr=target/miri/tee.stderr"},
+    )
+    check_file("target/miri/tee.stdout", "run.args.stdout.ref")
+    check_file("target/miri/tee.stderr", "run.args.stderr.ref")
+    target = os.environ.get('MIRI_TEST_TARGET', "windows" if os.name == 'nt' else "")
+    test_exit_code("`cargo miri run` (native abort exit code)",
+        cargo_miri("run") + ["--bin", "cargo-miri-test", "--", "abort"],
+        3 if "windows" in target else 134,
+        env={'MIRIFLAGS': "-Zmiri-abort-exit-code"},
+    )
+    # Cargo passes the `panic` setting of the profile on to every crate it builds.
+    test("`cargo miri run` (panic=abort profile)",
+        cargo_miri("run") + ["--bin", "cargo-miri-test", "--config=profile.dev.panic=\"abort\"", "-Zunstable-options",
+            "--", "hello world", '"hello world"', r'he\\llo\"world'],
+        "run.args.stdout.ref", "run.args.stderr.ref",
+    )
     test("`cargo miri r` (subcrate, no isolation)",
         cargo_miri("r") + ["-p", "subcrate"],
         "run.subcrate.stdout.ref", "run.subcrate.stderr.ref",
         env={'MIRIFLAGS': "-Zmiri-disable-isolation"},
     )
+    if 'MIRI_TEST_TARGET' not in os.environ: # build scripts cannot be interpreted for foreign targets
+        test("`cargo miri run` (subcrate, interpreted build script)",
+            # Use a separate target dir, so the build script does not get reused from above.
+            cargo_miri("run") + ["-p", "subcrate", "--target-dir=custom-build-script"],
+            "run.subcrate-build-script.stdout.ref", "run.subcrate.stderr.ref",
+            env={
+                'MIRIFLAGS': "-Zmiri-disable-isolation",
+                'MIRI_INTERPRET_BUILD_SCRIPTS': "subcrate",
+            },
+        )
+    if 'MIRI_TEST_TARGET' not in os.environ: # we pick the targets ourselves
+        test("`cargo miri run` (subcrate, multiple targets)",
+            ["cargo", "miri", "run", "-q", "-p", "subcrate",
+                "--target", "i686-unknown-linux-gnu", "--target=x86_64-unknown-linux-gnu"],
+            "run.multi-target.stdout.ref", "run.multi-target.stderr.ref",
+            env={'MIRIFLAGS': "-Zmiri-disable-isolation"},
+        )
     test("`cargo miri run` (custom target dir)",
         # Attempt to confuse the argument parser.
         cargo_miri("run") + ["--target-dir=custom-run", "--", "--target-dir=target/custom-run"],
@@ -149,6 +250,23 @@ def test_cargo_miri_test():
         "test.test-target.stdout.ref", "test.stderr-empty.ref",
         env={'MIRIFLAGS': "-Zmiri-permissive-provenance"},
     )
+    test("`cargo miri test` (test target, separate interpreter for each test)",
+        cargo_miri("test") + ["--test", "test"],
+        "test.test-target.stdout.ref", "test.stderr-empty.ref",
+        env={'MIRIFLAGS': "-Zmiri-permissive-provenance -Zmiri-test-jobs=2"},
+        parallel=True,
+    )
+    test_grouped_output("`cargo miri test` (tests that print while they run at the same time)",
+        cargo_miri("test") + ["-p", "test_jobs"],
+        {
+            "prints_then_fails_a":
+                [f"output of a, line {i}\n" for i in range(3)] + ["panicked at 'a failed'"],
+            "prints_then_fails_b":
+                [f"output of b, line {i}\n" for i in range(3)] + ["panicked at 'b failed'"],
+            "undefined_behavior": ["error: Undefined Behavior: constructing invalid value"],
+        },
+        env={'MIRIFLAGS': "-Zmiri-test-jobs=3"},
+    )
     test("`cargo miri test` (bin target)",
         cargo_miri("test") + ["--bin", "cargo-miri-test", "--", "--format=pretty"],
         "test.bin-target.stdout.ref", "test.stderr-empty.ref",
@@ -185,7 +303,10 @@ print(CGREEN + CBOLD + "## Running `cargo miri` tests{}".format(target_str) + CE
 test_cargo_miri_run()
 test_cargo_miri_test()
 # Ensure we did not create anything outside the expected target dir.
-for target_dir in ["target", "custom-run", "custom-test", "config-cli"]:
+target_dirs = ["target", "custom-run", "custom-test", "config-cli"]
+if 'MIRI_TEST_TARGET' not in os.environ:
+    target_dirs.append("custom-build-script")
+for target_dir in target_dirs:
     if os.listdir(target_dir) != ["miri"]:
         fail(f"`{target_dir}` contains unexpected files")
     # Ensure something exists inside that target dir.
@@ -8,6 +8,9 @@
 /// ```rust,compile_fail
 /// assert!(cargo_miri_test::make_true() == 5);
 /// ```
+/// ```rust,ignore
+/// this is not Rust, but it is never built or run
+/// ```
 #[no_mangle]
 pub fn make_true() -> bool {
     issue_1567::use_the_dependency();
@@ -18,6 +18,11 @@ fn main() {
         eprintln!("{}", arg);
     }
 
+    // Abort if asked to, so that we can check the exit code Miri reports for that.
+    if std::env::args().nth(1).as_deref() == Some("abort") {
+        std::process::abort();
+    }
+
     // If there were no arguments, access stdin and test working dir.
     // (We rely on the test runner to always disable isolation when passing no arguments.)
     if std::env::args().len() <= 1 {
@@ -0,0 +1,5 @@
+fn main() {
+    // Let the binary know whether this build script was interpreted by Miri.
+    println!("cargo:rustc-env=SUBCRATE_BUILD_SCRIPT_IN_MIRI={}", cfg!(miri));
+    println!("cargo:rerun-if-changed=build.rs");
+}
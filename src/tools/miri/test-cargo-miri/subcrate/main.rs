@@ -3,6 +3,7 @@
 
 fn main() {
     println!("subcrate running");
+    println!("build script interpreted: {}", env!("SUBCRATE_BUILD_SCRIPT_IN_MIRI"));
 
     // CWD should be workspace root, i.e., one level up from crate root.
     // We have to normalize slashes, as the env var might be set for a different target's conventions.
@@ -0,0 +1,29 @@
+//! Tests for `-Zmiri-test-jobs`, which run at the same time and print their output while other
+//! tests do the same. `run-test.py` checks that the output of every test ends up in one piece.
+
+#[test]
+fn passes() {
+    assert_eq!(4, 4);
+}
+
+#[test]
+fn prints_then_fails_a() {
+    for i in 0..3 {
+        println!("output of a, line {i}");
+    }
+    panic!("a failed");
+}
+
+#[test]
+fn prints_then_fails_b() {
+    for i in 0..3 {
+        println!("output of b, line {i}");
+    }
+    panic!("b failed");
+}
+
+#[test]
+fn undefined_behavior() {
+    let b: bool = unsafe { std::mem::transmute(2u8) };
+    assert!(b);
+}
@@ -0,0 +1,13 @@
+#![feature(custom_test_frameworks)]
+#![test_runner(runner)]
+
+fn runner(tests: &[&i32]) {
+    let sum: i32 = tests.iter().copied().sum();
+    println!("custom test framework: {} tests with sum {}", tests.len(), sum);
+}
+
+#[test_case]
+const ONE: i32 = 1;
+
+#[test_case]
+const TWO: i32 = 2;
@@ -0,0 +1,6 @@
+// Without a harness, the test has to make sense of its arguments by itself.
+fn main() {
+    let filters: Vec<String> =
+        std::env::args().skip(1).filter(|arg| !arg.starts_with("--")).collect();
+    println!("test without harness, filters: {filters:?}");
+}
@@ -0,0 +1,11 @@
+//@ignore-target-windows: No `aligned_alloc` on Windows
+
+extern "C" {
+    fn aligned_alloc(align: usize, size: usize) -> *mut std::ffi::c_void;
+}
+
+fn main() {
+    unsafe {
+        let _ = aligned_alloc(24, 48); //~ ERROR: called with an alignment of 24, which is not a power of 2
+    }
+}
@@ -0,0 +1,14 @@
+use std::alloc::{dealloc, Layout};
+
+//@error-pattern: which is C heap memory, using Rust heap deallocation operation
+
+extern "C" {
+    fn malloc(size: usize) -> *mut u8;
+}
+
+fn main() {
+    unsafe {
+        let ptr = malloc(8);
+        dealloc(ptr, Layout::from_size_align_unchecked(8, 8));
+    }
+}
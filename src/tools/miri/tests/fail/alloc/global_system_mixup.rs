@@ -3,7 +3,7 @@
 //@error-pattern: /deallocating .*, which is Rust heap memory, using .* heap deallocation operation/
 
 //@normalize-stderr-test: "using [A-Za-z]+ heap deallocation operation" -> "using PLATFORM heap deallocation operation"
-//@normalize-stderr-test: "\| +\^+" -> "| ^"
+//@normalize-stderr-test: "\| +\^+ deallocating" -> "| ^ deallocating"
 //@normalize-stderr-test: "libc::free\([^()]*\)|unsafe \{ HeapFree\([^()]*\) \};" -> "FREE();"
 
 #![feature(allocator_api, slice_ptr_get)]
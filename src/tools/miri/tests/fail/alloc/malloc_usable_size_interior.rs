@@ -0,0 +1,14 @@
+//@ignore-target-windows: No `malloc_usable_size` on Windows
+//@ignore-target-apple: macOS calls this `malloc_size`
+
+extern "C" {
+    fn malloc(size: usize) -> *mut u8;
+    fn malloc_usable_size(ptr: *mut u8) -> usize;
+}
+
+fn main() {
+    unsafe {
+        let ptr = malloc(16);
+        let _ = malloc_usable_size(ptr.add(1)); //~ ERROR: does not point to the start of a live C heap allocation
+    }
+}
@@ -0,0 +1,13 @@
+extern "C" {
+    fn malloc(size: usize) -> *mut u8;
+    fn free(ptr: *mut u8);
+    fn realloc(ptr: *mut u8, size: usize) -> *mut u8;
+}
+
+fn main() {
+    unsafe {
+        let ptr = malloc(8);
+        free(ptr);
+        let _ = realloc(ptr, 16); //~ ERROR: which was already deallocated
+    }
+}
@@ -0,0 +1,14 @@
+//@only-target-windows: Uses win32 api functions
+
+use std::ffi::c_void;
+
+extern "system" {
+    fn GetProcessHeap() -> isize;
+    fn HeapFree(heap: isize, flags: u32, ptr: *mut c_void) -> i32;
+}
+
+fn main() {
+    unsafe {
+        let _ = HeapFree(GetProcessHeap(), 0, std::ptr::null_mut()); //~ ERROR: `HeapFree` called on a null pointer
+    }
+}
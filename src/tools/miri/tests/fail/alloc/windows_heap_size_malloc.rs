@@ -0,0 +1,19 @@
+//@only-target-windows: Uses win32 api functions
+
+use std::ffi::c_void;
+
+extern "C" {
+    fn malloc(size: usize) -> *mut c_void;
+}
+
+extern "system" {
+    fn GetProcessHeap() -> isize;
+    fn HeapSize(heap: isize, flags: u32, ptr: *const c_void) -> usize;
+}
+
+fn main() {
+    unsafe {
+        let ptr = malloc(16);
+        let _ = HeapSize(GetProcessHeap(), 0, ptr); //~ ERROR: does not point to the start of a live Windows heap allocation
+    }
+}
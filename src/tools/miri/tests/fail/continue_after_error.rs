@@ -0,0 +1,19 @@
+//@compile-flags: -Zmiri-continue-after-error
+// Both violations are reported and the program runs to completion. The violation in the loop
+// happens twice, but it is only reported once.
+
+fn main() {
+    let mut x = 15;
+    let xraw = &mut x as *mut i32;
+    let xref = unsafe { &mut *xraw };
+    let _val = unsafe { *xraw };
+    let _val = *xref; //~ ERROR: /read access .* tag does not exist in the borrow stack/
+
+    for _ in 0..2 {
+        let mut y = 0;
+        let yraw = &mut y as *mut i32;
+        let yref = unsafe { &mut *yraw };
+        unsafe { *yraw = 1 };
+        *yref = 2; //~ ERROR: /write access .* tag does not exist in the borrow stack/
+    }
+}
@@ -0,0 +1,15 @@
+#![feature(platform_intrinsics, repr_simd)]
+
+extern "platform-intrinsic" {
+    pub(crate) fn simd_reduce_max_nanless<T, U>(x: T) -> U;
+}
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+struct f32x2(f32, f32);
+
+fn main() {
+    unsafe {
+        let _x: f32 = simd_reduce_max_nanless(f32x2(0.0, f32::NAN)); //~ERROR: encountered NaN in SIMD lane 1
+    }
+}
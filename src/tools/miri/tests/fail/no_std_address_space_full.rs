@@ -0,0 +1,20 @@
+#![feature(lang_items, start)]
+#![no_std]
+//@only-target-avr: needs a 16-bit address space
+
+// Each of these arrays takes more than a third of the address space.
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    let _a = [0u8; 25000];
+    let _b = [1u8; 25000];
+    let _c = [2u8; 25000]; //~ ERROR: there are no more free addresses in the 16-bit address space
+    0
+}
+
+#[panic_handler]
+fn panic_handler(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[lang = "eh_personality"]
+fn eh_personality() {}
@@ -0,0 +1,14 @@
+//@error-pattern: the program aborted execution by calling `abort`
+//@ignore-target-windows: `process::abort` uses the `abort` intrinsic instead of `libc::abort` there
+//@normalize-stderr-test: "\| +\^+" -> "| ^"
+//@normalize-stderr-test: "unsafe \{ libc::abort\(\) \}" -> "ABORT();"
+
+use std::process;
+use std::thread;
+
+fn main() {
+    // The other thread is gone by the time we abort, so there is no need to say which thread
+    // terminated the program.
+    thread::spawn(|| {}).join().unwrap();
+    process::abort();
+}
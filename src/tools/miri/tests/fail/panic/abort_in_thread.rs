@@ -0,0 +1,16 @@
+//@error-pattern: the program aborted execution by calling `abort`
+//@ignore-target-windows: `process::abort` uses the `abort` intrinsic instead of `libc::abort` there
+//@normalize-stderr-test: "\| +\^+" -> "| ^"
+//@normalize-stderr-test: "unsafe \{ libc::abort\(\) \}" -> "ABORT();"
+
+use std::process;
+use std::thread;
+
+fn main() {
+    thread::Builder::new()
+        .name("aborter".to_owned())
+        .spawn(|| process::abort())
+        .unwrap()
+        .join()
+        .unwrap();
+}
@@ -1,6 +1,5 @@
-//@error-pattern: the program aborted execution
+//@error-pattern: the program panicked with `-C panic=abort`
 //@normalize-stderr-test: "\| +\^+" -> "| ^"
-//@normalize-stderr-test: "libc::abort\(\);|core::intrinsics::abort\(\);" -> "ABORT();"
 //@compile-flags: -C panic=abort
 
 fn main() {
@@ -0,0 +1,6 @@
+//@compile-flags: -Zmiri-relaxed-crates=relax_dependencies_fully_checked -Zmiri-fully-checked-crates=relax_dependencies_fully_checked
+// Being fully checked takes precedence over being relaxed.
+
+fn main() {
+    let _b = unsafe { std::mem::transmute::<u8, bool>(2) }; //~ ERROR: expected a boolean
+}
@@ -0,0 +1,6 @@
+//@compile-flags: -Zmiri-relax-dependencies
+// The local crate is not a dependency, so it is still fully checked.
+
+fn main() {
+    let _b = unsafe { std::mem::transmute::<u8, bool>(2) }; //~ ERROR: expected a boolean
+}
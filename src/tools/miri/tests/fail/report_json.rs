@@ -0,0 +1,15 @@
+//@compile-flags: -Zmiri-report-json
+// Errors on a thread named like a libtest test are reported as that test failing.
+use std::thread;
+
+#[allow(deref_nullptr)]
+fn main() {
+    let handle = thread::Builder::new()
+        .name("tests::null_deref".to_string())
+        .spawn(|| {
+            let x: i32 = unsafe { *std::ptr::null() }; //~ ERROR: null pointer is a dangling pointer
+            x
+        })
+        .unwrap();
+    handle.join().unwrap();
+}
@@ -0,0 +1,13 @@
+//@ignore-target-windows: File handling is not implemented yet
+//@error-pattern: `open` not available when isolation is enabled
+
+// `miri::allow(isolation)` only lifts isolation while the marked item is on the stack.
+#[cfg_attr(miri, miri::allow(isolation))]
+fn exempt() -> std::path::PathBuf {
+    std::env::current_dir().unwrap()
+}
+
+fn main() {
+    assert!(exempt().is_absolute());
+    let _file = std::fs::File::open("file.txt").unwrap();
+}
@@ -0,0 +1,24 @@
+//@ignore-target-windows: `_setjmp` takes an extra argument on Windows
+#![feature(ffi_returns_twice)]
+
+use std::mem::MaybeUninit;
+
+// Large enough for the `jmp_buf` of every target.
+type JmpBuf = [u64; 64];
+
+extern "C" {
+    #[ffi_returns_twice]
+    fn setjmp(env: *mut JmpBuf) -> i32;
+    fn longjmp(env: *mut JmpBuf, val: i32) -> !;
+}
+
+fn fail(env: *mut JmpBuf) {
+    unsafe { longjmp(env, 1) } //~ ERROR: non-local jumps via `longjmp` are not supported
+}
+
+fn main() {
+    let mut env = MaybeUninit::<JmpBuf>::uninit();
+    if unsafe { setjmp(env.as_mut_ptr()) } == 0 {
+        fail(env.as_mut_ptr());
+    }
+}
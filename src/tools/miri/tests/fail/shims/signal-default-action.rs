@@ -0,0 +1,8 @@
+//! Raising a signal that has no handler terminates the program, as it would natively.
+//@ignore-target-windows: No libc on Windows
+
+fn main() {
+    unsafe {
+        libc::raise(libc::SIGTERM); //~ ERROR: the program was terminated by signal 15
+    }
+}
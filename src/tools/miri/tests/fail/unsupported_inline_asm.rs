@@ -0,0 +1,8 @@
+//@only-target-x86_64: uses an x86 instruction
+use std::arch::asm;
+
+fn main() {
+    unsafe {
+        asm!("nop"); //~ ERROR: inline assembly is only supported if it does not contain any instructions
+    }
+}
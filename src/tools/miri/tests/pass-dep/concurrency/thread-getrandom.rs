@@ -0,0 +1,36 @@
+//@only-target-linux
+//@compile-flags: -Zmiri-preemption-rate=0.5
+// `thread-getrandom-no-preemption.rs` runs the same program without preemption, and has to print
+// the same values.
+
+use std::thread;
+
+fn getrandom() -> [u8; 16] {
+    let mut buf = [0u8; 16];
+    let ret = unsafe { libc::getrandom(buf.as_mut_ptr().cast(), buf.len(), 0) };
+    assert_eq!(ret, 16);
+    buf
+}
+
+fn hex(buf: [u8; 16]) -> String {
+    buf.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn main() {
+    // The threads draw while interleaved with each other and with `main`, but every thread has its
+    // own stream of entropy, so they get different values, and the same ones in every run (the
+    // recorded ones are those of the default seed).
+    let handles: Vec<_> = (0..3).map(|_| thread::spawn(|| [getrandom(), getrandom()])).collect();
+    let main = [getrandom(), getrandom()];
+    let mut all: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    all.push(main);
+    for [a, b] in &all {
+        println!("{} {}", hex(*a), hex(*b));
+    }
+    let all: Vec<_> = all.into_iter().flatten().collect();
+    for (i, a) in all.iter().enumerate() {
+        for b in &all[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-shuffle-tls-dtors
+//! Test that with shuffled destructor order, pthread_key destructors still run in rounds: every
+//! destructor runs once before any destructor runs a second time.
+
+use std::mem;
+use std::thread;
+
+pub type Key = libc::pthread_key_t;
+
+const N: usize = 4;
+
+static mut KEYS: [Key; N] = [0; N];
+// How often each destructor should re-register its value.
+static mut GLOBALS: [u64; N] = [1; N];
+static mut RECORD: Vec<usize> = Vec::new();
+
+unsafe fn create(dtor: Option<unsafe extern "C" fn(*mut u8)>) -> Key {
+    let mut key = 0;
+    assert_eq!(libc::pthread_key_create(&mut key, mem::transmute(dtor)), 0);
+    key
+}
+
+unsafe fn set(key: Key, value: *mut u8) {
+    let r = libc::pthread_setspecific(key, value as *mut _);
+    assert_eq!(r, 0);
+}
+
+unsafe extern "C" fn dtor(ptr: *mut u64) {
+    let which_key = GLOBALS
+        .iter()
+        .position(|global| global as *const _ == ptr)
+        .expect("Should find my global");
+    RECORD.push(which_key);
+
+    let val = *ptr;
+    if val > 0 {
+        *ptr = val - 1;
+        set(KEYS[which_key], ptr as *mut _);
+    }
+}
+
+fn main() {
+    unsafe {
+        for key in KEYS.iter_mut() {
+            *key = create(Some(mem::transmute(dtor as unsafe extern "C" fn(*mut u64))));
+        }
+        // The destructors run when the thread exits.
+        thread::spawn(|| {
+            for (key, global) in KEYS.iter().zip(GLOBALS.iter_mut()) {
+                set(*key, global as *mut _ as *mut u8);
+            }
+        })
+        .join()
+        .unwrap();
+
+        // Each destructor ran twice, once in each of two rounds.
+        assert_eq!(RECORD.len(), 2 * N);
+        for round in RECORD.chunks(N) {
+            let mut round = round.to_vec();
+            round.sort();
+            assert_eq!(round, (0..N).collect::<Vec<_>>());
+        }
+        // Every round is shuffled on its own, so with the default seed the two rounds do not
+        // run in the same order. (The exact order also depends on the keys that std registers.)
+        assert_ne!(RECORD[..N], RECORD[N..]);
+    }
+}
@@ -0,0 +1,14 @@
+//@compile-flags: -Zmiri-relax-dependencies -Zmiri-fully-checked-crates=rand
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+fn main() {
+    // `transmute_copy` creates the invalid `bool` inside of `core`, which is a dependency, so Miri
+    // does not check it. The same thing in this crate would be an error, see
+    // `fail/relax_dependencies_local.rs`.
+    let _b: bool = unsafe { std::mem::transmute_copy(&2u8) };
+
+    // `rand` is a dependency as well, but we asked for it to be fully checked.
+    let mut rng = SmallRng::seed_from_u64(0);
+    let x: u32 = rng.gen_range(0..10);
+    assert!(x < 10);
+}
@@ -0,0 +1,70 @@
+//@ignore-target-windows: No libc on Windows
+
+#![feature(pointer_is_aligned)]
+
+use std::ffi::c_void;
+
+extern "C" {
+    fn aligned_alloc(align: usize, size: usize) -> *mut c_void;
+    #[cfg_attr(target_os = "macos", link_name = "malloc_size")]
+    fn malloc_usable_size(ptr: *mut c_void) -> usize;
+}
+
+fn main() {
+    // A normal allocation.
+    unsafe {
+        let ptr = aligned_alloc(64, 128);
+        assert!(!ptr.is_null());
+        assert!(ptr.is_aligned_to(64));
+        ptr.cast::<u8>().write_bytes(1, 128);
+        assert!(malloc_usable_size(ptr) >= 128);
+        libc::free(ptr);
+    }
+
+    // Small alignments still give the alignment `malloc` guarantees.
+    unsafe {
+        let ptr = aligned_alloc(1, 64);
+        assert!(!ptr.is_null());
+        assert!(ptr.is_aligned_to(std::mem::size_of::<usize>()));
+        libc::free(ptr);
+    }
+
+    // Size not multiple of align, and growing the allocation.
+    unsafe {
+        let ptr = aligned_alloc(16, 31);
+        assert!(!ptr.is_null());
+        assert!(ptr.is_aligned_to(16));
+        ptr.cast::<u8>().write_bytes(1, 31);
+        let ptr = libc::realloc(ptr, 64);
+        assert!(!ptr.is_null());
+        assert_eq!(*ptr.cast::<u8>().add(30), 1);
+        libc::free(ptr);
+    }
+
+    // Size == 0
+    unsafe {
+        let ptr = aligned_alloc(64, 0);
+        // Regardless of what we return, it must be `free`able.
+        libc::free(ptr);
+    }
+
+    // The usable size covers at least what was requested, for all kinds of C heap allocations.
+    unsafe {
+        let ptr = libc::malloc(7);
+        assert!(malloc_usable_size(ptr) >= 7);
+        let ptr = libc::realloc(ptr, 100);
+        assert!(malloc_usable_size(ptr) >= 100);
+        libc::free(ptr);
+
+        let ptr = libc::calloc(3, 5);
+        assert!(malloc_usable_size(ptr) >= 15);
+        libc::free(ptr);
+
+        let mut ptr = std::ptr::null_mut();
+        assert_eq!(libc::posix_memalign(&mut ptr, 32, 40), 0);
+        assert!(malloc_usable_size(ptr) >= 40);
+        libc::free(ptr);
+
+        assert_eq!(malloc_usable_size(std::ptr::null_mut()), 0);
+    }
+}
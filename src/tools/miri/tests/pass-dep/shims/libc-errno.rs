@@ -0,0 +1,72 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-disable-isolation
+
+#[cfg(target_os = "linux")]
+use libc::__errno_location;
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+use libc::__error as __errno_location;
+
+const SENTINEL: i32 = 0xBEEF;
+
+fn errno() -> i32 {
+    unsafe { *__errno_location() }
+}
+
+fn set_errno(val: i32) {
+    unsafe { *__errno_location() = val };
+}
+
+/// Successful calls must not touch errno.
+fn test_success_preserves_errno() {
+    set_errno(SENTINEL);
+
+    let mut buf = [0 as libc::c_char; 1024];
+    assert!(!unsafe { libc::getcwd(buf.as_mut_ptr(), buf.len()) }.is_null());
+    assert!(unsafe { libc::sysconf(libc::_SC_PAGESIZE) } > 0);
+    let mut tv = std::mem::MaybeUninit::<libc::timeval>::uninit();
+    assert_eq!(unsafe { libc::gettimeofday(tv.as_mut_ptr(), std::ptr::null_mut()) }, 0);
+    let name = b"MIRI_ERRNO_TEST\0".as_ptr().cast();
+    assert_eq!(unsafe { libc::setenv(name, b"1\0".as_ptr().cast(), 1) }, 0);
+    assert_eq!(unsafe { libc::unsetenv(name) }, 0);
+
+    assert_eq!(errno(), SENTINEL);
+}
+
+/// Failing calls must set errno to the right value.
+fn test_failure_sets_errno() {
+    set_errno(0);
+    let fd = unsafe { libc::open(b"./does/not/exist\0".as_ptr().cast(), libc::O_RDONLY) };
+    assert_eq!(fd, -1);
+    assert_eq!(errno(), libc::ENOENT);
+
+    set_errno(0);
+    assert_eq!(unsafe { libc::close(-1) }, -1);
+    assert_eq!(errno(), libc::EBADF);
+
+    set_errno(0);
+    assert_eq!(unsafe { libc::setenv(b"\0".as_ptr().cast(), b"1\0".as_ptr().cast(), 1) }, -1);
+    assert_eq!(errno(), libc::EINVAL);
+
+    set_errno(0);
+    let mut buf = [0 as libc::c_char; 1];
+    assert!(unsafe { libc::getcwd(buf.as_mut_ptr(), buf.len()) }.is_null());
+    assert_eq!(errno(), libc::ERANGE);
+}
+
+/// A failure in one thread does not affect errno in other threads.
+fn test_errno_is_per_thread() {
+    set_errno(SENTINEL);
+    std::thread::spawn(|| {
+        assert_eq!(unsafe { libc::close(-1) }, -1);
+        assert_eq!(errno(), libc::EBADF);
+    })
+    .join()
+    .unwrap();
+    assert_eq!(errno(), SENTINEL);
+}
+
+fn main() {
+    test_success_preserves_errno();
+    test_failure_sets_errno();
+    test_errno_is_per_thread();
+}
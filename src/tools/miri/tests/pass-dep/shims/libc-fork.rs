@@ -0,0 +1,8 @@
+//@ignore-target-windows: No libc on Windows
+
+fn main() {
+    // We cannot fork the interpreter, so this fails and the program can handle the error.
+    let pid = unsafe { libc::fork() };
+    assert_eq!(pid, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::ENOSYS));
+}
@@ -0,0 +1,42 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-disable-isolation
+
+use std::fs::{remove_file, File};
+use std::os::unix::io::AsRawFd;
+use std::path::PathBuf;
+
+fn tmp() -> PathBuf {
+    std::env::var("MIRI_TEMP")
+        .map(|tmp| {
+            // MIRI_TEMP is set outside of our emulated
+            // program, so it may have path separators that don't
+            // correspond to our target platform. We normalize them here
+            // before constructing a `PathBuf`
+            return PathBuf::from(tmp.replace("\\", "/"));
+        })
+        .unwrap_or_else(|_| std::env::temp_dir())
+}
+
+fn main() {
+    let path = tmp().join("miri_test_libc_ioctl.txt");
+    let file = File::create(&path).unwrap();
+    let fd = file.as_raw_fd();
+
+    unsafe {
+        // A regular file is not a terminal.
+        let mut winsize = std::mem::MaybeUninit::<libc::winsize>::uninit();
+        assert_eq!(libc::ioctl(fd, libc::TIOCGWINSZ, winsize.as_mut_ptr()), -1);
+        assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::ENOTTY));
+
+        let mut nonblocking: libc::c_int = 1;
+        assert_eq!(libc::ioctl(fd, libc::FIONBIO, &mut nonblocking), 0);
+        assert_eq!(libc::ioctl(fd, libc::FIOCLEX), 0);
+
+        // Invalid file descriptors are reported as such.
+        assert_eq!(libc::ioctl(-1, libc::FIOCLEX), -1);
+        assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EBADF));
+    }
+
+    drop(file);
+    remove_file(&path).unwrap();
+}
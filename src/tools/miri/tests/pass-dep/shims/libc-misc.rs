@@ -192,6 +192,34 @@ fn test_clocks() {
     assert_eq!(is_error, 0);
     let is_error = unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC_COARSE, tp.as_mut_ptr()) };
     assert_eq!(is_error, 0);
+    let is_error = unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC_RAW, tp.as_mut_ptr()) };
+    assert_eq!(is_error, 0);
+    let is_error = unsafe { libc::clock_gettime(libc::CLOCK_BOOTTIME, tp.as_mut_ptr()) };
+    assert_eq!(is_error, 0);
+
+    // CPU-time clocks advance as the program executes.
+    let cpu_time = |clock| {
+        let mut tp = std::mem::MaybeUninit::<libc::timespec>::uninit();
+        let is_error = unsafe { libc::clock_gettime(clock, tp.as_mut_ptr()) };
+        assert_eq!(is_error, 0);
+        let tp = unsafe { tp.assume_init() };
+        std::time::Duration::new(tp.tv_sec as u64, tp.tv_nsec as u32)
+    };
+    for clock in [libc::CLOCK_PROCESS_CPUTIME_ID, libc::CLOCK_THREAD_CPUTIME_ID] {
+        let before = cpu_time(clock);
+        let mut sum = 0u64;
+        for i in 0..100 {
+            sum += i;
+        }
+        assert_eq!(sum, 4950);
+        let after = cpu_time(clock);
+        assert!(after > before);
+    }
+    // A newly spawned thread starts with (almost) no CPU time of its own.
+    let process_time = cpu_time(libc::CLOCK_PROCESS_CPUTIME_ID);
+    let thread_time =
+        std::thread::spawn(move || cpu_time(libc::CLOCK_THREAD_CPUTIME_ID)).join().unwrap();
+    assert!(thread_time < process_time);
 }
 
 fn test_posix_gettimeofday() {
@@ -0,0 +1,48 @@
+//@ignore-target-windows: No libc on Windows
+use std::ffi::CStr;
+
+fn test_getpid() {
+    // Under isolation, we get a fixed fake PID.
+    let pid = unsafe { libc::getpid() };
+    assert_eq!(pid, 1000);
+    assert_eq!(std::process::id(), 1000);
+}
+
+#[cfg(target_os = "linux")]
+fn test_gettid() {
+    fn gettid() -> libc::c_long {
+        unsafe { libc::syscall(libc::SYS_gettid) }
+    }
+
+    // The main thread's TID is the PID.
+    let main_tid = gettid();
+    assert_eq!(main_tid, libc::c_long::from(unsafe { libc::getpid() }));
+    // Other threads get their own, distinct TIDs.
+    let other_tid = std::thread::spawn(gettid).join().unwrap();
+    assert_ne!(main_tid, other_tid);
+    assert_eq!(gettid(), main_tid);
+}
+
+fn test_uname() {
+    let mut buf = std::mem::MaybeUninit::<libc::utsname>::uninit();
+    assert_eq!(unsafe { libc::uname(buf.as_mut_ptr()) }, 0);
+    let buf = unsafe { buf.assume_init() };
+    let sysname = unsafe { CStr::from_ptr(buf.sysname.as_ptr()) }.to_str().unwrap();
+    let expected = if cfg!(target_os = "macos") {
+        "Darwin"
+    } else if cfg!(target_os = "freebsd") {
+        "FreeBSD"
+    } else {
+        "Linux"
+    };
+    assert_eq!(sysname, expected);
+    let nodename = unsafe { CStr::from_ptr(buf.nodename.as_ptr()) }.to_str().unwrap();
+    assert_eq!(nodename, "miri");
+}
+
+fn main() {
+    test_getpid();
+    #[cfg(target_os = "linux")]
+    test_gettid();
+    test_uname();
+}
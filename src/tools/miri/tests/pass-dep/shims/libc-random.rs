@@ -0,0 +1,34 @@
+//@ignore-target-windows: No libc on Windows
+
+extern "C" {
+    // Not exposed by `libc` on all targets, but available everywhere we support.
+    fn getentropy(buf: *mut libc::c_void, buflen: libc::size_t) -> libc::c_int;
+}
+
+fn test_getentropy() {
+    let mut buf = [0u8; 257];
+    unsafe {
+        assert_eq!(getentropy(buf.as_mut_ptr().cast(), 0), 0);
+        assert_eq!(getentropy(buf.as_mut_ptr().cast(), 256), 0);
+        // More than 256 bytes at once are refused.
+        assert_eq!(getentropy(buf.as_mut_ptr().cast(), 257), -1);
+        assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EIO));
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+fn test_arc4random_buf() {
+    let mut buf = [0u8; 512];
+    unsafe {
+        libc::arc4random_buf(buf.as_mut_ptr().cast(), 0);
+        libc::arc4random_buf(buf.as_mut_ptr().cast(), buf.len());
+    }
+    // The chance of getting all zeros is negligible.
+    assert!(buf.iter().any(|&b| b != 0));
+}
+
+fn main() {
+    test_getentropy();
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    test_arc4random_buf();
+}
@@ -0,0 +1,78 @@
+//@ignore-target-windows: No libc on Windows
+// Handlers are registered as integers, so calling them involves an int-to-pointer cast.
+//@compile-flags: -Zmiri-permissive-provenance
+
+use std::io;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+static LAST_SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+extern "C" fn handler(sig: libc::c_int) {
+    LAST_SIGNAL.store(sig, Ordering::Relaxed);
+}
+
+extern "C" fn siginfo_handler(
+    sig: libc::c_int,
+    info: *mut libc::siginfo_t,
+    _ucontext: *mut libc::c_void,
+) {
+    assert_eq!(unsafe { (*info).si_signo }, sig);
+    LAST_SIGNAL.store(sig, Ordering::Relaxed);
+}
+
+fn test_signal() {
+    unsafe {
+        // The standard library ignores `SIGPIPE` before `main` runs.
+        assert_eq!(libc::signal(libc::SIGPIPE, libc::SIG_IGN), libc::SIG_IGN);
+        assert_eq!(libc::raise(libc::SIGPIPE), 0);
+
+        // The default action for `SIGCHLD` is to do nothing.
+        assert_eq!(libc::raise(libc::SIGCHLD), 0);
+
+        assert_eq!(libc::signal(libc::SIGUSR1, handler as libc::sighandler_t), libc::SIG_DFL);
+        assert_eq!(libc::raise(libc::SIGUSR1), 0);
+        assert_eq!(LAST_SIGNAL.swap(0, Ordering::Relaxed), libc::SIGUSR1);
+        // The handler stays installed.
+        assert_eq!(libc::raise(libc::SIGUSR1), 0);
+        assert_eq!(LAST_SIGNAL.swap(0, Ordering::Relaxed), libc::SIGUSR1);
+
+        assert_eq!(libc::signal(libc::SIGUSR1, libc::SIG_IGN), handler as libc::sighandler_t);
+        assert_eq!(libc::raise(libc::SIGUSR1), 0);
+        assert_eq!(LAST_SIGNAL.load(Ordering::Relaxed), 0);
+    }
+}
+
+fn test_sigaction() {
+    unsafe {
+        let mut act: libc::sigaction = mem::zeroed();
+        act.sa_sigaction = siginfo_handler as libc::sighandler_t;
+        act.sa_flags = libc::SA_SIGINFO | libc::SA_RESETHAND;
+        let mut old: libc::sigaction = mem::zeroed();
+        assert_eq!(libc::sigaction(libc::SIGUSR2, &act, &mut old), 0);
+        assert_eq!(old.sa_sigaction, libc::SIG_DFL);
+
+        let mut cur: libc::sigaction = mem::zeroed();
+        assert_eq!(libc::sigaction(libc::SIGUSR2, ptr::null(), &mut cur), 0);
+        assert_eq!(cur.sa_sigaction, act.sa_sigaction);
+        assert_eq!(cur.sa_flags, act.sa_flags);
+
+        // Sending the signal to our own process delivers it as well.
+        assert_eq!(libc::kill(libc::getpid(), libc::SIGUSR2), 0);
+        assert_eq!(LAST_SIGNAL.swap(0, Ordering::Relaxed), libc::SIGUSR2);
+
+        // `SA_RESETHAND` restored the default action.
+        assert_eq!(libc::sigaction(libc::SIGUSR2, ptr::null(), &mut cur), 0);
+        assert_eq!(cur.sa_sigaction, libc::SIG_DFL);
+
+        // `SIGKILL` cannot be caught.
+        assert_eq!(libc::sigaction(libc::SIGKILL, &act, ptr::null_mut()), -1);
+        assert_eq!(io::Error::last_os_error().raw_os_error(), Some(libc::EINVAL));
+    }
+}
+
+fn main() {
+    test_signal();
+    test_sigaction();
+}
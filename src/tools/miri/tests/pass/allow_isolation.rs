@@ -0,0 +1,21 @@
+// Isolation stays enabled for the whole program, except for the items that opt out of it.
+use std::time::SystemTime;
+
+#[cfg_attr(miri, miri::allow(isolation))]
+fn wall_clock() -> SystemTime {
+    // Closures are covered by the attribute of the function they are defined in.
+    let now = || SystemTime::now();
+    now()
+}
+
+#[cfg_attr(miri, miri::allow(isolation))]
+mod host {
+    pub fn current_dir() -> std::path::PathBuf {
+        std::env::current_dir().unwrap()
+    }
+}
+
+fn main() {
+    assert!(wall_clock() > SystemTime::UNIX_EPOCH);
+    assert!(host::current_dir().is_absolute());
+}
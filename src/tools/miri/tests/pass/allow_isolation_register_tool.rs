@@ -0,0 +1,12 @@
+// Crates that register the `miri` tool themselves must keep building under Miri.
+#![feature(register_tool)]
+#![register_tool(miri)]
+
+#[miri::allow(isolation)]
+fn current_dir() -> std::path::PathBuf {
+    std::env::current_dir().unwrap()
+}
+
+fn main() {
+    assert!(current_dir().is_absolute());
+}
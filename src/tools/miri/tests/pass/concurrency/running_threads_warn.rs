@@ -0,0 +1,21 @@
+//@compile-flags: -Zmiri-warn=threads
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let started = Arc::new(AtomicBool::new(false));
+    let started_clone = Arc::clone(&started);
+    thread::Builder::new()
+        .name("spinner".to_owned())
+        .spawn(move || {
+            started_clone.store(true, Ordering::Relaxed);
+            loop {}
+        })
+        .unwrap();
+    // Make sure the thread is in the loop when we return, so the backtrace points there.
+    while !started.load(Ordering::Relaxed) {
+        thread::yield_now();
+    }
+}
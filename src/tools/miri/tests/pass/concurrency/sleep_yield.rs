@@ -0,0 +1,31 @@
+// Without preemption, the only way for another thread to make progress is for us to yield.
+//@compile-flags: -Zmiri-preemption-rate=0 -Zmiri-disable-isolation
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Even a zero-length sleep lets other threads run.
+fn zero_sleep_yields() {
+    static FLAG: AtomicBool = AtomicBool::new(false);
+
+    let t = thread::spawn(|| FLAG.store(true, Ordering::Relaxed));
+    while !FLAG.load(Ordering::Relaxed) {
+        thread::sleep(Duration::ZERO);
+    }
+    t.join().unwrap();
+}
+
+/// Sleeping does not block the host even when isolation is disabled, so this finishes quickly.
+fn long_sleep_with_host_clock() {
+    let before = Instant::now();
+    let t = thread::spawn(|| thread::sleep(Duration::from_secs(1800)));
+    thread::sleep(Duration::from_secs(3600));
+    t.join().unwrap();
+    assert!(before.elapsed().as_secs() >= 3600);
+}
+
+fn main() {
+    zero_sleep_yields();
+    long_sleep_with_host_clock();
+}
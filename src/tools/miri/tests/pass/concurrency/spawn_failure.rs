@@ -0,0 +1,17 @@
+//@compile-flags: -Zmiri-spawn-failure-at=2,3
+//! Test that `-Zmiri-spawn-failure-at` makes exactly the given attempts to spawn a thread fail, and
+//! that the program can recover from that.
+
+use std::thread;
+
+fn spawn(n: u32) -> std::io::Result<thread::JoinHandle<u32>> {
+    thread::Builder::new().name(format!("thread {n}")).spawn(move || n)
+}
+
+fn main() {
+    assert_eq!(spawn(1).unwrap().join().unwrap(), 1);
+    // The closure is dropped without having run.
+    assert!(spawn(2).is_err());
+    assert!(spawn(3).is_err());
+    assert_eq!(spawn(4).unwrap().join().unwrap(), 4);
+}
@@ -0,0 +1,12 @@
+//@compile-flags: -Zmiri-spawn-failure-rate=1.0
+//! Test that with a failure rate of 1, no thread can be spawned.
+
+use std::thread;
+
+fn main() {
+    for _ in 0..3 {
+        let err = thread::Builder::new().spawn(|| unreachable!()).unwrap_err();
+        // Whatever the error is, it must be an OS error the program can inspect.
+        assert!(err.raw_os_error().is_some());
+    }
+}
@@ -0,0 +1,52 @@
+//@only-target-windows: Uses the Windows TLS callback sections
+//! Test that all TLS callbacks registered in the `.CRT$XL?` linker sections run, sorted by section
+//! name, both when a thread exits and when the process exits.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+type Callback = unsafe extern "system" fn(*mut u8, u32, *mut u8);
+
+const DLL_PROCESS_DETACH: u32 = 0;
+const DLL_THREAD_DETACH: u32 = 3;
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn report(name: &str, reason: u32) {
+    CALLS.fetch_add(1, Ordering::Relaxed);
+    let event = match reason {
+        DLL_PROCESS_DETACH => "process",
+        DLL_THREAD_DETACH => "thread",
+        _ => panic!("unexpected reason {reason}"),
+    };
+    println!("{name}: {event} detach");
+}
+
+unsafe extern "system" fn first(_h: *mut u8, reason: u32, _pv: *mut u8) {
+    report("first", reason);
+}
+
+unsafe extern "system" fn second(_h: *mut u8, reason: u32, _pv: *mut u8) {
+    report("second", reason);
+}
+
+unsafe extern "system" fn third(_h: *mut u8, reason: u32, _pv: *mut u8) {
+    report("third", reason);
+}
+
+// Declared out of order on purpose: the section names determine the order.
+#[used]
+#[link_section = ".CRT$XLY"]
+static THIRD: Callback = third;
+
+// A section can also hold several callbacks; null entries are skipped.
+#[used]
+#[link_section = ".CRT$XLC"]
+static FIRST_AND_SECOND: [Option<Callback>; 3] = [Some(first), None, Some(second)];
+
+fn main() {
+    thread::spawn(|| {}).join().unwrap();
+    // The statics are not `#[no_mangle]`, but all three callbacks must still have run.
+    assert_eq!(CALLS.load(Ordering::Relaxed), 3);
+    println!("main: exiting");
+}
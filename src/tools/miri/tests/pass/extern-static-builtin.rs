@@ -0,0 +1,14 @@
+//@only-target-linux: Miri provides `environ` on Linux
+//@compile-flags: -Zmiri-extern-static=__environ=builtin:environ
+
+extern "C" {
+    static environ: *const *const u8;
+    static __environ: *const *const u8;
+}
+
+fn main() {
+    unsafe {
+        assert!(!environ.is_null());
+        assert_eq!(environ, __environ);
+    }
+}
@@ -0,0 +1,20 @@
+//@compile-flags: -Zmiri-extern-static=MIRI_TEST_ZEROED=zeroed -Zmiri-extern-static=MIRI_TEST_BYTES=bytes:01020304 -Zmiri-extern-static=renamed_in_rust=zeroed
+
+extern "C" {
+    static MIRI_TEST_ZEROED: [u64; 3];
+    static mut MIRI_TEST_BYTES: [u8; 4];
+    #[link_name = "renamed_in_rust"]
+    static RENAMED: *const u8;
+}
+
+fn main() {
+    unsafe {
+        assert_eq!(MIRI_TEST_ZEROED, [0; 3]);
+        assert_eq!(MIRI_TEST_BYTES, [1, 2, 3, 4]);
+        assert!(RENAMED.is_null());
+
+        // The statics live in mutable memory, like they would if some C code defined them.
+        MIRI_TEST_BYTES[0] = 42;
+        assert_eq!(MIRI_TEST_BYTES, [42, 2, 3, 4]);
+    }
+}
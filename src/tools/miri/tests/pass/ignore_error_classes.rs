@@ -0,0 +1,15 @@
+//@compile-flags: -Zmiri-ignore=alignment,leaks
+// Ignoring these classes has the same effect as `-Zmiri-disable-alignment-check` and
+// `-Zmiri-ignore-leaks`, while all other errors are still caught.
+
+fn main() {
+    let mut x = [0u8; 20];
+    let x_ptr: *mut u8 = x.as_mut_ptr();
+    // At least one of these is definitely unaligned.
+    unsafe {
+        *(x_ptr as *mut u64) = 42;
+        *(x_ptr.add(1) as *mut u64) = 42;
+    }
+
+    std::mem::forget(Box::new(42));
+}
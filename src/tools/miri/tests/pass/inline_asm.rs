@@ -0,0 +1,31 @@
+//@only-target-x86_64: the operands use x86 register classes
+#![feature(asm_const)]
+
+use std::arch::asm;
+
+static mut COUNTER: u32 = 0;
+
+extern "C" fn callee() {}
+
+fn main() {
+    unsafe {
+        // Compiler barriers.
+        asm!("");
+        asm!("", options(nomem, nostack, preserves_flags));
+        asm!("/* barrier */", options(nostack));
+
+        // Keeping a value alive, like some `black_box` implementations do.
+        let x = 42;
+        asm!("/* {0} */", in(reg) &x, options(nostack, preserves_flags));
+
+        // `inout` operands keep their value, since no instruction changes the register.
+        let mut y: u64 = 1;
+        asm!("// {0}", inout(reg) y, options(nomem, nostack));
+        assert_eq!(y, 1);
+        let z: i64;
+        asm!("# {0}", inout(reg) u64::MAX => z, options(nomem, nostack));
+        assert_eq!(z, -1);
+
+        asm!("/* {0} {1} {2} */", const 5, sym callee, sym COUNTER);
+    }
+}
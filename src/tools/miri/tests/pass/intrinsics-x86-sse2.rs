@@ -0,0 +1,186 @@
+//@only-target-x86_64: SSE2 is part of the baseline of x86_64, so we do not need feature detection
+
+use std::arch::x86_64::*;
+use std::mem::transmute;
+
+fn i16x8(v: __m128i) -> [i16; 8] {
+    unsafe { transmute(v) }
+}
+
+fn i32x4(v: __m128i) -> [i32; 4] {
+    unsafe { transmute(v) }
+}
+
+fn u64x2(v: __m128i) -> [u64; 2] {
+    unsafe { transmute(v) }
+}
+
+fn f64x2(v: __m128d) -> [f64; 2] {
+    unsafe { transmute(v) }
+}
+
+fn test_integer_arith() {
+    unsafe {
+        let a = _mm_setr_epi16(1, 2, 3, 4, i16::MIN, i16::MIN, -5, 6);
+        let b = _mm_setr_epi16(10, 20, 30, 40, i16::MIN, i16::MIN, 7, 8);
+        assert_eq!(i32x4(_mm_madd_epi16(a, b)), [50, 250, i32::MIN, 13]);
+
+        let a = _mm_setr_epi16(i16::MAX, i16::MIN, -1, 0x4000, 0, 1, 2, 3);
+        let b = _mm_setr_epi16(i16::MAX, i16::MIN, 1, 4, 0, 1, 2, 3);
+        assert_eq!(i16x8(_mm_mulhi_epi16(a, b)), [0x3fff, 0x4000, -1, 1, 0, 0, 0, 0]);
+        assert_eq!(i16x8(_mm_mulhi_epu16(a, b)), [0x3fff, 0x4000, 0, 1, 0, 0, 0, 0]);
+
+        let a = _mm_setr_epi16(0, 1, 2, 3, -1, -1, 100, 101);
+        let b = _mm_setr_epi16(0, 2, 2, 4, -1, 0, 200, 100);
+        assert_eq!(i16x8(_mm_avg_epu16(a, b)), [0, 2, 2, 4, -1, i16::MIN, 150, 101]);
+
+        let a = _mm_setr_epi8(1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0, 0, -1);
+        let b = _mm_setr_epi8(8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0, 0, 0, 0, 0);
+        assert_eq!(u64x2(_mm_sad_epu8(a, b)), [32, 255]);
+    }
+}
+
+fn test_shifts() {
+    unsafe {
+        let a = _mm_setr_epi16(1, -1, 0x4000, -0x4000, 0, 0, 0, 0);
+        assert_eq!(i16x8(_mm_slli_epi16::<1>(a)), [2, -2, i16::MIN, i16::MIN, 0, 0, 0, 0]);
+        assert_eq!(i16x8(_mm_srli_epi16::<14>(a)), [0, 3, 1, 3, 0, 0, 0, 0]);
+        assert_eq!(i16x8(_mm_srai_epi16::<14>(a)), [0, -1, 1, -1, 0, 0, 0, 0]);
+        // Shifting by at least the element width clears the element (or fills it with the sign
+        // bit for arithmetic shifts).
+        assert_eq!(i16x8(_mm_slli_epi16::<16>(a)), [0; 8]);
+        assert_eq!(i16x8(_mm_srai_epi16::<200>(a)), [0, -1, 0, -1, 0, 0, 0, 0]);
+
+        // The vector versions use the low 64 bits of the count.
+        let a = _mm_setr_epi32(1, -1, 8, i32::MIN);
+        assert_eq!(i32x4(_mm_sll_epi32(a, _mm_set_epi64x(-1, 3))), [8, -8, 64, 0]);
+        assert_eq!(i32x4(_mm_sra_epi32(a, _mm_set_epi64x(0, 3))), [0, -1, 1, -0x10000000]);
+        assert_eq!(i32x4(_mm_srl_epi32(a, _mm_set_epi64x(0, 1 << 32))), [0; 4]);
+
+        let a = _mm_set_epi64x(-1, 1);
+        assert_eq!(u64x2(_mm_srl_epi64(a, _mm_set_epi64x(0, 60))), [0, 0xf]);
+        assert_eq!(u64x2(_mm_slli_epi64::<63>(a)), [1 << 63, 1 << 63]);
+    }
+}
+
+fn test_packs() {
+    unsafe {
+        let a = _mm_setr_epi16(0, 1, -1, 127, 128, -128, -129, i16::MAX);
+        let b = _mm_setr_epi16(255, 256, i16::MIN, 2, 3, 4, 5, 6);
+        let res: [i8; 16] = transmute(_mm_packs_epi16(a, b));
+        assert_eq!(res, [0, 1, -1, 127, 127, -128, -128, 127, 127, 127, -128, 2, 3, 4, 5, 6]);
+        let res: [u8; 16] = transmute(_mm_packus_epi16(a, b));
+        assert_eq!(res, [0, 1, 0, 127, 128, 0, 0, 255, 255, 255, 0, 2, 3, 4, 5, 6]);
+
+        let a = _mm_setr_epi32(1, -1, 0x8000, -0x8001);
+        let b = _mm_setr_epi32(i32::MAX, i32::MIN, 0x7fff, -0x8000);
+        assert_eq!(
+            i16x8(_mm_packs_epi32(a, b)),
+            [1, -1, i16::MAX, i16::MIN, i16::MAX, i16::MIN, i16::MAX, i16::MIN],
+        );
+    }
+}
+
+fn test_movemask() {
+    unsafe {
+        let a = _mm_setr_epi8(-1, 0, -128, 127, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -2);
+        assert_eq!(_mm_movemask_epi8(a), 0b1000_0000_0000_0101);
+        assert_eq!(_mm_movemask_pd(_mm_setr_pd(-0.0, 1.0)), 0b01);
+        assert_eq!(_mm_movemask_pd(_mm_setr_pd(f64::NAN, -f64::INFINITY)), 0b10);
+    }
+}
+
+fn test_float_arith() {
+    unsafe {
+        let a = _mm_setr_pd(1.0, f64::NAN);
+        let b = _mm_setr_pd(2.0, 3.0);
+        assert_eq!(f64x2(_mm_max_pd(a, b)), [2.0, 3.0]);
+        assert_eq!(f64x2(_mm_min_pd(a, b)), [1.0, 3.0]);
+        // If either operand is NaN, the result is the second operand.
+        assert!(f64x2(_mm_max_pd(b, a))[1].is_nan());
+        // If both operands are zero, the result is the second operand, too.
+        let res = f64x2(_mm_min_pd(_mm_set1_pd(0.0), _mm_set1_pd(-0.0)));
+        assert!(res[0].is_sign_negative() && res[1].is_sign_negative());
+
+        // The scalar versions copy the upper element from the first operand.
+        let a = _mm_setr_pd(5.0, 6.0);
+        let b = _mm_setr_pd(4.0, 7.0);
+        assert_eq!(f64x2(_mm_min_sd(a, b)), [4.0, 6.0]);
+        assert_eq!(f64x2(_mm_max_sd(a, b)), [5.0, 6.0]);
+
+        assert_eq!(f64x2(_mm_sqrt_pd(_mm_setr_pd(4.0, 9.0))), [2.0, 3.0]);
+        assert_eq!(f64x2(_mm_sqrt_sd(a, _mm_setr_pd(16.0, 25.0))), [4.0, 6.0]);
+    }
+}
+
+fn test_float_cmp() {
+    unsafe {
+        let a = _mm_setr_pd(1.0, f64::NAN);
+        let b = _mm_setr_pd(1.0, 2.0);
+        let mask = |v: __m128d| -> [u64; 2] { transmute(v) };
+        assert_eq!(mask(_mm_cmpeq_pd(a, b)), [u64::MAX, 0]);
+        assert_eq!(mask(_mm_cmplt_pd(a, b)), [0, 0]);
+        assert_eq!(mask(_mm_cmple_pd(a, b)), [u64::MAX, 0]);
+        assert_eq!(mask(_mm_cmpneq_pd(a, b)), [0, u64::MAX]);
+        assert_eq!(mask(_mm_cmpnlt_pd(a, b)), [u64::MAX, u64::MAX]);
+        assert_eq!(mask(_mm_cmpord_pd(a, b)), [u64::MAX, 0]);
+        assert_eq!(mask(_mm_cmpunord_pd(a, b)), [0, u64::MAX]);
+
+        let a = _mm_setr_pd(1.0, 5.0);
+        let b = _mm_setr_pd(2.0, 1.0);
+        assert_eq!(f64x2(_mm_cmplt_sd(a, b))[1], 5.0);
+        assert_eq!(mask(_mm_cmplt_sd(a, b))[0], u64::MAX);
+
+        assert_eq!(_mm_comieq_sd(a, a), 1);
+        assert_eq!(_mm_comilt_sd(a, b), 1);
+        assert_eq!(_mm_comige_sd(a, b), 0);
+        assert_eq!(_mm_ucomineq_sd(a, b), 1);
+        let nan = _mm_set1_pd(f64::NAN);
+        assert_eq!(_mm_comieq_sd(nan, nan), 0);
+        assert_eq!(_mm_ucomile_sd(nan, a), 0);
+        assert_eq!(_mm_comineq_sd(nan, nan), 1);
+    }
+}
+
+fn test_conversions() {
+    unsafe {
+        // Conversions round to nearest, ties to even, unless they truncate.
+        let a = _mm_setr_ps(1.5, 2.5, -1.7, f32::NAN);
+        assert_eq!(i32x4(_mm_cvtps_epi32(a)), [2, 2, -2, i32::MIN]);
+        assert_eq!(i32x4(_mm_cvttps_epi32(a)), [1, 2, -1, i32::MIN]);
+
+        let a = _mm_setr_pd(-3.5, 1e10);
+        assert_eq!(i32x4(_mm_cvtpd_epi32(a)), [-4, i32::MIN, 0, 0]);
+        assert_eq!(i32x4(_mm_cvttpd_epi32(a)), [-3, i32::MIN, 0, 0]);
+        assert_eq!(_mm_cvtsd_si32(a), -4);
+        assert_eq!(_mm_cvttsd_si32(a), -3);
+        assert_eq!(_mm_cvtsd_si64(_mm_setr_pd(1e10, 0.0)), 10_000_000_000);
+        assert_eq!(_mm_cvttsd_si64(_mm_setr_pd(f64::INFINITY, 0.0)), i64::MIN);
+
+        let res: [f32; 4] = transmute(_mm_cvtepi32_ps(_mm_setr_epi32(1, -2, 16777217, 0)));
+        assert_eq!(res, [1.0, -2.0, 16777216.0, 0.0]);
+
+        let res: [f32; 4] = transmute(_mm_cvtsd_ss(_mm_setr_ps(1.0, 2.0, 3.0, 4.0), a));
+        assert_eq!(res, [-3.5, 2.0, 3.0, 4.0]);
+        assert_eq!(f64x2(_mm_cvtss_sd(a, _mm_set1_ps(0.25))), [0.25, 1e10]);
+    }
+}
+
+fn test_misc() {
+    unsafe {
+        _mm_pause();
+        _mm_lfence();
+        _mm_mfence();
+    }
+}
+
+fn main() {
+    test_integer_arith();
+    test_shifts();
+    test_packs();
+    test_movemask();
+    test_float_arith();
+    test_float_cmp();
+    test_conversions();
+    test_misc();
+}
@@ -0,0 +1,7 @@
+//@compile-flags: -Zmiri-warn=leaks
+//@normalize-stderr-test: ".*│.*" -> "$$stripped$$"
+// The leak is reported as a warning, and does not make the run fail.
+
+fn main() {
+    std::mem::forget(Box::new(42));
+}
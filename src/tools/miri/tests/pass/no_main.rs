@@ -0,0 +1,14 @@
+#![no_main]
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+
+// Without a Rust entry point, the program is started through the `main` symbol it exports.
+#[no_mangle]
+extern "C" fn main(argc: c_int, argv: *const *const c_char) -> c_int {
+    assert_eq!(argc, 1);
+    let program_name = unsafe { CStr::from_ptr(*argv) };
+    assert!(!program_name.to_bytes().is_empty());
+    println!("Hello from a `#[no_main]` program!");
+    0
+}
@@ -0,0 +1,60 @@
+#![feature(lang_items, start, alloc_error_handler)]
+#![no_std]
+//@ignore-target-windows: no-std not supported on Windows
+
+extern crate alloc;
+
+use alloc::alloc::{handle_alloc_error, GlobalAlloc, Layout};
+use alloc::vec::Vec;
+
+extern "Rust" {
+    fn miri_alloc(size: usize, align: usize) -> *mut u8;
+    fn miri_dealloc(ptr: *mut u8, size: usize, align: usize);
+    fn miri_write_to_stdout(bytes: &[u8]);
+}
+
+extern "C" {
+    fn exit(code: i32) -> !;
+}
+
+struct MiriAllocator;
+
+unsafe impl GlobalAlloc for MiriAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        miri_alloc(layout.size(), layout.align())
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        miri_dealloc(ptr, layout.size(), layout.align())
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: MiriAllocator = MiriAllocator;
+
+#[alloc_error_handler]
+fn alloc_error_handler(layout: Layout) -> ! {
+    assert_eq!((layout.size(), layout.align()), (42, 8));
+    unsafe {
+        miri_write_to_stdout(b"the alloc error handler was called\n");
+        exit(0)
+    }
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    let mut v = Vec::new();
+    v.extend_from_slice(&[1u8, 2, 3]);
+    assert_eq!(v.iter().sum::<u8>(), 6);
+    drop(v);
+
+    handle_alloc_error(Layout::from_size_align(42, 8).unwrap())
+}
+
+#[panic_handler]
+fn panic_handler(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[lang = "eh_personality"]
+fn eh_personality() {}
@@ -0,0 +1,30 @@
+#![feature(c_unwind)]
+
+use std::panic::catch_unwind;
+
+extern "C-unwind" fn panic_through_c_unwind(msg: &'static str) {
+    panic!("{msg}");
+}
+
+#[no_mangle]
+extern "C-unwind" fn exported_c_unwind() {
+    panic!("exported");
+}
+
+fn main() {
+    // A panic propagates out of an `extern "C-unwind"` function to its caller.
+    let err = catch_unwind(|| panic_through_c_unwind("direct")).unwrap_err();
+    assert_eq!(*err.downcast_ref::<String>().unwrap(), "direct");
+
+    // That also works when the function is called through a function pointer...
+    let f: extern "C-unwind" fn(&'static str) = panic_through_c_unwind;
+    let err = catch_unwind(|| f("pointer")).unwrap_err();
+    assert_eq!(*err.downcast_ref::<String>().unwrap(), "pointer");
+
+    // ...and when it is called through its exported symbol.
+    extern "C-unwind" {
+        fn exported_c_unwind();
+    }
+    let err = catch_unwind(|| unsafe { exported_c_unwind() }).unwrap_err();
+    assert_eq!(*err.downcast_ref::<&str>().unwrap(), "exported");
+}
@@ -1,5 +1,5 @@
 //@compile-flags: -Zmiri-strict-provenance
-#![feature(portable_simd, platform_intrinsics)]
+#![feature(portable_simd, platform_intrinsics, repr_simd)]
 use std::simd::*;
 
 fn simd_ops_f32() {
@@ -357,6 +357,11 @@ fn simd_intrinsics() {
         fn simd_reduce_any<T>(x: T) -> bool;
         fn simd_reduce_all<T>(x: T) -> bool;
         fn simd_select<M, T>(m: M, yes: T, no: T) -> T;
+        fn simd_reduce_add_unordered<T, U>(x: T) -> U;
+        fn simd_reduce_mul_unordered<T, U>(x: T) -> U;
+        fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32; 4]) -> U;
+        fn simd_cast_ptr<T, U>(x: T) -> U;
+        fn simd_expose_addr<T, U>(x: T) -> U;
     }
     unsafe {
         // Make sure simd_eq returns all-1 for `true`
@@ -380,6 +385,55 @@ fn simd_intrinsics() {
             simd_select(i8x4::from_array([0, -1, -1, 0]), b, a),
             i32x4::from_array([10, 2, 10, 10])
         );
+
+        assert_eq!(simd_reduce_add_unordered::<_, i32>(b), 17);
+        assert_eq!(simd_reduce_mul_unordered::<_, i32>(b), 80);
+        assert_eq!(simd_reduce_add_unordered::<_, f32>(f32x4::splat(0.5)), 2.0);
+
+        let c: i32x4 = simd_shuffle4(a, b, [7, 0, 4, 1]);
+        assert_eq!(c, i32x4::from_array([4, 10, 1, 10]));
+
+        #[repr(simd)]
+        #[derive(Copy, Clone)]
+        struct PtrX2<T>(*const T, *const T);
+        #[repr(simd)]
+        #[derive(Copy, Clone)]
+        struct UsizeX2(usize, usize);
+        let x = [0u32; 2];
+        let ptrs = PtrX2(&x[0], &x[1]);
+        let bytes: PtrX2<u8> = simd_cast_ptr(ptrs);
+        assert_eq!(bytes.1, ptrs.1.cast::<u8>());
+        let addrs: UsizeX2 = simd_expose_addr(ptrs);
+        assert_eq!(addrs.1 - addrs.0, 4);
+    }
+}
+
+fn simd_float_math() {
+    extern "platform-intrinsic" {
+        fn simd_fsin<T>(x: T) -> T;
+        fn simd_fcos<T>(x: T) -> T;
+        fn simd_fexp<T>(x: T) -> T;
+        fn simd_fexp2<T>(x: T) -> T;
+        fn simd_flog<T>(x: T) -> T;
+        fn simd_flog10<T>(x: T) -> T;
+        fn simd_flog2<T>(x: T) -> T;
+        fn simd_fpow<T>(x: T, y: T) -> T;
+        fn simd_fpowi<T>(x: T, y: i32) -> T;
+    }
+    unsafe {
+        assert_eq!(simd_fsin(f32x2::splat(0.0)), f32x2::splat(0.0));
+        assert_eq!(simd_fcos(f64x2::splat(0.0)), f64x2::splat(1.0));
+        assert_eq!(simd_fexp(f32x2::splat(0.0)), f32x2::splat(1.0));
+        assert_eq!(simd_fexp2(f64x2::from_array([3.0, -1.0])), f64x2::from_array([8.0, 0.5]));
+        assert_eq!(simd_flog(f32x2::splat(1.0)), f32x2::splat(0.0));
+        assert_eq!(simd_flog10(f64x2::from_array([100.0, 0.1])), f64x2::from_array([2.0, -1.0]));
+        assert_eq!(simd_flog2(f32x2::from_array([8.0, 0.5])), f32x2::from_array([3.0, -1.0]));
+        assert_eq!(
+            simd_fpow(f64x2::from_array([2.0, 9.0]), f64x2::from_array([3.0, 0.5])),
+            f64x2::from_array([8.0, 3.0])
+        );
+        assert_eq!(simd_fpowi(f32x2::from_array([3.0, -2.0]), 3), f32x2::from_array([27.0, -8.0]));
+        assert!(simd_flog(f32x2::splat(-1.0)).is_nan().all());
     }
 }
 
@@ -393,4 +447,5 @@ fn main() {
     simd_gather_scatter();
     simd_round();
     simd_intrinsics();
+    simd_float_math();
 }
@@ -0,0 +1,12 @@
+//@compile-flags: -Zmiri-provenance-audit
+
+fn main() {
+    let x = 42u32;
+    let addr = &x as *const u32 as usize;
+    for _ in 0..3 {
+        let ptr = addr as *const u32;
+        assert_eq!(unsafe { *ptr }, 42);
+    }
+    // Nothing is ever accessed through this pointer.
+    let _unused = (addr + 1) as *const u8;
+}
@@ -0,0 +1,19 @@
+//@compile-flags: -Zmiri-relax-dependencies -Zmiri-relaxed-crates=relaxed_crates
+
+fn main() {
+    // Both of these are Undefined Behavior, but we told Miri to not look for them in this crate:
+    // Using a reference that was invalidated by creating another one from the same raw pointer
+    // is an aliasing violation, and creating a `bool` that is not 0 or 1 violates validity.
+    let mut x = 0;
+    let raw = &mut x as *mut i32;
+    let r1 = unsafe { &mut *raw };
+    let r2 = unsafe { &mut *raw };
+    *r2 = 1;
+    *r1 = 2;
+    let _b: bool = unsafe { std::mem::transmute(2u8) };
+
+    // The standard library is relaxed as well, but should of course still work.
+    let mut v = vec![1, 2, 3];
+    v.push(x);
+    assert_eq!(v.iter().sum::<i32>(), 8);
+}
@@ -0,0 +1,31 @@
+#![feature(lang_items, start)]
+#![no_std]
+// We use `no_std` so that the report only lists the shims that this test calls itself.
+//@ignore-target-windows: no-std not supported on Windows
+//@compile-flags: -Zmiri-shim-report -Zmiri-isolation-error=hide
+
+extern "C" {
+    fn malloc(size: usize) -> *mut u8;
+    fn free(ptr: *mut u8);
+    fn getcwd(buf: *mut u8, size: usize) -> *mut u8;
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        let buf = malloc(64);
+        // Isolation makes this fail.
+        assert!(getcwd(buf, 64).is_null());
+        free(buf);
+        free(core::ptr::null_mut());
+    }
+    0
+}
+
+#[panic_handler]
+fn panic_handler(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[lang = "eh_personality"]
+fn eh_personality() {}
@@ -0,0 +1,10 @@
+//@compile-flags: -Zmiri-env-forward-all -Zmiri-env-exclude=MIRI_ENV_VAR_T?ST -Zmiri-env-set=MIRI_TEMP=synthetic
+
+fn main() {
+    assert!(std::env::var("MIRI_ENV_VAR_TEST").is_err());
+    // Synthetic variables replace the host's.
+    assert_eq!(std::env::var("MIRI_TEMP"), Ok("synthetic".to_owned()));
+    assert!(std::env::var("PATH").is_ok());
+    // Isolation is still enabled.
+    assert_eq!(std::process::id(), 1000);
+}
@@ -0,0 +1,8 @@
+//@compile-flags: -Zmiri-env-forward=MIRI_ENV_*_TEST -Zmiri-env-set=MIRI_SYNTHETIC_VAR=synthetic
+
+fn main() {
+    assert_eq!(std::env::var("MIRI_ENV_VAR_TEST"), Ok("0".to_owned()));
+    // Variables that do not match the pattern are still not forwarded.
+    assert!(std::env::var("MIRI_TEMP").is_err());
+    assert_eq!(std::env::var("MIRI_SYNTHETIC_VAR"), Ok("synthetic".to_owned()));
+}
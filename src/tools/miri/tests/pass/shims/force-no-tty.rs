@@ -0,0 +1,11 @@
+//@compile-flags: -Zmiri-disable-isolation -Zmiri-force-tty=no
+#![feature(is_terminal)]
+
+use std::io::IsTerminal;
+
+fn main() {
+    // Even with isolation disabled, the host's answer is overridden.
+    assert!(!std::io::stdin().is_terminal());
+    assert!(!std::io::stdout().is_terminal());
+    assert!(!std::io::stderr().is_terminal());
+}
@@ -0,0 +1,10 @@
+//@compile-flags: -Zmiri-force-tty=yes
+#![feature(is_terminal)]
+
+use std::io::IsTerminal;
+
+fn main() {
+    assert!(std::io::stdin().is_terminal());
+    assert!(std::io::stdout().is_terminal());
+    assert!(std::io::stderr().is_terminal());
+}
@@ -0,0 +1,23 @@
+//@ignore-target-windows: File handling is not implemented yet
+//@compile-flags: -Zmiri-isolation-error=warn-nobacktrace -Zmiri-isolation=clock:passthrough,fs-read:passthrough
+
+use std::fs::{self, File};
+use std::io::ErrorKind;
+use std::time::{Duration, SystemTime};
+
+fn main() {
+    // The real clock is available.
+    let since_epoch = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+    assert!(since_epoch > Duration::from_secs(50 * 365 * 24 * 60 * 60));
+
+    // Reading the file system works...
+    assert!(fs::metadata("/").unwrap().is_dir());
+    assert!(fs::read_dir("/").is_ok());
+
+    // ...but writing to it is still isolated.
+    assert_eq!(File::create("foo.txt").unwrap_err().kind(), ErrorKind::PermissionDenied);
+    assert_eq!(fs::remove_file("foo.txt").unwrap_err().kind(), ErrorKind::PermissionDenied);
+
+    // So is the rest of the host environment.
+    assert_eq!(std::env::current_dir().unwrap_err().kind(), ErrorKind::PermissionDenied);
+}
@@ -0,0 +1,46 @@
+//@only-target-apple: tests macOS-specific time APIs
+
+#[repr(C)]
+#[derive(Default)]
+struct MachTimebaseInfo {
+    numer: u32,
+    denom: u32,
+}
+
+extern "C" {
+    fn mach_absolute_time() -> u64;
+    fn mach_approximate_time() -> u64;
+    fn mach_continuous_time() -> u64;
+    fn mach_continuous_approximate_time() -> u64;
+    fn mach_timebase_info(info: *mut MachTimebaseInfo) -> i32;
+}
+
+fn main() {
+    let mut info = MachTimebaseInfo::default();
+    assert_eq!(unsafe { mach_timebase_info(&mut info) }, 0); // KERN_SUCCESS
+    assert!(info.numer > 0 && info.denom > 0);
+    let to_nanos = |ticks: u64| u128::from(ticks) * u128::from(info.numer) / u128::from(info.denom);
+
+    for clock in [
+        mach_absolute_time as unsafe extern "C" fn() -> u64,
+        mach_approximate_time,
+        mach_continuous_time,
+        mach_continuous_approximate_time,
+    ] {
+        let before = unsafe { clock() };
+        // Do some work to make time pass.
+        for _ in 0..10 {
+            drop(vec![42]);
+        }
+        let after = unsafe { clock() };
+        assert!(after > before);
+        // The virtual clock is deterministic, so this cannot take long.
+        assert!(to_nanos(after - before) < 1_000_000);
+    }
+
+    // Sleeping an hour advances the clock by (at least) an hour.
+    let before = unsafe { mach_absolute_time() };
+    std::thread::sleep(std::time::Duration::from_secs(3600));
+    let after = unsafe { mach_absolute_time() };
+    assert!(to_nanos(after - before) >= 3600 * 1_000_000_000);
+}
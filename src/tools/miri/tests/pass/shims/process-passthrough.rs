@@ -0,0 +1,27 @@
+//@ignore-target-windows: Windows does not use `posix_spawn`
+//@only-on-host
+//@compile-flags: -Zmiri-disable-isolation -Zmiri-process-passthrough
+
+use std::io::ErrorKind;
+use std::process::Command;
+
+fn main() {
+    let status = Command::new("sh").args(["-c", "exit 3"]).status().unwrap();
+    assert_eq!(status.code(), Some(3));
+
+    // The environment of the interpreted program is passed on.
+    let status = Command::new("sh")
+        .args(["-c", "test \"$MIRI_SPAWN_TEST\" = value"])
+        .env("MIRI_SPAWN_TEST", "value")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    assert!(child.try_wait().unwrap().is_none());
+    child.kill().unwrap();
+    assert!(!child.wait().unwrap().success());
+
+    let err = Command::new("/this/program/does/not/exist").status().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
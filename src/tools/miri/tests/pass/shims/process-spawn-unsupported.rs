@@ -0,0 +1,14 @@
+//@only-target-linux: the exact functions that fail differ between targets
+//@compile-flags: -Zmiri-disable-isolation
+
+use std::io::ErrorKind;
+use std::process::Command;
+
+fn main() {
+    // Without `-Zmiri-process-passthrough`, spawning a process is an error the program can handle.
+    let err = Command::new("true").status().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+    // This fails already when setting up the pipes for the output.
+    let err = Command::new("echo").arg("hello").output().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
@@ -0,0 +1,15 @@
+//@only-target-linux: the name of the shim reading the real-time clock is platform-specific
+//@compile-flags: -Zmiri-isolation-error=warn-nobacktrace
+
+use std::time::{Duration, SystemTime};
+
+fn main() {
+    // Under isolation, the real-time clock starts at the Unix epoch. (The warning is only shown
+    // once.)
+    let before = SystemTime::now();
+    assert!(before.duration_since(SystemTime::UNIX_EPOCH).unwrap() < Duration::from_secs(1));
+    // It still moves forward, along with the monotonic clock.
+    std::thread::sleep(Duration::from_secs(3600));
+    let after = SystemTime::now();
+    assert!(after.duration_since(before).unwrap() >= Duration::from_secs(3600));
+}
@@ -0,0 +1,10 @@
+//@compile-flags: -Zmiri-force-tty=yes
+// On Windows, this makes the standard library write through `WriteConsoleW`, which involves
+// converting the output to UTF-16 and back.
+
+fn main() {
+    println!("Hello, wörld! 🦀");
+    eprintln!("Grüße an stderr 🦀");
+    print!("split ");
+    println!("line");
+}
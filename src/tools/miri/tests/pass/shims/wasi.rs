@@ -0,0 +1,31 @@
+//@only-target-wasi: tests the WASI shims
+//@compile-flags: -Zmiri-env-forward=MIRI_WASI_TEST_VAR
+//@rustc-env: MIRI_WASI_TEST_VAR=forwarded
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+fn main() {
+    // `args_sizes_get` and `args_get`.
+    assert_eq!(std::env::args().count(), 1);
+
+    // `environ`, `getenv` and `setenv` from wasi-libc.
+    assert_eq!(std::env::var("MIRI_WASI_TEST_VAR").unwrap(), "forwarded");
+    std::env::set_var("MIRI_WASI_OTHER_VAR", "set");
+    assert_eq!(std::env::var("MIRI_WASI_OTHER_VAR").unwrap(), "set");
+
+    // `clock_time_get` and `poll_oneoff`.
+    let before = Instant::now();
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(before.elapsed() >= Duration::from_millis(100));
+
+    // `random_get` seeds the hasher keys.
+    let mut map = HashMap::new();
+    map.insert(1, 2);
+    assert_eq!(map[&1], 2);
+
+    // There are no pre-opened directories.
+    assert!(std::fs::File::open("some-file").is_err());
+
+    // `fd_write`.
+    println!("Hello from WASI!");
+}
@@ -0,0 +1,55 @@
+//@only-target-windows: Uses win32 api functions
+
+use std::ffi::c_void;
+
+const HEAP_ZERO_MEMORY: u32 = 0x00000008;
+const HEAP_REALLOC_IN_PLACE_ONLY: u32 = 0x00000010;
+
+extern "system" {
+    fn GetProcessHeap() -> isize;
+    fn HeapAlloc(heap: isize, flags: u32, size: usize) -> *mut c_void;
+    fn HeapReAlloc(heap: isize, flags: u32, ptr: *mut c_void, size: usize) -> *mut c_void;
+    fn HeapSize(heap: isize, flags: u32, ptr: *const c_void) -> usize;
+    fn HeapFree(heap: isize, flags: u32, ptr: *mut c_void) -> i32;
+}
+
+fn main() {
+    unsafe {
+        let heap = GetProcessHeap();
+        assert_ne!(heap, 0);
+        assert_eq!(GetProcessHeap(), heap);
+
+        // `HEAP_ZERO_MEMORY` zeroes the new allocation...
+        let ptr = HeapAlloc(heap, HEAP_ZERO_MEMORY, 16).cast::<u8>();
+        assert!(!ptr.is_null());
+        assert_eq!(HeapSize(heap, 0, ptr.cast()), 16);
+        assert!(std::slice::from_raw_parts(ptr, 16).iter().all(|&b| b == 0));
+        ptr.write_bytes(1, 16);
+
+        // ... and, when growing, only the bytes that were added.
+        let ptr = HeapReAlloc(heap, HEAP_ZERO_MEMORY, ptr.cast(), 32).cast::<u8>();
+        assert!(!ptr.is_null());
+        assert_eq!(HeapSize(heap, 0, ptr.cast()), 32);
+        let bytes = std::slice::from_raw_parts(ptr, 32);
+        assert!(bytes[..16].iter().all(|&b| b == 1));
+        assert!(bytes[16..].iter().all(|&b| b == 0));
+
+        // Resizing in place may fail, and then leaves the allocation alone.
+        let res = HeapReAlloc(heap, HEAP_REALLOC_IN_PLACE_ONLY, ptr.cast(), 64);
+        if res.is_null() {
+            assert_eq!(HeapSize(heap, 0, ptr.cast()), 32);
+            assert_eq!(*ptr, 1);
+        }
+
+        // Shrinking to 0 keeps a valid allocation.
+        let ptr = HeapReAlloc(heap, 0, ptr.cast(), 0);
+        assert!(!ptr.is_null());
+        assert_eq!(HeapSize(heap, 0, ptr), 0);
+        assert_ne!(HeapFree(heap, 0, ptr), 0);
+
+        // So does allocating 0 bytes.
+        let ptr = HeapAlloc(heap, 0, 0);
+        assert!(!ptr.is_null());
+        assert_ne!(HeapFree(heap, 0, ptr), 0);
+    }
+}
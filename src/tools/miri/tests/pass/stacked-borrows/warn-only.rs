@@ -0,0 +1,13 @@
+//@compile-flags: -Zmiri-warn=stacked-borrows
+// The violation is reported as a warning, and the program keeps running.
+
+fn main() {
+    let mut x = 15;
+    let xraw = &mut x as *mut i32;
+    let xref = unsafe { &mut *xraw };
+    let _val = unsafe { *xraw };
+    let _val = *xref;
+    // Miri forgot about the borrows of `x` after the violation, so this is fine now.
+    *xref = 16;
+    println!("the program still ran to the end");
+}